@@ -1,5 +1,5 @@
 use chainbills::contract::sv::{
-    ContractExecMsg, ContractQueryMsg, InstantiateMsg,
+    ContractExecMsg, ContractQueryMsg, InstantiateMsg, MigrateMsg,
 };
 use sylvia::cw_schema::write_api;
 
@@ -8,5 +8,6 @@ fn main() {
       instantiate: InstantiateMsg,
       execute: ContractExecMsg,
       query: ContractQueryMsg,
+      migrate: MigrateMsg,
   }
 }
@@ -1,31 +1,67 @@
 use crate::error::ChainbillsError;
 use crate::messages::{
-  AddressMessage, CountMessage, IdMessage, InstantiateMessage,
+  AddressMessage, ChainIdMessage, CountMessage, IdMessage, InstantiateMessage,
+  MaxWithdrawalAmountMessage, MigrateTokenBalancesMessage, RelayerMessage,
+  RelayersMessage, UpdateFeeSplitMessage,
 };
 use crate::state::{
-  ActivityRecord, ActivityType, ChainStats, Config, Payable, PayablePayment,
-  TokenAndAmount, TokenDetails, User, UserPayment, Withdrawal,
+  ActivityRecord, ActivityType, BalanceCheckpoint, ChainStats, Config, Payable,
+  PayablePayment, PaymentRefund, TokenAndAmount, TokenDetails, User,
+  UserPayment, Withdrawal,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
 use cw20::Cw20ExecuteMsg;
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Bound, Item, Map};
 use sha2::{Digest, Sha256};
+use std::cmp::min;
 use sylvia::cw_std::{
-  to_json_binary, Addr, Api, Attribute, BankMsg, Coin, Env, HexBinary,
-  Response, StdResult, Storage, Uint128, WasmMsg,
+  to_json_binary, Addr, Api, Attribute, BankMsg, Coin, Env, HexBinary, Order,
+  Response, StdError, StdResult, Storage, Uint128, WasmMsg,
 };
-use sylvia::types::{ExecCtx, InstantiateCtx, QueryCtx};
+use sylvia::types::{ExecCtx, InstantiateCtx, MigrateCtx, QueryCtx};
 #[allow(unused_imports)]
 // RustRover IDE doesn't see the use of `entry_points` macro.
 use sylvia::{contract, entry_points};
 
-const CONTRACT_NAME: &str = "crates.io:chainbills";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const CONTRACT_NAME: &str = "crates.io:chainbills";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The number of items returned by paginated queries if no limit is given.
+pub const DEFAULT_PAGE_LIMIT: u32 = 10;
+/// The maximum number of items that paginated queries can return.
+pub const MAX_PAGE_LIMIT: u32 = 30;
+/// The maximum number of items that filtering queries scan per call, whether
+/// or not they match.
+pub const MAX_SCAN_LIMIT: usize = 100;
+/// The smallest amount (with its decimals) of any token that can be
+/// withdrawn. Smaller amounts are dust on which percentage fees truncate.
+pub const MIN_WITHDRAWAL_AMOUNT: u128 = 10;
+/// The decimals of a whole token down to which withdrawals are allowed, so
+/// that the minimum withdrawal is a millionth of a token (when its decimals
+/// are known).
+pub const MIN_WITHDRAWAL_PRECISION: u8 = 6;
+/// The number of most recent balance checkpoints kept per payable and token.
+pub const MAX_BALANCE_CHECKPOINTS: usize = 50;
+/// The maximum number of distinct tokens a payable can hold balances in.
+/// Keeps payables from being bloated with payments in many junk tokens.
+pub const MAX_PAYABLE_BALANCES: usize = 20;
+/// The maximum length (in bytes) of the memo attached to a payment.
+pub const MAX_MEMO_LENGTH: usize = 64;
+/// The maximum number of wallets in a payable's payer allowlist.
+pub const MAX_PAYER_ALLOWLIST: usize = 50;
+
+/// Decodes a hex-encoded 32-byte ID. Returns None if the ID isn't valid hex
+/// or doesn't decode to exactly 32 bytes.
+pub fn decode_id(id: &str) -> Option<[u8; 32]> {
+  HexBinary::from_hex(id).ok()?.as_slice().try_into().ok()
+}
 
 pub struct Chainbills {
   pub config: Item<Config>,
   pub chain_stats: Item<ChainStats>,
   pub token_details: Map<String, TokenDetails>,
+  pub collected_fees: Map<String, Uint128>,
+  pub foreign_contracts: Map<u16, [u8; 32]>,
   pub activities: Map<[u8; 32], ActivityRecord>,
   pub chain_activity_ids: Item<Vec<[u8; 32]>>,
   pub users: Map<&'static Addr, User>,
@@ -33,6 +69,7 @@ pub struct Chainbills {
   pub user_payable_ids: Map<&'static Addr, Vec<[u8; 32]>>,
   pub user_payments: Map<[u8; 32], UserPayment>,
   pub user_payment_ids: Map<&'static Addr, Vec<[u8; 32]>>,
+  pub user_paid_payable_ids: Map<&'static Addr, Vec<[u8; 32]>>,
   pub user_withdrawal_ids: Map<&'static Addr, Vec<[u8; 32]>>,
   pub user_activity_ids: Map<&'static Addr, Vec<[u8; 32]>>,
   pub payables: Map<[u8; 32], Payable>,
@@ -42,15 +79,27 @@ pub struct Chainbills {
   pub payable_activity_ids: Map<[u8; 32], Vec<[u8; 32]>>,
   pub per_chain_payable_payments_count: Map<(Vec<u8>, u16), u64>,
   pub per_chain_payable_payment_ids: Map<(Vec<u8>, u16), Vec<[u8; 32]>>,
+  pub payable_token_payment_ids: Map<(Vec<u8>, String), Vec<[u8; 32]>>,
   pub withdrawals: Map<[u8; 32], Withdrawal>,
+  pub payment_refunds: Map<[u8; 32], PaymentRefund>,
+  pub payable_balance_checkpoints:
+    Map<(Vec<u8>, String), Vec<BalanceCheckpoint>>,
+}
+
+impl Default for Chainbills {
+  fn default() -> Self {
+    Self::new()
+  }
 }
 
 #[cfg_attr(not(feature = "library"), entry_points)]
 #[contract]
 #[sv::error(crate::error::ChainbillsError)]
 #[sv::messages(crate::interfaces::activities as Activities)]
+#[sv::messages(crate::interfaces::foreign_contracts as ForeignContracts)]
 #[sv::messages(crate::interfaces::payables as Payables)]
 #[sv::messages(crate::interfaces::payments as Payments)]
+#[sv::messages(crate::interfaces::refunds as Refunds)]
 #[sv::messages(crate::interfaces::token_details as TokenDetailsInterface)]
 #[sv::messages(crate::interfaces::withdrawals as Withdrawals)]
 impl Chainbills {
@@ -59,6 +108,8 @@ impl Chainbills {
       config: Item::new("config"),
       chain_stats: Item::new("chain_stats"),
       token_details: Map::new("token_details"),
+      collected_fees: Map::new("collected_fees"),
+      foreign_contracts: Map::new("foreign_contracts"),
       activities: Map::new("activities"),
       chain_activity_ids: Item::new("chain_activity_ids"),
       users: Map::new("users"),
@@ -66,6 +117,7 @@ impl Chainbills {
       user_payable_ids: Map::new("user_payable_ids"),
       user_payments: Map::new("user_payments"),
       user_payment_ids: Map::new("user_payment_ids"),
+      user_paid_payable_ids: Map::new("user_paid_payable_ids"),
       user_withdrawal_ids: Map::new("user_withdrawal_ids"),
       user_activity_ids: Map::new("user_activity_ids"),
       payables: Map::new("payables"),
@@ -77,7 +129,10 @@ impl Chainbills {
         "per_chain_payable_payments_count",
       ),
       per_chain_payable_payment_ids: Map::new("per_chain_payable_payment_ids"),
+      payable_token_payment_ids: Map::new("payable_token_payment_ids"),
       withdrawals: Map::new("withdrawals"),
+      payment_refunds: Map::new("payment_refunds"),
+      payable_balance_checkpoints: Map::new("payable_balance_checkpoints"),
     }
   }
 
@@ -86,14 +141,20 @@ impl Chainbills {
     &self,
     ctx: InstantiateCtx,
     msg: InstantiateMessage,
-  ) -> StdResult<Response> {
+  ) -> Result<Response, ChainbillsError> {
+    // Ensure that the Wormhole chain ID is set. A wrong chain ID would
+    // corrupt the attribution of cross-chain payments.
+    if msg.chain_id == 0 {
+      return Err(ChainbillsError::InvalidChainId { chain_id: 0 });
+    }
+
     // Set Contract Version
     set_contract_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     // Initialize ChainStats
     self
       .chain_stats
-      .save(ctx.deps.storage, &mut ChainStats::initialize())?;
+      .save(ctx.deps.storage, &ChainStats::initialize())?;
 
     // Initialize Config
     let cbfc = ctx.deps.api.addr_validate(&msg.chainbills_fee_collector)?;
@@ -104,6 +165,10 @@ impl Chainbills {
         owner: ctx.info.sender.clone(),
         chainbills_fee_collector: cbfc,
         withdrawal_fee_percentage: Uint128::new(200),
+        fee_split_bps: Uint128::zero(),
+        secondary_fee_collector: None,
+        max_withdrawal_amount: Uint128::zero(),
+        relayers: vec![],
       },
     )?;
 
@@ -114,19 +179,87 @@ impl Chainbills {
     // Emit an event and return a response.
     Ok(Response::new().add_attributes([
       ("action", "instantiated"),
-      ("owner", &*ctx.info.sender.as_str()),
+      ("owner", ctx.info.sender.as_str()),
       ("version", CONTRACT_VERSION),
     ]))
   }
 
+  /// Upgrades the contract's stored state to this version. Migrating to the
+  /// same version is a no-op, while downgrades are rejected.
+  #[sv::msg(migrate)]
+  fn migrate(&self, ctx: MigrateCtx) -> Result<Response, ChainbillsError> {
+    // Ensure that the stored contract is Chainbills.
+    let stored = get_contract_version(ctx.deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+      return Err(ChainbillsError::InvalidMigrationContract {
+        contract: stored.contract,
+      });
+    }
+
+    // Ensure that this isn't a downgrade.
+    let parse = |version: &str| {
+      version
+        .parse::<semver::Version>()
+        .map_err(|e| StdError::generic_err(e.to_string()))
+    };
+    if parse(&stored.version)? > parse(CONTRACT_VERSION)? {
+      return Err(ChainbillsError::MigrationDowngrade {
+        stored: stored.version,
+        current: CONTRACT_VERSION.to_string(),
+      });
+    }
+
+    // State transformations for future versions go here, keyed on the
+    // stored version. Fields added so far have serde defaults.
+
+    // Bump the stored version.
+    set_contract_version(ctx.deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Return the Response.
+    Ok(Response::new().add_attributes([
+      ("action", "migrated".to_string()),
+      ("from_version", stored.version),
+      ("to_version", CONTRACT_VERSION.to_string()),
+    ]))
+  }
+
   #[sv::msg(query)]
   fn chain_stats(&self, ctx: QueryCtx) -> StdResult<ChainStats> {
-    Ok(self.chain_stats.load(ctx.deps.storage)?)
+    self.chain_stats.load(ctx.deps.storage)
   }
 
   #[sv::msg(query)]
   fn config(&self, ctx: QueryCtx) -> StdResult<Config> {
-    Ok(self.config.load(ctx.deps.storage)?)
+    self.config.load(ctx.deps.storage)
+  }
+
+  #[sv::msg(query)]
+  fn chain_id(&self, ctx: QueryCtx) -> StdResult<ChainIdMessage> {
+    let chain_id = self.config.load(ctx.deps.storage)?.chain_id;
+    Ok(ChainIdMessage { chain_id })
+  }
+
+  #[sv::msg(query)]
+  fn relayers(&self, ctx: QueryCtx) -> StdResult<RelayersMessage> {
+    let relayers = self.config.load(ctx.deps.storage)?.relayers;
+    Ok(RelayersMessage { relayers })
+  }
+
+  #[sv::msg(query)]
+  fn collected_fees(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> StdResult<TokenAndAmount> {
+    // Fees kept in this contract for the token and not yet swept.
+    let amount = self
+      .collected_fees
+      .may_load(ctx.deps.storage, msg.id.clone())?
+      .unwrap_or_default();
+    Ok(TokenAndAmount {
+      token: msg.id,
+      amount,
+    })
   }
 
   #[sv::msg(query)]
@@ -158,6 +291,24 @@ impl Chainbills {
     })
   }
 
+  #[sv::msg(exec)]
+  fn initialize_user(&self, ctx: ExecCtx) -> Result<Response, ChainbillsError> {
+    // Ensure that the caller hasn't been initialized before.
+    if self.users.has(ctx.deps.storage, &ctx.info.sender) {
+      return Err(ChainbillsError::UserAlreadyInitialized {});
+    }
+
+    // Initialize the caller ahead of their first payable or payment.
+    let user_resp_attrib = self.initialize_user_if_is_new(
+      ctx.deps.storage,
+      &ctx.env,
+      &ctx.info.sender,
+    )?;
+    Ok(Response::new().add_attributes(user_resp_attrib))
+  }
+
+  /// Sweeps withdrawal fees kept in this contract to the chainbills fee
+  /// collector. Can't take more than the fees collected in the token.
   #[sv::msg(exec)]
   fn owner_withdraw(
     &self,
@@ -191,12 +342,29 @@ impl Chainbills {
       }),
     }?;
 
-    // Prepare messages for transfer to add to the response.
+    // Ensure that only collected fees are withdrawn, so that the funds of
+    // payables can't be taken.
+    let collected_fees = self
+      .collected_fees
+      .may_load(ctx.deps.storage, token.clone())?
+      .unwrap_or_default();
+    if amount > collected_fees {
+      return Err(ChainbillsError::InsufficientCollectedFees {
+        collected: collected_fees,
+      });
+    }
+    self.collected_fees.save(
+      ctx.deps.storage,
+      token.clone(),
+      &(collected_fees - amount),
+    )?;
+
+    // Prepare messages for transfer to the fee collector.
     let mut bank_messages = vec![];
     let mut cw20_messages = vec![];
     if is_native_token {
       bank_messages.push(BankMsg::Send {
-        to_address: ctx.info.sender.to_string(),
+        to_address: config.chainbills_fee_collector.to_string(),
         amount: vec![Coin {
           denom: token.clone(),
           amount,
@@ -207,7 +375,7 @@ impl Chainbills {
         contract_addr: token.clone(),
         funds: vec![],
         msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
-          recipient: ctx.info.sender.to_string(),
+          recipient: config.chainbills_fee_collector.to_string(),
           amount,
         })?,
       });
@@ -227,20 +395,267 @@ impl Chainbills {
     )
   }
 
+  /// Rewrites payable balances in old_token to new_token, for when a token
+  /// migrates to a new contract or denom. new_token must already be
+  /// supported. As all payables have to be walked, this is resumable: pass
+  /// the returned `next_start_after` attribute as `start_after` until it is
+  /// empty.
+  ///
+  /// This only updates Chainbills' records. The contract's actual holdings of
+  /// old_token must be swapped for new_token out-of-band (before or right
+  /// after migrating), otherwise withdrawals in new_token can't be honoured.
+  #[sv::msg(exec)]
+  fn migrate_token_balances(
+    &self,
+    ctx: ExecCtx,
+    msg: MigrateTokenBalancesMessage,
+  ) -> Result<Response, ChainbillsError> {
+    // Ensure the caller is the owner.
+    let config = self.config.load(ctx.deps.storage)?;
+    if ctx.info.sender != config.owner {
+      return Err(ChainbillsError::OwnerUnauthorized {});
+    }
+
+    let MigrateTokenBalancesMessage {
+      old_token,
+      new_token,
+      start_after,
+      limit,
+    } = msg;
+
+    // Ensure the tokens are different and that the new one is supported.
+    if old_token == new_token {
+      return Err(ChainbillsError::SameTokenMigration { token: old_token });
+    }
+    let is_new_token_supported = self
+      .token_details
+      .may_load(ctx.deps.storage, new_token.clone())?
+      .is_some_and(|details| details.is_supported);
+    if !is_new_token_supported {
+      return Err(ChainbillsError::UnsupportedToken { token: new_token });
+    }
+
+    // Walk the requested page of payables.
+    let limit =
+      limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let start_after = match start_after {
+      Some(id) => {
+        Some(decode_id(&id).ok_or(ChainbillsError::InvalidPayableId { id })?)
+      }
+      None => None,
+    };
+    let payables = self
+      .payables
+      .range(
+        ctx.deps.storage,
+        start_after.map(Bound::exclusive),
+        None,
+        Order::Ascending,
+      )
+      .take(limit)
+      .collect::<StdResult<Vec<_>>>()?;
+
+    let mut migrated_count = 0u64;
+    for (payable_id, mut payable) in payables.iter().cloned() {
+      let Some(old_index) = payable
+        .balances
+        .iter()
+        .position(|bal| bal.token == old_token)
+      else {
+        continue;
+      };
+
+      // Merge into an existing new_token balance or rename the old one.
+      let old_balance = payable.balances[old_index].amount;
+      match payable
+        .balances
+        .iter_mut()
+        .find(|bal| bal.token == new_token)
+      {
+        Some(new_balance) => {
+          new_balance.amount = new_balance
+            .amount
+            .checked_add(old_balance)
+            .map_err(|_| ChainbillsError::Overflow {})?;
+          payable.balances.remove(old_index);
+        }
+        None => payable.balances[old_index].token = new_token.clone(),
+      }
+      self.payables.save(ctx.deps.storage, payable_id, &payable)?;
+      for token in [&old_token, &new_token] {
+        self.record_balance_checkpoint(
+          ctx.deps.storage,
+          &ctx.env,
+          payable_id,
+          &payable,
+          token,
+        )?;
+      }
+      migrated_count += 1;
+    }
+
+    // Only point to the next page if this one was full.
+    let next_start_after = if payables.len() == limit {
+      payables
+        .last()
+        .map(|(id, _)| HexBinary::from(id).to_hex())
+        .unwrap_or_default()
+    } else {
+      String::new()
+    };
+
+    // Return the Response.
+    Ok(Response::new().add_attributes([
+      ("action", "migrated_token_balances".to_string()),
+      ("old_token", old_token),
+      ("new_token", new_token),
+      ("migrated_count", migrated_count.to_string()),
+      ("next_start_after", next_start_after),
+    ]))
+  }
+
+  #[sv::msg(exec)]
+  fn update_fee_split(
+    &self,
+    ctx: ExecCtx,
+    msg: UpdateFeeSplitMessage,
+  ) -> Result<Response, ChainbillsError> {
+    // Ensure the caller is the owner.
+    let mut config = self.config.load(ctx.deps.storage)?;
+    if ctx.info.sender != config.owner {
+      return Err(ChainbillsError::OwnerUnauthorized {});
+    }
+
+    // Ensure the split is at most 100%.
+    let UpdateFeeSplitMessage {
+      fee_split_bps,
+      secondary_fee_collector,
+    } = msg;
+    if fee_split_bps > Uint128::new(10000) {
+      return Err(ChainbillsError::InvalidFeeSplit { fee_split_bps });
+    }
+
+    // Ensure the secondary fee collector is set if fees will be routed to it.
+    let secondary_fee_collector = match secondary_fee_collector {
+      Some(addr) => Some(ctx.deps.api.addr_validate(&addr)?),
+      None => None,
+    };
+    if !fee_split_bps.is_zero() && secondary_fee_collector.is_none() {
+      return Err(ChainbillsError::SecondaryFeeCollectorNotConfigured {});
+    }
+
+    // Save the updated Config.
+    config.fee_split_bps = fee_split_bps;
+    config.secondary_fee_collector = secondary_fee_collector.clone();
+    self.config.save(ctx.deps.storage, &config)?;
+
+    // Return the Response.
+    Ok(
+      Response::new().add_attributes([
+        ("action", "updated_fee_split".to_string()),
+        ("fee_split_bps", fee_split_bps.to_string()),
+        (
+          "secondary_fee_collector",
+          secondary_fee_collector
+            .map(|addr| addr.to_string())
+            .unwrap_or_default(),
+        ),
+      ]),
+    )
+  }
+
+  #[sv::msg(exec)]
+  fn update_max_withdrawal_amount(
+    &self,
+    ctx: ExecCtx,
+    msg: MaxWithdrawalAmountMessage,
+  ) -> Result<Response, ChainbillsError> {
+    // Ensure the caller is the owner.
+    let mut config = self.config.load(ctx.deps.storage)?;
+    if ctx.info.sender != config.owner {
+      return Err(ChainbillsError::OwnerUnauthorized {});
+    }
+
+    // Save the updated Config.
+    config.max_withdrawal_amount = msg.max_withdrawal_amount;
+    self.config.save(ctx.deps.storage, &config)?;
+
+    // Return the Response.
+    Ok(Response::new().add_attributes([
+      ("action", "updated_max_withdrawal_amount".to_string()),
+      (
+        "max_withdrawal_amount",
+        msg.max_withdrawal_amount.to_string(),
+      ),
+    ]))
+  }
+
+  #[sv::msg(exec)]
+  fn add_relayer(
+    &self,
+    ctx: ExecCtx,
+    msg: RelayerMessage,
+  ) -> Result<Response, ChainbillsError> {
+    // Ensure the caller is the owner.
+    let mut config = self.config.load(ctx.deps.storage)?;
+    if ctx.info.sender != config.owner {
+      return Err(ChainbillsError::OwnerUnauthorized {});
+    }
+
+    // Save the relayer if it isn't whitelisted already.
+    let relayer = ctx.deps.api.addr_validate(&msg.relayer)?;
+    if !config.relayers.contains(&relayer) {
+      config.relayers.push(relayer.clone());
+      self.config.save(ctx.deps.storage, &config)?;
+    }
+
+    // Return the Response.
+    Ok(Response::new().add_attributes([
+      ("action", "added_relayer".to_string()),
+      ("relayer", relayer.to_string()),
+    ]))
+  }
+
+  #[sv::msg(exec)]
+  fn remove_relayer(
+    &self,
+    ctx: ExecCtx,
+    msg: RelayerMessage,
+  ) -> Result<Response, ChainbillsError> {
+    // Ensure the caller is the owner.
+    let mut config = self.config.load(ctx.deps.storage)?;
+    if ctx.info.sender != config.owner {
+      return Err(ChainbillsError::OwnerUnauthorized {});
+    }
+
+    // Remove the relayer if it is whitelisted.
+    let relayer = ctx.deps.api.addr_validate(&msg.relayer)?;
+    if config.relayers.contains(&relayer) {
+      config.relayers.retain(|r| *r != relayer);
+      self.config.save(ctx.deps.storage, &config)?;
+    }
+
+    // Return the Response.
+    Ok(Response::new().add_attributes([
+      ("action", "removed_relayer".to_string()),
+      ("relayer", relayer.to_string()),
+    ]))
+  }
+
   pub fn initialize_user_if_is_new(
     &self,
     storage: &mut dyn Storage,
     env: &Env,
     wallet: &Addr,
-  ) -> StdResult<Vec<Attribute>> {
+  ) -> Result<Vec<Attribute>, ChainbillsError> {
     let mut response_attribs: Vec<Attribute> = vec![];
 
     // If this is the first time this wallet is interacting with the contract
     if !self.users.has(storage, wallet) {
       // Increment chain count for users and activities.
       let mut chain_stats = self.chain_stats.load(storage)?;
-      chain_stats.users_count = chain_stats.next_user();
-      chain_stats.activities_count = chain_stats.next_activity();
+      chain_stats.users_count = chain_stats.next_user()?;
+      chain_stats.activities_count = chain_stats.next_activity()?;
       self.chain_stats.save(storage, &chain_stats)?;
 
       // Initialize the user.
@@ -257,7 +672,7 @@ impl Chainbills {
 
       // Get a new ActivityRecord ID.
       let activity_id =
-        self.create_id(storage, env, &wallet.to_string(), "activity", 1)?;
+        self.create_id(storage, env, wallet.as_ref(), "activity", 1)?;
 
       // Save the ActivityRecord ID to chain_activity_ids.
       let mut chain_activity_ids = self.chain_activity_ids.load(storage)?;
@@ -267,7 +682,7 @@ impl Chainbills {
       // Save the ActivityRecord ID to user_activity_ids.
       self
         .user_activity_ids
-        .save(storage, &wallet, &vec![activity_id])?;
+        .save(storage, wallet, &vec![activity_id])?;
 
       // Create and Save the ActivityRecord.
       self.activities.save(
@@ -294,6 +709,72 @@ impl Chainbills {
     Ok(response_attribs)
   }
 
+  pub fn check_allowed_tokens_and_amounts(
+    &self,
+    storage: &dyn Storage,
+    allowed_tokens_and_amounts: &[TokenAndAmount],
+  ) -> Result<(), ChainbillsError> {
+    for (i, taa) in allowed_tokens_and_amounts.iter().enumerate() {
+      // Ensure that the token is supported.
+      let token_details = self
+        .token_details
+        .load(storage, taa.token.clone())
+        .unwrap_or(TokenDetails::initialize(false, false, Uint128::zero()));
+      if !token_details.is_supported {
+        return Err(ChainbillsError::UnsupportedToken {
+          token: taa.token.clone(),
+        });
+      }
+
+      // Ensure that all specified acceptable amounts are greater than zero.
+      if taa.amount.is_zero() {
+        return Err(ChainbillsError::ZeroAmountSpecified {});
+      }
+
+      // Ensure that the token and amount pair doesn't appear more than once.
+      // A token can still be listed with different amounts.
+      if allowed_tokens_and_amounts[..i]
+        .iter()
+        .any(|other| other.token == taa.token && other.amount == taa.amount)
+      {
+        return Err(ChainbillsError::DuplicateTokenAndAmountInPayable {
+          token: taa.token.clone(),
+          amount: taa.amount,
+        });
+      }
+    }
+    Ok(())
+  }
+
+  /// Ensures that the payable accepts payments from other chains. Should be
+  /// checked before recording a payment received through a bridge.
+  pub fn check_accepts_cross_chain(
+    &self,
+    payable: &Payable,
+  ) -> Result<(), ChainbillsError> {
+    if !payable.accepts_cross_chain {
+      return Err(ChainbillsError::PayableRejectsCrossChain {});
+    }
+    Ok(())
+  }
+
+  /// Computes the fees of withdrawing the amount of a token: the withdrawal
+  /// fee percentage of the amount, capped at the token's max withdrawal fees.
+  pub fn withdrawal_fees(
+    &self,
+    config: &Config,
+    token_details: &TokenDetails,
+    amount: Uint128,
+  ) -> Uint128 {
+    // Multiplying with a wider intermediate so that large amounts don't
+    // overflow.
+    let percent = amount.multiply_ratio(
+      config.withdrawal_fee_percentage,
+      Uint128::new(10000), // 10000 is 100%
+    );
+    min(percent, token_details.max_withdrawal_fees)
+  }
+
   pub fn create_id(
     &self,
     storage: &dyn Storage,
@@ -337,7 +818,7 @@ impl Chainbills {
     user_activity_ids.push(activity_id);
     self
       .user_activity_ids
-      .save(storage, &wallet, &user_activity_ids)?;
+      .save(storage, wallet, &user_activity_ids)?;
 
     // Save the ActivityRecord ID to payable_activity_ids.
     let mut payable_activity_ids = self
@@ -354,6 +835,44 @@ impl Chainbills {
     Ok(())
   }
 
+  /// Records the payable's current balance of the token as a checkpoint for
+  /// time-weighted average balances. Only the most recent
+  /// MAX_BALANCE_CHECKPOINTS are kept.
+  pub fn record_balance_checkpoint(
+    &self,
+    storage: &mut dyn Storage,
+    env: &Env,
+    payable_id: [u8; 32],
+    payable: &Payable,
+    token: &str,
+  ) -> StdResult<()> {
+    let balance = payable
+      .balances
+      .iter()
+      .find(|balance| balance.token == token)
+      .map(|balance| balance.amount)
+      .unwrap_or_default();
+    let timestamp = env.block.time.seconds();
+    let key = (payable_id.to_vec(), token.to_string());
+    let mut checkpoints = self
+      .payable_balance_checkpoints
+      .may_load(storage, key.clone())?
+      .unwrap_or_default();
+
+    // Overwrite the last checkpoint if the balance changed again within the
+    // same block time.
+    match checkpoints.last_mut() {
+      Some(last) if last.timestamp == timestamp => last.balance = balance,
+      _ => checkpoints.push(BalanceCheckpoint { timestamp, balance }),
+    }
+    if checkpoints.len() > MAX_BALANCE_CHECKPOINTS {
+      checkpoints.remove(0);
+    }
+    self
+      .payable_balance_checkpoints
+      .save(storage, key, &checkpoints)
+  }
+
   pub fn record_update_payable_activity(
     &self,
     storage: &mut dyn Storage,
@@ -362,16 +881,16 @@ impl Chainbills {
     payable_id: [u8; 32],
     payable_count: u64,
     activity_type: ActivityType,
-  ) -> StdResult<()> {
+  ) -> Result<(), ChainbillsError> {
     /* COUNTS */
     // Increment the chain stats for activities_count.
     let mut chain_stats = self.chain_stats.load(storage)?;
-    chain_stats.activities_count = chain_stats.next_activity();
+    chain_stats.activities_count = chain_stats.next_activity()?;
     self.chain_stats.save(storage, &chain_stats)?;
 
     // Increment activities counts on the host (address) making the update.
     let mut user = self.users.load(storage, wallet)?;
-    user.activities_count = user.next_activity();
+    user.activities_count = user.next_activity()?;
     self.users.save(storage, wallet, &user)?;
 
     // Not Retrieving the Payable to update the activities count in it because
@@ -381,7 +900,7 @@ impl Chainbills {
     let activity_id = self.create_id(
       storage,
       env,
-      &wallet.to_string(),
+      wallet.as_ref(),
       "activity",
       user.activities_count,
     )?;
@@ -1,5 +1,5 @@
 use cw_utils::PaymentError;
-use sylvia::cw_std::StdError;
+use sylvia::cw_std::{StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -34,18 +34,33 @@ pub enum ChainbillsError {
   #[error("Payable is Closed")]
   PayableIsClosed {},
 
+  #[error("Payable Expired")]
+  PayableExpired {},
+
+  #[error("Payable Rejects Cross Chain")]
+  PayableRejectsCrossChain {},
+
   #[error("Payable Is Already Closed")]
   PayableIsAlreadyClosed {},
 
   #[error("Payable Is Not Closed")]
   PayableIsNotClosed {},
 
+  #[error("Duplicate Token And Amount In Payable: {token} {amount}")]
+  DuplicateTokenAndAmountInPayable { token: String, amount: Uint128 },
+
   #[error("Matching Token and Amount Not Found")]
   MatchingTokenAndAmountNotFound {},
 
   #[error("Invalid Native Token Payment")]
   InvalidNativeTokenPayment {},
 
+  #[error("Invalid Cw20 Receive Payment")]
+  InvalidCw20ReceivePayment {},
+
+  #[error("Unexpected Funds")]
+  UnexpectedFunds {},
+
   #[error("Invalid User Payment Count: {count}")]
   InvalidUserPaymentCount { count: u64 },
 
@@ -79,6 +94,9 @@ pub enum ChainbillsError {
   #[error("Insufficient Withdraw Amount")]
   InsufficientWithdrawAmount {},
 
+  #[error("Withdrawal Amount Too Small: minimum is {min}")]
+  WithdrawalAmountTooSmall { min: Uint128 },
+
   #[error("Invalid Activity Count: {count}")]
   InvalidChainActivityCount { count: u64 },
 
@@ -90,4 +108,88 @@ pub enum ChainbillsError {
 
   #[error("Invalid Activity ID: {id}")]
   InvalidActivityId { id: String },
+
+  #[error("Invalid Twab Window")]
+  InvalidTwabWindow {},
+
+  #[error("Twab Window Before Retained Checkpoints")]
+  TwabWindowBeforeRetainedCheckpoints {},
+
+  #[error("Same Token Migration: {token}")]
+  SameTokenMigration { token: String },
+
+  #[error("Invalid Fee Split: {fee_split_bps}")]
+  InvalidFeeSplit { fee_split_bps: Uint128 },
+
+  #[error("Secondary Fee Collector Not Configured")]
+  SecondaryFeeCollectorNotConfigured {},
+
+  #[error("Refund Exceeds Payment")]
+  RefundExceedsPayment {},
+
+  #[error("Token Not In Payment: {token}")]
+  TokenNotInPayment { token: String },
+
+  #[error("Insufficient Payable Balance")]
+  InsufficientPayableBalance {},
+
+  #[error("Invalid Foreign Contract")]
+  InvalidForeignContract {},
+
+  #[error("Unregistered Foreign Chain: {chain_id}")]
+  UnregisteredForeignChain { chain_id: u16 },
+
+  #[error("User Already Initialized")]
+  UserAlreadyInitialized {},
+
+  #[error("Insufficient Collected Fees: {collected}")]
+  InsufficientCollectedFees { collected: Uint128 },
+
+  #[error("Withdrawal Exceeds Max: {max}")]
+  WithdrawalExceedsMax { max: Uint128 },
+
+  #[error("Relayer Unauthorized")]
+  RelayerUnauthorized {},
+
+  #[error("Too Many Payable Balances: {max}")]
+  TooManyPayableBalances { max: u64 },
+
+  #[error("Invalid Migration Contract: {contract}")]
+  InvalidMigrationContract { contract: String },
+
+  #[error("Migration Downgrade from {stored} to {current}")]
+  MigrationDowngrade { stored: String, current: String },
+
+  #[error("Invalid Payload")]
+  InvalidPayload {},
+
+  #[error("No Payment Tokens")]
+  NoPaymentTokens {},
+
+  #[error("Duplicate Payment Token: {token}")]
+  DuplicatePaymentToken { token: String },
+
+  #[error("Overflow")]
+  Overflow {},
+
+  #[error("Memo Too Long: max {max} bytes")]
+  MemoTooLong { max: u64 },
+
+  #[error("Payment Below Minimum: min {min}")]
+  PaymentBelowMinimum { min: Uint128 },
+
+  #[error("No Withdrawal Tokens")]
+  NoWithdrawalTokens {},
+
+  #[error("Duplicate Withdrawal Token: {token}")]
+  DuplicateWithdrawalToken { token: String },
+
+  #[error("Payer Not Allowed")]
+  PayerNotAllowed {},
+
+  #[error("Payer Allowlist Too Long: max {max}")]
+  PayerAllowlistTooLong { max: u64 },
+
+  #[error("Expiry Not In Future")]
+  ExpiryNotInFuture {},
 }
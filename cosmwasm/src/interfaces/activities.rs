@@ -1,6 +1,11 @@
-use crate::contract::Chainbills;
+use crate::contract::{
+  decode_id, Chainbills, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT,
+};
 use crate::error::ChainbillsError;
-use crate::messages::{CountMessage, FetchIdMessage, IdMessage};
+use crate::messages::{
+  CountMessage, FetchIdMessage, IdMessage, PaginatedActivitiesMessage,
+  PaginatedFetchMessage,
+};
 use crate::state::{ActivityRecord, User};
 use sylvia::cw_std::{HexBinary, StdError};
 use sylvia::interface;
@@ -24,6 +29,13 @@ pub trait Activities {
     msg: FetchIdMessage,
   ) -> Result<IdMessage, Self::Error>;
 
+  #[sv::msg(query)]
+  fn user_activities(
+    &self,
+    ctx: QueryCtx,
+    msg: PaginatedFetchMessage,
+  ) -> Result<PaginatedActivitiesMessage, Self::Error>;
+
   #[sv::msg(query)]
   fn payable_activity_id(
     &self,
@@ -31,6 +43,13 @@ pub trait Activities {
     msg: FetchIdMessage,
   ) -> Result<IdMessage, Self::Error>;
 
+  #[sv::msg(query)]
+  fn payable_activities(
+    &self,
+    ctx: QueryCtx,
+    msg: PaginatedFetchMessage,
+  ) -> Result<PaginatedActivitiesMessage, Self::Error>;
+
   #[sv::msg(query)]
   fn activity(
     &self,
@@ -86,15 +105,43 @@ impl Activities for Chainbills {
     Ok(IdMessage { id })
   }
 
+  fn user_activities(
+    &self,
+    ctx: QueryCtx,
+    msg: PaginatedFetchMessage,
+  ) -> Result<PaginatedActivitiesMessage, Self::Error> {
+    // Validate the wallet address.
+    let valid_wallet = ctx.deps.api.addr_validate(&msg.reference)?;
+    let limit =
+      msg.limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let start_after = msg.start_after.unwrap_or(0) as usize;
+
+    // Get and return the activities in the requested page.
+    let mut activities = vec![];
+    for activity_id in self
+      .user_activity_ids
+      .may_load(ctx.deps.storage, &valid_wallet)?
+      .unwrap_or_default()
+      .iter()
+      .skip(start_after)
+      .take(limit)
+    {
+      activities.push(self.activities.load(ctx.deps.storage, *activity_id)?);
+    }
+    Ok(PaginatedActivitiesMessage { activities })
+  }
+
   fn payable_activity_id(
     &self,
     ctx: QueryCtx,
     msg: FetchIdMessage,
   ) -> Result<IdMessage, Self::Error> {
     // Ensure that the payable_id is valid.
-    let payable_id =
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.reference)?.as_slice())
-        .unwrap();
+    let payable_id = decode_id(&msg.reference).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId {
+        id: msg.reference.clone(),
+      }
+    })?;
     if !self.payables.has(ctx.deps.storage, payable_id) {
       return Err(ChainbillsError::InvalidPayableId { id: msg.reference });
     }
@@ -114,6 +161,39 @@ impl Activities for Chainbills {
     Ok(IdMessage { id })
   }
 
+  fn payable_activities(
+    &self,
+    ctx: QueryCtx,
+    msg: PaginatedFetchMessage,
+  ) -> Result<PaginatedActivitiesMessage, Self::Error> {
+    // Ensure that the payable_id is valid.
+    let payable_id = decode_id(&msg.reference).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId {
+        id: msg.reference.clone(),
+      }
+    })?;
+    if !self.payables.has(ctx.deps.storage, payable_id) {
+      return Err(ChainbillsError::InvalidPayableId { id: msg.reference });
+    }
+    let limit =
+      msg.limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let start_after = msg.start_after.unwrap_or(0) as usize;
+
+    // Get and return the activities in the requested page.
+    let mut activities = vec![];
+    for activity_id in self
+      .payable_activity_ids
+      .may_load(ctx.deps.storage, payable_id)?
+      .unwrap_or_default()
+      .iter()
+      .skip(start_after)
+      .take(limit)
+    {
+      activities.push(self.activities.load(ctx.deps.storage, *activity_id)?);
+    }
+    Ok(PaginatedActivitiesMessage { activities })
+  }
+
   fn activity(
     &self,
     ctx: QueryCtx,
@@ -121,7 +201,9 @@ impl Activities for Chainbills {
   ) -> Result<ActivityRecord, Self::Error> {
     match self.activities.may_load(
       ctx.deps.storage,
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.id)?.as_slice()).unwrap(),
+      decode_id(&msg.id).ok_or_else(|| ChainbillsError::InvalidActivityId {
+        id: msg.id.clone(),
+      })?,
     )? {
       Some(activity) => Ok(activity),
       None => Err(ChainbillsError::InvalidActivityId { id: msg.id }),
@@ -0,0 +1,111 @@
+use crate::contract::Chainbills;
+use crate::error::ChainbillsError;
+use crate::messages::{
+  ChainIdMessage, ForeignContractMessage, ForeignContractsMessage,
+};
+use sylvia::cw_std::{HexBinary, Order, Response, StdError, StdResult};
+use sylvia::interface;
+use sylvia::types::{ExecCtx, QueryCtx};
+
+#[interface]
+pub trait ForeignContracts {
+  type Error: From<StdError>;
+
+  #[sv::msg(query)]
+  fn foreign_contract(
+    &self,
+    ctx: QueryCtx,
+    msg: ChainIdMessage,
+  ) -> Result<ForeignContractMessage, Self::Error>;
+
+  #[sv::msg(query)]
+  fn foreign_contracts(
+    &self,
+    ctx: QueryCtx,
+  ) -> Result<ForeignContractsMessage, Self::Error>;
+
+  #[sv::msg(exec)]
+  fn register_foreign_contract(
+    &self,
+    ctx: ExecCtx,
+    msg: ForeignContractMessage,
+  ) -> Result<Response, Self::Error>;
+}
+
+impl ForeignContracts for Chainbills {
+  type Error = ChainbillsError;
+
+  fn foreign_contract(
+    &self,
+    ctx: QueryCtx,
+    msg: ChainIdMessage,
+  ) -> Result<ForeignContractMessage, Self::Error> {
+    let chain_id = msg.chain_id;
+    match self
+      .foreign_contracts
+      .may_load(ctx.deps.storage, chain_id)?
+    {
+      Some(address) => Ok(ForeignContractMessage {
+        chain_id,
+        address: HexBinary::from(&address).to_hex(),
+      }),
+      None => Err(ChainbillsError::UnregisteredForeignChain { chain_id }),
+    }
+  }
+
+  fn foreign_contracts(
+    &self,
+    ctx: QueryCtx,
+  ) -> Result<ForeignContractsMessage, Self::Error> {
+    // Foreign contracts are few (one per chain), so return all of them.
+    let contracts = self
+      .foreign_contracts
+      .range(ctx.deps.storage, None, None, Order::Ascending)
+      .map(|item| {
+        let (chain_id, address) = item?;
+        Ok(ForeignContractMessage {
+          chain_id,
+          address: HexBinary::from(&address).to_hex(),
+        })
+      })
+      .collect::<StdResult<Vec<_>>>()?;
+    Ok(ForeignContractsMessage { contracts })
+  }
+
+  fn register_foreign_contract(
+    &self,
+    ctx: ExecCtx,
+    msg: ForeignContractMessage,
+  ) -> Result<Response, Self::Error> {
+    // Only the owner can register foreign contracts.
+    let config = self.config.load(ctx.deps.storage)?;
+    if ctx.info.sender != config.owner {
+      return Err(ChainbillsError::OwnerUnauthorized {});
+    }
+
+    // A foreign contract can't share this chain's Wormhole Chain ID and its
+    // address must be a non-zero 32-byte address.
+    let ForeignContractMessage { chain_id, address } = msg;
+    let address =
+      <[u8; 32]>::try_from(HexBinary::from_hex(&address)?.as_slice())
+        .map_err(|_| ChainbillsError::InvalidForeignContract {})?;
+    if chain_id == 0
+      || chain_id == config.chain_id
+      || address.iter().all(|&x| x == 0)
+    {
+      return Err(ChainbillsError::InvalidForeignContract {});
+    }
+
+    // Save the address, overwriting any previously registered for the chain.
+    self
+      .foreign_contracts
+      .save(ctx.deps.storage, chain_id, &address)?;
+
+    // Return the Response.
+    Ok(Response::new().add_attributes([
+      ("action", "registered_foreign_contract".to_string()),
+      ("chain_id", chain_id.to_string()),
+      ("address", HexBinary::from(&address).to_hex()),
+    ]))
+  }
+}
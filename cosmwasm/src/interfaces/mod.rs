@@ -1,5 +1,7 @@
 pub mod activities;
+pub mod foreign_contracts;
 pub mod payables;
 pub mod payments;
+pub mod refunds;
 pub mod token_details;
 pub mod withdrawals;
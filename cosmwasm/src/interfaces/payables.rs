@@ -1,11 +1,21 @@
-use crate::contract::Chainbills;
+use crate::contract::{
+  decode_id, Chainbills, DEFAULT_PAGE_LIMIT, MAX_BALANCE_CHECKPOINTS,
+  MAX_PAGE_LIMIT, MAX_PAYER_ALLOWLIST,
+};
 use crate::error::ChainbillsError;
+use crate::interfaces::token_details::cw20_token_info;
 use crate::messages::{
-  CreatePayableMessage, FetchIdMessage, IdMessage,
+  CreatePayableMessage, FetchIdMessage, IdMessage, IdsMessage,
+  PayableBalancesMessage, PayableIntegrityReport, PayableSummaryMessage,
+  PayableTwabMessage, PayableVolumeMessage, PayablesByHostMessage,
+  PaymentOption, PaymentOptionsMessage, TwabMessage,
+  UpdatePayableAcceptsCrossChainMessage, UpdatePayablePayerAllowlistMessage,
   UpdatePayableTokensAndAmountsMessage,
 };
 use crate::state::{ActivityRecord, ActivityType, Payable, TokenDetails, User};
-use sylvia::cw_std::{HexBinary, Response, StdError, Uint128};
+use sylvia::cw_std::{
+  to_json_binary, HexBinary, Order, QuerierWrapper, Response, StdError, Uint128,
+};
 use sylvia::interface;
 use sylvia::types::{ExecCtx, QueryCtx};
 
@@ -20,6 +30,13 @@ pub trait Payables {
     msg: FetchIdMessage,
   ) -> Result<IdMessage, Self::Error>;
 
+  #[sv::msg(query)]
+  fn payables_by_host(
+    &self,
+    ctx: QueryCtx,
+    msg: PayablesByHostMessage,
+  ) -> Result<IdsMessage, Self::Error>;
+
   #[sv::msg(query)]
   fn payable(
     &self,
@@ -27,6 +44,50 @@ pub trait Payables {
     msg: IdMessage,
   ) -> Result<Payable, Self::Error>;
 
+  #[sv::msg(query)]
+  fn payable_balances(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PayableBalancesMessage, Self::Error>;
+
+  /// Returns the cumulative amount of each token ever paid into the payable.
+  /// Unlike its balances, these amounts don't decrease with withdrawals.
+  #[sv::msg(query)]
+  fn payable_volume(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PayableVolumeMessage, Self::Error>;
+
+  #[sv::msg(query)]
+  fn payable_summary(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PayableSummaryMessage, Self::Error>;
+
+  #[sv::msg(query)]
+  fn payable_payment_options(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PaymentOptionsMessage, Self::Error>;
+
+  #[sv::msg(query)]
+  fn payable_twab(
+    &self,
+    ctx: QueryCtx,
+    msg: PayableTwabMessage,
+  ) -> Result<TwabMessage, Self::Error>;
+
+  #[sv::msg(query)]
+  fn verify_payable_integrity(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PayableIntegrityReport, Self::Error>;
+
   #[sv::msg(exec)]
   fn create_payable(
     &self,
@@ -54,6 +115,20 @@ pub trait Payables {
     ctx: ExecCtx,
     msg: UpdatePayableTokensAndAmountsMessage,
   ) -> Result<Response, Self::Error>;
+
+  #[sv::msg(exec)]
+  fn update_payable_accepts_cross_chain(
+    &self,
+    ctx: ExecCtx,
+    msg: UpdatePayableAcceptsCrossChainMessage,
+  ) -> Result<Response, Self::Error>;
+
+  #[sv::msg(exec)]
+  fn update_payable_payer_allowlist(
+    &self,
+    ctx: ExecCtx,
+    msg: UpdatePayablePayerAllowlistMessage,
+  ) -> Result<Response, Self::Error>;
 }
 
 impl Payables for Chainbills {
@@ -85,6 +160,32 @@ impl Payables for Chainbills {
     Ok(IdMessage { id })
   }
 
+  /// Returns the host's payable IDs in the order they were created. To fetch
+  /// the next page, pass the count of the last received ID as `start_after`.
+  fn payables_by_host(
+    &self,
+    ctx: QueryCtx,
+    msg: PayablesByHostMessage,
+  ) -> Result<IdsMessage, Self::Error> {
+    // Validate the host address.
+    let valid_host = ctx.deps.api.addr_validate(&msg.host)?;
+    let limit =
+      msg.limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let start_after = msg.start_after.unwrap_or(0) as usize;
+
+    // Get and return the payable IDs in the requested page.
+    let ids = self
+      .user_payable_ids
+      .may_load(ctx.deps.storage, &valid_host)?
+      .unwrap_or_default()
+      .iter()
+      .skip(start_after)
+      .take(limit)
+      .map(|id| HexBinary::from(id).to_hex())
+      .collect();
+    Ok(IdsMessage { ids })
+  }
+
   fn payable(
     &self,
     ctx: QueryCtx,
@@ -92,13 +193,281 @@ impl Payables for Chainbills {
   ) -> Result<Payable, Self::Error> {
     match self.payables.may_load(
       ctx.deps.storage,
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.id)?.as_slice()).unwrap(),
+      decode_id(&msg.id).ok_or_else(|| ChainbillsError::InvalidPayableId {
+        id: msg.id.clone(),
+      })?,
     )? {
       Some(payable) => Ok(payable),
       None => Err(ChainbillsError::InvalidPayableId { id: msg.id }),
     }
   }
 
+  fn payable_balances(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PayableBalancesMessage, Self::Error> {
+    let payable = self.payable(ctx, msg)?;
+    Ok(PayableBalancesMessage {
+      balances: payable.balances,
+      is_closed: payable.is_closed,
+      payments_count: payable.payments_count,
+      withdrawals_count: payable.withdrawals_count,
+    })
+  }
+
+  fn payable_volume(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PayableVolumeMessage, Self::Error> {
+    let payable = self.payable(ctx, msg)?;
+    Ok(PayableVolumeMessage {
+      volume: payable.total_received_per_token,
+    })
+  }
+
+  fn payable_summary(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PayableSummaryMessage, Self::Error> {
+    let payable = self.payable(ctx, msg)?;
+    Ok(PayableSummaryMessage {
+      host: payable.host,
+      allowed_tokens_and_amounts: payable.allowed_tokens_and_amounts,
+      is_closed: payable.is_closed,
+      payments_count: payable.payments_count,
+      balances_count: payable.balances.len() as u64,
+    })
+  }
+
+  fn payable_payment_options(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PaymentOptionsMessage, Self::Error> {
+    let payable = match self.payables.may_load(
+      ctx.deps.storage,
+      decode_id(&msg.id).ok_or_else(|| ChainbillsError::InvalidPayableId {
+        id: msg.id.clone(),
+      })?,
+    )? {
+      Some(payable) => payable,
+      None => return Err(ChainbillsError::InvalidPayableId { id: msg.id }),
+    };
+
+    // For free payables, offer every currently supported token with no
+    // specific amount.
+    if payable.accepts_free_payments() {
+      let mut options = vec![];
+      for item in
+        self
+          .token_details
+          .range(ctx.deps.storage, None, None, Order::Ascending)
+      {
+        let (token, details) = item?;
+        if details.is_supported {
+          options.push(payment_option(&ctx.deps.querier, token, None, details));
+        }
+      }
+      return Ok(PaymentOptionsMessage { options });
+    }
+
+    // Otherwise, enrich each of the payable's allowed tokens and amounts.
+    let mut options = vec![];
+    for taa in payable.allowed_tokens_and_amounts {
+      let details = self
+        .token_details
+        .load(ctx.deps.storage, taa.token.clone())
+        .unwrap_or(TokenDetails::initialize(false, false, Uint128::zero()));
+      options.push(payment_option(
+        &ctx.deps.querier,
+        taa.token,
+        Some(taa.amount),
+        details,
+      ));
+    }
+    Ok(PaymentOptionsMessage { options })
+  }
+
+  /// Returns the time-weighted average balance of the token in the payable
+  /// over [start_ts, end_ts), from the payable's balance checkpoints.
+  ///
+  /// Only the most recent MAX_BALANCE_CHECKPOINTS balance changes are kept
+  /// per token. If older ones were dropped, windows that start before the
+  /// oldest retained checkpoint can't be computed and are rejected.
+  fn payable_twab(
+    &self,
+    ctx: QueryCtx,
+    msg: PayableTwabMessage,
+  ) -> Result<TwabMessage, Self::Error> {
+    let PayableTwabMessage {
+      payable_id,
+      token,
+      start_ts,
+      end_ts,
+    } = msg;
+    let payable_id = decode_id(&payable_id).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId {
+        id: payable_id.clone(),
+      }
+    })?;
+    if !self.payables.has(ctx.deps.storage, payable_id) {
+      return Err(ChainbillsError::InvalidPayableId {
+        id: HexBinary::from(&payable_id).to_hex(),
+      });
+    }
+    if start_ts >= end_ts {
+      return Err(ChainbillsError::InvalidTwabWindow {});
+    }
+
+    let checkpoints = self
+      .payable_balance_checkpoints
+      .may_load(ctx.deps.storage, (payable_id.to_vec(), token))?
+      .unwrap_or_default();
+    if checkpoints.len() == MAX_BALANCE_CHECKPOINTS
+      && start_ts < checkpoints[0].timestamp
+    {
+      return Err(ChainbillsError::TwabWindowBeforeRetainedCheckpoints {});
+    }
+
+    // Sum up each balance multiplied by how long it was held within the
+    // window. The balance before the first checkpoint is zero.
+    let mut cumulative = Uint128::zero();
+    let mut balance = Uint128::zero();
+    let mut from = start_ts;
+    for checkpoint in checkpoints.iter() {
+      if checkpoint.timestamp >= end_ts {
+        break;
+      }
+      if checkpoint.timestamp > from {
+        let duration = Uint128::from(checkpoint.timestamp - from);
+        cumulative = balance
+          .checked_mul(duration)
+          .and_then(|held| cumulative.checked_add(held))
+          .map_err(|_| ChainbillsError::Overflow {})?;
+        from = checkpoint.timestamp;
+      }
+      balance = checkpoint.balance;
+    }
+    let duration = Uint128::from(end_ts - from);
+    cumulative = balance
+      .checked_mul(duration)
+      .and_then(|held| cumulative.checked_add(held))
+      .map_err(|_| ChainbillsError::Overflow {})?;
+
+    let twab = cumulative
+      .checked_div(Uint128::from(end_ts - start_ts))
+      .map_err(|_| ChainbillsError::Overflow {})?;
+    Ok(TwabMessage { twab })
+  }
+
+  /// Checks that the payable's stored counts match its indexed payments,
+  /// withdrawals, and activities, that each indexed record exists and points
+  /// back to this payable at the right count, and that the host's payable
+  /// IDs include this payable at its host_count.
+  fn verify_payable_integrity(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PayableIntegrityReport, Self::Error> {
+    let payable_id = decode_id(&msg.id).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId { id: msg.id.clone() }
+    })?;
+    let payable = match self.payables.may_load(ctx.deps.storage, payable_id)? {
+      Some(payable) => payable,
+      None => return Err(ChainbillsError::InvalidPayableId { id: msg.id }),
+    };
+    let mut issues = vec![];
+
+    // Ensure that the host's payable IDs resolve to this payable.
+    let host_payable_ids = self
+      .user_payable_ids
+      .may_load(ctx.deps.storage, &payable.host)?
+      .unwrap_or_default();
+    if payable.host_count == 0
+      || host_payable_ids.get((payable.host_count - 1) as usize)
+        != Some(&payable_id)
+    {
+      issues.push(format!(
+        "host payable id at count {} does not match",
+        payable.host_count
+      ));
+    }
+
+    // Ensure that the payments match the payments_count.
+    let payment_ids = self
+      .payable_payment_ids
+      .may_load(ctx.deps.storage, payable_id)?
+      .unwrap_or_default();
+    if payment_ids.len() as u64 != payable.payments_count {
+      issues.push(format!(
+        "payments_count is {} but {} payment ids are stored",
+        payable.payments_count,
+        payment_ids.len()
+      ));
+    }
+    for (i, id) in payment_ids.iter().enumerate() {
+      let count = i as u64 + 1;
+      match self.payable_payments.may_load(ctx.deps.storage, *id)? {
+        Some(p) if p.payable_id == payable_id && p.payable_count == count => {}
+        Some(_) => issues.push(format!("payment {count} is inconsistent")),
+        None => issues.push(format!("payment {count} is missing")),
+      }
+    }
+
+    // Ensure that the withdrawals match the withdrawals_count.
+    let withdrawal_ids = self
+      .payable_withdrawal_ids
+      .may_load(ctx.deps.storage, payable_id)?
+      .unwrap_or_default();
+    if withdrawal_ids.len() as u64 != payable.withdrawals_count {
+      issues.push(format!(
+        "withdrawals_count is {} but {} withdrawal ids are stored",
+        payable.withdrawals_count,
+        withdrawal_ids.len()
+      ));
+    }
+    for (i, id) in withdrawal_ids.iter().enumerate() {
+      let count = i as u64 + 1;
+      match self.withdrawals.may_load(ctx.deps.storage, *id)? {
+        Some(w) if w.payable_id == payable_id && w.payable_count == count => {}
+        Some(_) => issues.push(format!("withdrawal {count} is inconsistent")),
+        None => issues.push(format!("withdrawal {count} is missing")),
+      }
+    }
+
+    // Ensure that the activities match the activities_count.
+    let activity_ids = self
+      .payable_activity_ids
+      .may_load(ctx.deps.storage, payable_id)?
+      .unwrap_or_default();
+    if activity_ids.len() as u64 != payable.activities_count {
+      issues.push(format!(
+        "activities_count is {} but {} activity ids are stored",
+        payable.activities_count,
+        activity_ids.len()
+      ));
+    }
+    for (i, id) in activity_ids.iter().enumerate() {
+      let count = i as u64 + 1;
+      match self.activities.may_load(ctx.deps.storage, *id)? {
+        Some(a) if a.payable_count == count => {}
+        Some(_) => issues.push(format!("activity {count} is inconsistent")),
+        None => issues.push(format!("activity {count} is missing")),
+      }
+    }
+
+    Ok(PayableIntegrityReport {
+      is_consistent: issues.is_empty(),
+      payment_ids_count: payment_ids.len() as u64,
+      withdrawal_ids_count: withdrawal_ids.len() as u64,
+      activity_ids_count: activity_ids.len() as u64,
+      issues,
+    })
+  }
+
   fn create_payable(
     &self,
     ctx: ExecCtx,
@@ -107,22 +476,16 @@ impl Payables for Chainbills {
     /* CHECKS */
     let CreatePayableMessage {
       allowed_tokens_and_amounts,
+      expires_at,
     } = msg;
-    for taa in allowed_tokens_and_amounts.iter() {
-      // Ensure that the token is supported.
-      let token_details = self
-        .token_details
-        .load(ctx.deps.storage, taa.token.clone())
-        .unwrap_or(TokenDetails::initialize(false, false, Uint128::zero()));
-      if !token_details.is_supported {
-        return Err(ChainbillsError::UnsupportedToken {
-          token: taa.token.clone(),
-        });
-      }
-
-      // Ensure that all specified acceptable amounts are greater than zero.
-      if taa.amount.is_zero() {
-        return Err(ChainbillsError::ZeroAmountSpecified {});
+    self.check_allowed_tokens_and_amounts(
+      ctx.deps.storage,
+      &allowed_tokens_and_amounts,
+    )?;
+    // Ensure that the payable doesn't expire at or before its creation.
+    if let Some(expires_at) = expires_at {
+      if expires_at <= ctx.env.block.time.seconds() {
+        return Err(ChainbillsError::ExpiryNotInFuture {});
       }
     }
 
@@ -136,14 +499,14 @@ impl Payables for Chainbills {
       &ctx.info.sender,
     )?;
     let mut user = self.users.load(ctx.deps.storage, &ctx.info.sender)?;
-    user.payables_count = user.next_payable();
-    user.activities_count = user.next_activity();
+    user.payables_count = user.next_payable()?;
+    user.activities_count = user.next_activity()?;
     self.users.save(ctx.deps.storage, &ctx.info.sender, &user)?;
 
     // Increment the chain stats for payables_count and activities_count.
     let mut chain_stats = self.chain_stats.load(ctx.deps.storage)?;
-    chain_stats.payables_count = chain_stats.next_payable();
-    chain_stats.activities_count = chain_stats.next_activity();
+    chain_stats.payables_count = chain_stats.next_payable()?;
+    chain_stats.activities_count = chain_stats.next_activity()?;
     self.chain_stats.save(ctx.deps.storage, &chain_stats)?;
 
     /* PAYABLE DATA STRUCTURE */
@@ -151,7 +514,7 @@ impl Payables for Chainbills {
     let payable_id = self.create_id(
       ctx.deps.storage,
       &ctx.env,
-      &ctx.info.sender.as_str(),
+      ctx.info.sender.as_str(),
       "payable",
       user.payables_count,
     )?;
@@ -180,6 +543,10 @@ impl Payables for Chainbills {
       withdrawals_count: 0,
       activities_count: 1,
       is_closed: false,
+      accepts_cross_chain: true,
+      expires_at,
+      total_received_per_token: vec![],
+      payer_allowlist: vec![],
     };
     self.payables.save(ctx.deps.storage, payable_id, &payable)?;
 
@@ -188,7 +555,7 @@ impl Payables for Chainbills {
     let activity_id = self.create_id(
       ctx.deps.storage,
       &ctx.env,
-      &ctx.info.sender.as_str(),
+      ctx.info.sender.as_str(),
       "activity",
       user.activities_count,
     )?;
@@ -219,6 +586,11 @@ impl Payables for Chainbills {
     // Return the Response.
     Ok(
       Response::new()
+        // Return the payable ID as data to save clients from parsing it from
+        // the events.
+        .set_data(to_json_binary(&IdMessage {
+          id: HexBinary::from(&payable_id).to_hex(),
+        })?)
         .add_attributes(user_resp_attribs) // Add the user init attributes.
         .add_attributes([
           ("action", "created_payable".to_string()),
@@ -237,15 +609,16 @@ impl Payables for Chainbills {
   ) -> Result<Response, Self::Error> {
     /* CHECKS */
     // Ensure that the payable_id is valid.
-    let payable_id =
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.id)?.as_slice()).unwrap();
+    let payable_id = decode_id(&msg.id).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId { id: msg.id.clone() }
+    })?;
     if !self.payables.has(ctx.deps.storage, payable_id) {
       return Err(ChainbillsError::InvalidPayableId { id: msg.id });
     }
     let mut payable = self.payables.load(ctx.deps.storage, payable_id)?;
 
     // Ensure that the caller owns the payable.
-    if payable.host != &ctx.info.sender {
+    if payable.host != ctx.info.sender {
       return Err(ChainbillsError::NotYourPayable {});
     }
 
@@ -259,7 +632,7 @@ impl Payables for Chainbills {
     payable.is_closed = true;
 
     // Increment the activity count on the payable.
-    payable.activities_count = payable.next_activity();
+    payable.activities_count = payable.next_activity()?;
 
     // Save the payable.
     self.payables.save(ctx.deps.storage, payable_id, &payable)?;
@@ -289,15 +662,16 @@ impl Payables for Chainbills {
   ) -> Result<Response, Self::Error> {
     /* CHECKS */
     // Ensure that the payable_id is valid.
-    let payable_id =
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.id)?.as_slice()).unwrap();
+    let payable_id = decode_id(&msg.id).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId { id: msg.id.clone() }
+    })?;
     if !self.payables.has(ctx.deps.storage, payable_id) {
       return Err(ChainbillsError::InvalidPayableId { id: msg.id });
     }
     let mut payable = self.payables.load(ctx.deps.storage, payable_id)?;
 
     // Ensure that the caller owns the payable.
-    if payable.host != &ctx.info.sender {
+    if payable.host != ctx.info.sender {
       return Err(ChainbillsError::NotYourPayable {});
     }
 
@@ -311,7 +685,7 @@ impl Payables for Chainbills {
     payable.is_closed = false;
 
     // Increment the activity count on the payable.
-    payable.activities_count = payable.next_activity();
+    payable.activities_count = payable.next_activity()?;
 
     // Save the payable.
     self.payables.save(ctx.deps.storage, payable_id, &payable)?;
@@ -341,16 +715,18 @@ impl Payables for Chainbills {
   ) -> Result<Response, Self::Error> {
     /* CHECKS */
     // Ensure that the payable_id is valid.
-    let payable_id =
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.payable_id)?.as_slice())
-        .unwrap();
+    let payable_id = decode_id(&msg.payable_id).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId {
+        id: msg.payable_id.clone(),
+      }
+    })?;
     if !self.payables.has(ctx.deps.storage, payable_id) {
       return Err(ChainbillsError::InvalidPayableId { id: msg.payable_id });
     }
     let mut payable = self.payables.load(ctx.deps.storage, payable_id)?;
 
     // Ensure that the caller owns the payable.
-    if payable.host != &ctx.info.sender {
+    if payable.host != ctx.info.sender {
       return Err(ChainbillsError::NotYourPayable {});
     }
 
@@ -358,30 +734,17 @@ impl Payables for Chainbills {
       allowed_tokens_and_amounts,
       ..
     } = msg;
-    for taa in allowed_tokens_and_amounts.iter() {
-      // Ensure that the token is supported.
-      let token_details = self
-        .token_details
-        .load(ctx.deps.storage, taa.token.clone())
-        .unwrap_or(TokenDetails::initialize(false, false, Uint128::zero()));
-      if !token_details.is_supported {
-        return Err(ChainbillsError::UnsupportedToken {
-          token: taa.token.clone(),
-        });
-      }
-
-      // Ensure that all specified acceptable amounts are greater than zero.
-      if taa.amount.is_zero() {
-        return Err(ChainbillsError::ZeroAmountSpecified {});
-      }
-    }
+    self.check_allowed_tokens_and_amounts(
+      ctx.deps.storage,
+      &allowed_tokens_and_amounts,
+    )?;
 
     /* STATE CHANGES */
     // Update the payable's allowed_tokens_and_amounts.
     payable.allowed_tokens_and_amounts = allowed_tokens_and_amounts;
 
     // Increment the activity count on the payable.
-    payable.activities_count = payable.next_activity();
+    payable.activities_count = payable.next_activity()?;
 
     // Save the payable.
     self.payables.save(ctx.deps.storage, payable_id, &payable)?;
@@ -403,4 +766,110 @@ impl Payables for Chainbills {
       ("host_wallet", ctx.info.sender.to_string()),
     ]))
   }
+
+  fn update_payable_accepts_cross_chain(
+    &self,
+    ctx: ExecCtx,
+    msg: UpdatePayableAcceptsCrossChainMessage,
+  ) -> Result<Response, Self::Error> {
+    /* CHECKS */
+    // Ensure that the payable_id is valid.
+    let payable_id = decode_id(&msg.payable_id).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId {
+        id: msg.payable_id.clone(),
+      }
+    })?;
+    if !self.payables.has(ctx.deps.storage, payable_id) {
+      return Err(ChainbillsError::InvalidPayableId { id: msg.payable_id });
+    }
+    let mut payable = self.payables.load(ctx.deps.storage, payable_id)?;
+
+    // Ensure that the caller owns the payable.
+    if payable.host != ctx.info.sender {
+      return Err(ChainbillsError::NotYourPayable {});
+    }
+
+    /* STATE CHANGES */
+    // Update and save the payable.
+    payable.accepts_cross_chain = msg.accepts_cross_chain;
+    self.payables.save(ctx.deps.storage, payable_id, &payable)?;
+
+    // Return the Response.
+    Ok(Response::new().add_attributes([
+      ("action", "updated_payable_accepts_cross_chain".to_string()),
+      ("payable_id", HexBinary::from(&payable_id).to_hex()),
+      ("host_wallet", ctx.info.sender.to_string()),
+      ("accepts_cross_chain", msg.accepts_cross_chain.to_string()),
+    ]))
+  }
+
+  fn update_payable_payer_allowlist(
+    &self,
+    ctx: ExecCtx,
+    msg: UpdatePayablePayerAllowlistMessage,
+  ) -> Result<Response, Self::Error> {
+    /* CHECKS */
+    // Ensure that the payable_id is valid.
+    let payable_id = decode_id(&msg.payable_id).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId {
+        id: msg.payable_id.clone(),
+      }
+    })?;
+    if !self.payables.has(ctx.deps.storage, payable_id) {
+      return Err(ChainbillsError::InvalidPayableId { id: msg.payable_id });
+    }
+    let mut payable = self.payables.load(ctx.deps.storage, payable_id)?;
+
+    // Ensure that the caller owns the payable.
+    if payable.host != ctx.info.sender {
+      return Err(ChainbillsError::NotYourPayable {});
+    }
+
+    // Ensure that the allowlist isn't too long.
+    if msg.payer_allowlist.len() > MAX_PAYER_ALLOWLIST {
+      return Err(ChainbillsError::PayerAllowlistTooLong {
+        max: MAX_PAYER_ALLOWLIST as u64,
+      });
+    }
+
+    // Validate the wallets, skipping repeated ones.
+    let mut payer_allowlist = Vec::with_capacity(msg.payer_allowlist.len());
+    for wallet in &msg.payer_allowlist {
+      let wallet = ctx.deps.api.addr_validate(wallet)?;
+      if !payer_allowlist.contains(&wallet) {
+        payer_allowlist.push(wallet);
+      }
+    }
+
+    /* STATE CHANGES */
+    // Update and save the payable.
+    payable.payer_allowlist = payer_allowlist;
+    self.payables.save(ctx.deps.storage, payable_id, &payable)?;
+
+    // Return the Response.
+    Ok(Response::new().add_attributes([
+      ("action", "updated_payable_payer_allowlist".to_string()),
+      ("payable_id", HexBinary::from(&payable_id).to_hex()),
+      ("host_wallet", ctx.info.sender.to_string()),
+      ("payers_count", payable.payer_allowlist.len().to_string()),
+    ]))
+  }
+}
+
+/// Builds a PaymentOption, fetching the symbol and decimals of Cw20 tokens.
+fn payment_option(
+  querier: &QuerierWrapper,
+  token: String,
+  amount: Option<Uint128>,
+  details: TokenDetails,
+) -> PaymentOption {
+  let info = cw20_token_info(querier, &token, details.is_native_token);
+  PaymentOption {
+    token,
+    amount,
+    is_native_token: details.is_native_token,
+    is_supported: details.is_supported,
+    symbol: info.as_ref().map(|i| i.symbol.clone()),
+    decimals: info.map(|i| i.decimals),
+  }
 }
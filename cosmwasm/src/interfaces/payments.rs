@@ -1,16 +1,28 @@
-use crate::contract::Chainbills;
+use crate::contract::{
+  decode_id, Chainbills, DEFAULT_PAGE_LIMIT, MAX_MEMO_LENGTH, MAX_PAGE_LIMIT,
+  MAX_PAYABLE_BALANCES, MAX_SCAN_LIMIT,
+};
 use crate::error::ChainbillsError;
+use crate::interfaces::payables::Payables;
 use crate::messages::{
-  CountMessage, FetchIdMessage, IdMessage, PerChainPayablePaymentIdMessage,
+  ChainPaymentsCount, CountMessage, FetchIdMessage, IdMessage, IdsMessage,
+  PaginatedFetchMessage, PaginatedPayablePaymentsMessage,
+  PaginatedPaymentsMessage, PaginatedUserPaymentsMessage, PayMultipleMessage,
+  PayablePaymentStatsMessage, PayablePaymentsByTokenMessage,
+  PayablePaymentsPerChainMessage, PerChainPayablePaymentIdMessage,
   PerChainPayablePaymentsCountMessage, TransactionInfoMessage,
+  UserPaymentRecord, UserPaymentsMessage, UserPaymentsToHostMessage,
 };
 use crate::state::{
   ActivityRecord, ActivityType, PayablePayment, TokenAndAmount, TokenDetails,
   User, UserPayment,
 };
 use cw20::Cw20ExecuteMsg;
+use cw_storage_plus::Map;
+use serde::{de::DeserializeOwned, Serialize};
 use sylvia::cw_std::{
-  to_json_binary, HexBinary, Response, StdError, Uint128, WasmMsg,
+  from_json, to_json_binary, Addr, Binary, HexBinary, Order, Response,
+  StdError, StdResult, Storage, Uint128, WasmMsg,
 };
 use sylvia::interface;
 use sylvia::types::{ExecCtx, QueryCtx};
@@ -33,6 +45,29 @@ pub trait Payments {
     msg: IdMessage,
   ) -> Result<UserPayment, Self::Error>;
 
+  /// Returns the wallet's payments in the order they were made, optionally
+  /// only those made within the (inclusive) time range.
+  #[sv::msg(query)]
+  fn user_payments(
+    &self,
+    ctx: QueryCtx,
+    msg: PaginatedPaymentsMessage,
+  ) -> Result<PaginatedUserPaymentsMessage, Self::Error>;
+
+  #[sv::msg(query)]
+  fn user_paid_payables(
+    &self,
+    ctx: QueryCtx,
+    msg: PaginatedFetchMessage,
+  ) -> Result<IdsMessage, Self::Error>;
+
+  #[sv::msg(query)]
+  fn user_payments_to_host(
+    &self,
+    ctx: QueryCtx,
+    msg: UserPaymentsToHostMessage,
+  ) -> Result<UserPaymentsMessage, Self::Error>;
+
   #[sv::msg(query)]
   fn payable_payment_id(
     &self,
@@ -47,6 +82,25 @@ pub trait Payments {
     msg: IdMessage,
   ) -> Result<PayablePayment, Self::Error>;
 
+  /// Returns the payable's payments in the order they were made, optionally
+  /// only those made within the (inclusive) time range.
+  #[sv::msg(query)]
+  fn payable_payments(
+    &self,
+    ctx: QueryCtx,
+    msg: PaginatedPaymentsMessage,
+  ) -> Result<PaginatedPayablePaymentsMessage, Self::Error>;
+
+  /// Returns the payable's payments that involved the given token, in the
+  /// order they were made, optionally only those made within the
+  /// (inclusive) time range.
+  #[sv::msg(query)]
+  fn payable_payments_by_token(
+    &self,
+    ctx: QueryCtx,
+    msg: PayablePaymentsByTokenMessage,
+  ) -> Result<PaginatedPayablePaymentsMessage, Self::Error>;
+
   #[sv::msg(query)]
   fn per_chain_payable_payment_count(
     &self,
@@ -54,6 +108,20 @@ pub trait Payments {
     msg: PerChainPayablePaymentsCountMessage,
   ) -> Result<CountMessage, Self::Error>;
 
+  #[sv::msg(query)]
+  fn payable_payments_per_chain(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PayablePaymentsPerChainMessage, Self::Error>;
+
+  #[sv::msg(query)]
+  fn payable_payment_stats(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PayablePaymentStatsMessage, Self::Error>;
+
   #[sv::msg(query)]
   fn per_chain_payable_payment_id(
     &self,
@@ -67,6 +135,26 @@ pub trait Payments {
     ctx: ExecCtx,
     data: TransactionInfoMessage,
   ) -> Result<Response, Self::Error>;
+
+  /// Pays a combination of tokens into a payable as a single payment. Each
+  /// token and amount must match the payable's allowed set if it has one.
+  #[sv::msg(exec)]
+  fn pay_multiple(
+    &self,
+    ctx: ExecCtx,
+    data: PayMultipleMessage,
+  ) -> Result<Response, Self::Error>;
+
+  /// Cw20 receive hook. Allows paying with a single CW20 Send whose msg is
+  /// the TransactionInfoMessage of the payment, without a prior allowance.
+  #[sv::msg(exec)]
+  fn receive(
+    &self,
+    ctx: ExecCtx,
+    sender: String,
+    amount: Uint128,
+    msg: Binary,
+  ) -> Result<Response, Self::Error>;
 }
 
 impl Payments for Chainbills {
@@ -105,22 +193,129 @@ impl Payments for Chainbills {
   ) -> Result<UserPayment, Self::Error> {
     match self.user_payments.may_load(
       ctx.deps.storage,
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.id)?.as_slice()).unwrap(),
+      decode_id(&msg.id).ok_or_else(|| ChainbillsError::InvalidPaymentId {
+        id: msg.id.clone(),
+      })?,
     )? {
       Some(payment) => Ok(payment),
       None => Err(ChainbillsError::InvalidPaymentId { id: msg.id }),
     }
   }
 
+  fn user_payments(
+    &self,
+    ctx: QueryCtx,
+    msg: PaginatedPaymentsMessage,
+  ) -> Result<PaginatedUserPaymentsMessage, Self::Error> {
+    // Validate the wallet address.
+    let valid_wallet = ctx.deps.api.addr_validate(&msg.reference)?;
+
+    // Get and return the payments in the requested page.
+    let payment_ids = self
+      .user_payment_ids
+      .may_load(ctx.deps.storage, &valid_wallet)?
+      .unwrap_or_default();
+    let payments = self.payments_page(
+      ctx.deps.storage,
+      &self.user_payments,
+      |payment| payment.timestamp,
+      &payment_ids,
+      msg.start_after,
+      msg.limit,
+      (msg.from_timestamp, msg.to_timestamp),
+    )?;
+    Ok(PaginatedUserPaymentsMessage { payments })
+  }
+
+  /// Returns the distinct payable IDs that the wallet has paid, in the order
+  /// of their first payments. To fetch the next page, pass the count of the
+  /// last received ID as `start_after`.
+  fn user_paid_payables(
+    &self,
+    ctx: QueryCtx,
+    msg: PaginatedFetchMessage,
+  ) -> Result<IdsMessage, Self::Error> {
+    // Validate the wallet address.
+    let valid_wallet = ctx.deps.api.addr_validate(&msg.reference)?;
+    let limit =
+      msg.limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let start_after = msg.start_after.unwrap_or(0) as usize;
+
+    // Get and return the payable IDs in the requested page.
+    let ids = self
+      .user_paid_payable_ids
+      .may_load(ctx.deps.storage, &valid_wallet)?
+      .unwrap_or_default()
+      .iter()
+      .skip(start_after)
+      .take(limit)
+      .map(|id| HexBinary::from(id).to_hex())
+      .collect();
+    Ok(IdsMessage { ids })
+  }
+
+  /// Walks the wallet's payments (from `start`) and joins each with its
+  /// payable, keeping those whose payable belongs to the given host. As this
+  /// scans the user's payments, results are capped by the (clamped) limit
+  /// and at most MAX_SCAN_LIMIT payments are scanned per call. The returned
+  /// cursor continues the scan, even if no payment matched in this call.
+  fn user_payments_to_host(
+    &self,
+    ctx: QueryCtx,
+    msg: UserPaymentsToHostMessage,
+  ) -> Result<UserPaymentsMessage, Self::Error> {
+    // Validate the wallet and host addresses.
+    let valid_wallet = ctx.deps.api.addr_validate(&msg.wallet)?;
+    let valid_host = ctx.deps.api.addr_validate(&msg.host)?;
+    let limit =
+      msg.limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let start = msg.start.unwrap_or(1).max(1);
+
+    let payment_ids = self
+      .user_payment_ids
+      .may_load(ctx.deps.storage, &valid_wallet)?
+      .unwrap_or_default();
+
+    let mut payments = vec![];
+    let mut next = None;
+    for (scanned, (i, payment_id)) in payment_ids
+      .iter()
+      .enumerate()
+      .skip((start - 1) as usize)
+      .enumerate()
+    {
+      if payments.len() == limit || scanned == MAX_SCAN_LIMIT {
+        next = Some(i as u64 + 1);
+        break;
+      }
+
+      let payment = self.user_payments.load(ctx.deps.storage, *payment_id)?;
+      let is_to_host = self
+        .payables
+        .may_load(ctx.deps.storage, payment.payable_id)?
+        .is_some_and(|payable| payable.host == valid_host);
+      if is_to_host {
+        payments.push(UserPaymentRecord {
+          id: HexBinary::from(payment_id).to_hex(),
+          payment,
+        });
+      }
+    }
+
+    Ok(UserPaymentsMessage { payments, next })
+  }
+
   fn payable_payment_id(
     &self,
     ctx: QueryCtx,
     msg: FetchIdMessage,
   ) -> Result<IdMessage, Self::Error> {
     // Ensure that the payable_id is valid.
-    let payable_id =
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.reference)?.as_slice())
-        .unwrap();
+    let payable_id = decode_id(&msg.reference).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId {
+        id: msg.reference.clone(),
+      }
+    })?;
     if !self.payables.has(ctx.deps.storage, payable_id) {
       return Err(ChainbillsError::InvalidPayableId { id: msg.reference });
     }
@@ -147,22 +342,93 @@ impl Payments for Chainbills {
   ) -> Result<PayablePayment, Self::Error> {
     match self.payable_payments.may_load(
       ctx.deps.storage,
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.id)?.as_slice()).unwrap(),
+      decode_id(&msg.id).ok_or_else(|| ChainbillsError::InvalidPaymentId {
+        id: msg.id.clone(),
+      })?,
     )? {
       Some(payment) => Ok(payment),
       None => Err(ChainbillsError::InvalidPaymentId { id: msg.id }),
     }
   }
 
+  fn payable_payments(
+    &self,
+    ctx: QueryCtx,
+    msg: PaginatedPaymentsMessage,
+  ) -> Result<PaginatedPayablePaymentsMessage, Self::Error> {
+    // Ensure that the payable_id is valid.
+    let payable_id = decode_id(&msg.reference).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId {
+        id: msg.reference.clone(),
+      }
+    })?;
+    if !self.payables.has(ctx.deps.storage, payable_id) {
+      return Err(ChainbillsError::InvalidPayableId { id: msg.reference });
+    }
+
+    // Get and return the payments in the requested page.
+    let payment_ids = self
+      .payable_payment_ids
+      .may_load(ctx.deps.storage, payable_id)?
+      .unwrap_or_default();
+    let payments = self.payments_page(
+      ctx.deps.storage,
+      &self.payable_payments,
+      |payment| payment.timestamp,
+      &payment_ids,
+      msg.start_after,
+      msg.limit,
+      (msg.from_timestamp, msg.to_timestamp),
+    )?;
+    Ok(PaginatedPayablePaymentsMessage { payments })
+  }
+
+  /// Pages through the payable_token_payment_ids index, so that only the
+  /// token's payments are loaded. Multi-token payments are listed under each
+  /// of their tokens.
+  fn payable_payments_by_token(
+    &self,
+    ctx: QueryCtx,
+    msg: PayablePaymentsByTokenMessage,
+  ) -> Result<PaginatedPayablePaymentsMessage, Self::Error> {
+    // Ensure that the payable_id is valid.
+    let payable_id = decode_id(&msg.payable_id).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId {
+        id: msg.payable_id.clone(),
+      }
+    })?;
+    if !self.payables.has(ctx.deps.storage, payable_id) {
+      return Err(ChainbillsError::InvalidPayableId { id: msg.payable_id });
+    }
+
+    // Get and return the token's payments in the requested page.
+    let payment_ids = self
+      .payable_token_payment_ids
+      .may_load(ctx.deps.storage, (payable_id.to_vec(), msg.token))?
+      .unwrap_or_default();
+    let payments = self.payments_page(
+      ctx.deps.storage,
+      &self.payable_payments,
+      |payment| payment.timestamp,
+      &payment_ids,
+      msg.start_after,
+      msg.limit,
+      (msg.from_timestamp, msg.to_timestamp),
+    )?;
+    Ok(PaginatedPayablePaymentsMessage { payments })
+  }
+
   fn per_chain_payable_payment_count(
     &self,
     ctx: QueryCtx,
     msg: PerChainPayablePaymentsCountMessage,
   ) -> Result<CountMessage, Self::Error> {
     // Ensure that the payable_id is valid.
-    let payable_id =
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.payable_id)?.as_slice())
-        .unwrap();
+    let payable_id = decode_id(&msg.payable_id).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId {
+        id: msg.payable_id.clone(),
+      }
+    })?;
     if !self.payables.has(ctx.deps.storage, payable_id) {
       return Err(ChainbillsError::InvalidPayableId { id: msg.payable_id });
     }
@@ -178,15 +444,55 @@ impl Payments for Chainbills {
     }
   }
 
+  fn payable_payments_per_chain(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PayablePaymentsPerChainMessage, Self::Error> {
+    // Ensure that the payable_id is valid.
+    let payable_id = decode_id(&msg.id).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId { id: msg.id.clone() }
+    })?;
+    if !self.payables.has(ctx.deps.storage, payable_id) {
+      return Err(ChainbillsError::InvalidPayableId { id: msg.id });
+    }
+
+    // Walk the payable's counts in order of chain id.
+    let counts = self
+      .per_chain_payable_payments_count
+      .prefix(payable_id.to_vec())
+      .range(ctx.deps.storage, None, None, Order::Ascending)
+      .map(|item| {
+        let (chain_id, count) = item?;
+        Ok(ChainPaymentsCount { chain_id, count })
+      })
+      .collect::<StdResult<Vec<_>>>()?;
+    Ok(PayablePaymentsPerChainMessage { counts })
+  }
+
+  fn payable_payment_stats(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PayablePaymentStatsMessage, Self::Error> {
+    let total = self
+      .payable(QueryCtx::from((ctx.deps, ctx.env.clone())), msg.clone())?
+      .payments_count;
+    let per_chain = self.payable_payments_per_chain(ctx, msg)?.counts;
+    Ok(PayablePaymentStatsMessage { total, per_chain })
+  }
+
   fn per_chain_payable_payment_id(
     &self,
     ctx: QueryCtx,
     msg: PerChainPayablePaymentIdMessage,
   ) -> Result<IdMessage, Self::Error> {
     // Ensure that the payable_id is valid.
-    let payable_id =
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.payable_id)?.as_slice())
-        .unwrap();
+    let payable_id = decode_id(&msg.payable_id).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId {
+        id: msg.payable_id.clone(),
+      }
+    })?;
     if !self.payables.has(ctx.deps.storage, payable_id) {
       return Err(ChainbillsError::InvalidPayableId { id: msg.payable_id });
     }
@@ -222,11 +528,82 @@ impl Payments for Chainbills {
     ctx: ExecCtx,
     msg: TransactionInfoMessage,
   ) -> Result<Response, Self::Error> {
+    let payer = ctx.info.sender.clone();
+    self.process_payment(ctx, payer, msg.into(), false)
+  }
+
+  fn pay_multiple(
+    &self,
+    ctx: ExecCtx,
+    msg: PayMultipleMessage,
+  ) -> Result<Response, Self::Error> {
+    let payer = ctx.info.sender.clone();
+    self.process_payment(ctx, payer, msg, false)
+  }
+
+  fn receive(
+    &self,
+    ctx: ExecCtx,
+    sender: String,
+    amount: Uint128,
+    msg: Binary,
+  ) -> Result<Response, Self::Error> {
+    // The hook is called by the CW20 token contract itself, so the sender of
+    // this message must be a known CW20 token.
+    let token = ctx.info.sender.to_string();
+    let token_details = self
+      .token_details
+      .load(ctx.deps.storage, token.clone())
+      .unwrap_or(TokenDetails::initialize(false, false, Uint128::zero()));
+    if !token_details.is_supported || token_details.is_native_token {
+      return Err(ChainbillsError::UnsupportedToken { token });
+    }
+
+    // Ensure that the hook message matches the tokens that were sent.
+    let msg: TransactionInfoMessage = from_json(&msg)?;
+    if msg.token != token || msg.amount != amount {
+      return Err(ChainbillsError::InvalidCw20ReceivePayment {});
+    }
+
+    // Credit the original sender of the tokens as the payer.
+    let payer = ctx.deps.api.addr_validate(&sender)?;
+    self.process_payment(ctx, payer, msg.into(), true)
+  }
+}
+
+impl Chainbills {
+  /// Records a payment (of one or more tokens) from sender into a payable.
+  /// is_cw20_received should be true if the CW20 tokens were already sent to
+  /// this contract (through the receive hook), in which case no TransferFrom
+  /// is made.
+  pub fn process_payment(
+    &self,
+    ctx: ExecCtx,
+    sender: Addr,
+    msg: PayMultipleMessage,
+    is_cw20_received: bool,
+  ) -> Result<Response, ChainbillsError> {
     /* CHECKS */
+    // Ensure that only whitelisted relayers pay on behalf of other wallets.
+    // The override only changes who the payment is recorded under, the
+    // funds still come from the sender.
+    let payer = match &msg.payer_override {
+      Some(wallet) => {
+        let config = self.config.load(ctx.deps.storage)?;
+        if !config.relayers.contains(&sender) {
+          return Err(ChainbillsError::RelayerUnauthorized {});
+        }
+        ctx.deps.api.addr_validate(wallet)?
+      }
+      None => sender.clone(),
+    };
+
     // Ensure that the payable_id is valid.
-    let payable_id =
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.payable_id)?.as_slice())
-        .unwrap();
+    let payable_id = decode_id(&msg.payable_id).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId {
+        id: msg.payable_id.clone(),
+      }
+    })?;
     if !self.payables.has(ctx.deps.storage, payable_id) {
       return Err(ChainbillsError::InvalidPayableId { id: msg.payable_id });
     }
@@ -237,120 +614,235 @@ impl Payments for Chainbills {
       return Err(ChainbillsError::PayableIsClosed {});
     }
 
-    // Extract the token and amount for the payment.
-    let TransactionInfoMessage { token, amount, .. } = msg;
+    // Ensure that the payable has not expired.
+    if let Some(expires_at) = payable.expires_at {
+      if ctx.env.block.time.seconds() > expires_at {
+        return Err(ChainbillsError::PayableExpired {});
+      }
+    }
 
-    // Fetch the TokenDetails details for the involved token.
-    let mut token_details = self
-      .token_details
-      .load(ctx.deps.storage, token.clone())
-      .unwrap_or(TokenDetails::initialize(false, false, Uint128::zero()));
+    // Ensure that the payer is allowed to pay into the payable. Relayers
+    // must be allowed too, so they can't pay as a listed wallet.
+    if !payable.is_payer_allowed(&payer) || !payable.is_payer_allowed(&sender) {
+      return Err(ChainbillsError::PayerNotAllowed {});
+    }
 
-    // Return an error if the token isn't supported.
-    if !token_details.is_supported {
-      return Err(ChainbillsError::UnsupportedToken {
-        token: token.clone(),
-      });
+    // Extract the tokens and amounts and the memo for the payment.
+    let PayMultipleMessage {
+      tokens_and_amounts,
+      memo,
+      ..
+    } = msg;
+
+    // Ensure that the memo isn't too long.
+    if let Some(memo) = &memo {
+      if memo.len() > MAX_MEMO_LENGTH {
+        return Err(ChainbillsError::MemoTooLong {
+          max: MAX_MEMO_LENGTH as u64,
+        });
+      }
     }
 
-    // Ensure that amount is greater than zero.
-    if amount.is_zero() {
-      return Err(ChainbillsError::ZeroAmountSpecified {});
+    // Ensure that at least one token is paid and that no token is repeated.
+    if tokens_and_amounts.is_empty() {
+      return Err(ChainbillsError::NoPaymentTokens {});
+    }
+    for (i, taa) in tokens_and_amounts.iter().enumerate() {
+      if tokens_and_amounts[..i].iter().any(|t| t.token == taa.token) {
+        return Err(ChainbillsError::DuplicatePaymentToken {
+          token: taa.token.clone(),
+        });
+      }
     }
 
-    // If this payable specified the tokens and amounts it can accept, ensure
-    // that the token and amount are matching.
-    if !payable.allowed_tokens_and_amounts.is_empty() {
-      let mut ataa_it = payable.allowed_tokens_and_amounts.iter().peekable();
-      while let Some(taa) = ataa_it.next() {
-        if taa.token == token && taa.amount == amount {
-          break;
-        }
-        if ataa_it.peek().is_none() {
-          return Err(ChainbillsError::MatchingTokenAndAmountNotFound {});
+    let mut tokens_details = vec![];
+    for TokenAndAmount { token, amount } in tokens_and_amounts.iter() {
+      // Fetch the TokenDetails details for the involved token.
+      let token_details = self
+        .token_details
+        .load(ctx.deps.storage, token.clone())
+        .unwrap_or(TokenDetails::initialize(false, false, Uint128::zero()));
+
+      // Return an error if the token isn't supported.
+      if !token_details.is_supported {
+        return Err(ChainbillsError::UnsupportedToken {
+          token: token.clone(),
+        });
+      }
+
+      // Ensure that amount is greater than zero.
+      if amount.is_zero() {
+        return Err(ChainbillsError::ZeroAmountSpecified {});
+      }
+
+      // Ensure that free payments aren't below the token's minimum, if any.
+      // Payables with specified amounts already fix what can be paid.
+      if let Some(min) = token_details.min_payment_amount {
+        if payable.accepts_free_payments() && *amount < min {
+          return Err(ChainbillsError::PaymentBelowMinimum { min });
         }
       }
+
+      // Unless this payable accepts free payments, ensure that any of the
+      // tokens and amounts it specified matches the token and amount. A token
+      // can be listed more than once with different amounts.
+      if !payable.accepts_token_and_amount(token, *amount) {
+        return Err(ChainbillsError::MatchingTokenAndAmountNotFound {});
+      }
+
+      tokens_details.push(token_details);
+    }
+
+    // Ensure that payments in new tokens don't take the payable's balances
+    // past the maximum. Tokens it already holds can still be paid.
+    let new_tokens_count = tokens_and_amounts
+      .iter()
+      .filter(|taa| {
+        !payable
+          .balances
+          .iter()
+          .any(|balance| balance.token == taa.token)
+      })
+      .count();
+    if new_tokens_count > 0
+      && payable.balances.len() + new_tokens_count > MAX_PAYABLE_BALANCES
+    {
+      return Err(ChainbillsError::TooManyPayableBalances {
+        max: MAX_PAYABLE_BALANCES as u64,
+      });
     }
 
     /* FUNDS TRANSFER */
+    // Ensure that no coins other than the native tokens being paid were
+    // attached, so that they are not absorbed by this contract. CW20 payments
+    // shouldn't come with any native coins.
+    let native_tokens_count = tokens_details
+      .iter()
+      .filter(|details| details.is_native_token)
+      .count();
+    if ctx.info.funds.len() > native_tokens_count {
+      return Err(ChainbillsError::UnexpectedFunds {});
+    }
+
     let mut cw20_messages = vec![];
-    if token_details.is_native_token {
-      // Verify Native Token Payment was made.
-      let verified_amount = cw_utils::must_pay(&ctx.info, &token.clone())?;
-      if verified_amount != amount {
-        return Err(ChainbillsError::InvalidNativeTokenPayment {});
+    for (taa, token_details) in tokens_and_amounts.iter().zip(&tokens_details) {
+      let TokenAndAmount { token, amount } = taa;
+      if token_details.is_native_token {
+        // Verify Native Token Payment was made.
+        let verified_amount = if native_tokens_count == 1 {
+          cw_utils::must_pay(&ctx.info, token)?
+        } else {
+          ctx
+            .info
+            .funds
+            .iter()
+            .find(|coin| &coin.denom == token)
+            .map_or(Uint128::zero(), |coin| coin.amount)
+        };
+        if verified_amount != *amount {
+          return Err(ChainbillsError::InvalidNativeTokenPayment {});
+        }
+      } else if !is_cw20_received {
+        // Prepare the message for the CW20 Token Transfer to add to the
+        // response. It isn't dispatched as a reply_on_success SubMsg as there
+        // is no need to: if it fails, the whole execution (including the
+        // state changes below) is reverted, so no payment is recorded without
+        // its funds.
+        cw20_messages.push(WasmMsg::Execute {
+          contract_addr: token.to_string(),
+          funds: vec![],
+          msg: to_json_binary(&Cw20ExecuteMsg::TransferFrom {
+            owner: sender.to_string(),
+            recipient: ctx.env.contract.address.to_string(),
+            amount: *amount,
+          })?,
+        });
       }
-    } else {
-      // Prepare the message for the CW20 Token Transfer to add to the response.
-      cw20_messages.push(WasmMsg::Execute {
-        contract_addr: token.to_string(),
-        funds: vec![],
-        msg: to_json_binary(&Cw20ExecuteMsg::TransferFrom {
-          owner: ctx.info.sender.to_string(),
-          recipient: ctx.env.contract.address.to_string(),
-          amount,
-        })?,
-      });
     }
 
     /* STATE CHANGES */
     /* COUNTS */
     // Increment payments and activities count on the payer (address) making
     // this payable.
-    let user_resp_attrib = self.initialize_user_if_is_new(
-      ctx.deps.storage,
-      &ctx.env,
-      &ctx.info.sender,
-    )?;
-    let mut user = self.users.load(ctx.deps.storage, &ctx.info.sender)?;
-    user.payments_count = user.next_payment();
-    user.activities_count = user.next_activity();
-    self.users.save(ctx.deps.storage, &ctx.info.sender, &user)?;
+    let user_resp_attrib =
+      self.initialize_user_if_is_new(ctx.deps.storage, &ctx.env, &payer)?;
+    let mut user = self.users.load(ctx.deps.storage, &payer)?;
+    user.payments_count = user.next_payment()?;
+    user.activities_count = user.next_activity()?;
+    self.users.save(ctx.deps.storage, &payer, &user)?;
 
     // Increment the chain stats for counts of payments.
     let mut chain_stats = self.chain_stats.load(ctx.deps.storage)?;
-    chain_stats.user_payments_count = chain_stats.next_user_payment();
-    chain_stats.payable_payments_count = chain_stats.next_payable_payment();
+    chain_stats.user_payments_count = chain_stats.next_user_payment()?;
+    chain_stats.payable_payments_count = chain_stats.next_payable_payment()?;
 
     // Increment the chain stats for activities_count.
     //
-    // Incrementing twice to account for recording two activities: one for the
-    // user and one for the payable.
-    chain_stats.activities_count = chain_stats.next_activity();
-    chain_stats.activities_count = chain_stats.next_activity();
+    // Incrementing by two to account for recording two activities: one for
+    // the user and one for the payable.
+    chain_stats.activities_count = chain_stats.next_activities(2)?;
 
     // Save the updated chain stats.
     self.chain_stats.save(ctx.deps.storage, &chain_stats)?;
 
     // Increment global payments_count and the activities_count on the payable.
-    payable.payments_count = payable.next_payment();
-    payable.activities_count = payable.next_activity();
+    payable.payments_count = payable.next_payment()?;
+    payable.activities_count = payable.next_activity()?;
 
-    // Update payable's balances to add this token and its amount.
+    // Update payable's balances and totals received to add each token and
+    // its amount.
     //
     // This boolean and the following two scopes was used (instead of peekable)
     // to solve the borrowing twice bug with rust on the payable variable.
-    let mut was_matching_balance_updated = false;
-    {
-      for balance in payable.balances.iter_mut() {
-        if balance.token == token.clone() {
-          balance.amount = balance.amount.checked_add(amount).unwrap();
-          was_matching_balance_updated = true;
-          break;
+    for TokenAndAmount { token, amount } in tokens_and_amounts.iter() {
+      payable.add_received(token, *amount)?;
+      let mut was_matching_balance_updated = false;
+      {
+        for balance in payable.balances.iter_mut() {
+          if &balance.token == token {
+            balance.amount = balance
+              .amount
+              .checked_add(*amount)
+              .map_err(|_| ChainbillsError::Overflow {})?;
+            was_matching_balance_updated = true;
+            break;
+          }
         }
       }
-    }
-    {
-      if !was_matching_balance_updated {
-        payable.balances.push(TokenAndAmount {
-          token: token.clone(),
-          amount,
-        });
+      {
+        if !was_matching_balance_updated {
+          payable.balances.push(TokenAndAmount {
+            token: token.clone(),
+            amount: *amount,
+          });
+        }
       }
     }
 
+    // The payable's balances of the tokens after this payment. Emitted so
+    // that indexers can track balances from events alone.
+    let payable_balances = tokens_and_amounts
+      .iter()
+      .map(|taa| {
+        payable
+          .balances
+          .iter()
+          .find(|balance| balance.token == taa.token)
+          .map_or(Uint128::zero(), |balance| balance.amount)
+      })
+      .collect::<Vec<_>>();
+
     // Save the Updated Payable.
     self.payables.save(ctx.deps.storage, payable_id, &payable)?;
+    for taa in tokens_and_amounts.iter() {
+      self.record_balance_checkpoint(
+        ctx.deps.storage,
+        &ctx.env,
+        payable_id,
+        &payable,
+        &taa.token,
+      )?;
+    }
 
     let config = self.config.load(ctx.deps.storage)?;
 
@@ -359,26 +851,34 @@ impl Payments for Chainbills {
       .per_chain_payable_payments_count
       .may_load(ctx.deps.storage, (payable_id.to_vec(), config.chain_id))?
       .unwrap_or_default();
-    local_chain_count = local_chain_count.checked_add(1).unwrap();
+    local_chain_count = local_chain_count
+      .checked_add(1)
+      .ok_or(ChainbillsError::Overflow {})?;
     self.per_chain_payable_payments_count.save(
       ctx.deps.storage,
       (payable_id.to_vec(), config.chain_id),
       &local_chain_count,
     )?;
 
-    // Increase the supported token's totals from this payment.
-    token_details.add_user_paid(amount);
-    token_details.add_payable_received(amount);
-    self
-      .token_details
-      .save(ctx.deps.storage, token.clone(), &token_details)?;
+    // Increase the supported tokens' totals from this payment.
+    for (taa, mut token_details) in
+      tokens_and_amounts.iter().zip(tokens_details)
+    {
+      token_details.add_user_paid(taa.amount)?;
+      token_details.add_payable_received(taa.amount)?;
+      self.token_details.save(
+        ctx.deps.storage,
+        taa.token.clone(),
+        &token_details,
+      )?;
+    }
 
     /* PAYMENTS DATA STRUCTURES */
     // Get a new Payment ID for the User
     let user_payment_id = self.create_id(
       ctx.deps.storage,
       &ctx.env,
-      &ctx.info.sender.as_str(),
+      payer.as_str(),
       "payment",
       user.payments_count,
     )?;
@@ -386,30 +886,45 @@ impl Payments for Chainbills {
     // Save the Payment ID to the users_payment_ids.
     let mut user_payment_ids = self
       .user_payment_ids
-      .may_load(ctx.deps.storage, &ctx.info.sender)?
+      .may_load(ctx.deps.storage, &payer)?
       .unwrap_or_default();
     user_payment_ids.push(user_payment_id);
-    self.user_payment_ids.save(
-      ctx.deps.storage,
-      &ctx.info.sender,
-      &user_payment_ids,
-    )?;
+    self
+      .user_payment_ids
+      .save(ctx.deps.storage, &payer, &user_payment_ids)?;
+
+    // Save the Payable ID to the user_paid_payable_ids if this is the user's
+    // first payment to it.
+    let mut user_paid_payable_ids = self
+      .user_paid_payable_ids
+      .may_load(ctx.deps.storage, &payer)?
+      .unwrap_or_default();
+    if !user_paid_payable_ids.contains(&payable_id) {
+      user_paid_payable_ids.push(payable_id);
+      self.user_paid_payable_ids.save(
+        ctx.deps.storage,
+        &payer,
+        &user_paid_payable_ids,
+      )?;
+    }
 
     let timestamp = ctx.env.block.time.seconds();
-    let details = TokenAndAmount {
-      token: token.clone(),
-      amount,
-    };
+    let mut tokens_and_amounts = tokens_and_amounts.into_iter();
+    // Safe to unwrap as the tokens and amounts were ensured to not be empty.
+    let details = tokens_and_amounts.next().unwrap();
+    let other_details = tokens_and_amounts.collect::<Vec<_>>();
 
     // Create and Save the UserPayment.
     let user_payment = UserPayment {
       payable_id,
-      payer: ctx.info.sender.clone(),
+      payer: payer.clone(),
       payable_chain_id: config.chain_id,
       chain_count: chain_stats.user_payments_count,
       payer_count: user.payments_count,
       timestamp,
       details: details.clone(),
+      other_details: other_details.clone(),
+      memo: memo.clone(),
     };
     self.user_payments.save(
       ctx.deps.storage,
@@ -450,16 +965,34 @@ impl Payments for Chainbills {
       &per_chain_payable_payment_ids,
     )?;
 
+    // Save the Payment ID to the payable_token_payment_ids of each of its
+    // tokens. Tokens were ensured to not be repeated in a payment.
+    for taa in std::iter::once(&details).chain(other_details.iter()) {
+      let key = (payable_id.to_vec(), taa.token.clone());
+      let mut payable_token_payment_ids = self
+        .payable_token_payment_ids
+        .may_load(ctx.deps.storage, key.clone())?
+        .unwrap_or_default();
+      payable_token_payment_ids.push(payable_payment_id);
+      self.payable_token_payment_ids.save(
+        ctx.deps.storage,
+        key,
+        &payable_token_payment_ids,
+      )?;
+    }
+
     // Create and Save the PayablePayment.
     let payable_payment = PayablePayment {
       payable_id,
-      payer: self.address_to_bytes32(&ctx.info.sender, ctx.deps.api),
+      payer: self.address_to_bytes32(&payer, ctx.deps.api),
       chain_count: chain_stats.payable_payments_count,
       payer_chain_id: config.chain_id,
       local_chain_count,
       payable_count: payable.payments_count,
       timestamp,
       details: details.clone(),
+      other_details: other_details.clone(),
+      memo: memo.clone(),
     };
     self.payable_payments.save(
       ctx.deps.storage,
@@ -472,7 +1005,7 @@ impl Payments for Chainbills {
     let user_activity_id = self.create_id(
       ctx.deps.storage,
       &ctx.env,
-      &ctx.info.sender.clone().as_str(),
+      payer.as_str(),
       "activity",
       user.activities_count,
     )?;
@@ -498,12 +1031,12 @@ impl Payments for Chainbills {
     // Save the User ActivityRecord ID to user_activity_ids.
     let mut user_activity_ids = self
       .user_activity_ids
-      .may_load(ctx.deps.storage, &ctx.info.sender)?
+      .may_load(ctx.deps.storage, &payer)?
       .unwrap_or_default();
     user_activity_ids.push(user_activity_id);
     self.user_activity_ids.save(
       ctx.deps.storage,
-      &ctx.info.sender,
+      &payer,
       &user_activity_ids,
     )?;
 
@@ -523,8 +1056,11 @@ impl Payments for Chainbills {
       ctx.deps.storage,
       user_activity_id,
       &ActivityRecord {
-        // subtracting 1 because we incremented the activities_count twice.
-        chain_count: chain_stats.activities_count.checked_sub(1).unwrap(),
+        // subtracting 1 because we incremented the activities_count by two.
+        chain_count: chain_stats
+          .activities_count
+          .checked_sub(1)
+          .ok_or(ChainbillsError::Overflow {})?,
         user_count: user.activities_count,
         payable_count: 0, // Setting 0 because it's not a payable activity.
         timestamp: ctx.env.block.time.seconds(),
@@ -549,14 +1085,22 @@ impl Payments for Chainbills {
 
     /* FINISH */
     // Return the Response.
+    let balance_attribs = payable_balances
+      .into_iter()
+      .map(|balance| ("payable_balance", balance.to_string()));
     Ok(
       Response::new()
         .add_messages(cw20_messages) // Add the cw20 messages
+        // Return the user payment ID as data to save clients from parsing it
+        // from the events.
+        .set_data(to_json_binary(&IdMessage {
+          id: HexBinary::from(&user_payment_id).to_hex(),
+        })?)
         .add_attributes(user_resp_attrib) // Add the user init attributes
         .add_attributes([
           // Shared Details
           ("payable_id", HexBinary::from(&payable_id).to_hex()),
-          ("payer_wallet", ctx.info.sender.to_string()),
+          ("payer_wallet", payer.to_string()),
           // Details relative to the user
           ("action", "user_paid".to_string()),
           ("user_payment_id", HexBinary::from(&user_payment_id).to_hex()),
@@ -569,7 +1113,62 @@ impl Payments for Chainbills {
           ("payable_chain_count", chain_stats.payable_payments_count.to_string()),
           ("payer_chain_id", config.chain_id.to_string()),
           ("payable_count", payable.payments_count.to_string()),
-        ]),
+        ])
+        .add_attributes(memo.map(|memo| ("memo", memo)))
+        .add_attributes(balance_attribs),
     )
   }
+
+  /// Loads the requested page of payments from their IDs. If a time range
+  /// (from and to, both inclusive) is given, only payments made within it
+  /// are counted for the page. As IDs are stored in the order the payments
+  /// were made, the walk stops at the first payment after the range.
+  #[allow(clippy::too_many_arguments)]
+  fn payments_page<T: Serialize + DeserializeOwned>(
+    &self,
+    storage: &dyn Storage,
+    payments: &Map<[u8; 32], T>,
+    timestamp: fn(&T) -> u64,
+    payment_ids: &[[u8; 32]],
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    (from_timestamp, to_timestamp): (Option<u64>, Option<u64>),
+  ) -> StdResult<Vec<T>> {
+    let limit =
+      limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let start_after = start_after.unwrap_or(0) as usize;
+
+    // Without a range, skip straight to the page.
+    if from_timestamp.is_none() && to_timestamp.is_none() {
+      return payment_ids
+        .iter()
+        .skip(start_after)
+        .take(limit)
+        .map(|payment_id| payments.load(storage, *payment_id))
+        .collect();
+    }
+
+    let mut page = vec![];
+    let mut skipped = 0;
+    for payment_id in payment_ids {
+      if page.len() == limit {
+        break;
+      }
+
+      let payment = payments.load(storage, *payment_id)?;
+      let at = timestamp(&payment);
+      if to_timestamp.is_some_and(|to| at > to) {
+        break;
+      }
+      if from_timestamp.is_some_and(|from| at < from) {
+        continue;
+      }
+      if skipped < start_after {
+        skipped += 1;
+        continue;
+      }
+      page.push(payment);
+    }
+    Ok(page)
+  }
 }
@@ -0,0 +1,220 @@
+use crate::contract::{decode_id, Chainbills};
+use crate::error::ChainbillsError;
+use crate::messages::{IdMessage, RefundPaymentMessage};
+use crate::state::{ActivityType, PaymentRefund, UserPayment};
+use cw20::Cw20ExecuteMsg;
+use sylvia::cw_std::{
+  to_json_binary, BankMsg, Coin, HexBinary, Response, StdError, WasmMsg,
+};
+use sylvia::interface;
+use sylvia::types::{ExecCtx, QueryCtx};
+
+#[interface]
+pub trait Refunds {
+  type Error: From<StdError>;
+
+  #[sv::msg(query)]
+  fn payment_refund(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PaymentRefund, Self::Error>;
+
+  #[sv::msg(exec)]
+  fn refund_payment_partial(
+    &self,
+    ctx: ExecCtx,
+    msg: RefundPaymentMessage,
+  ) -> Result<Response, Self::Error>;
+}
+
+impl Refunds for Chainbills {
+  type Error = ChainbillsError;
+
+  fn payment_refund(
+    &self,
+    ctx: QueryCtx,
+    msg: IdMessage,
+  ) -> Result<PaymentRefund, Self::Error> {
+    // Ensure that the payment_id is valid.
+    let payment_id = decode_id(&msg.id).ok_or_else(|| {
+      ChainbillsError::InvalidPaymentId { id: msg.id.clone() }
+    })?;
+    if !self.user_payments.has(ctx.deps.storage, payment_id) {
+      return Err(ChainbillsError::InvalidPaymentId { id: msg.id });
+    }
+
+    // Return the refund record or an empty one if there has been no refund.
+    Ok(
+      self
+        .payment_refunds
+        .may_load(ctx.deps.storage, payment_id)?
+        .unwrap_or(PaymentRefund {
+          refunded: vec![],
+          is_fully_refunded: false,
+        }),
+    )
+  }
+
+  fn refund_payment_partial(
+    &self,
+    ctx: ExecCtx,
+    msg: RefundPaymentMessage,
+  ) -> Result<Response, Self::Error> {
+    /* CHECKS */
+    // Ensure that the payment_id is valid.
+    let payment_id = decode_id(&msg.payment_id).ok_or_else(|| {
+      ChainbillsError::InvalidPaymentId {
+        id: msg.payment_id.clone(),
+      }
+    })?;
+    let UserPayment {
+      payable_id,
+      payer,
+      details,
+      other_details,
+      ..
+    } = match self.user_payments.may_load(ctx.deps.storage, payment_id)? {
+      Some(payment) => Ok(payment),
+      None => Err(ChainbillsError::InvalidPaymentId { id: msg.payment_id }),
+    }?;
+    let mut payable = self.payables.load(ctx.deps.storage, payable_id)?;
+
+    // Ensure that the caller owns the payable that received the payment.
+    if payable.host != ctx.info.sender {
+      return Err(ChainbillsError::NotYourPayable {});
+    }
+
+    // Ensure that the specified amount is greater than zero.
+    let amount = msg.amount;
+    if amount.is_zero() {
+      return Err(ChainbillsError::ZeroAmountSpecified {});
+    }
+
+    // Ensure that the token is one of the payment's tokens.
+    let token = msg.token;
+    let paid = std::iter::once(&details)
+      .chain(other_details.iter())
+      .find(|taa| taa.token == token)
+      .map(|taa| taa.amount)
+      .ok_or_else(|| ChainbillsError::TokenNotInPayment {
+        token: token.clone(),
+      })?;
+
+    // Ensure that the cumulative refunds of the token don't exceed the
+    // payment's amount of it.
+    let mut refund = self
+      .payment_refunds
+      .may_load(ctx.deps.storage, payment_id)?
+      .unwrap_or(PaymentRefund {
+        refunded: vec![],
+        is_fully_refunded: false,
+      });
+    let refunded = refund
+      .refunded_of(&token)
+      .checked_add(amount)
+      .map_err(|_| ChainbillsError::Overflow {})?;
+    if refunded > paid {
+      return Err(ChainbillsError::RefundExceedsPayment {});
+    }
+
+    // Ensure that the payable still has enough of the token to refund.
+    let has_balance = payable
+      .balances
+      .iter()
+      .any(|balance| balance.token == token && balance.amount >= amount);
+    if !has_balance {
+      return Err(ChainbillsError::InsufficientPayableBalance {});
+    }
+
+    /* FUNDS TRANSFER */
+    // Prepare messages for transfer to add to the response.
+    let mut token_details =
+      self.token_details.load(ctx.deps.storage, token.clone())?;
+    let mut bank_messages = vec![];
+    let mut cw20_messages = vec![];
+    if token_details.is_native_token {
+      bank_messages.push(BankMsg::Send {
+        to_address: payer.to_string(),
+        amount: vec![Coin {
+          denom: token.clone(),
+          amount,
+        }],
+      });
+    } else {
+      cw20_messages.push(WasmMsg::Execute {
+        contract_addr: token.clone(),
+        funds: vec![],
+        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+          recipient: payer.to_string(),
+          amount,
+        })?,
+      });
+    }
+
+    /* STATE CHANGES */
+    // Record the cumulative refund of the token on the payment.
+    refund.set_refunded(&token, refunded);
+    refund.is_fully_refunded = std::iter::once(&details)
+      .chain(other_details.iter())
+      .all(|taa| refund.refunded_of(&taa.token) == taa.amount);
+    self
+      .payment_refunds
+      .save(ctx.deps.storage, payment_id, &refund)?;
+
+    // Deduct the refunded amount from the totals that the payment added to.
+    token_details.deduct_refunded(amount)?;
+    self
+      .token_details
+      .save(ctx.deps.storage, token.clone(), &token_details)?;
+    payable.deduct_received(&token, amount)?;
+
+    // Deduct the refunded amount from the payable's balance and increment
+    // its activity count.
+    for balance in payable.balances.iter_mut() {
+      if balance.token == token {
+        balance.amount = balance
+          .amount
+          .checked_sub(amount)
+          .map_err(|_| ChainbillsError::Overflow {})?;
+        break;
+      }
+    }
+    payable.activities_count = payable.next_activity()?;
+    self.payables.save(ctx.deps.storage, payable_id, &payable)?;
+    self.record_balance_checkpoint(
+      ctx.deps.storage,
+      &ctx.env,
+      payable_id,
+      &payable,
+      &token,
+    )?;
+
+    // Record the activity.
+    self.record_update_payable_activity(
+      ctx.deps.storage,
+      &ctx.env,
+      &ctx.info.sender,
+      payable_id,
+      payable.activities_count,
+      ActivityType::RefundedPayment,
+    )?;
+
+    // Return the Response.
+    Ok(
+      Response::new()
+        .add_messages(bank_messages) // Add the bank messages
+        .add_messages(cw20_messages) // Add the cw20 messages
+        .add_attributes([
+          ("action", "refunded_payment".to_string()),
+          ("payment_id", HexBinary::from(&payment_id).to_hex()),
+          ("payable_id", HexBinary::from(&payable_id).to_hex()),
+          ("payer_wallet", payer.to_string()),
+          ("token", token),
+          ("amount", amount.to_string()),
+          ("total_refunded", refunded.to_string()),
+          ("is_fully_refunded", refund.is_fully_refunded.to_string()),
+        ]),
+    )
+  }
+}
@@ -1,8 +1,17 @@
-use crate::contract::Chainbills;
+use crate::contract::{Chainbills, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
 use crate::error::ChainbillsError;
-use crate::messages::{IdMessage, UpdateMaxWithdrawalFeesMessage};
+use crate::messages::{
+  IdMessage, SetMinPaymentAmountMessage, SetTokenSupportedMessage,
+  SupportedTokenDetails, SupportedTokensDetailedMessage,
+  SupportedTokensDetailedResponse, TokensMessage,
+  UpdateMaxWithdrawalFeesMessage,
+};
 use crate::state::TokenDetails;
-use sylvia::cw_std::{Response, StdError};
+use cw20::{Cw20QueryMsg, TokenInfoResponse};
+use cw_storage_plus::Bound;
+use sylvia::cw_std::{
+  Order, QuerierWrapper, Response, StdError, StdResult, Uint128,
+};
 use sylvia::interface;
 use sylvia::types::{ExecCtx, QueryCtx};
 
@@ -17,12 +26,39 @@ pub trait TokenDetailsInterface {
     msg: IdMessage,
   ) -> Result<TokenDetails, Self::Error>;
 
+  #[sv::msg(query)]
+  fn supported_tokens(
+    &self,
+    ctx: QueryCtx,
+  ) -> Result<TokensMessage, Self::Error>;
+
+  #[sv::msg(query)]
+  fn supported_tokens_detailed(
+    &self,
+    ctx: QueryCtx,
+    msg: SupportedTokensDetailedMessage,
+  ) -> Result<SupportedTokensDetailedResponse, Self::Error>;
+
   #[sv::msg(exec)]
   fn update_max_withdrawal_fees(
     &self,
     ctx: ExecCtx,
     msg: UpdateMaxWithdrawalFeesMessage,
   ) -> Result<Response, Self::Error>;
+
+  #[sv::msg(exec)]
+  fn set_token_supported(
+    &self,
+    ctx: ExecCtx,
+    msg: SetTokenSupportedMessage,
+  ) -> Result<Response, Self::Error>;
+
+  #[sv::msg(exec)]
+  fn set_min_payment_amount(
+    &self,
+    ctx: ExecCtx,
+    msg: SetMinPaymentAmountMessage,
+  ) -> Result<Response, Self::Error>;
 }
 
 impl TokenDetailsInterface for Chainbills {
@@ -46,6 +82,54 @@ impl TokenDetailsInterface for Chainbills {
     }
   }
 
+  fn supported_tokens(
+    &self,
+    ctx: QueryCtx,
+  ) -> Result<TokensMessage, Self::Error> {
+    // Only list tokens in which payments are currently accepted.
+    let tokens = self
+      .token_details
+      .range(ctx.deps.storage, None, None, Order::Ascending)
+      .filter_map(|item| match item {
+        Ok((token, details)) if details.is_supported => Some(Ok(token)),
+        Ok(_) => None,
+        Err(e) => Some(Err(e)),
+      })
+      .collect::<StdResult<Vec<_>>>()?;
+    Ok(TokensMessage { tokens })
+  }
+
+  fn supported_tokens_detailed(
+    &self,
+    ctx: QueryCtx,
+    msg: SupportedTokensDetailedMessage,
+  ) -> Result<SupportedTokensDetailedResponse, Self::Error> {
+    let limit =
+      msg.limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let start = msg.start_after.map(Bound::exclusive);
+
+    // Walk the tokens in key order and attach Cw20 metadata to each.
+    let tokens = self
+      .token_details
+      .range(ctx.deps.storage, start, None, Order::Ascending)
+      .take(limit)
+      .map(|item| {
+        let (token, details) = item?;
+        let info =
+          cw20_token_info(&ctx.deps.querier, &token, details.is_native_token);
+        Ok(SupportedTokenDetails {
+          token,
+          is_native_token: details.is_native_token,
+          is_supported: details.is_supported,
+          max_withdrawal_fees: details.max_withdrawal_fees,
+          symbol: info.as_ref().map(|i| i.symbol.clone()),
+          decimals: details.decimals.or(info.map(|i| i.decimals)),
+        })
+      })
+      .collect::<StdResult<Vec<_>>>()?;
+    Ok(SupportedTokensDetailedResponse { tokens })
+  }
+
   fn update_max_withdrawal_fees(
     &self,
     ctx: ExecCtx,
@@ -81,9 +165,21 @@ impl TokenDetailsInterface for Chainbills {
       ));
 
     // Update the max_withdrawal_fees and native token status for the token.
+    // Newly added tokens had no fees.
+    let previous_max_withdrawal_fees =
+      if self.token_details.has(ctx.deps.storage, token.clone()) {
+        token_details.max_withdrawal_fees
+      } else {
+        Uint128::zero()
+      };
     token_details.max_withdrawal_fees = max_withdrawal_fees;
     token_details.is_native_token = is_native_token;
 
+    // Keep the decimals of Cw20 tokens so that clients can display amounts.
+    token_details.decimals =
+      cw20_token_info(&ctx.deps.querier, &token, is_native_token)
+        .map(|info| info.decimals);
+
     // Save the updated TokenDetails.
     self
       .token_details
@@ -94,7 +190,100 @@ impl TokenDetailsInterface for Chainbills {
       ("action", "updated_max_withdrawal_fees".to_string()),
       ("token", token.clone()),
       ("is_native_token", is_native_token.to_string()),
+      (
+        "previous_max_withdrawal_fees",
+        previous_max_withdrawal_fees.to_string(),
+      ),
       ("max_withdrawal_fees", max_withdrawal_fees.to_string()),
     ]))
   }
+
+  fn set_token_supported(
+    &self,
+    ctx: ExecCtx,
+    msg: SetTokenSupportedMessage,
+  ) -> Result<Response, Self::Error> {
+    // Only the owner can start or stop accepting payments in a token.
+    let owner = self.config.load(ctx.deps.storage)?.owner;
+    if ctx.info.sender != owner {
+      return Err(ChainbillsError::OwnerUnauthorized {});
+    }
+
+    // The token must have been registered before. Its details are kept (and
+    // not removed) so that existing balances in it can still be withdrawn.
+    let SetTokenSupportedMessage {
+      token,
+      is_supported,
+    } = msg;
+    let mut token_details = self
+      .token_details
+      .may_load(ctx.deps.storage, token.clone())?
+      .ok_or(ChainbillsError::InvalidToken {
+        token: token.clone(),
+      })?;
+    token_details.is_supported = is_supported;
+    self
+      .token_details
+      .save(ctx.deps.storage, token.clone(), &token_details)?;
+
+    // Return the Response.
+    Ok(Response::new().add_attributes([
+      ("action", "set_token_supported".to_string()),
+      ("token", token),
+      ("is_supported", is_supported.to_string()),
+    ]))
+  }
+
+  fn set_min_payment_amount(
+    &self,
+    ctx: ExecCtx,
+    msg: SetMinPaymentAmountMessage,
+  ) -> Result<Response, Self::Error> {
+    // Only the owner can set the minimum payment amount of a token.
+    let owner = self.config.load(ctx.deps.storage)?.owner;
+    if ctx.info.sender != owner {
+      return Err(ChainbillsError::OwnerUnauthorized {});
+    }
+
+    // The token must have been registered before.
+    let SetMinPaymentAmountMessage {
+      token,
+      min_payment_amount,
+    } = msg;
+    let mut token_details = self
+      .token_details
+      .may_load(ctx.deps.storage, token.clone())?
+      .ok_or(ChainbillsError::InvalidToken {
+        token: token.clone(),
+      })?;
+    token_details.min_payment_amount = min_payment_amount;
+    self
+      .token_details
+      .save(ctx.deps.storage, token.clone(), &token_details)?;
+
+    // Return the Response.
+    Ok(Response::new().add_attributes([
+      ("action", "set_min_payment_amount".to_string()),
+      ("token", token),
+      (
+        "min_payment_amount",
+        min_payment_amount.unwrap_or_default().to_string(),
+      ),
+    ]))
+  }
+}
+
+/// Fetches the token info of a Cw20 token. None for native tokens or if the
+/// token doesn't respond to the Cw20 TokenInfo query.
+pub fn cw20_token_info(
+  querier: &QuerierWrapper,
+  token: &str,
+  is_native_token: bool,
+) -> Option<TokenInfoResponse> {
+  if is_native_token {
+    return None;
+  }
+  let resp: StdResult<TokenInfoResponse> =
+    querier.query_wasm_smart(token, &Cw20QueryMsg::TokenInfo {});
+  resp.ok()
 }
@@ -1,13 +1,15 @@
-use crate::contract::Chainbills;
+use crate::contract::{decode_id, Chainbills};
 use crate::error::ChainbillsError;
-use crate::messages::{FetchIdMessage, IdMessage, TransactionInfoMessage};
+use crate::messages::{
+  FetchIdMessage, IdMessage, TransactionInfoMessage, WithdrawMultipleMessage,
+  WithdrawalFeeMessage, WithdrawalFeePreview,
+};
 use crate::state::{
-  ActivityRecord, ActivityType, TokenAndAmount, TokenDetails, User, Withdrawal,
+  ActivityRecord, ActivityType, TokenAndAmount, User, Withdrawal,
 };
 use cw20::Cw20ExecuteMsg;
-use std::cmp::min;
 use sylvia::cw_std::{
-  to_json_binary, BankMsg, Coin, HexBinary, Response, StdError, Uint128,
+  to_json_binary, BankMsg, Coin, Event, HexBinary, Response, StdError, Uint128,
   WasmMsg,
 };
 use sylvia::interface;
@@ -38,12 +40,29 @@ pub trait Withdrawals {
     msg: IdMessage,
   ) -> Result<Withdrawal, Self::Error>;
 
+  #[sv::msg(query)]
+  fn withdrawal_fee(
+    &self,
+    ctx: QueryCtx,
+    msg: WithdrawalFeeMessage,
+  ) -> Result<WithdrawalFeePreview, Self::Error>;
+
   #[sv::msg(exec)]
   fn withdraw(
     &self,
     ctx: ExecCtx,
     data: TransactionInfoMessage,
   ) -> Result<Response, Self::Error>;
+
+  /// Withdraws several tokens from a payable in one transaction. Each token
+  /// is withdrawn as with withdraw, with its own Withdrawal, and if any
+  /// fails, none is withdrawn.
+  #[sv::msg(exec)]
+  fn withdraw_multi(
+    &self,
+    ctx: ExecCtx,
+    data: WithdrawMultipleMessage,
+  ) -> Result<Response, Self::Error>;
 }
 
 impl Withdrawals for Chainbills {
@@ -81,9 +100,11 @@ impl Withdrawals for Chainbills {
     msg: FetchIdMessage,
   ) -> Result<IdMessage, Self::Error> {
     // Ensure that the payable_id is valid.
-    let payable_id =
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.reference)?.as_slice())
-        .unwrap();
+    let payable_id = decode_id(&msg.reference).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId {
+        id: msg.reference.clone(),
+      }
+    })?;
     if !self.payables.has(ctx.deps.storage, payable_id) {
       return Err(ChainbillsError::InvalidPayableId { id: msg.reference });
     }
@@ -110,13 +131,39 @@ impl Withdrawals for Chainbills {
   ) -> Result<Withdrawal, Self::Error> {
     match self.withdrawals.may_load(
       ctx.deps.storage,
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.id)?.as_slice()).unwrap(),
+      decode_id(&msg.id).ok_or_else(|| {
+        ChainbillsError::InvalidWithdrawalId { id: msg.id.clone() }
+      })?,
     )? {
       Some(withdrawal) => Ok(withdrawal),
       None => Err(ChainbillsError::InvalidWithdrawalId { id: msg.id }),
     }
   }
 
+  fn withdrawal_fee(
+    &self,
+    ctx: QueryCtx,
+    msg: WithdrawalFeeMessage,
+  ) -> Result<WithdrawalFeePreview, Self::Error> {
+    let WithdrawalFeeMessage { token, amount } = msg;
+    let token_details = match self
+      .token_details
+      .may_load(ctx.deps.storage, token.clone())?
+    {
+      Some(details) => details,
+      None => return Err(ChainbillsError::InvalidToken { token }),
+    };
+    let config = self.config.load(ctx.deps.storage)?;
+
+    // Use the same computation as withdraw so both always agree.
+    let fee = self.withdrawal_fees(&config, &token_details, amount);
+    Ok(WithdrawalFeePreview {
+      gross: amount,
+      fee,
+      net: amount.checked_sub(fee).map_err(StdError::from)?,
+    })
+  }
+
   fn withdraw(
     &self,
     ctx: ExecCtx,
@@ -124,16 +171,18 @@ impl Withdrawals for Chainbills {
   ) -> Result<Response, Self::Error> {
     /* CHECKS */
     // Ensure that the payable_id is valid.
-    let payable_id =
-      <[u8; 32]>::try_from(HexBinary::from_hex(&msg.payable_id)?.as_slice())
-        .unwrap();
+    let payable_id = decode_id(&msg.payable_id).ok_or_else(|| {
+      ChainbillsError::InvalidPayableId {
+        id: msg.payable_id.clone(),
+      }
+    })?;
     if !self.payables.has(ctx.deps.storage, payable_id) {
       return Err(ChainbillsError::InvalidPayableId { id: msg.payable_id });
     }
     let mut payable = self.payables.load(ctx.deps.storage, payable_id)?;
 
     // Ensure that the caller owns the payable.
-    if payable.host != &ctx.info.sender {
+    if payable.host != ctx.info.sender {
       return Err(ChainbillsError::NotYourPayable {});
     }
 
@@ -165,23 +214,56 @@ impl Withdrawals for Chainbills {
       }
     }
 
+    // Ensure that the amount doesn't exceed the maximum of a single
+    // withdrawal, if one is set.
+    let config = self.config.load(ctx.deps.storage)?;
+    if !config.max_withdrawal_amount.is_zero()
+      && amount > config.max_withdrawal_amount
+    {
+      return Err(ChainbillsError::WithdrawalExceedsMax {
+        max: config.max_withdrawal_amount,
+      });
+    }
+
     /* FUNDS TRANSFER */
     // Prepare withdraw amounts and fees
-    let config = self.config.load(ctx.deps.storage)?;
-    let percent = amount
-      .checked_mul(config.withdrawal_fee_percentage)
-      .unwrap()
-      .checked_div(Uint128::new(10000))  // 10000 is 100%
-      .unwrap();
     let mut token_details =
       self.token_details.load(ctx.deps.storage, token.clone())?;
-    let TokenDetails {
-      is_native_token, // Determine if token is a native one
-      max_withdrawal_fees,
-      ..
-    } = token_details;
-    let fees = min(percent, max_withdrawal_fees);
-    let amount_due = amount.checked_sub(fees).unwrap();
+    // Ensure that the amount isn't dust.
+    let min = token_details.min_withdrawal_amount();
+    if amount < min {
+      return Err(ChainbillsError::WithdrawalAmountTooSmall { min });
+    }
+    // Determine if token is a native one
+    let is_native_token = token_details.is_native_token;
+    let fees = self.withdrawal_fees(&config, &token_details, amount);
+    let amount_due = amount
+      .checked_sub(fees)
+      .map_err(|_| ChainbillsError::Overflow {})?;
+
+    // Split the fees between the primary and secondary fee collectors.
+    let secondary_fees = fees
+      .checked_mul(config.fee_split_bps)
+      .map_err(|_| ChainbillsError::Overflow {})?
+      .checked_div(Uint128::new(10000)) // 10000 is 100%
+      .map_err(|_| ChainbillsError::Overflow {})?;
+    let primary_fees = fees
+      .checked_sub(secondary_fees)
+      .map_err(|_| ChainbillsError::Overflow {})?;
+
+    // Keep the primary fees in this contract. The owner sweeps them to the
+    // fee collector with owner_withdraw.
+    let collected_fees = self
+      .collected_fees
+      .may_load(ctx.deps.storage, token.clone())?
+      .unwrap_or_default();
+    self.collected_fees.save(
+      ctx.deps.storage,
+      token.clone(),
+      &collected_fees
+        .checked_add(primary_fees)
+        .map_err(|_| ChainbillsError::Overflow {})?,
+    )?;
 
     // Prepare messages for transfer to add to the response.
     let mut bank_messages = vec![];
@@ -195,14 +277,18 @@ impl Withdrawals for Chainbills {
           amount: amount_due,
         }],
       });
-      // Transfer the withdrawal fee to the fee collector.
-      bank_messages.push(BankMsg::Send {
-        to_address: config.chainbills_fee_collector.to_string(),
-        amount: vec![Coin {
-          denom: token.clone(),
-          amount: fees,
-        }],
-      });
+      // Transfer the fee split to the secondary fee collector.
+      if let Some(secondary) = &config.secondary_fee_collector {
+        if !secondary_fees.is_zero() {
+          bank_messages.push(BankMsg::Send {
+            to_address: secondary.to_string(),
+            amount: vec![Coin {
+              denom: token.clone(),
+              amount: secondary_fees,
+            }],
+          });
+        }
+      }
     } else {
       cw20_messages.push(WasmMsg::Execute {
         contract_addr: token.clone(),
@@ -212,46 +298,60 @@ impl Withdrawals for Chainbills {
           amount: amount_due,
         })?,
       });
-      cw20_messages.push(WasmMsg::Execute {
-        contract_addr: token.clone(),
-        funds: vec![],
-        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
-          recipient: config.chainbills_fee_collector.to_string(),
-          amount: fees,
-        })?,
-      });
+      if let Some(secondary) = &config.secondary_fee_collector {
+        if !secondary_fees.is_zero() {
+          cw20_messages.push(WasmMsg::Execute {
+            contract_addr: token.clone(),
+            funds: vec![],
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+              recipient: secondary.to_string(),
+              amount: secondary_fees,
+            })?,
+          });
+        }
+      }
     }
 
     /* STATE CHANGES */
     /* COUNTS */
     // Increment the chain stats for counts of withdrawals.
     let mut chain_stats = self.chain_stats.load(ctx.deps.storage)?;
-    chain_stats.withdrawals_count = chain_stats.next_withdrawal();
-    chain_stats.activities_count = chain_stats.next_activity();
+    chain_stats.withdrawals_count = chain_stats.next_withdrawal()?;
+    chain_stats.activities_count = chain_stats.next_activity()?;
     self.chain_stats.save(ctx.deps.storage, &chain_stats)?;
 
     // Increment withdrawals and activities count in the host(address) that
     // just withdrew.
     let mut user = self.users.load(ctx.deps.storage, &ctx.info.sender)?;
-    user.withdrawals_count = user.next_withdrawal();
-    user.activities_count = user.next_activity();
+    user.withdrawals_count = user.next_withdrawal()?;
+    user.activities_count = user.next_activity()?;
     self.users.save(ctx.deps.storage, &ctx.info.sender, &user)?;
 
     // Increment withdrawals_count and activities_count on the payable.
     // Also deduct balances on the involved payable.
-    payable.withdrawals_count = payable.next_withdrawal();
-    payable.activities_count = payable.next_activity();
+    payable.withdrawals_count = payable.next_withdrawal()?;
+    payable.activities_count = payable.next_activity()?;
     for balance in payable.balances.iter_mut() {
       if balance.token == token {
-        balance.amount = balance.amount.checked_sub(amount).unwrap();
+        balance.amount = balance
+          .amount
+          .checked_sub(amount)
+          .map_err(|_| ChainbillsError::Overflow {})?;
         break;
       }
     }
     self.payables.save(ctx.deps.storage, payable_id, &payable)?;
+    self.record_balance_checkpoint(
+      ctx.deps.storage,
+      &ctx.env,
+      payable_id,
+      &payable,
+      &token,
+    )?;
 
     // Increase the supported token's totals from this withdrawal.
-    token_details.add_withdrawn(amount);
-    token_details.add_withdrawal_fees_collected(fees);
+    token_details.add_withdrawn(amount)?;
+    token_details.add_withdrawal_fees_collected(fees)?;
     self
       .token_details
       .save(ctx.deps.storage, token.clone(), &token_details)?;
@@ -261,7 +361,7 @@ impl Withdrawals for Chainbills {
     let withdrawal_id = self.create_id(
       ctx.deps.storage,
       &ctx.env,
-      &ctx.info.sender.as_str(),
+      ctx.info.sender.as_str(),
       "withdrawal",
       user.withdrawals_count,
     )?;
@@ -312,7 +412,7 @@ impl Withdrawals for Chainbills {
     let activity_id = self.create_id(
       ctx.deps.storage,
       &ctx.env,
-      &ctx.info.sender.clone().as_str(),
+      ctx.info.sender.as_str(),
       "activity",
       user.activities_count,
     )?;
@@ -353,7 +453,76 @@ impl Withdrawals for Chainbills {
         ("chain_count", chain_stats.withdrawals_count.to_string()),
         ("host_count", user.withdrawals_count.to_string()),
         ("payable_count", payable.withdrawals_count.to_string()),
-      ]),
+        ("fee_split_bps", config.fee_split_bps.to_string()),
+        ("primary_fees", primary_fees.to_string()),
+        ("secondary_fees", secondary_fees.to_string()),
+      ])
+      .add_event(Event::new("withdrawal_made").add_attributes([
+        ("payable_id", HexBinary::from(&payable_id).to_hex()),
+        ("host_wallet", ctx.info.sender.to_string()),
+        ("withdrawal_id", HexBinary::from(&withdrawal_id).to_hex()),
+        ("token", token),
+        ("amount", amount.to_string()),
+        ("fee", fees.to_string()),
+      ])),
     )
   }
+  fn withdraw_multi(
+    &self,
+    ctx: ExecCtx,
+    msg: WithdrawMultipleMessage,
+  ) -> Result<Response, Self::Error> {
+    let WithdrawMultipleMessage {
+      payable_id,
+      tokens_and_amounts,
+    } = msg;
+
+    // Ensure that at least one token is withdrawn and that no token is
+    // repeated.
+    if tokens_and_amounts.is_empty() {
+      return Err(ChainbillsError::NoWithdrawalTokens {});
+    }
+    for (i, taa) in tokens_and_amounts.iter().enumerate() {
+      if tokens_and_amounts[..i].iter().any(|t| t.token == taa.token) {
+        return Err(ChainbillsError::DuplicateWithdrawalToken {
+          token: taa.token.clone(),
+        });
+      }
+    }
+
+    // Withdraw each token, keeping the transfers and events of each. The
+    // attributes of each withdrawal are kept in a withdrew event as their
+    // keys repeat across withdrawals.
+    let mut response = Response::new().add_attributes([
+      ("action", "withdrew_multiple".to_string()),
+      ("host_wallet", ctx.info.sender.to_string()),
+      ("withdrawals_count", tokens_and_amounts.len().to_string()),
+    ]);
+    let ExecCtx {
+      mut deps,
+      env,
+      info,
+    } = ctx;
+    for TokenAndAmount { token, amount } in tokens_and_amounts {
+      let withdrawn = self.withdraw(
+        ExecCtx {
+          deps: deps.branch(),
+          env: env.clone(),
+          info: info.clone(),
+        },
+        TransactionInfoMessage {
+          payable_id: payable_id.clone(),
+          token,
+          amount,
+          payer_override: None,
+          memo: None,
+        },
+      )?;
+      response = response
+        .add_submessages(withdrawn.messages)
+        .add_event(Event::new("withdrew").add_attributes(withdrawn.attributes))
+        .add_events(withdrawn.events);
+    }
+    Ok(response)
+  }
 }
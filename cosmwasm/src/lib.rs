@@ -4,4 +4,5 @@ pub mod interfaces;
 pub mod messages;
 #[cfg(test)]
 mod multitest;
+pub mod payload;
 pub mod state;
@@ -1,4 +1,6 @@
-use crate::state::TokenAndAmount;
+use crate::state::{
+  ActivityRecord, PayablePayment, TokenAndAmount, UserPayment,
+};
 use sylvia::cw_schema::cw_serde;
 use sylvia::cw_std::{Addr, Uint128};
 
@@ -20,9 +22,57 @@ pub struct UpdateMaxWithdrawalFeesMessage {
   pub max_withdrawal_fees: Uint128,
 }
 
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct SetTokenSupportedMessage {
+  pub token: String,
+  pub is_supported: bool,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct SetMinPaymentAmountMessage {
+  pub token: String,
+  /// The smallest amount of the token that payables accepting free payments
+  /// can be paid. None removes the minimum.
+  pub min_payment_amount: Option<Uint128>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct MigrateTokenBalancesMessage {
+  pub old_token: String,
+  pub new_token: String,
+  /// The ID of the last payable migrated in a previous call. Defaults to
+  /// starting from the first payable.
+  pub start_after: Option<String>,
+  pub limit: Option<u32>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct UpdateFeeSplitMessage {
+  pub fee_split_bps: Uint128,
+  pub secondary_fee_collector: Option<String>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct RelayerMessage {
+  pub relayer: String,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct RelayersMessage {
+  /// The whitelisted relayers, in the order they were added.
+  pub relayers: Vec<Addr>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct MaxWithdrawalAmountMessage {
+  /// The maximum amount of a single withdrawal. Zero means unlimited.
+  pub max_withdrawal_amount: Uint128,
+}
+
 #[cw_serde(crate = "sylvia::cw_schema")]
 pub struct CreatePayableMessage {
   pub allowed_tokens_and_amounts: Vec<TokenAndAmount>,
+  pub expires_at: Option<u64>,
 }
 
 #[cw_serde(crate = "sylvia::cw_schema")]
@@ -36,6 +86,18 @@ pub struct UpdatePayableTokensAndAmountsMessage {
   pub allowed_tokens_and_amounts: Vec<TokenAndAmount>,
 }
 
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct UpdatePayableAcceptsCrossChainMessage {
+  pub payable_id: String,
+  pub accepts_cross_chain: bool,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct UpdatePayablePayerAllowlistMessage {
+  pub payable_id: String,
+  pub payer_allowlist: Vec<String>,
+}
+
 #[cw_serde(crate = "sylvia::cw_schema")]
 pub struct FetchIdMessage {
   pub reference: String,
@@ -48,6 +110,28 @@ pub struct PerChainPayablePaymentsCountMessage {
   pub chain_id: u16,
 }
 
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct ChainPaymentsCount {
+  pub chain_id: u16,
+  pub count: u64,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PayablePaymentsPerChainMessage {
+  /// The count of payments a payable received from each chain, in order of
+  /// chain id. Chains without payments are omitted.
+  pub counts: Vec<ChainPaymentsCount>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PayablePaymentStatsMessage {
+  /// The count of all payments the payable received, from all chains.
+  pub total: u64,
+  /// The count of payments the payable received from each chain, in order of
+  /// chain id. Chains without payments are omitted.
+  pub per_chain: Vec<ChainPaymentsCount>,
+}
+
 #[cw_serde(crate = "sylvia::cw_schema")]
 pub struct CountMessage {
   pub count: u64,
@@ -65,4 +149,288 @@ pub struct TransactionInfoMessage {
   pub payable_id: String,
   pub token: String,
   pub amount: Uint128,
+  /// The wallet to record a payment under, when a whitelisted relayer pays
+  /// on its behalf. Ignored in withdrawals.
+  #[serde(default)]
+  pub payer_override: Option<String>,
+  /// A free-text reference (e.g. an order number or invoice ID) stored with
+  /// a payment. Ignored in withdrawals.
+  #[serde(default)]
+  pub memo: Option<String>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PayMultipleMessage {
+  pub payable_id: String,
+  /// The tokens and amounts paid together as a single payment. A token
+  /// can't be repeated.
+  pub tokens_and_amounts: Vec<TokenAndAmount>,
+  /// The wallet to record a payment under, when a whitelisted relayer pays
+  /// on its behalf.
+  #[serde(default)]
+  pub payer_override: Option<String>,
+  /// A free-text reference (e.g. an order number or invoice ID) stored with
+  /// the payment.
+  #[serde(default)]
+  pub memo: Option<String>,
+}
+
+impl From<TransactionInfoMessage> for PayMultipleMessage {
+  fn from(msg: TransactionInfoMessage) -> Self {
+    PayMultipleMessage {
+      payable_id: msg.payable_id,
+      tokens_and_amounts: vec![TokenAndAmount {
+        token: msg.token,
+        amount: msg.amount,
+      }],
+      payer_override: msg.payer_override,
+      memo: msg.memo,
+    }
+  }
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct WithdrawMultipleMessage {
+  pub payable_id: String,
+  /// The tokens and amounts withdrawn together, each recorded as its own
+  /// Withdrawal. A token can't be repeated.
+  pub tokens_and_amounts: Vec<TokenAndAmount>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PayableTwabMessage {
+  pub payable_id: String,
+  pub token: String,
+  pub start_ts: u64,
+  pub end_ts: u64,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct TwabMessage {
+  pub twab: Uint128,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct WithdrawalFeeMessage {
+  pub token: String,
+  pub amount: Uint128,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct WithdrawalFeePreview {
+  /// The amount to be withdrawn.
+  pub gross: Uint128,
+  /// The fees that will be deducted from the amount.
+  pub fee: Uint128,
+  /// The amount the host will receive.
+  pub net: Uint128,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PayableVolumeMessage {
+  /// The cumulative amount of each token ever paid into the payable.
+  pub volume: Vec<TokenAndAmount>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PayableBalancesMessage {
+  /// How much of each token is currently in the payable.
+  pub balances: Vec<TokenAndAmount>,
+  pub is_closed: bool,
+  pub payments_count: u64,
+  pub withdrawals_count: u64,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PayableSummaryMessage {
+  /// The wallet address that created the payable.
+  pub host: Addr,
+  /// The tokens (and their amounts) that the payable accepts.
+  pub allowed_tokens_and_amounts: Vec<TokenAndAmount>,
+  pub is_closed: bool,
+  pub payments_count: u64,
+  /// The number of tokens the payable has balances in.
+  pub balances_count: u64,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PayableIntegrityReport {
+  /// Whether no issues were found.
+  pub is_consistent: bool,
+  /// The number of stored payment IDs of the payable.
+  pub payment_ids_count: u64,
+  /// The number of stored withdrawal IDs of the payable.
+  pub withdrawal_ids_count: u64,
+  /// The number of stored activity IDs of the payable.
+  pub activity_ids_count: u64,
+  /// Descriptions of the inconsistencies that were found.
+  pub issues: Vec<String>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PaginatedFetchMessage {
+  pub reference: String,
+  /// The count to start after. Defaults to 0 (the first item).
+  pub start_after: Option<u64>,
+  pub limit: Option<u32>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PaginatedPaymentsMessage {
+  pub reference: String,
+  /// The count (among payments in the time range) to start after. Defaults
+  /// to 0 (the first payment).
+  pub start_after: Option<u64>,
+  pub limit: Option<u32>,
+  /// Only payments made at or after this time (in seconds) are returned.
+  #[serde(default)]
+  pub from_timestamp: Option<u64>,
+  /// Only payments made at or before this time (in seconds) are returned.
+  #[serde(default)]
+  pub to_timestamp: Option<u64>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PayablesByHostMessage {
+  pub host: String,
+  /// The payable count to start after. Defaults to 0 (the first payable).
+  pub start_after: Option<u64>,
+  pub limit: Option<u32>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct IdsMessage {
+  pub ids: Vec<String>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PaginatedUserPaymentsMessage {
+  pub payments: Vec<UserPayment>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PaginatedActivitiesMessage {
+  pub activities: Vec<ActivityRecord>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PaginatedPayablePaymentsMessage {
+  pub payments: Vec<PayablePayment>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PayablePaymentsByTokenMessage {
+  pub payable_id: String,
+  pub token: String,
+  /// The count of the token's payments (in the time range) to start after.
+  /// Defaults to 0 (the first payment in the token).
+  pub start_after: Option<u64>,
+  pub limit: Option<u32>,
+  /// Only payments made at or after this time (in seconds) are returned.
+  #[serde(default)]
+  pub from_timestamp: Option<u64>,
+  /// Only payments made at or before this time (in seconds) are returned.
+  #[serde(default)]
+  pub to_timestamp: Option<u64>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct UserPaymentsToHostMessage {
+  pub wallet: String,
+  pub host: String,
+  /// The user payment count to start scanning from. Defaults to 1.
+  pub start: Option<u64>,
+  pub limit: Option<u32>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct UserPaymentRecord {
+  pub id: String,
+  pub payment: UserPayment,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct UserPaymentsMessage {
+  pub payments: Vec<UserPaymentRecord>,
+  /// The count to pass as `start` to fetch the next page. None if there
+  /// are no more payments.
+  pub next: Option<u64>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct RefundPaymentMessage {
+  pub payment_id: String,
+  /// The token to refund. Has to be one of the payment's tokens.
+  pub token: String,
+  pub amount: Uint128,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PaymentOption {
+  pub token: String,
+  /// The amount required by the payable. None if the payable accepts any
+  /// amount (free payments).
+  pub amount: Option<Uint128>,
+  pub is_native_token: bool,
+  /// Whether payments are currently accepted in this token.
+  pub is_supported: bool,
+  /// The Cw20 symbol of the token. None for native tokens.
+  pub symbol: Option<String>,
+  /// The Cw20 decimals of the token. None for native tokens.
+  pub decimals: Option<u8>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct TokensMessage {
+  pub tokens: Vec<String>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct SupportedTokensDetailedMessage {
+  /// The last token returned in a previous call. Defaults to starting from
+  /// the first token.
+  pub start_after: Option<String>,
+  pub limit: Option<u32>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct SupportedTokenDetails {
+  /// The denom of a native token or the address of a Cw20 token.
+  pub token: String,
+  pub is_native_token: bool,
+  /// Whether payments are currently accepted in this token.
+  pub is_supported: bool,
+  pub max_withdrawal_fees: Uint128,
+  /// The Cw20 symbol of the token. None for native tokens.
+  pub symbol: Option<String>,
+  /// The Cw20 decimals of the token. None for native tokens.
+  pub decimals: Option<u8>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct SupportedTokensDetailedResponse {
+  pub tokens: Vec<SupportedTokenDetails>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct PaymentOptionsMessage {
+  pub options: Vec<PaymentOption>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct ChainIdMessage {
+  pub chain_id: u16,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct ForeignContractMessage {
+  /// Wormhole Chain ID of the foreign contract's chain.
+  pub chain_id: u16,
+  /// The 32-byte Wormhole-normalized emitter address in hex.
+  pub address: String,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+pub struct ForeignContractsMessage {
+  pub contracts: Vec<ForeignContractMessage>,
 }
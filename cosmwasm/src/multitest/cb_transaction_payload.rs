@@ -0,0 +1,38 @@
+use crate::error::ChainbillsError;
+use crate::payload::{CbTransaction, CB_TRANSACTION_LENGTH};
+
+fn transaction() -> CbTransaction {
+  CbTransaction {
+    payable_id: [1; 32],
+    token: [2; 32],
+    amount: 0x0102030405060708,
+    payer: [3; 32],
+  }
+}
+
+#[test]
+fn cb_transaction_encoding() {
+  // Must match the bytes asserted for the Solana program's CbTransaction.
+  let mut expected = vec![1u8; 32];
+  expected.extend([2u8; 32]);
+  expected.extend([1, 2, 3, 4, 5, 6, 7, 8]);
+  expected.extend([3u8; 32]);
+
+  let encoded = transaction().encode();
+  assert_eq!(encoded.len(), CB_TRANSACTION_LENGTH);
+  assert_eq!(encoded, expected);
+
+  // Round trip.
+  assert_eq!(CbTransaction::decode(&encoded).unwrap(), transaction());
+}
+
+#[test]
+fn malformed_cb_transaction() {
+  let encoded = transaction().encode();
+  for buf in [&[][..], &encoded[..40], &encoded[..103], &[0; 105][..]] {
+    assert_eq!(
+      CbTransaction::decode(buf).unwrap_err(),
+      ChainbillsError::InvalidPayload {}
+    );
+  }
+}
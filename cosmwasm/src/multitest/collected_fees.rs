@@ -0,0 +1,131 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::TokenAndAmount;
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn collected_fees() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(2000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  contract
+    .pay(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(2000),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&coins(2000, "native"))
+    .call(&user)
+    .unwrap();
+
+  let collected = || {
+    contract
+      .collected_fees(IdMessage {
+        id: "native".to_string(),
+      })
+      .unwrap()
+      .amount
+  };
+  assert_eq!(collected(), Uint128::zero());
+
+  // Each withdrawal adds its 2% fee to the collected fees.
+  for (amount, total_fees) in [(500, 10), (1000, 30), (250, 35)] {
+    contract
+      .withdraw(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .call(&host)
+      .unwrap();
+    assert_eq!(collected(), Uint128::new(total_fees));
+  }
+
+  // The remaining payable balance can't be swept by the owner.
+  let err = contract
+    .owner_withdraw(TokenAndAmount {
+      token: "native".to_string(),
+      amount: Uint128::new(36),
+    })
+    .call(&owner)
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::InsufficientCollectedFees {
+      collected: Uint128::new(35)
+    }
+  );
+
+  // Sweeping decrements the collected fees.
+  contract
+    .owner_withdraw(TokenAndAmount {
+      token: "native".to_string(),
+      amount: Uint128::new(15),
+    })
+    .call(&owner)
+    .unwrap();
+  assert_eq!(collected(), Uint128::new(20));
+
+  // The host can still withdraw the rest of the payable's balance.
+  contract
+    .withdraw(TransactionInfoMessage {
+      payable_id,
+      token: "native".to_string(),
+      amount: Uint128::new(250),
+      payer_override: None,
+      memo: None,
+    })
+    .call(&host)
+    .unwrap();
+  assert_eq!(collected(), Uint128::new(25));
+}
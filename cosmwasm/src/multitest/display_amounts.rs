@@ -0,0 +1,151 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  IdMessage, InstantiateMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::TokenAndAmount;
+use cw20_base::msg::InstantiateMsg;
+use std::str::FromStr;
+use sylvia::cw_multi_test::{Contract, ContractWrapper, Executor, IntoAddr};
+use sylvia::cw_std::{Decimal, Empty, Uint128};
+use sylvia::multitest::App;
+
+fn contract_cw20() -> Box<dyn Contract<Empty>> {
+  let contract = ContractWrapper::new(
+    cw20_base::contract::execute,
+    cw20_base::contract::instantiate,
+    cw20_base::contract::query,
+  );
+  Box::new(contract)
+}
+
+fn token_and_amount(amount: u128) -> TokenAndAmount {
+  TokenAndAmount {
+    token: "token".to_string(),
+    amount: Uint128::new(amount),
+  }
+}
+
+fn decimal(value: &str) -> Decimal {
+  Decimal::from_str(value).unwrap()
+}
+
+#[test]
+fn display_amounts() {
+  // 6 decimals
+  let taa = token_and_amount(1_500_000);
+  assert_eq!(taa.to_display_amount(6).unwrap(), decimal("1.5"));
+  assert_eq!(
+    TokenAndAmount::from_display_amount("token".to_string(), decimal("1.5"), 6)
+      .unwrap(),
+    taa
+  );
+
+  // 8 decimals (Wormhole-normalized amounts)
+  let taa = token_and_amount(12_345_678_901);
+  assert_eq!(taa.to_display_amount(8).unwrap(), decimal("123.45678901"));
+  assert_eq!(
+    TokenAndAmount::from_display_amount(
+      "token".to_string(),
+      decimal("123.45678901"),
+      8
+    )
+    .unwrap(),
+    taa
+  );
+
+  // 18 decimals
+  let taa = token_and_amount(2_000_000_000_000_000_001);
+  assert_eq!(
+    taa.to_display_amount(18).unwrap(),
+    decimal("2.000000000000000001")
+  );
+  assert_eq!(
+    TokenAndAmount::from_display_amount(
+      "token".to_string(),
+      decimal("2.000000000000000001"),
+      18
+    )
+    .unwrap(),
+    taa
+  );
+
+  // Precision beyond the token's decimals is rounded down.
+  assert_eq!(
+    TokenAndAmount::from_display_amount(
+      "token".to_string(),
+      decimal("1.2345679"),
+      6
+    )
+    .unwrap(),
+    token_and_amount(1_234_567)
+  );
+  assert_eq!(
+    TokenAndAmount::from_display_amount(
+      "token".to_string(),
+      decimal("0.000000009"),
+      8
+    )
+    .unwrap(),
+    token_and_amount(0)
+  );
+}
+
+#[test]
+fn stored_token_decimals() {
+  let owner = "owner".into_addr();
+
+  let mut app = sylvia::cw_multi_test::App::default();
+  let cw20_id = app.store_code(contract_cw20());
+  let usdc_addr = app
+    .instantiate_contract(
+      cw20_id,
+      owner.clone(),
+      &InstantiateMsg {
+        name: "USDC".to_string(),
+        symbol: "USDC".to_string(),
+        decimals: 6,
+        initial_balances: vec![],
+        mint: None,
+        marketing: None,
+      },
+      &[],
+      "USDC",
+      None,
+    )
+    .unwrap();
+
+  let app = App::new(app);
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  for (token, is_native_token) in
+    [("native".to_string(), true), (usdc_addr.to_string(), false)]
+  {
+    contract
+      .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+        token,
+        max_withdrawal_fees: Uint128::new(100),
+        is_native_token,
+      })
+      .call(&owner)
+      .unwrap();
+  }
+
+  // Cw20 decimals are stored, native ones are unknown.
+  let usdc = contract
+    .token_details(IdMessage {
+      id: usdc_addr.to_string(),
+    })
+    .unwrap();
+  assert_eq!(usdc.decimals, Some(6));
+  let native = contract
+    .token_details(IdMessage {
+      id: "native".to_string(),
+    })
+    .unwrap();
+  assert_eq!(native.decimals, None);
+}
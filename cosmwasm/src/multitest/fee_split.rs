@@ -0,0 +1,157 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, InstantiateMessage, TransactionInfoMessage,
+  UpdateFeeSplitMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::TokenAndAmount;
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn fee_split() {
+  let owner = "owner".into_addr();
+  let user = "user".into_addr();
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &owner, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+
+  let fee_collector = "fee_collector".into_addr();
+  let secondary_pool = "secondary_pool".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Only the owner can update the fee split
+  let err = contract
+    .update_fee_split(UpdateFeeSplitMessage {
+      fee_split_bps: Uint128::new(5000),
+      secondary_fee_collector: Some(secondary_pool.to_string()),
+    })
+    .call(&user)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::OwnerUnauthorized {});
+
+  // The split can't exceed 100%
+  let err = contract
+    .update_fee_split(UpdateFeeSplitMessage {
+      fee_split_bps: Uint128::new(10001),
+      secondary_fee_collector: Some(secondary_pool.to_string()),
+    })
+    .call(&owner)
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::InvalidFeeSplit {
+      fee_split_bps: Uint128::new(10001)
+    }
+  );
+
+  // A non-zero split requires a secondary fee collector
+  let err = contract
+    .update_fee_split(UpdateFeeSplitMessage {
+      fee_split_bps: Uint128::new(5000),
+      secondary_fee_collector: None,
+    })
+    .call(&owner)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::SecondaryFeeCollectorNotConfigured {});
+
+  // Set a 50/50 split
+  contract
+    .update_fee_split(UpdateFeeSplitMessage {
+      fee_split_bps: Uint128::new(5000),
+      secondary_fee_collector: Some(secondary_pool.to_string()),
+    })
+    .call(&owner)
+    .unwrap();
+  let config = contract.config().unwrap();
+  assert_eq!(config.fee_split_bps, Uint128::new(5000));
+  assert_eq!(config.secondary_fee_collector, Some(secondary_pool.clone()));
+
+  // Set MaxWithdrawalFees for Native Token
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable and pay into it
+  let payable_resp = contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&user)
+    .unwrap();
+  let payable_id = payable_resp
+    .events
+    .iter()
+    .find(|ev| ev.ty == "wasm")
+    .unwrap()
+    .attributes
+    .iter()
+    .find(|attr| attr.key == "payable_id")
+    .unwrap()
+    .value
+    .clone();
+  let tx_info = TransactionInfoMessage {
+    payable_id,
+    token: "native".to_string(),
+    amount: Uint128::new(1000),
+    payer_override: None,
+    memo: None,
+  };
+  contract
+    .pay(tx_info.clone())
+    .with_funds(&coins(1000, "native"))
+    .call(&owner)
+    .unwrap();
+
+  // Withdraw. 2% fees are 20, split into 10 for each collector.
+  let resp = contract.withdraw(tx_info).call(&user).unwrap();
+  let wasm = resp.events.iter().find(|ev| ev.ty == "wasm").unwrap();
+  let attr = |key: &str| {
+    wasm
+      .attributes
+      .iter()
+      .find(|attr| attr.key == key)
+      .unwrap()
+      .value
+      .clone()
+  };
+  assert_eq!(attr("fee_split_bps"), "5000");
+  assert_eq!(attr("primary_fees"), "10");
+  assert_eq!(attr("secondary_fees"), "10");
+
+  let querier = app.querier();
+  let balance = |addr| querier.query_balance(addr, "native").unwrap().amount;
+  assert_eq!(balance(&user), Uint128::new(980));
+  assert_eq!(balance(&secondary_pool), Uint128::new(10));
+  // The primary fees stay in the contract until the owner sweeps them.
+  assert_eq!(balance(&contract.contract_addr), Uint128::new(10));
+  contract
+    .owner_withdraw(TokenAndAmount {
+      token: "native".to_string(),
+      amount: Uint128::new(10),
+    })
+    .call(&owner)
+    .unwrap();
+  assert_eq!(balance(&fee_collector), Uint128::new(10));
+  assert_eq!(balance(&contract.contract_addr), Uint128::zero());
+}
@@ -0,0 +1,87 @@
+use crate::contract::sv::mt::CodeId;
+use crate::error::ChainbillsError;
+use crate::interfaces::foreign_contracts::sv::mt::ForeignContractsProxy;
+use crate::messages::{
+  ChainIdMessage, ForeignContractMessage, InstantiateMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::HexBinary;
+use sylvia::multitest::App;
+
+#[test]
+fn foreign_contracts() {
+  let app = App::default();
+  let code_id = CodeId::store_code(&app);
+  let owner = "owner".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  let contract_msg = |chain_id, byte: u8| ForeignContractMessage {
+    chain_id,
+    address: HexBinary::from(&[byte; 32]).to_hex(),
+  };
+
+  // Only the owner can register foreign contracts.
+  let err = contract
+    .register_foreign_contract(contract_msg(2, 1))
+    .call(&"other".into_addr())
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::OwnerUnauthorized {});
+
+  // This chain, zero addresses, and non-32-byte addresses are invalid.
+  for msg in [
+    contract_msg(1, 1),
+    contract_msg(2, 0),
+    ForeignContractMessage {
+      chain_id: 2,
+      address: "0101".to_string(),
+    },
+  ] {
+    let err = contract
+      .register_foreign_contract(msg)
+      .call(&owner)
+      .unwrap_err();
+    assert_eq!(err, ChainbillsError::InvalidForeignContract {});
+  }
+
+  // Register two foreign contracts and read both back.
+  for msg in [contract_msg(30, 2), contract_msg(2, 1)] {
+    contract
+      .register_foreign_contract(msg)
+      .call(&owner)
+      .unwrap();
+  }
+  assert_eq!(
+    contract
+      .foreign_contract(ChainIdMessage { chain_id: 2 })
+      .unwrap(),
+    contract_msg(2, 1)
+  );
+  assert_eq!(
+    contract.foreign_contracts().unwrap().contracts,
+    vec![contract_msg(2, 1), contract_msg(30, 2)]
+  );
+
+  // Re-registering a chain updates its address.
+  contract
+    .register_foreign_contract(contract_msg(2, 3))
+    .call(&owner)
+    .unwrap();
+  assert_eq!(
+    contract
+      .foreign_contract(ChainIdMessage { chain_id: 2 })
+      .unwrap(),
+    contract_msg(2, 3)
+  );
+
+  // Unregistered chains can't be read.
+  let err = contract
+    .foreign_contract(ChainIdMessage { chain_id: 4 })
+    .unwrap_err();
+  // Testing the error message because a Querrier error is returned instead
+  // of a ChainbillsError.
+  assert!(err.to_string().contains("Unregistered Foreign Chain: 4"));
+}
@@ -1,4 +1,5 @@
 use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
 use crate::messages::InstantiateMessage;
 use sylvia::cw_multi_test::IntoAddr;
 use sylvia::multitest::App;
@@ -24,3 +25,22 @@ fn instantiate() {
   assert_eq!(config.owner, owner);
   assert_eq!(config.chainbills_fee_collector, fee_collector);
 }
+
+#[test]
+fn instantiate_validates_chain_id() {
+  let app = App::default();
+  let code_id = CodeId::store_code(&app);
+  let owner = "owner".into_addr();
+  let init_msg = |chain_id| InstantiateMessage {
+    chain_id,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+
+  // A zero chain ID is rejected.
+  let err = code_id.instantiate(init_msg(0)).call(&owner).unwrap_err();
+  assert_eq!(err, ChainbillsError::InvalidChainId { chain_id: 0 });
+
+  // A valid chain ID is accepted and readable.
+  let contract = code_id.instantiate(init_msg(3)).call(&owner).unwrap();
+  assert_eq!(contract.chain_id().unwrap().chain_id, 3);
+}
@@ -0,0 +1,117 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage,
+  MaxWithdrawalAmountMessage, TransactionInfoMessage,
+  UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn max_withdrawal_amount() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  contract
+    .pay(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(1000),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&coins(1000, "native"))
+    .call(&user)
+    .unwrap();
+
+  // Unlimited by default.
+  assert_eq!(
+    contract.config().unwrap().max_withdrawal_amount,
+    Uint128::zero()
+  );
+
+  // Only the owner can set the maximum.
+  let set_max = |max: u128| MaxWithdrawalAmountMessage {
+    max_withdrawal_amount: Uint128::new(max),
+  };
+  let err = contract
+    .update_max_withdrawal_amount(set_max(100))
+    .call(&host)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::OwnerUnauthorized {});
+  contract
+    .update_max_withdrawal_amount(set_max(100))
+    .call(&owner)
+    .unwrap();
+
+  let withdraw = |amount: u128| {
+    contract
+      .withdraw(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .call(&host)
+  };
+
+  // Below and at the cap are allowed, above it isn't.
+  withdraw(99).unwrap();
+  withdraw(100).unwrap();
+  assert_eq!(
+    withdraw(101).unwrap_err(),
+    ChainbillsError::WithdrawalExceedsMax {
+      max: Uint128::new(100)
+    }
+  );
+
+  // Setting zero removes the cap.
+  contract
+    .update_max_withdrawal_amount(set_max(0))
+    .call(&owner)
+    .unwrap();
+  withdraw(801).unwrap();
+}
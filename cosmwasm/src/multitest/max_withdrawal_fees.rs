@@ -4,7 +4,7 @@ use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
 use crate::messages::{
   IdMessage, InstantiateMessage, UpdateMaxWithdrawalFeesMessage,
 };
-use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_multi_test::{AppResponse, IntoAddr};
 use sylvia::cw_std::Uint128;
 use sylvia::multitest::App;
 
@@ -61,6 +61,35 @@ fn max_withdrawal_fees() {
     })
     .unwrap();
   assert_eq!(supported_token_resp.max_withdrawal_fees, amount);
+  assert!(supported_token_resp.is_supported);
+  assert!(!supported_token_resp.is_native_token);
+
+  // The event carries the previous and new fees.
+  let attr = |resp: &AppResponse, key: &str| {
+    resp
+      .events
+      .iter()
+      .find(|ev| ev.ty == "wasm")
+      .unwrap()
+      .attributes
+      .iter()
+      .find(|attr| attr.key == key)
+      .unwrap()
+      .value
+      .clone()
+  };
+  assert_eq!(attr(&resp, "previous_max_withdrawal_fees"), "0");
+  let resp = contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "token".into_addr().to_string(),
+      max_withdrawal_fees: Uint128::new(40),
+      is_native_token: false,
+    })
+    .call(&owner)
+    .unwrap();
+  assert_eq!(attr(&resp, "token"), "token".into_addr().to_string());
+  assert_eq!(attr(&resp, "previous_max_withdrawal_fees"), "100");
+  assert_eq!(attr(&resp, "max_withdrawal_fees"), "40");
 
   // Unauthorized
   let token = "token".into_addr().to_string();
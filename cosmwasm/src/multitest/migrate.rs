@@ -0,0 +1,74 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::contract::{CONTRACT_NAME, CONTRACT_VERSION};
+use crate::error::ChainbillsError;
+use crate::messages::InstantiateMessage;
+use cw2::{get_contract_version, set_contract_version};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+#[test]
+fn migrate() {
+  let app = App::default();
+  let code_id = CodeId::store_code(&app);
+
+  let owner = "owner".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id
+    .instantiate(init_msg)
+    .with_admin(owner.as_str())
+    .call(&owner)
+    .unwrap();
+
+  // Overwrites the contract version stored by the contract.
+  let set_stored_version = |name: &str, version: &str| {
+    let mut app = app.app_mut();
+    let mut storage = app.contract_storage_mut(&contract.contract_addr);
+    set_contract_version(storage.as_mut(), name, version).unwrap();
+  };
+  let stored_version = || {
+    let app = app.app();
+    let storage = app.contract_storage(&contract.contract_addr);
+    get_contract_version(storage.as_ref()).unwrap().version
+  };
+
+  // Migrating from an old version bumps the stored version without touching
+  // the rest of the state.
+  set_stored_version(CONTRACT_NAME, "0.0.1");
+  contract.migrate().call(&owner, code_id.code_id()).unwrap();
+  assert_eq!(stored_version(), CONTRACT_VERSION);
+  assert_eq!(contract.config().unwrap().chain_id, 1);
+
+  // Migrating to the same version is a no-op.
+  contract.migrate().call(&owner, code_id.code_id()).unwrap();
+  assert_eq!(stored_version(), CONTRACT_VERSION);
+
+  // Downgrades are rejected.
+  set_stored_version(CONTRACT_NAME, "99.0.0");
+  let err = contract
+    .migrate()
+    .call(&owner, code_id.code_id())
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::MigrationDowngrade {
+      stored: "99.0.0".to_string(),
+      current: CONTRACT_VERSION.to_string(),
+    }
+  );
+
+  // Other contracts can't be migrated into Chainbills.
+  set_stored_version("crates.io:other", "0.0.1");
+  let err = contract
+    .migrate()
+    .call(&owner, code_id.code_id())
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::InvalidMigrationContract {
+      contract: "crates.io:other".to_string(),
+    }
+  );
+}
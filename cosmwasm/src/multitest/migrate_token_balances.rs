@@ -0,0 +1,224 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  MigrateTokenBalancesMessage, TransactionInfoMessage,
+  UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::TokenAndAmount;
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Coin, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn migrate_token_balances() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+  let whale = "whale".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(
+          storage,
+          &user,
+          vec![Coin::new(100u128, "new"), Coin::new(100u128, "old")],
+        )
+        .unwrap();
+      router
+        .bank
+        .init_balance(
+          storage,
+          &whale,
+          vec![Coin::new(u128::MAX - 200, "new"), Coin::new(201u128, "old")],
+        )
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Support the old token
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "old".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create three Payables
+  let mut payable_ids = vec![];
+  for count in 1..=3 {
+    contract
+      .create_payable(CreatePayableMessage {
+        allowed_tokens_and_amounts: vec![],
+        expires_at: None,
+      })
+      .call(&host)
+      .unwrap();
+    payable_ids.push(
+      contract
+        .user_payable_id(FetchIdMessage {
+          reference: host.to_string(),
+          count,
+        })
+        .unwrap()
+        .id,
+    );
+  }
+  let pay_from = |payer, payable_id: &String, token: &str, amount: u128| {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: token.to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(amount, token))
+      .call(payer)
+      .unwrap();
+  };
+  let pay = |payable_id: &String, token: &str, amount: u128| {
+    pay_from(&user, payable_id, token, amount)
+  };
+
+  // The first payable only holds the old token.
+  pay(&payable_ids[0], "old", 10);
+
+  // The new token can't be migrated to until it is supported.
+  let migrate_msg = MigrateTokenBalancesMessage {
+    old_token: "old".to_string(),
+    new_token: "new".to_string(),
+    start_after: None,
+    limit: Some(2),
+  };
+  let err = contract
+    .migrate_token_balances(migrate_msg.clone())
+    .call(&owner)
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::UnsupportedToken {
+      token: "new".to_string()
+    }
+  );
+
+  // Support the new token. The second payable holds both tokens and the
+  // third holds none.
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "new".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+  pay(&payable_ids[1], "new", 5);
+  pay(&payable_ids[1], "old", 20);
+
+  // Only the owner can migrate.
+  let err = contract
+    .migrate_token_balances(migrate_msg.clone())
+    .call(&user)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::OwnerUnauthorized {});
+
+  // The tokens have to be different.
+  let err = contract
+    .migrate_token_balances(MigrateTokenBalancesMessage {
+      new_token: "old".to_string(),
+      ..migrate_msg.clone()
+    })
+    .call(&owner)
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::SameTokenMigration {
+      token: "old".to_string()
+    }
+  );
+
+  // Migrate in pages of two, resuming from the returned cursor.
+  let attr = |resp: &sylvia::cw_multi_test::AppResponse, key: &str| {
+    resp
+      .events
+      .iter()
+      .find(|ev| ev.ty == "wasm")
+      .unwrap()
+      .attributes
+      .iter()
+      .find(|attr| attr.key == key)
+      .unwrap()
+      .value
+      .clone()
+  };
+  let first_page = contract
+    .migrate_token_balances(migrate_msg.clone())
+    .call(&owner)
+    .unwrap();
+  let next_start_after = attr(&first_page, "next_start_after");
+  assert!(!next_start_after.is_empty());
+  let second_page = contract
+    .migrate_token_balances(MigrateTokenBalancesMessage {
+      start_after: Some(next_start_after),
+      ..migrate_msg
+    })
+    .call(&owner)
+    .unwrap();
+  assert_eq!(attr(&second_page, "next_start_after"), "");
+  let migrated_count: u64 =
+    attr(&first_page, "migrated_count").parse::<u64>().unwrap()
+      + attr(&second_page, "migrated_count").parse::<u64>().unwrap();
+  assert_eq!(migrated_count, 2);
+
+  // The old balance was renamed, merged, or left alone as applicable.
+  let balances = |payable_id: &String| {
+    contract
+      .payable(IdMessage {
+        id: payable_id.clone(),
+      })
+      .unwrap()
+      .balances
+  };
+  assert_eq!(
+    balances(&payable_ids[0]),
+    vec![TokenAndAmount {
+      token: "new".to_string(),
+      amount: Uint128::new(10),
+    }]
+  );
+  assert_eq!(
+    balances(&payable_ids[1]),
+    vec![TokenAndAmount {
+      token: "new".to_string(),
+      amount: Uint128::new(25),
+    }]
+  );
+  assert!(balances(&payable_ids[2]).is_empty());
+
+  // Merging balances whose sum overflows fails instead of panicking.
+  pay_from(&whale, &payable_ids[2], "new", u128::MAX - 200);
+  pay_from(&whale, &payable_ids[2], "old", 201);
+  let err = contract
+    .migrate_token_balances(MigrateTokenBalancesMessage {
+      old_token: "old".to_string(),
+      new_token: "new".to_string(),
+      start_after: None,
+      limit: None,
+    })
+    .call(&owner)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::Overflow {});
+}
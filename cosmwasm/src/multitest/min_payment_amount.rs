@@ -0,0 +1,138 @@
+use crate::contract::sv::mt::CodeId;
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  SetMinPaymentAmountMessage, TransactionInfoMessage,
+  UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::TokenAndAmount;
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn min_payment_amount() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a free payable and one that only accepts 5 native.
+  for allowed_tokens_and_amounts in [
+    vec![],
+    vec![TokenAndAmount {
+      token: "native".to_string(),
+      amount: Uint128::new(5),
+    }],
+  ] {
+    contract
+      .create_payable(CreatePayableMessage {
+        allowed_tokens_and_amounts,
+        expires_at: None,
+      })
+      .call(&host)
+      .unwrap();
+  }
+  let payable_id = |count| {
+    contract
+      .user_payable_id(FetchIdMessage {
+        reference: host.to_string(),
+        count,
+      })
+      .unwrap()
+      .id
+  };
+  let free_id = payable_id(1);
+  let constrained_id = payable_id(2);
+  let pay = |payable_id: &String, amount: u128| {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(amount, "native"))
+      .call(&user)
+  };
+  let set_min = |min_payment_amount: Option<u128>| {
+    contract.set_min_payment_amount(SetMinPaymentAmountMessage {
+      token: "native".to_string(),
+      min_payment_amount: min_payment_amount.map(Uint128::new),
+    })
+  };
+
+  // Only the owner can set the minimum, and only on registered tokens.
+  let err = set_min(Some(10)).call(&user).unwrap_err();
+  assert_eq!(err, ChainbillsError::OwnerUnauthorized {});
+  let err = contract
+    .set_min_payment_amount(SetMinPaymentAmountMessage {
+      token: "unknown".to_string(),
+      min_payment_amount: Some(Uint128::new(10)),
+    })
+    .call(&owner)
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::InvalidToken {
+      token: "unknown".to_string()
+    }
+  );
+
+  // Without a minimum, any non-zero amount is accepted.
+  pay(&free_id, 1).unwrap();
+
+  set_min(Some(10)).call(&owner).unwrap();
+  let details = contract
+    .token_details(IdMessage {
+      id: "native".to_string(),
+    })
+    .unwrap();
+  assert_eq!(details.min_payment_amount, Some(Uint128::new(10)));
+
+  // Below the minimum is rejected, at and above it are accepted.
+  let err = pay(&free_id, 9).unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::PaymentBelowMinimum {
+      min: Uint128::new(10)
+    }
+  );
+  pay(&free_id, 10).unwrap();
+  pay(&free_id, 11).unwrap();
+
+  // Payables with specified amounts aren't affected by the minimum.
+  pay(&constrained_id, 5).unwrap();
+
+  // Removing the minimum accepts small payments again.
+  set_min(None).call(&owner).unwrap();
+  pay(&free_id, 9).unwrap();
+
+  let payable = contract.payable(IdMessage { id: free_id }).unwrap();
+  assert_eq!(payable.payments_count, 4);
+}
@@ -1,4 +1,17 @@
+mod cb_transaction_payload;
+mod collected_fees;
+mod display_amounts;
+mod fee_split;
+mod foreign_contracts;
 mod instantiate;
+mod migrate;
+mod migrate_token_balances;
+mod min_payment_amount;
+mod max_withdrawal_amount;
 mod max_withdrawal_fees;
 mod owner_can_withdraw;
+mod payment_options;
+mod relayers;
+mod supported_tokens;
 mod users;
+mod withdrawal_payload;
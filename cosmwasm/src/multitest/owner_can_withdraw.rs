@@ -1,6 +1,13 @@
 use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
 use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
-use crate::messages::{InstantiateMessage, UpdateMaxWithdrawalFeesMessage};
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
 use crate::state::TokenAndAmount;
 use cw20::{BalanceResponse, Cw20Coin};
 use cw20_base::msg::InstantiateMsg;
@@ -20,12 +27,13 @@ fn contract_cw20() -> Box<dyn Contract<Empty>> {
 #[test]
 fn owner_can_withdraw() {
   let owner = "owner".into_addr();
+  let host = "host".into_addr();
   let user = "user".into_addr();
 
   let app = sylvia::cw_multi_test::App::new(|router, _api, storage| {
     router
       .bank
-      .init_balance(storage, &user, coins(100, "native"))
+      .init_balance(storage, &user, coins(1100, "native"))
       .unwrap();
   });
 
@@ -49,8 +57,8 @@ fn owner_can_withdraw() {
         symbol: "USDC".to_string(),
         decimals: 6,
         initial_balances: vec![Cw20Coin {
-          address: contract.contract_addr.clone().to_string(),
-          amount: Uint128::new(100),
+          address: user.to_string(),
+          amount: Uint128::new(1000),
         }],
         mint: None,
         marketing: None,
@@ -60,149 +68,165 @@ fn owner_can_withdraw() {
       None,
     )
     .unwrap();
-
-  println!(
-    "Owner Native Balance: {:?}",
-    app.querier().query_balance(&owner, "native")
-  );
-  println!(
-    "User Native Balance: {:?}",
-    app.querier().query_balance(&user, "native")
-  );
-  println!(
-    "Contract Native Balance: {:?}",
-    app
-      .querier()
-      .query_balance(&contract.contract_addr, "native")
-  );
-  println!(
-    "Owner USDC Balance: {:?}",
-    (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
-      &cw20::Cw20QueryMsg::Balance {
-        address: owner.clone().to_string(),
-      }
-    ) as StdResult<BalanceResponse>)
-      .unwrap()
-  );
-  println!(
-    "User USDC Balance: {:?}",
-    (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
-      &cw20::Cw20QueryMsg::Balance {
-        address: user.clone().to_string(),
-      }
-    ) as StdResult<BalanceResponse>)
-      .unwrap()
-  );
-  println!(
-    "Contract USDC Balance: {:?}",
+  let usdc_balance = |addr: &str| {
     (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
+      usdc_addr.clone(),
       &cw20::Cw20QueryMsg::Balance {
-        address: contract.contract_addr.clone().to_string(),
-      }
+        address: addr.to_string(),
+      },
     ) as StdResult<BalanceResponse>)
       .unwrap()
-  );
-  println!();
+      .balance
+  };
 
   // Set MaxWithdrawalFee for Native Token and Cw20 Token
   // Necessary for the contract to know if token is native or cw20
   // when making owner withdrawals
+  for (token, is_native_token) in
+    [("native".to_string(), true), (usdc_addr.to_string(), false)]
+  {
+    contract
+      .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+        token,
+        max_withdrawal_fees: Uint128::new(100),
+        is_native_token,
+      })
+      .call(&owner)
+      .unwrap();
+  }
+
+  // Pay into a payable and withdraw from it in both tokens. 2% withdrawal
+  // fees of 1000 are 20, which the contract keeps.
   contract
-    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
-      token: "native".to_string(),
-      max_withdrawal_fees: Uint128::new(100),
-      is_native_token: true,
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
     })
-    .call(&owner)
+    .call(&host)
     .unwrap();
-  contract
-    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
-      token: usdc_addr.clone().to_string(),
-      max_withdrawal_fees: Uint128::new(100),
-      is_native_token: false,
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
     })
-    .call(&owner)
+    .unwrap()
+    .id;
+  app
+    .app_mut()
+    .execute_contract(
+      user.clone(),
+      usdc_addr.clone(),
+      &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+        spender: contract.contract_addr.to_string(),
+        amount: Uint128::new(1000),
+        expires: None,
+      },
+      &[],
+    )
     .unwrap();
+  for token in ["native".to_string(), usdc_addr.to_string()] {
+    let tx_info = TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: token.clone(),
+      amount: Uint128::new(1000),
+      payer_override: None,
+      memo: None,
+    };
+    let funds = if token == "native" { coins(1000, "native") } else { vec![] };
+    contract
+      .pay(tx_info.clone())
+      .with_funds(&funds)
+      .call(&user)
+      .unwrap();
+    contract.withdraw(tx_info).call(&host).unwrap();
+  }
+  assert_eq!(
+    app
+      .querier()
+      .query_balance(&contract.contract_addr, "native")
+      .unwrap()
+      .amount,
+    Uint128::new(20)
+  );
+  assert_eq!(
+    usdc_balance(contract.contract_addr.as_str()),
+    Uint128::new(20)
+  );
 
-  // send funds to contract
-  let respo = app
+  // Funds sent to the contract outside of payments are not fees.
+  app
     .app_mut()
     .execute_multi(
       user.clone(),
       vec![CosmosMsg::Bank(BankMsg::Send {
-        to_address: contract.contract_addr.clone().to_string(),
+        to_address: contract.contract_addr.to_string(),
         amount: coins(100, "native"),
       })],
     )
     .unwrap();
-  println!("{:?}", respo);
 
-  // Owner Withdraw for Native Token
-  let respo1 = contract
+  // Only the owner can withdraw fees.
+  let native_fees = TokenAndAmount {
+    token: "native".to_string(),
+    amount: Uint128::new(20),
+  };
+  let err = contract
+    .owner_withdraw(native_fees.clone())
+    .call(&user)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::OwnerUnauthorized {});
+
+  // The owner can't withdraw more than the collected fees.
+  let err = contract
     .owner_withdraw(TokenAndAmount {
       token: "native".to_string(),
-      amount: Uint128::new(100),
+      amount: Uint128::new(21),
     })
     .call(&owner)
-    .unwrap();
-  println!("{:?}", respo1);
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::InsufficientCollectedFees {
+      collected: Uint128::new(20)
+    }
+  );
 
-  // Owner Withdraw for Cw20 Token
-  let respo2 = contract
+  // Owner Withdraw for Native Token and Cw20 Token. Fees go to the fee
+  // collector.
+  contract.owner_withdraw(native_fees).call(&owner).unwrap();
+  contract
     .owner_withdraw(TokenAndAmount {
-      token: usdc_addr.clone().to_string(),
-      amount: Uint128::new(100),
+      token: usdc_addr.to_string(),
+      amount: Uint128::new(20),
     })
     .call(&owner)
     .unwrap();
-  println!("{:?}", respo2);
-
-  println!();
-  println!(
-    "Owner Native Balance: {:?}",
-    app.querier().query_balance(&owner, "native")
-  );
-  println!(
-    "User Native Balance: {:?}",
-    app.querier().query_balance(&user, "native")
-  );
-  println!(
-    "Contract Native Balance: {:?}",
+  assert_eq!(
     app
       .querier()
-      .query_balance(&contract.contract_addr, "native")
-  );
-  println!(
-    "Owner USDC Balance: {:?}",
-    (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
-      &cw20::Cw20QueryMsg::Balance {
-        address: owner.clone().to_string(),
-      }
-    ) as StdResult<BalanceResponse>)
+      .query_balance(&fee_collector, "native")
       .unwrap()
+      .amount,
+    Uint128::new(20)
   );
-  println!(
-    "User USDC Balance: {:?}",
-    (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
-      &cw20::Cw20QueryMsg::Balance {
-        address: user.clone().to_string(),
-      }
-    ) as StdResult<BalanceResponse>)
-      .unwrap()
+  assert_eq!(usdc_balance(fee_collector.as_str()), Uint128::new(20));
+  assert_eq!(
+    usdc_balance(contract.contract_addr.as_str()),
+    Uint128::zero()
   );
-  println!(
-    "Contract USDC Balance: {:?}",
-    (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
-      &cw20::Cw20QueryMsg::Balance {
-        address: contract.contract_addr.clone().to_string(),
-      }
-    ) as StdResult<BalanceResponse>)
-      .unwrap()
+
+  // Nothing more can be swept.
+  let err = contract
+    .owner_withdraw(TokenAndAmount {
+      token: "native".to_string(),
+      amount: Uint128::new(10),
+    })
+    .call(&owner)
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::InsufficientCollectedFees {
+      collected: Uint128::zero()
+    }
   );
 }
@@ -0,0 +1,155 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, IdMessage, InstantiateMessage,
+  UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::TokenAndAmount;
+use cw20::Cw20Coin;
+use cw20_base::msg::InstantiateMsg;
+use sylvia::cw_multi_test::{Contract, ContractWrapper, Executor, IntoAddr};
+use sylvia::cw_std::{Empty, Uint128};
+use sylvia::multitest::App;
+
+fn contract_cw20() -> Box<dyn Contract<Empty>> {
+  let contract = ContractWrapper::new(
+    cw20_base::contract::execute,
+    cw20_base::contract::instantiate,
+    cw20_base::contract::query,
+  );
+  Box::new(contract)
+}
+
+#[test]
+fn payable_payment_options() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+
+  let mut app = sylvia::cw_multi_test::App::default();
+  let cw20_id = app.store_code(contract_cw20());
+  let usdc_addr = app
+    .instantiate_contract(
+      cw20_id,
+      owner.clone(),
+      &InstantiateMsg {
+        name: "USDC".to_string(),
+        symbol: "USDC".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin {
+          address: host.to_string(),
+          amount: Uint128::new(100),
+        }],
+        mint: None,
+        marketing: None,
+      },
+      &[],
+      "USDC",
+      None,
+    )
+    .unwrap();
+
+  let app = App::new(app);
+  let code_id = CodeId::store_code(&app);
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Support the Native Token and USDC
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: usdc_addr.to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: false,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a fixed Payable
+  let payable_resp = contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![
+        TokenAndAmount {
+          token: usdc_addr.to_string(),
+          amount: Uint128::new(20),
+        },
+        TokenAndAmount {
+          token: "native".to_string(),
+          amount: Uint128::new(10),
+        },
+      ],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = payable_resp
+    .events
+    .iter()
+    .find(|ev| ev.ty == "wasm")
+    .unwrap()
+    .attributes
+    .iter()
+    .find(|attr| attr.key == "payable_id")
+    .unwrap()
+    .value
+    .clone();
+
+  // Fetch the enriched Payment Options
+  let options = contract
+    .payable_payment_options(IdMessage {
+      id: payable_id.clone(),
+    })
+    .unwrap()
+    .options;
+  assert_eq!(options.len(), 2);
+  assert_eq!(options[0].token, usdc_addr.to_string());
+  assert_eq!(options[0].amount, Some(Uint128::new(20)));
+  assert_eq!(options[0].symbol, Some("USDC".to_string()));
+  assert_eq!(options[0].decimals, Some(6));
+  assert!(options[0].is_supported);
+  assert!(!options[0].is_native_token);
+  assert_eq!(options[1].token, "native".to_string());
+  assert_eq!(options[1].amount, Some(Uint128::new(10)));
+  assert_eq!(options[1].symbol, None);
+  assert!(options[1].is_supported);
+  assert!(options[1].is_native_token);
+
+  // Create a free Payable
+  let payable_resp = contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = payable_resp
+    .events
+    .iter()
+    .find(|ev| ev.ty == "wasm")
+    .unwrap()
+    .attributes
+    .iter()
+    .find(|attr| attr.key == "payable_id")
+    .unwrap()
+    .value
+    .clone();
+
+  // Free Payables offer all supported tokens without amounts
+  let options = contract
+    .payable_payment_options(IdMessage { id: payable_id })
+    .unwrap()
+    .options;
+  assert_eq!(options.len(), 2);
+  assert!(options.iter().all(|o| o.amount.is_none() && o.is_supported));
+}
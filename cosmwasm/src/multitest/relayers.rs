@@ -0,0 +1,57 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
+use crate::messages::{InstantiateMessage, RelayerMessage};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+#[test]
+fn relayers() {
+  let app = App::default();
+  let code_id = CodeId::store_code(&app);
+
+  let owner = "owner".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  let relayer = "relayer".into_addr();
+  let other_relayer = "other_relayer".into_addr();
+  let stranger = "stranger".into_addr();
+  let msg = |relayer: &sylvia::cw_std::Addr| RelayerMessage {
+    relayer: relayer.to_string(),
+  };
+
+  // There are no relayers at first.
+  assert!(contract.relayers().unwrap().relayers.is_empty());
+
+  // Only the owner can add or remove relayers.
+  let err = contract
+    .add_relayer(msg(&relayer))
+    .call(&stranger)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::OwnerUnauthorized {});
+  let err = contract
+    .remove_relayer(msg(&relayer))
+    .call(&stranger)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::OwnerUnauthorized {});
+
+  // Adding a relayer twice doesn't duplicate it.
+  for relayer in [&relayer, &relayer, &other_relayer] {
+    contract.add_relayer(msg(relayer)).call(&owner).unwrap();
+  }
+  assert_eq!(
+    contract.relayers().unwrap().relayers,
+    vec![relayer.clone(), other_relayer.clone()]
+  );
+
+  // Removed relayers are no longer listed. Removing an unknown relayer is a
+  // no-op.
+  contract.remove_relayer(msg(&relayer)).call(&owner).unwrap();
+  contract
+    .remove_relayer(msg(&stranger))
+    .call(&owner)
+    .unwrap();
+  assert_eq!(contract.relayers().unwrap().relayers, vec![other_relayer]);
+}
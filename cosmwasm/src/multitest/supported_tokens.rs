@@ -0,0 +1,171 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  InstantiateMessage, SetTokenSupportedMessage, SupportedTokensDetailedMessage,
+  UpdateMaxWithdrawalFeesMessage,
+};
+use cw20_base::msg::InstantiateMsg;
+use sylvia::cw_multi_test::{Contract, ContractWrapper, Executor, IntoAddr};
+use sylvia::cw_std::{Empty, Uint128};
+use sylvia::multitest::App;
+
+fn contract_cw20() -> Box<dyn Contract<Empty>> {
+  let contract = ContractWrapper::new(
+    cw20_base::contract::execute,
+    cw20_base::contract::instantiate,
+    cw20_base::contract::query,
+  );
+  Box::new(contract)
+}
+
+#[test]
+fn supported_tokens_detailed() {
+  let owner = "owner".into_addr();
+
+  let mut app = sylvia::cw_multi_test::App::default();
+  let cw20_id = app.store_code(contract_cw20());
+  let mut instantiate_cw20 = |symbol: &str, decimals: u8| {
+    app
+      .instantiate_contract(
+        cw20_id,
+        owner.clone(),
+        &InstantiateMsg {
+          name: symbol.to_string(),
+          symbol: symbol.to_string(),
+          decimals,
+          initial_balances: vec![],
+          mint: None,
+          marketing: None,
+        },
+        &[],
+        symbol,
+        None,
+      )
+      .unwrap()
+  };
+  let usdc_addr = instantiate_cw20("USDC", 6);
+  let usdt_addr = instantiate_cw20("USDT", 8);
+
+  let app = App::new(app);
+  let code_id = CodeId::store_code(&app);
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Support the Native Token, USDC, and USDT with different max fees.
+  let tokens = [
+    ("native".to_string(), true, 10u128),
+    (usdc_addr.to_string(), false, 20),
+    (usdt_addr.to_string(), false, 30),
+  ];
+  for (token, is_native_token, max_withdrawal_fees) in tokens.iter() {
+    contract
+      .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+        token: token.clone(),
+        max_withdrawal_fees: Uint128::new(*max_withdrawal_fees),
+        is_native_token: *is_native_token,
+      })
+      .call(&owner)
+      .unwrap();
+  }
+
+  let page = |start_after, limit| {
+    contract
+      .supported_tokens_detailed(SupportedTokensDetailedMessage {
+        start_after,
+        limit,
+      })
+      .unwrap()
+      .tokens
+  };
+
+  // Walk through pages of two.
+  let first = page(None, Some(2));
+  assert_eq!(first.len(), 2);
+  let second = page(Some(first[1].token.clone()), Some(2));
+  assert_eq!(second.len(), 1);
+  assert!(page(Some(second[0].token.clone()), Some(2)).is_empty());
+
+  // All tokens are returned once, in key order, with their details.
+  let all = [first, second].concat();
+  assert_eq!(all, page(None, None));
+  let mut expected_order: Vec<_> = tokens.iter().map(|t| t.0.clone()).collect();
+  expected_order.sort();
+  assert_eq!(
+    all.iter().map(|t| t.token.clone()).collect::<Vec<_>>(),
+    expected_order
+  );
+  for details in all.iter() {
+    let (_, is_native_token, max_withdrawal_fees) =
+      tokens.iter().find(|t| t.0 == details.token).unwrap();
+    assert_eq!(details.is_native_token, *is_native_token);
+    assert!(details.is_supported);
+    assert_eq!(
+      details.max_withdrawal_fees,
+      Uint128::new(*max_withdrawal_fees)
+    );
+    if details.token == usdc_addr.to_string() {
+      assert_eq!(details.symbol, Some("USDC".to_string()));
+      assert_eq!(details.decimals, Some(6));
+    } else if details.token == usdt_addr.to_string() {
+      assert_eq!(details.symbol, Some("USDT".to_string()));
+      assert_eq!(details.decimals, Some(8));
+    } else {
+      assert_eq!(details.symbol, None);
+      assert_eq!(details.decimals, None);
+    }
+  }
+
+  // An oversized limit is clamped and still returns every token.
+  assert_eq!(page(None, Some(1000)).len(), 3);
+}
+
+#[test]
+fn supported_tokens() {
+  let app = App::default();
+  let code_id = CodeId::store_code(&app);
+  let owner = "owner".into_addr();
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // No tokens are supported at first.
+  assert!(contract.supported_tokens().unwrap().tokens.is_empty());
+
+  // Register two tokens.
+  let usdc = "usdc".into_addr().to_string();
+  for (token, is_native_token) in
+    [("native".to_string(), true), (usdc.clone(), false)]
+  {
+    contract
+      .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+        token,
+        max_withdrawal_fees: Uint128::new(100),
+        is_native_token,
+      })
+      .call(&owner)
+      .unwrap();
+  }
+  let mut expected = vec!["native".to_string(), usdc.clone()];
+  expected.sort();
+  assert_eq!(contract.supported_tokens().unwrap().tokens, expected);
+
+  // A deregistered token is no longer listed.
+  contract
+    .set_token_supported(SetTokenSupportedMessage {
+      token: usdc,
+      is_supported: false,
+    })
+    .call(&owner)
+    .unwrap();
+  assert_eq!(
+    contract.supported_tokens().unwrap().tokens,
+    vec!["native".to_string()]
+  );
+}
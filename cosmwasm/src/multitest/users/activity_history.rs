@@ -0,0 +1,157 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::activities::sv::mt::ActivitiesProxy;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage,
+  PaginatedFetchMessage, TransactionInfoMessage,
+  UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::{ActivityRecord, ActivityType};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, HexBinary, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn activity_history() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable, pay into it, and withdraw from it.
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let transaction = TransactionInfoMessage {
+    payable_id: payable_id.clone(),
+    token: "native".to_string(),
+    amount: Uint128::new(500),
+    payer_override: None,
+    memo: None,
+  };
+  contract
+    .pay(transaction.clone())
+    .with_funds(&coins(500, "native"))
+    .call(&user)
+    .unwrap();
+  contract.withdraw(transaction).call(&host).unwrap();
+
+  let types = |activities: Vec<ActivityRecord>| {
+    activities
+      .into_iter()
+      .map(|a| a.activity_type)
+      .collect::<Vec<_>>()
+  };
+  let page = |reference: String, start_after, limit| PaginatedFetchMessage {
+    reference,
+    start_after,
+    limit,
+  };
+
+  // The host's activities are recorded in order.
+  let host_activities = contract
+    .user_activities(page(host.to_string(), None, None))
+    .unwrap()
+    .activities;
+  assert_eq!(
+    types(host_activities.clone()),
+    vec![
+      ActivityType::InitializedUser,
+      ActivityType::CreatedPayable,
+      ActivityType::Withdrew
+    ]
+  );
+  assert_eq!(
+    host_activities
+      .iter()
+      .map(|a| a.user_count)
+      .collect::<Vec<_>>(),
+    vec![1, 2, 3]
+  );
+
+  // The payer's activities are recorded too.
+  let user_activities = contract
+    .user_activities(page(user.to_string(), None, None))
+    .unwrap()
+    .activities;
+  assert_eq!(
+    types(user_activities),
+    vec![ActivityType::InitializedUser, ActivityType::UserPaid]
+  );
+
+  // The payable's activities are recorded in order.
+  let payable_activities = contract
+    .payable_activities(page(payable_id.clone(), None, None))
+    .unwrap()
+    .activities;
+  assert_eq!(
+    types(payable_activities.clone()),
+    vec![
+      ActivityType::CreatedPayable,
+      ActivityType::PayableReceived,
+      ActivityType::Withdrew
+    ]
+  );
+  assert_eq!(
+    payable_activities
+      .iter()
+      .map(|a| a.payable_count)
+      .collect::<Vec<_>>(),
+    vec![1, 2, 3]
+  );
+
+  // Pages are respected.
+  let second_page = contract
+    .payable_activities(page(payable_id, Some(1), Some(1)))
+    .unwrap()
+    .activities;
+  assert_eq!(types(second_page), vec![ActivityType::PayableReceived]);
+
+  // Unknown wallets have no activities, and unknown payables are invalid.
+  assert!(contract
+    .user_activities(page("other".into_addr().to_string(), None, None))
+    .unwrap()
+    .activities
+    .is_empty());
+  let err = contract
+    .payable_activities(page(HexBinary::from(&[0u8; 32]).to_hex(), None, None))
+    .unwrap_err();
+  // Testing the error message because a Querrier error is returned instead
+  // of a ChainbillsError.
+  assert!(err.to_string().contains("Invalid Payable ID"));
+}
@@ -1,11 +1,17 @@
 use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
 use crate::interfaces::activities::sv::mt::ActivitiesProxy;
 use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
 use crate::messages::{
   CountMessage, CreatePayableMessage, FetchIdMessage, IdMessage,
-  InstantiateMessage,
+  InstantiateMessage, TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+  UpdatePayableTokensAndAmountsMessage,
 };
+use crate::state::TokenAndAmount;
 use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
 use sylvia::multitest::App;
 
 #[test]
@@ -26,6 +32,7 @@ fn creating_payables() {
   contract
     .create_payable(CreatePayableMessage {
       allowed_tokens_and_amounts: vec![],
+      expires_at: None,
     })
     .call(&user)
     .unwrap();
@@ -100,3 +107,203 @@ fn creating_payables() {
     );
   }
 }
+
+#[test]
+fn creating_free_and_fixed_payables() {
+  let user = "user".into_addr();
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+
+  let owner = "owner".into_addr();
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // An empty allowed_tokens_and_amounts means the payable accepts free
+  // payments (any token and any amount).
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&user)
+    .unwrap();
+  let free_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: user.to_string(),
+      count: 1,
+    })
+    .unwrap();
+  let free_payable = contract.payable(free_id.clone()).unwrap();
+  assert!(free_payable.allowed_tokens_and_amounts.is_empty());
+
+  // Fixed payables only accept supported tokens.
+  let fixed = vec![TokenAndAmount {
+    token: "native".to_string(),
+    amount: Uint128::new(10),
+  }];
+  let err = contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: fixed.clone(),
+      expires_at: None,
+    })
+    .call(&user)
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::UnsupportedToken {
+      token: "native".to_string()
+    }
+  );
+
+  // Once supported, the same token and amount can be required.
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: fixed.clone(),
+      expires_at: None,
+    })
+    .call(&user)
+    .unwrap();
+  let fixed_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: user.to_string(),
+      count: 2,
+    })
+    .unwrap();
+  let fixed_payable = contract.payable(fixed_id.clone()).unwrap();
+  assert_eq!(fixed_payable.allowed_tokens_and_amounts, fixed);
+
+  let pay = |payable_id: &IdMessage, amount: u128| {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(amount, "native"))
+      .call(&user)
+  };
+
+  // The free payable takes arbitrary amounts.
+  for amount in [1, 7, 123] {
+    pay(&free_id, amount).unwrap();
+  }
+  let free_payable = contract.payable(free_id).unwrap();
+  assert_eq!(free_payable.payments_count, 3);
+  assert_eq!(
+    free_payable.balances,
+    vec![TokenAndAmount {
+      token: "native".to_string(),
+      amount: Uint128::new(131),
+    }]
+  );
+
+  // The fixed payable only takes its amount.
+  for amount in [9, 11, 100] {
+    let err = pay(&fixed_id, amount).unwrap_err();
+    assert_eq!(err, ChainbillsError::MatchingTokenAndAmountNotFound {});
+  }
+  pay(&fixed_id, 10).unwrap();
+  let fixed_payable = contract.payable(fixed_id).unwrap();
+  assert_eq!(fixed_payable.payments_count, 1);
+  assert_eq!(fixed_payable.balances, fixed);
+}
+
+#[test]
+fn creating_payables_with_duplicate_tokens_and_amounts() {
+  let app = App::default();
+  let code_id = CodeId::store_code(&app);
+
+  let owner = "owner".into_addr();
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  let user = "user".into_addr();
+
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // The same token and amount can't be specified twice.
+  let duplicated = vec![
+    TokenAndAmount {
+      token: "native".to_string(),
+      amount: Uint128::new(10),
+    },
+    TokenAndAmount {
+      token: "native".to_string(),
+      amount: Uint128::new(10),
+    },
+  ];
+  let err = contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: duplicated.clone(),
+      expires_at: None,
+    })
+    .call(&user)
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::DuplicateTokenAndAmountInPayable {
+      token: "native".to_string(),
+      amount: Uint128::new(10)
+    }
+  );
+
+  // Same for updating a payable's allowed tokens and amounts.
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&user)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: user.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let err = contract
+    .update_payable_tokens_and_amounts(UpdatePayableTokensAndAmountsMessage {
+      payable_id,
+      allowed_tokens_and_amounts: duplicated,
+    })
+    .call(&user)
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::DuplicateTokenAndAmountInPayable {
+      token: "native".to_string(),
+      amount: Uint128::new(10)
+    }
+  );
+}
@@ -0,0 +1,117 @@
+use crate::contract::sv::mt::CodeId;
+use crate::contract::Chainbills;
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+  UpdatePayableAcceptsCrossChainMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn cross_chain_acceptance() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(100, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Set MaxWithdrawalFees for Native Token
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let payable = || {
+    contract
+      .payable(IdMessage {
+        id: payable_id.clone(),
+      })
+      .unwrap()
+  };
+
+  // Payables accept cross-chain payments by default.
+  assert!(payable().accepts_cross_chain);
+  assert!(Chainbills::new()
+    .check_accepts_cross_chain(&payable())
+    .is_ok());
+
+  // Only the host can update the flag.
+  let err = contract
+    .update_payable_accepts_cross_chain(UpdatePayableAcceptsCrossChainMessage {
+      payable_id: payable_id.clone(),
+      accepts_cross_chain: false,
+    })
+    .call(&user)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::NotYourPayable {});
+
+  // Make the Payable local-only.
+  contract
+    .update_payable_accepts_cross_chain(UpdatePayableAcceptsCrossChainMessage {
+      payable_id: payable_id.clone(),
+      accepts_cross_chain: false,
+    })
+    .call(&host)
+    .unwrap();
+  assert!(!payable().accepts_cross_chain);
+
+  // Local payments still go through.
+  contract
+    .pay(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(10),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&coins(10, "native"))
+    .call(&user)
+    .unwrap();
+  assert_eq!(payable().payments_count, 1);
+
+  // A payment bridged from another chain is rejected.
+  assert_eq!(
+    Chainbills::new()
+      .check_accepts_cross_chain(&payable())
+      .unwrap_err(),
+    ChainbillsError::PayableRejectsCrossChain {}
+  );
+}
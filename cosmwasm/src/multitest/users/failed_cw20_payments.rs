@@ -0,0 +1,167 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use cw20::{Cw20Coin, Cw20ExecuteMsg};
+use cw20_base::msg::InstantiateMsg;
+use sylvia::cw_multi_test::{Contract, ContractWrapper, Executor, IntoAddr};
+use sylvia::cw_std::{Empty, Uint128};
+use sylvia::multitest::App;
+
+fn contract_cw20() -> Box<dyn Contract<Empty>> {
+  let contract = ContractWrapper::new(
+    cw20_base::contract::execute,
+    cw20_base::contract::instantiate,
+    cw20_base::contract::query,
+  );
+  Box::new(contract)
+}
+
+#[test]
+fn failed_cw20_payments() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let payer = "payer".into_addr();
+
+  let mut app = sylvia::cw_multi_test::App::default();
+  let cw20_id = app.store_code(contract_cw20());
+  let usdc_addr = app
+    .instantiate_contract(
+      cw20_id,
+      owner.clone(),
+      &InstantiateMsg {
+        name: "USDC".to_string(),
+        symbol: "USDC".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin {
+          address: payer.to_string(),
+          amount: Uint128::new(100),
+        }],
+        mint: None,
+        marketing: None,
+      },
+      &[],
+      "USDC",
+      None,
+    )
+    .unwrap();
+
+  let app = App::new(app);
+  let code_id = CodeId::store_code(&app);
+  let contract = code_id
+    .instantiate(InstantiateMessage {
+      chain_id: 1,
+      chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+    })
+    .call(&owner)
+    .unwrap();
+
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: usdc_addr.to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: false,
+    })
+    .call(&owner)
+    .unwrap();
+
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let stats_before = contract.chain_stats().unwrap();
+
+  let tx_info = |amount: u128| TransactionInfoMessage {
+    payable_id: payable_id.clone(),
+    token: usdc_addr.to_string(),
+    amount: Uint128::new(amount),
+    payer_override: None,
+    memo: None,
+  };
+  // Paying directly through the app as the TransferFrom's error isn't a
+  // ChainbillsError.
+  let pay = |amount: u128| {
+    app.app_mut().execute_contract(
+      payer.clone(),
+      contract.contract_addr.clone(),
+      &payments::sv::ExecMsg::Pay {
+        data: tx_info(amount),
+      },
+      &[],
+    )
+  };
+  let assert_nothing_recorded = || {
+    let payable = contract
+      .payable(IdMessage {
+        id: payable_id.clone(),
+      })
+      .unwrap();
+    assert_eq!(payable.payments_count, 0);
+    assert!(payable.balances.is_empty());
+
+    let user = contract
+      .user(IdMessage {
+        id: payer.to_string(),
+      })
+      .unwrap();
+    assert_eq!(user.payments_count, 0);
+    assert!(contract
+      .user_payment_id(FetchIdMessage {
+        reference: payer.to_string(),
+        count: 1,
+      })
+      .is_err());
+
+    let stats = contract.chain_stats().unwrap();
+    assert_eq!(stats.users_count, stats_before.users_count);
+    assert_eq!(stats.user_payments_count, stats_before.user_payments_count);
+    assert_eq!(
+      stats.payable_payments_count,
+      stats_before.payable_payments_count
+    );
+    assert_eq!(stats.activities_count, stats_before.activities_count);
+  };
+
+  // The TransferFrom fails without an allowance. As the whole execution is
+  // reverted, none of the payment's state changes persist.
+  assert!(pay(40).is_err());
+  assert_nothing_recorded();
+
+  // The TransferFrom also fails if the payer doesn't have enough tokens.
+  app
+    .app_mut()
+    .execute_contract(
+      payer.clone(),
+      usdc_addr.clone(),
+      &Cw20ExecuteMsg::IncreaseAllowance {
+        spender: contract.contract_addr.to_string(),
+        amount: Uint128::new(500),
+        expires: None,
+      },
+      &[],
+    )
+    .unwrap();
+  assert!(pay(200).is_err());
+  assert_nothing_recorded();
+
+  // A successful transfer records the payment.
+  pay(40).unwrap();
+  let payable = contract.payable(IdMessage { id: payable_id }).unwrap();
+  assert_eq!(payable.payments_count, 1);
+  assert_eq!(payable.balances[0].amount, Uint128::new(40));
+}
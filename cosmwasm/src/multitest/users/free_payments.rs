@@ -0,0 +1,150 @@
+use crate::contract::sv::mt::CodeId;
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::TokenAndAmount;
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coin, coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn free_payments() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(
+          storage,
+          &user,
+          vec![
+            coin(1000, "native"),
+            coin(1000, "other"),
+            coin(1000, "rogue"),
+          ],
+        )
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Support two native tokens. "rogue" is never supported.
+  for token in ["native", "other"] {
+    contract
+      .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+        token: token.to_string(),
+        max_withdrawal_fees: Uint128::new(100),
+        is_native_token: true,
+      })
+      .call(&owner)
+      .unwrap();
+  }
+
+  // Create a free payable and one that only accepts 10 native.
+  for allowed_tokens_and_amounts in [
+    vec![],
+    vec![TokenAndAmount {
+      token: "native".to_string(),
+      amount: Uint128::new(10),
+    }],
+  ] {
+    contract
+      .create_payable(CreatePayableMessage {
+        allowed_tokens_and_amounts,
+        expires_at: None,
+      })
+      .call(&host)
+      .unwrap();
+  }
+  let payable_id = |count| {
+    contract
+      .user_payable_id(FetchIdMessage {
+        reference: host.to_string(),
+        count,
+      })
+      .unwrap()
+      .id
+  };
+  let free_id = payable_id(1);
+  let constrained_id = payable_id(2);
+  let pay = |payable_id: &String, token: &str, amount: u128| {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: token.to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(amount, token))
+      .call(&user)
+  };
+
+  let free = contract
+    .payable(IdMessage {
+      id: free_id.clone(),
+    })
+    .unwrap();
+  assert!(free.accepts_free_payments());
+  let constrained = contract
+    .payable(IdMessage {
+      id: constrained_id.clone(),
+    })
+    .unwrap();
+  assert!(!constrained.accepts_free_payments());
+
+  // The free payable accepts any supported token in any amount.
+  for (token, amount) in [("native", 7), ("other", 123), ("native", 1)] {
+    assert!(free.accepts_token_and_amount(token, Uint128::new(amount)));
+    pay(&free_id, token, amount).unwrap();
+  }
+  let free = contract
+    .payable(IdMessage {
+      id: free_id.clone(),
+    })
+    .unwrap();
+  assert_eq!(free.payments_count, 3);
+
+  // It still rejects unsupported tokens and zero amounts.
+  let err = pay(&free_id, "rogue", 5).unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::UnsupportedToken {
+      token: "rogue".to_string()
+    }
+  );
+  let err = contract
+    .pay(TransactionInfoMessage {
+      payable_id: free_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::zero(),
+      payer_override: None,
+      memo: None,
+    })
+    .call(&user)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::ZeroAmountSpecified {});
+
+  // The constrained payable rejects other amounts and other tokens.
+  for (token, amount) in [("native", 7), ("other", 10)] {
+    assert!(!constrained.accepts_token_and_amount(token, Uint128::new(amount)));
+    let err = pay(&constrained_id, token, amount).unwrap_err();
+    assert_eq!(err, ChainbillsError::MatchingTokenAndAmountNotFound {});
+  }
+
+  // But accepts its matching token and amount.
+  assert!(constrained.accepts_token_and_amount("native", Uint128::new(10)));
+  pay(&constrained_id, "native", 10).unwrap();
+}
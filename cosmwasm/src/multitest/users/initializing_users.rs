@@ -0,0 +1,49 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
+use crate::messages::{IdMessage, InstantiateMessage};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+#[test]
+fn initializing_users() {
+  let owner = "owner".into_addr();
+  let user = "user".into_addr();
+
+  let app = App::default();
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Initialize a user ahead of any payment.
+  let resp = contract.initialize_user().call(&user).unwrap();
+  let wasm = resp.events.iter().find(|ev| ev.ty == "wasm").unwrap();
+  let attribute = |key: &str| {
+    wasm
+      .attributes
+      .iter()
+      .find(|attr| attr.key == key)
+      .unwrap()
+      .value
+      .clone()
+  };
+  assert_eq!(attribute("action"), "initialized_user");
+  assert_eq!(attribute("wallet"), user.to_string());
+  assert_eq!(attribute("chain_count"), "1");
+
+  let user_data = contract
+    .user(IdMessage {
+      id: user.to_string(),
+    })
+    .unwrap();
+  assert_eq!(user_data.chain_count, 1);
+  assert_eq!(user_data.activities_count, 1);
+  assert_eq!(contract.chain_stats().unwrap().users_count, 1);
+
+  // A user can't be initialized twice.
+  let err = contract.initialize_user().call(&user).unwrap_err();
+  assert_eq!(err, ChainbillsError::UserAlreadyInitialized {});
+  assert_eq!(contract.chain_stats().unwrap().users_count, 1);
+}
@@ -1,4 +1,5 @@
 use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
 use crate::interfaces::activities::sv::mt::ActivitiesProxy;
 use crate::interfaces::payables::sv::mt::PayablesProxy;
 use crate::interfaces::payments::sv::mt::PaymentsProxy;
@@ -6,6 +7,7 @@ use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
 use crate::messages::{
   CountMessage, CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage, TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage
 };
+use crate::state::TokenAndAmount;
 use cw20::{BalanceResponse, Cw20Coin};
 use cw20_base::msg::InstantiateMsg;
 use sylvia::cw_multi_test::{Contract, ContractWrapper, Executor, IntoAddr};
@@ -67,6 +69,7 @@ fn making_payments() {
   let payable_resp = contract
     .create_payable(CreatePayableMessage {
       allowed_tokens_and_amounts: vec![],
+      expires_at: None,
     })
     .call(&owner)
     .unwrap();
@@ -111,7 +114,7 @@ fn making_payments() {
   println!(
     "User USDC Balance Before Payments: {:?}",
     (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
+      usdc_addr.clone(),
       &cw20::Cw20QueryMsg::Balance {
         address: user.clone().to_string(),
       }
@@ -121,7 +124,7 @@ fn making_payments() {
   println!(
     "Contract USDC Balance Before Payments: {:?}",
     (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
+      usdc_addr.clone(),
       &cw20::Cw20QueryMsg::Balance {
         address: contract.contract_addr.clone().to_string(),
       }
@@ -171,6 +174,8 @@ fn making_payments() {
       payable_id: payable_id.clone(),
       token: "native".to_string(),
       amount: Uint128::new(100),
+      payer_override: None,
+      memo: None,
     })
     .with_funds(&coins(100, "native"))
     .call(&user)
@@ -197,6 +202,8 @@ fn making_payments() {
       payable_id: payable_id.clone(),
       token: usdc_addr.clone().to_string(),
       amount: Uint128::new(100),
+      payer_override: None,
+      memo: None,
     })
     .call(&user)
     .unwrap();
@@ -271,7 +278,7 @@ fn making_payments() {
   println!(
     "User USDC Balance After Payments: {:?}",
     (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
+      usdc_addr.clone(),
       &cw20::Cw20QueryMsg::Balance {
         address: user.clone().to_string(),
       }
@@ -281,7 +288,7 @@ fn making_payments() {
   println!(
     "Contract USDC Balance After Payments: {:?}",
     (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
+      usdc_addr.clone(),
       &cw20::Cw20QueryMsg::Balance {
         address: contract.contract_addr.clone().to_string(),
       }
@@ -336,3 +343,93 @@ fn making_payments() {
         );
     }
 }
+
+#[test]
+fn paying_with_multiple_amounts_per_token() {
+  let owner = "owner".into_addr();
+  let user = "user".into_addr();
+  let app = App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+    router
+      .bank
+      .init_balance(storage, &user, coins(100, "native"))
+      .unwrap();
+  }));
+  let code_id = CodeId::store_code(&app);
+
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable accepting either 10 or 25 of the native token
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![
+        TokenAndAmount {
+          token: "native".to_string(),
+          amount: Uint128::new(10),
+        },
+        TokenAndAmount {
+          token: "native".to_string(),
+          amount: Uint128::new(25),
+        },
+      ],
+      expires_at: None,
+    })
+    .call(&owner)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: owner.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+
+  // Paying with either of the allowed amounts succeeds
+  for amount in [10, 25] {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(amount, "native"))
+      .call(&user)
+      .unwrap();
+  }
+  let payable = contract
+    .payable(IdMessage {
+      id: payable_id.clone(),
+    })
+    .unwrap();
+  assert_eq!(payable.payments_count, 2);
+  assert_eq!(payable.balances[0].amount, Uint128::new(35));
+
+  // Paying with any other amount fails
+  let err = contract
+    .pay(TransactionInfoMessage {
+      payable_id,
+      token: "native".to_string(),
+      amount: Uint128::new(20),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&coins(20, "native"))
+    .call(&user)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::MatchingTokenAndAmountNotFound {});
+}
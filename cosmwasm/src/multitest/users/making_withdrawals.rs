@@ -70,6 +70,7 @@ fn making_withdrawals() {
   let payable_resp = contract
     .create_payable(CreatePayableMessage {
       allowed_tokens_and_amounts: vec![],
+      expires_at: None,
     })
     .call(&user)
     .unwrap();
@@ -142,12 +143,16 @@ fn making_withdrawals() {
     payable_id: payable_id.clone(),
     token: "native".to_string(),
     amount: Uint128::new(100),
+    payer_override: None,
+    memo: None,
   };
 
   let tx_info_cw20 = TransactionInfoMessage {
     payable_id: payable_id.clone(),
     token: usdc_addr.clone().to_string(),
     amount: Uint128::new(100),
+    payer_override: None,
+    memo: None,
   };
 
   // Make a Payment in Native Token
@@ -194,7 +199,7 @@ fn making_withdrawals() {
   println!(
     "User USDC Balance Before Withdrawals: {:?}",
     (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
+      usdc_addr.clone(),
       &cw20::Cw20QueryMsg::Balance {
         address: user.clone().to_string(),
       }
@@ -204,7 +209,7 @@ fn making_withdrawals() {
   println!(
     "Contract USDC Balance Before Withdrawals: {:?}",
     (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
+      usdc_addr.clone(),
       &cw20::Cw20QueryMsg::Balance {
         address: contract.contract_addr.clone().to_string(),
       }
@@ -214,7 +219,7 @@ fn making_withdrawals() {
   println!(
     "FeeCollector USDC Balance Before Withdrawals: {:?}",
     (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
+      usdc_addr.clone(),
       &cw20::Cw20QueryMsg::Balance {
         address: fee_collector.clone().to_string(),
       }
@@ -312,7 +317,7 @@ fn making_withdrawals() {
   println!(
     "User USDC Balance Before Withdrawals: {:?}",
     (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
+      usdc_addr.clone(),
       &cw20::Cw20QueryMsg::Balance {
         address: user.clone().to_string(),
       }
@@ -322,7 +327,7 @@ fn making_withdrawals() {
   println!(
     "Contract USDC Balance Before Withdrawals: {:?}",
     (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
+      usdc_addr.clone(),
       &cw20::Cw20QueryMsg::Balance {
         address: contract.contract_addr.clone().to_string(),
       }
@@ -332,7 +337,7 @@ fn making_withdrawals() {
   println!(
     "FeeCollector USDC Balance Before Withdrawals: {:?}",
     (app.querier().query_wasm_smart(
-      &usdc_addr.clone(),
+      usdc_addr.clone(),
       &cw20::Cw20QueryMsg::Balance {
         address: fee_collector.clone().to_string(),
       }
@@ -0,0 +1,87 @@
+use crate::contract::sv::mt::CodeId;
+use crate::error::ChainbillsError;
+use crate::interfaces::activities::sv::mt::ActivitiesProxy;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  FetchIdMessage, IdMessage, InstantiateMessage, TransactionInfoMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{HexBinary, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn malformed_ids() {
+  let app = App::default();
+  let code_id = CodeId::store_code(&app);
+  let owner = "owner".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  let user = "user".into_addr();
+
+  // Short, long, and non-hex IDs.
+  let ids = [
+    HexBinary::from([1u8; 31]).to_hex(),
+    HexBinary::from([1u8; 33]).to_hex(),
+    "not-a-hex-id".to_string(),
+  ];
+
+  for id in ids {
+    let id_msg = || IdMessage { id: id.clone() };
+    let fetch_msg = || FetchIdMessage {
+      reference: id.clone(),
+      count: 1,
+    };
+    let payable_id_err =
+      ChainbillsError::InvalidPayableId { id: id.clone() }.to_string();
+    let payment_id_err =
+      ChainbillsError::InvalidPaymentId { id: id.clone() }.to_string();
+    let withdrawal_id_err =
+      ChainbillsError::InvalidWithdrawalId { id: id.clone() }.to_string();
+    let activity_id_err =
+      ChainbillsError::InvalidActivityId { id: id.clone() }.to_string();
+
+    // Testing the error message because a Querrier error is returned instead
+    // of a ChainbillsError.
+    let query_errs = [
+      (contract.payable(id_msg()).unwrap_err(), &payable_id_err),
+      (
+        contract.payable_payment_id(fetch_msg()).unwrap_err(),
+        &payable_id_err,
+      ),
+      (
+        contract.user_payment(id_msg()).unwrap_err(),
+        &payment_id_err,
+      ),
+      (
+        contract.payable_payment(id_msg()).unwrap_err(),
+        &payment_id_err,
+      ),
+      (
+        contract.withdrawal(id_msg()).unwrap_err(),
+        &withdrawal_id_err,
+      ),
+      (contract.activity(id_msg()).unwrap_err(), &activity_id_err),
+    ];
+    for (err, expected) in query_errs {
+      assert!(err.to_string().contains(expected.as_str()));
+    }
+
+    // Payments to malformed payable IDs are also rejected cleanly.
+    let err = contract
+      .pay(TransactionInfoMessage {
+        payable_id: id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(10),
+        payer_override: None,
+        memo: None,
+      })
+      .call(&user)
+      .unwrap_err();
+    assert_eq!(err, ChainbillsError::InvalidPayableId { id });
+  }
+}
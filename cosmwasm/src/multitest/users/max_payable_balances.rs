@@ -0,0 +1,103 @@
+use crate::contract::sv::mt::CodeId;
+use crate::contract::MAX_PAYABLE_BALANCES;
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coin, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn max_payable_balances() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  // One more native token than a payable can hold balances in.
+  let denoms: Vec<String> = (0..=MAX_PAYABLE_BALANCES)
+    .map(|i| format!("denom{i}"))
+    .collect();
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      let funds = denoms.iter().map(|denom| coin(100, denom)).collect();
+      router.bank.init_balance(storage, &user, funds).unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  for denom in denoms.iter() {
+    contract
+      .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+        token: denom.clone(),
+        max_withdrawal_fees: Uint128::new(100),
+        is_native_token: true,
+      })
+      .call(&owner)
+      .unwrap();
+  }
+
+  // Create a Payable
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let pay = |denom: &String| {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: denom.clone(),
+        amount: Uint128::new(10),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&[coin(10, denom)])
+      .call(&user)
+  };
+
+  // Fill the payable's balances up to the maximum.
+  for denom in denoms.iter().take(MAX_PAYABLE_BALANCES) {
+    pay(denom).unwrap();
+  }
+  let balances = || {
+    contract
+      .payable_balances(IdMessage {
+        id: payable_id.clone(),
+      })
+      .unwrap()
+      .balances
+  };
+  assert_eq!(balances().len(), MAX_PAYABLE_BALANCES);
+
+  // A payment in a new token is then rejected.
+  let err = pay(&denoms[MAX_PAYABLE_BALANCES]).unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::TooManyPayableBalances {
+      max: MAX_PAYABLE_BALANCES as u64
+    }
+  );
+
+  // While tokens that the payable already holds can still be paid.
+  pay(&denoms[0]).unwrap();
+  let balances = balances();
+  assert_eq!(balances.len(), MAX_PAYABLE_BALANCES);
+  assert_eq!(balances[0].amount, Uint128::new(20));
+}
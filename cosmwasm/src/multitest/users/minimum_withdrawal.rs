@@ -0,0 +1,102 @@
+use crate::contract::sv::mt::CodeId;
+use crate::contract::MIN_WITHDRAWAL_AMOUNT;
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn minimum_withdrawal() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable and pay into it.
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  contract
+    .pay(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(1000),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&coins(1000, "native"))
+    .call(&user)
+    .unwrap();
+
+  let withdraw = |amount| {
+    contract
+      .withdraw(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .call(&host)
+  };
+
+  // Dust withdrawals are rejected.
+  for amount in [1, MIN_WITHDRAWAL_AMOUNT - 1] {
+    assert_eq!(
+      withdraw(amount).unwrap_err(),
+      ChainbillsError::WithdrawalAmountTooSmall {
+        min: Uint128::new(MIN_WITHDRAWAL_AMOUNT)
+      }
+    );
+  }
+
+  // Fees round down, so the host always gets at least 98% (at 2% fees).
+  for amount in [MIN_WITHDRAWAL_AMOUNT, MIN_WITHDRAWAL_AMOUNT + 1, 51] {
+    let before = app.querier().query_balance(&host, "native").unwrap();
+    withdraw(amount).unwrap();
+    let after = app.querier().query_balance(&host, "native").unwrap();
+    let received = (after.amount - before.amount).u128();
+    assert!(received <= amount);
+    assert!(received * 100 >= amount * 98);
+  }
+}
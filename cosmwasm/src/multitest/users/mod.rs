@@ -1,3 +1,43 @@
+mod activity_history;
 mod creating_payables;
+mod failed_cw20_payments;
+mod free_payments;
+mod initializing_users;
+mod cross_chain_acceptance;
 mod making_payments;
 mod making_withdrawals;
+mod malformed_ids;
+mod max_payable_balances;
+mod minimum_withdrawal;
+mod multi_token_payments;
+mod multi_token_withdrawals;
+mod paginated_payments;
+mod paid_payables;
+mod paying_via_cw20_send;
+mod payable_balances;
+mod payable_expiry;
+mod payable_integrity;
+mod payable_summary;
+mod payable_twab;
+mod payable_volume;
+mod payer_allowlist;
+mod payment_overflow;
+mod payables_by_host;
+mod payment_balance_events;
+mod payment_memos;
+mod payments_by_token;
+mod payments_per_chain;
+mod payments_time_range;
+mod refunding_payments;
+mod relayed_cw20_payments;
+mod relayed_payments;
+mod response_data;
+mod token_deregistration;
+mod unexpected_funds;
+mod updating_payable_tokens;
+mod user_profile;
+mod withdrawal_events;
+mod withdrawal_fee_preview;
+mod withdrawal_rejections;
+mod payments_to_host;
+mod zero_counts;
@@ -0,0 +1,225 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  PayMultipleMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::TokenAndAmount;
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg};
+use cw20_base::msg::InstantiateMsg;
+use sylvia::cw_multi_test::{Contract, ContractWrapper, Executor, IntoAddr};
+use sylvia::cw_std::{coin, Empty, StdResult, Uint128};
+use sylvia::multitest::App;
+
+fn contract_cw20() -> Box<dyn Contract<Empty>> {
+  let contract = ContractWrapper::new(
+    cw20_base::contract::execute,
+    cw20_base::contract::instantiate,
+    cw20_base::contract::query,
+  );
+  Box::new(contract)
+}
+
+fn taa(token: &str, amount: u128) -> TokenAndAmount {
+  TokenAndAmount {
+    token: token.to_string(),
+    amount: Uint128::new(amount),
+  }
+}
+
+#[test]
+fn multi_token_payments() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let payer = "payer".into_addr();
+
+  let mut app = sylvia::cw_multi_test::App::new(|router, _api, storage| {
+    router
+      .bank
+      .init_balance(
+        storage,
+        &payer,
+        vec![coin(1000, "native"), coin(1000, "other")],
+      )
+      .unwrap();
+  });
+  let cw20_id = app.store_code(contract_cw20());
+  let usdc_addr = app
+    .instantiate_contract(
+      cw20_id,
+      owner.clone(),
+      &InstantiateMsg {
+        name: "USDC".to_string(),
+        symbol: "USDC".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin {
+          address: payer.to_string(),
+          amount: Uint128::new(1000),
+        }],
+        mint: None,
+        marketing: None,
+      },
+      &[],
+      "USDC",
+      None,
+    )
+    .unwrap();
+  let usdc = usdc_addr.as_str();
+
+  let app = App::new(app);
+  let code_id = CodeId::store_code(&app);
+  let contract = code_id
+    .instantiate(InstantiateMessage {
+      chain_id: 1,
+      chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+    })
+    .call(&owner)
+    .unwrap();
+  for (token, is_native_token) in
+    [("native", true), ("other", true), (usdc, false)]
+  {
+    contract
+      .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+        token: token.to_string(),
+        max_withdrawal_fees: Uint128::new(100),
+        is_native_token,
+      })
+      .call(&owner)
+      .unwrap();
+  }
+
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![
+        taa("native", 100),
+        taa("other", 50),
+        taa(usdc, 40),
+      ],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let pay_msg = |tokens_and_amounts: Vec<TokenAndAmount>| PayMultipleMessage {
+    payable_id: payable_id.clone(),
+    tokens_and_amounts,
+    payer_override: None,
+    memo: None,
+  };
+
+  app
+    .app_mut()
+    .execute_contract(
+      payer.clone(),
+      usdc_addr.clone(),
+      &Cw20ExecuteMsg::IncreaseAllowance {
+        spender: contract.contract_addr.to_string(),
+        amount: Uint128::new(1000),
+        expires: None,
+      },
+      &[],
+    )
+    .unwrap();
+
+  // At least one token has to be paid.
+  let err = contract
+    .pay_multiple(pay_msg(vec![]))
+    .call(&payer)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::NoPaymentTokens {});
+
+  // A token can't be repeated in the same payment.
+  let err = contract
+    .pay_multiple(pay_msg(vec![taa("native", 100), taa("native", 100)]))
+    .with_funds(&[coin(200, "native")])
+    .call(&payer)
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::DuplicatePaymentToken {
+      token: "native".to_string()
+    }
+  );
+
+  // Each token and amount must match the payable's allowed set.
+  let err = contract
+    .pay_multiple(pay_msg(vec![taa("native", 100), taa(usdc, 30)]))
+    .with_funds(&[coin(100, "native")])
+    .call(&payer)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::MatchingTokenAndAmountNotFound {});
+
+  // Each native token has to be attached in full.
+  let err = contract
+    .pay_multiple(pay_msg(vec![taa("native", 100), taa("other", 50)]))
+    .with_funds(&[coin(100, "native"), coin(20, "other")])
+    .call(&payer)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::InvalidNativeTokenPayment {});
+
+  // Pay a native token and a CW20 token at once.
+  contract
+    .pay_multiple(pay_msg(vec![taa("native", 100), taa(usdc, 40)]))
+    .with_funds(&[coin(100, "native")])
+    .call(&payer)
+    .unwrap();
+
+  // Both balances are updated by a single payment.
+  let payable = contract
+    .payable(IdMessage {
+      id: payable_id.clone(),
+    })
+    .unwrap();
+  assert_eq!(payable.payments_count, 1);
+  assert_eq!(payable.balances, vec![taa("native", 100), taa(usdc, 40)]);
+  assert_eq!(
+    contract
+      .user(IdMessage {
+        id: payer.to_string()
+      })
+      .unwrap()
+      .payments_count,
+    1
+  );
+
+  let payment_id = contract
+    .user_payment_id(FetchIdMessage {
+      reference: payer.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let payment = contract.user_payment(IdMessage { id: payment_id }).unwrap();
+  assert_eq!(payment.details, taa("native", 100));
+  assert_eq!(payment.other_details, vec![taa(usdc, 40)]);
+
+  let balance: StdResult<BalanceResponse> = app.querier().query_wasm_smart(
+    usdc_addr.clone(),
+    &cw20::Cw20QueryMsg::Balance {
+      address: contract.contract_addr.to_string(),
+    },
+  );
+  assert_eq!(balance.unwrap().balance, Uint128::new(40));
+
+  // Pay two native tokens at once.
+  contract
+    .pay_multiple(pay_msg(vec![taa("other", 50), taa("native", 100)]))
+    .with_funds(&[coin(100, "native"), coin(50, "other")])
+    .call(&payer)
+    .unwrap();
+  let payable = contract.payable(IdMessage { id: payable_id }).unwrap();
+  assert_eq!(payable.payments_count, 2);
+  assert_eq!(
+    payable.balances,
+    vec![taa("native", 200), taa(usdc, 40), taa("other", 50)]
+  );
+}
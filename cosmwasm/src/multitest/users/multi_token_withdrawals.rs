@@ -0,0 +1,231 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  PayMultipleMessage, UpdateMaxWithdrawalFeesMessage, WithdrawMultipleMessage,
+};
+use crate::state::TokenAndAmount;
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coin, Uint128};
+use sylvia::multitest::App;
+
+fn taa(token: &str, amount: u128) -> TokenAndAmount {
+  TokenAndAmount {
+    token: token.to_string(),
+    amount: Uint128::new(amount),
+  }
+}
+
+#[test]
+fn multi_token_withdrawals() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let payer = "payer".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(
+          storage,
+          &payer,
+          vec![coin(1000, "native"), coin(500, "other")],
+        )
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let contract = code_id
+    .instantiate(InstantiateMessage {
+      chain_id: 1,
+      chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+    })
+    .call(&owner)
+    .unwrap();
+  for token in ["native", "other"] {
+    contract
+      .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+        token: token.to_string(),
+        max_withdrawal_fees: Uint128::new(100),
+        is_native_token: true,
+      })
+      .call(&owner)
+      .unwrap();
+  }
+
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  contract
+    .pay_multiple(PayMultipleMessage {
+      payable_id: payable_id.clone(),
+      tokens_and_amounts: vec![taa("native", 1000), taa("other", 500)],
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&[coin(1000, "native"), coin(500, "other")])
+    .call(&payer)
+    .unwrap();
+  let withdraw_msg =
+    |tokens_and_amounts: Vec<TokenAndAmount>| WithdrawMultipleMessage {
+      payable_id: payable_id.clone(),
+      tokens_and_amounts,
+    };
+
+  // At least one token has to be withdrawn.
+  let err = contract
+    .withdraw_multi(withdraw_msg(vec![]))
+    .call(&host)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::NoWithdrawalTokens {});
+
+  // A token can't be repeated in the same withdrawal.
+  let err = contract
+    .withdraw_multi(withdraw_msg(vec![taa("native", 100), taa("native", 100)]))
+    .call(&host)
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::DuplicateWithdrawalToken {
+      token: "native".to_string()
+    }
+  );
+
+  // Only the host can withdraw.
+  let err = contract
+    .withdraw_multi(withdraw_msg(vec![taa("native", 100)]))
+    .call(&payer)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::NotYourPayable {});
+
+  // If any token can't be withdrawn, none is.
+  let err = contract
+    .withdraw_multi(withdraw_msg(vec![taa("native", 1000), taa("other", 600)]))
+    .call(&host)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::InsufficientWithdrawAmount {});
+  assert_eq!(
+    app.querier().query_balance(&host, "native").unwrap().amount,
+    Uint128::zero()
+  );
+  let payable = contract
+    .payable(IdMessage {
+      id: payable_id.clone(),
+    })
+    .unwrap();
+  assert_eq!(payable.withdrawals_count, 0);
+  assert_eq!(
+    payable.balances,
+    vec![taa("native", 1000), taa("other", 500)]
+  );
+
+  // Withdraw both tokens at once.
+  let resp = contract
+    .withdraw_multi(withdraw_msg(vec![taa("native", 1000), taa("other", 400)]))
+    .call(&host)
+    .unwrap();
+  assert_eq!(
+    resp
+      .events
+      .iter()
+      .filter(|e| e.ty == "wasm-withdrawal_made")
+      .count(),
+    2
+  );
+
+  // The attributes of each withdrawal are kept in its own withdrew event.
+  let withdrew: Vec<_> = resp
+    .events
+    .iter()
+    .filter(|e| e.ty == "wasm-withdrew")
+    .collect();
+  assert_eq!(withdrew.len(), 2);
+  for (count, event) in withdrew.iter().enumerate() {
+    let attr = |key: &str| {
+      event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .unwrap()
+        .value
+        .clone()
+    };
+    assert_eq!(attr("payable_id"), payable_id);
+    assert_eq!(attr("payable_count"), (count + 1).to_string());
+  }
+
+  // The host receives each amount less its 2% fee.
+  for (token, net) in [("native", 980), ("other", 392)] {
+    assert_eq!(
+      app.querier().query_balance(&host, token).unwrap().amount,
+      Uint128::new(net)
+    );
+  }
+
+  // Each token's balance, fees, and totals are accounted for.
+  let payable = contract
+    .payable(IdMessage {
+      id: payable_id.clone(),
+    })
+    .unwrap();
+  assert_eq!(payable.withdrawals_count, 2);
+  assert_eq!(payable.balances, vec![taa("native", 0), taa("other", 100)]);
+  for (token, amount, fees) in [("native", 1000, 20), ("other", 400, 8)] {
+    assert_eq!(
+      contract
+        .collected_fees(IdMessage {
+          id: token.to_string(),
+        })
+        .unwrap()
+        .amount,
+      Uint128::new(fees)
+    );
+    let token_details = contract
+      .token_details(IdMessage {
+        id: token.to_string(),
+      })
+      .unwrap();
+    assert_eq!(token_details.total_withdrawn, Uint128::new(amount));
+    assert_eq!(
+      token_details.total_withdrawal_fees_collected,
+      Uint128::new(fees)
+    );
+  }
+
+  // Each token is recorded as its own withdrawal.
+  assert_eq!(
+    contract
+      .user(IdMessage {
+        id: host.to_string()
+      })
+      .unwrap()
+      .withdrawals_count,
+    2
+  );
+  for (count, expected) in [(1, taa("native", 1000)), (2, taa("other", 400))] {
+    let id = contract
+      .payable_withdrawal_id(FetchIdMessage {
+        reference: payable_id.clone(),
+        count,
+      })
+      .unwrap()
+      .id;
+    let withdrawal = contract.withdrawal(IdMessage { id }).unwrap();
+    assert_eq!(withdrawal.payable_count, count);
+    assert_eq!(withdrawal.details, expected);
+  }
+}
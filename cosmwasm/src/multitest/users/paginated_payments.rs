@@ -0,0 +1,157 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  PaginatedPaymentsMessage, TransactionInfoMessage,
+  UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, HexBinary, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn paginated_payments() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(100, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Set MaxWithdrawalFees for Native Token
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+
+  let user_page = |start_after, limit| {
+    contract
+      .user_payments(PaginatedPaymentsMessage {
+        reference: user.to_string(),
+        start_after,
+        limit,
+        from_timestamp: None,
+        to_timestamp: None,
+      })
+      .unwrap()
+      .payments
+  };
+  let payable_page = |start_after, limit| {
+    contract
+      .payable_payments(PaginatedPaymentsMessage {
+        reference: payable_id.clone(),
+        start_after,
+        limit,
+        from_timestamp: None,
+        to_timestamp: None,
+      })
+      .unwrap()
+      .payments
+  };
+
+  // Empty histories
+  assert!(user_page(None, None).is_empty());
+  assert!(payable_page(None, None).is_empty());
+
+  // Make four payments of increasing amounts
+  for amount in 1..=4 {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(amount, "native"))
+      .call(&user)
+      .unwrap();
+  }
+
+  // Without pagination params, all payments are returned in order.
+  let user_payments = user_page(None, None);
+  let payable_payments = payable_page(None, None);
+  assert_eq!(user_payments.len(), 4);
+  assert_eq!(payable_payments.len(), 4);
+  for (i, (user_payment, payable_payment)) in user_payments
+    .iter()
+    .zip(payable_payments.iter())
+    .enumerate()
+  {
+    let expected = contract
+      .user_payment(
+        contract
+          .user_payment_id(FetchIdMessage {
+            reference: user.to_string(),
+            count: i as u64 + 1,
+          })
+          .unwrap(),
+      )
+      .unwrap();
+    assert_eq!(*user_payment, expected);
+    assert_eq!(user_payment.payer_count, i as u64 + 1);
+    assert_eq!(payable_payment.payable_count, i as u64 + 1);
+    assert_eq!(payable_payment.details.amount, Uint128::new(i as u128 + 1));
+  }
+
+  // Exact page boundaries with the start_after cursor
+  assert_eq!(user_page(None, Some(2)), user_payments[0..2]);
+  assert_eq!(user_page(Some(2), Some(2)), user_payments[2..4]);
+  assert!(user_page(Some(4), Some(2)).is_empty());
+  assert_eq!(payable_page(Some(1), Some(2)), payable_payments[1..3]);
+  assert_eq!(payable_page(Some(3), Some(2)), payable_payments[3..4]);
+  assert!(payable_page(Some(4), None).is_empty());
+
+  // Unknown payables are rejected
+  let unknown_id = HexBinary::from([1u8; 32]).to_hex();
+  let err = contract
+    .payable_payments(PaginatedPaymentsMessage {
+      reference: unknown_id,
+      start_after: None,
+      limit: None,
+      from_timestamp: None,
+      to_timestamp: None,
+    })
+    .unwrap_err();
+  // Testing the error message because a Querrier error is returned
+  // instead of a ChainbillsError.
+  assert!(err.to_string().contains("Invalid Payable ID"));
+
+  // Sanity check that the payable recorded all payments.
+  let payable = contract.payable(IdMessage { id: payable_id }).unwrap();
+  assert_eq!(payable.payments_count, 4);
+}
@@ -0,0 +1,100 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage,
+  PaginatedFetchMessage, TransactionInfoMessage,
+  UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn paid_payables() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(100, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Set MaxWithdrawalFees for Native Token
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create two Payables
+  let mut payable_ids = vec![];
+  for count in 1..=2 {
+    contract
+      .create_payable(CreatePayableMessage {
+        allowed_tokens_and_amounts: vec![],
+        expires_at: None,
+      })
+      .call(&host)
+      .unwrap();
+    payable_ids.push(
+      contract
+        .user_payable_id(FetchIdMessage {
+          reference: host.to_string(),
+          count,
+        })
+        .unwrap()
+        .id,
+    );
+  }
+
+  let paid_payables = |start_after, limit| {
+    contract
+      .user_paid_payables(PaginatedFetchMessage {
+        reference: user.to_string(),
+        start_after,
+        limit,
+      })
+      .unwrap()
+      .ids
+  };
+
+  // No payables have been paid yet
+  assert!(paid_payables(None, None).is_empty());
+
+  // Pay the first payable twice and the second one once
+  for payable_id in [&payable_ids[0], &payable_ids[0], &payable_ids[1]] {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(10),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(10, "native"))
+      .call(&user)
+      .unwrap();
+  }
+
+  // Exactly the two distinct payables are listed, in order of first payment
+  assert_eq!(paid_payables(None, None), payable_ids);
+  assert_eq!(paid_payables(None, Some(1)), payable_ids[0..1]);
+  assert_eq!(paid_payables(Some(1), Some(1)), payable_ids[1..2]);
+  assert!(paid_payables(Some(2), None).is_empty());
+}
@@ -0,0 +1,177 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::TokenAndAmount;
+use cw20::Cw20Coin;
+use cw20_base::msg::InstantiateMsg;
+use sylvia::cw_multi_test::{Contract, ContractWrapper, Executor, IntoAddr};
+use sylvia::cw_std::{coins, Empty, Uint128};
+use sylvia::multitest::App;
+
+fn contract_cw20() -> Box<dyn Contract<Empty>> {
+  let contract = ContractWrapper::new(
+    cw20_base::contract::execute,
+    cw20_base::contract::instantiate,
+    cw20_base::contract::query,
+  );
+  Box::new(contract)
+}
+
+#[test]
+fn payable_balances() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let mut app = sylvia::cw_multi_test::App::new(|router, _api, storage| {
+    router
+      .bank
+      .init_balance(storage, &user, coins(100, "native"))
+      .unwrap();
+  });
+  let cw20_id = app.store_code(contract_cw20());
+  let usdc_addr = app
+    .instantiate_contract(
+      cw20_id,
+      owner.clone(),
+      &InstantiateMsg {
+        name: "USDC".to_string(),
+        symbol: "USDC".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin {
+          address: user.to_string(),
+          amount: Uint128::new(100),
+        }],
+        mint: None,
+        marketing: None,
+      },
+      &[],
+      "USDC",
+      None,
+    )
+    .unwrap();
+
+  let app = App::new(app);
+  let code_id = CodeId::store_code(&app);
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Support the Native Token and USDC
+  for (token, is_native_token) in
+    [("native".to_string(), true), (usdc_addr.to_string(), false)]
+  {
+    contract
+      .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+        token,
+        max_withdrawal_fees: Uint128::new(100),
+        is_native_token,
+      })
+      .call(&owner)
+      .unwrap();
+  }
+
+  // Create a Payable
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let balances = || {
+    contract
+      .payable_balances(IdMessage {
+        id: payable_id.clone(),
+      })
+      .unwrap()
+  };
+
+  // A new Payable has no balances.
+  let resp = balances();
+  assert!(resp.balances.is_empty());
+  assert!(!resp.is_closed);
+  assert_eq!(resp.payments_count, 0);
+  assert_eq!(resp.withdrawals_count, 0);
+
+  // Pay in the Native Token twice and in USDC once.
+  for amount in [30, 20] {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(amount, "native"))
+      .call(&user)
+      .unwrap();
+  }
+  app
+    .app_mut()
+    .execute_contract(
+      user.clone(),
+      usdc_addr.clone(),
+      &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+        spender: contract.contract_addr.to_string(),
+        amount: Uint128::new(70),
+        expires: None,
+      },
+      &[],
+    )
+    .unwrap();
+  contract
+    .pay(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: usdc_addr.to_string(),
+      amount: Uint128::new(70),
+      payer_override: None,
+      memo: None,
+    })
+    .call(&user)
+    .unwrap();
+
+  // Both balances appear with their accumulated amounts.
+  let resp = balances();
+  assert_eq!(
+    resp.balances,
+    vec![
+      TokenAndAmount {
+        token: "native".to_string(),
+        amount: Uint128::new(50),
+      },
+      TokenAndAmount {
+        token: usdc_addr.to_string(),
+        amount: Uint128::new(70),
+      },
+    ]
+  );
+  assert_eq!(resp.payments_count, 3);
+  assert_eq!(resp.withdrawals_count, 0);
+
+  // Querying an unknown Payable fails.
+  let err = contract
+    .payable_balances(IdMessage {
+      id: "00".repeat(32),
+    })
+    .unwrap_err();
+  // Testing the error message because a Querrier error is returned instead
+  // of a ChainbillsError.
+  assert!(err.to_string().contains("Invalid Payable ID"));
+}
@@ -0,0 +1,111 @@
+use crate::contract::sv::mt::CodeId;
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn payable_expiry() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(100, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Set MaxWithdrawalFees for Native Token
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // A Payable can't expire at or before its creation.
+  let now = app.block_info().time.seconds();
+  for expires_at in [0, now - 1, now] {
+    let err = contract
+      .create_payable(CreatePayableMessage {
+        allowed_tokens_and_amounts: vec![],
+        expires_at: Some(expires_at),
+      })
+      .call(&host)
+      .unwrap_err();
+    assert_eq!(err, ChainbillsError::ExpiryNotInFuture {});
+  }
+
+  // Create a Payable that expires in 100 seconds
+  let expires_at = app.block_info().time.seconds() + 100;
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: Some(expires_at),
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  assert_eq!(
+    contract
+      .payable(IdMessage {
+        id: payable_id.clone(),
+      })
+      .unwrap()
+      .expires_at,
+    Some(expires_at)
+  );
+
+  let pay = || {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(10),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(10, "native"))
+      .call(&user)
+  };
+
+  // Paying before expiry works.
+  pay().unwrap();
+
+  // Paying exactly at expiry still works.
+  app
+    .app_mut()
+    .update_block(|b| b.time = b.time.plus_seconds(100));
+  pay().unwrap();
+
+  // Paying after expiry fails.
+  app
+    .app_mut()
+    .update_block(|b| b.time = b.time.plus_seconds(1));
+  assert_eq!(pay().unwrap_err(), ChainbillsError::PayableExpired {});
+}
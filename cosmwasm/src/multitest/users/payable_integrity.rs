@@ -0,0 +1,112 @@
+use crate::contract::sv::mt::CodeId;
+use crate::contract::Chainbills;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, HexBinary, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn payable_integrity() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(100, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Set MaxWithdrawalFees for Native Token
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable, pay into it twice, and withdraw from it.
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let tx_info = TransactionInfoMessage {
+    payable_id: payable_id.clone(),
+    token: "native".to_string(),
+    amount: Uint128::new(50),
+    payer_override: None,
+    memo: None,
+  };
+  for _ in 0..2 {
+    contract
+      .pay(tx_info.clone())
+      .with_funds(&coins(50, "native"))
+      .call(&user)
+      .unwrap();
+  }
+  contract.withdraw(tx_info).call(&host).unwrap();
+
+  // A healthy payable is consistent.
+  let report = contract
+    .verify_payable_integrity(IdMessage {
+      id: payable_id.clone(),
+    })
+    .unwrap();
+  assert!(report.is_consistent);
+  assert!(report.issues.is_empty());
+  assert_eq!(report.payment_ids_count, 2);
+  assert_eq!(report.withdrawal_ids_count, 1);
+  assert_eq!(report.activity_ids_count, 4);
+
+  // Artificially desync the payable's payments_count.
+  let payable_key =
+    <[u8; 32]>::try_from(HexBinary::from_hex(&payable_id).unwrap().as_slice())
+      .unwrap();
+  {
+    let mut app = app.app_mut();
+    let mut storage = app.contract_storage_mut(&contract.contract_addr);
+    let payables = Chainbills::new().payables;
+    let mut payable = payables.load(storage.as_ref(), payable_key).unwrap();
+    payable.payments_count = 3;
+    payables
+      .save(storage.as_mut(), payable_key, &payable)
+      .unwrap();
+  }
+
+  let report = contract
+    .verify_payable_integrity(IdMessage { id: payable_id })
+    .unwrap();
+  assert!(!report.is_consistent);
+  assert_eq!(
+    report.issues,
+    vec!["payments_count is 3 but 2 payment ids are stored".to_string()]
+  );
+}
@@ -0,0 +1,108 @@
+use crate::contract::sv::mt::CodeId;
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::TokenAndAmount;
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, HexBinary, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn payable_summary() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(100, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Support the Native Token
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable that accepts 40 of the Native Token
+  let allowed_tokens_and_amounts = vec![TokenAndAmount {
+    token: "native".to_string(),
+    amount: Uint128::new(40),
+  }];
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: allowed_tokens_and_amounts.clone(),
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let summary = || {
+    contract
+      .payable_summary(IdMessage {
+        id: payable_id.clone(),
+      })
+      .unwrap()
+  };
+
+  // The summary has the host and the tokens set at creation.
+  let resp = summary();
+  assert_eq!(resp.host, host);
+  assert_eq!(resp.allowed_tokens_and_amounts, allowed_tokens_and_amounts);
+  assert!(!resp.is_closed);
+  assert_eq!(resp.payments_count, 0);
+  assert_eq!(resp.balances_count, 0);
+
+  // Counts are updated after a payment.
+  contract
+    .pay(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(40),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&coins(40, "native"))
+    .call(&user)
+    .unwrap();
+  let resp = summary();
+  assert_eq!(resp.payments_count, 1);
+  assert_eq!(resp.balances_count, 1);
+
+  // Unknown payable IDs are rejected.
+  let unknown_id = HexBinary::from([7u8; 32]).to_hex();
+  let err = contract
+    .payable_summary(IdMessage {
+      id: unknown_id.clone(),
+    })
+    .unwrap_err();
+  // Testing the error message because a Querrier error is returned instead
+  // of a ChainbillsError.
+  assert!(err.to_string().contains(
+    &ChainbillsError::InvalidPayableId { id: unknown_id }.to_string()
+  ));
+}
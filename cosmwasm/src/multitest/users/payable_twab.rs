@@ -0,0 +1,153 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage, PayableTwabMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coin, coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn payable_twab() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(
+          storage,
+          &user,
+          vec![coin(100, "native"), coin(u128::MAX, "whale")],
+        )
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Set MaxWithdrawalFees for both Native Tokens
+  for token in ["native", "whale"] {
+    contract
+      .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+        token: token.to_string(),
+        max_withdrawal_fees: Uint128::new(100),
+        is_native_token: true,
+      })
+      .call(&owner)
+      .unwrap();
+  }
+
+  // Create a Payable
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let twab = |start_ts, end_ts| {
+    contract
+      .payable_twab(PayableTwabMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        start_ts,
+        end_ts,
+      })
+      .unwrap()
+      .twab
+  };
+  let advance = |seconds| {
+    app
+      .app_mut()
+      .update_block(|block| block.time = block.time.plus_seconds(seconds));
+  };
+
+  // The balance is 100 for 100 seconds and then 50 for 100 seconds.
+  let start = app.block_info().time.seconds();
+  contract
+    .pay(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(100),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&coins(100, "native"))
+    .call(&user)
+    .unwrap();
+  advance(100);
+  contract
+    .withdraw(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(50),
+      payer_override: None,
+      memo: None,
+    })
+    .call(&host)
+    .unwrap();
+  advance(100);
+
+  assert_eq!(twab(start, start + 200), Uint128::new(75));
+  assert_eq!(twab(start, start + 100), Uint128::new(100));
+  assert_eq!(twab(start + 100, start + 200), Uint128::new(50));
+
+  // The balance before the first payment was zero.
+  assert_eq!(twab(start - 100, start + 100), Uint128::new(50));
+
+  // Empty windows are rejected.
+  let err = contract
+    .payable_twab(PayableTwabMessage {
+      payable_id: payable_id.clone(),
+      token: "native".to_string(),
+      start_ts: start,
+      end_ts: start,
+    })
+    .unwrap_err();
+  // Testing the error message because a Querrier error is returned
+  // instead of a ChainbillsError.
+  assert!(err.to_string().contains("Invalid Twab Window"));
+
+  // A huge balance held over a long window overflows instead of panicking.
+  let huge = u128::MAX / 2 + 1;
+  contract
+    .pay(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: "whale".to_string(),
+      amount: Uint128::new(huge),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&[coin(huge, "whale")])
+    .call(&user)
+    .unwrap();
+  let now = app.block_info().time.seconds();
+  advance(100);
+  let err = contract
+    .payable_twab(PayableTwabMessage {
+      payable_id,
+      token: "whale".to_string(),
+      start_ts: now,
+      end_ts: now + 2,
+    })
+    .unwrap_err();
+  assert!(err.to_string().contains("Overflow"));
+}
@@ -0,0 +1,119 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::TokenAndAmount;
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn payable_volume() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let contract = code_id
+    .instantiate(InstantiateMessage {
+      chain_id: 1,
+      chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Set MaxWithdrawalFees for Native Token
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let tx_info = |amount| TransactionInfoMessage {
+    payable_id: payable_id.clone(),
+    token: "native".to_string(),
+    amount: Uint128::new(amount),
+    payer_override: None,
+    memo: None,
+  };
+  let volume = || {
+    contract
+      .payable_volume(IdMessage {
+        id: payable_id.clone(),
+      })
+      .unwrap()
+      .volume
+  };
+  let balances = || {
+    contract
+      .payable_balances(IdMessage {
+        id: payable_id.clone(),
+      })
+      .unwrap()
+      .balances
+  };
+  let native = |amount| {
+    vec![TokenAndAmount {
+      token: "native".to_string(),
+      amount: Uint128::new(amount),
+    }]
+  };
+
+  // No volume before any payment.
+  assert!(volume().is_empty());
+
+  // Volume grows with each payment, like the balance.
+  for amount in [100, 50] {
+    contract
+      .pay(tx_info(amount))
+      .with_funds(&coins(amount, "native"))
+      .call(&user)
+      .unwrap();
+  }
+  assert_eq!(volume(), native(150));
+  assert_eq!(balances(), native(150));
+
+  // A withdrawal reduces the balance but not the volume.
+  contract.withdraw(tx_info(120)).call(&host).unwrap();
+  assert_eq!(volume(), native(150));
+  assert_eq!(balances(), native(30));
+
+  // And later payments keep adding to the volume.
+  contract
+    .pay(tx_info(40))
+    .with_funds(&coins(40, "native"))
+    .call(&user)
+    .unwrap();
+  assert_eq!(volume(), native(190));
+  assert_eq!(balances(), native(70));
+}
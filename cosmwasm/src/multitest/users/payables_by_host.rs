@@ -0,0 +1,104 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage,
+  PayablesByHostMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::multitest::App;
+
+#[test]
+fn payables_by_host() {
+  let app = App::default();
+  let code_id = CodeId::store_code(&app);
+
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let other_host = "other_host".into_addr();
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // A host without payables has none listed.
+  let resp = contract
+    .payables_by_host(PayablesByHostMessage {
+      host: host.to_string(),
+      start_after: None,
+      limit: None,
+    })
+    .unwrap();
+  assert!(resp.ids.is_empty());
+
+  // Create five Payables for the host, with one for the other host in between.
+  let mut expected = vec![];
+  for count in 1..=5 {
+    contract
+      .create_payable(CreatePayableMessage {
+        allowed_tokens_and_amounts: vec![],
+        expires_at: None,
+      })
+      .call(&host)
+      .unwrap();
+    expected.push(
+      contract
+        .user_payable_id(FetchIdMessage {
+          reference: host.to_string(),
+          count,
+        })
+        .unwrap()
+        .id,
+    );
+
+    if count == 3 {
+      contract
+        .create_payable(CreatePayableMessage {
+          allowed_tokens_and_amounts: vec![],
+          expires_at: None,
+        })
+        .call(&other_host)
+        .unwrap();
+    }
+  }
+
+  // Without pagination params, all (within the default limit) are returned
+  // in the order they were created.
+  let resp = contract
+    .payables_by_host(PayablesByHostMessage {
+      host: host.to_string(),
+      start_after: None,
+      limit: None,
+    })
+    .unwrap();
+  assert_eq!(resp.ids, expected);
+
+  // Walk through pages of two.
+  let page = |start_after| {
+    contract
+      .payables_by_host(PayablesByHostMessage {
+        host: host.to_string(),
+        start_after,
+        limit: Some(2),
+      })
+      .unwrap()
+      .ids
+  };
+  assert_eq!(page(None), expected[0..2]);
+  assert_eq!(page(Some(2)), expected[2..4]);
+  assert_eq!(page(Some(4)), expected[4..5]);
+  assert!(page(Some(5)).is_empty());
+  assert!(page(Some(10)).is_empty());
+
+  // The other host's payables are listed separately.
+  let resp = contract
+    .payables_by_host(PayablesByHostMessage {
+      host: other_host.to_string(),
+      start_after: None,
+      limit: Some(1000),
+    })
+    .unwrap();
+  assert_eq!(resp.ids.len(), 1);
+  assert!(!expected.contains(&resp.ids[0]));
+}
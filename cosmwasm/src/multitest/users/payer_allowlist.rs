@@ -0,0 +1,181 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  RelayerMessage, TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+  UpdatePayablePayerAllowlistMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn payer_allowlist() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let allowed = "allowed".into_addr();
+  let stranger = "stranger".into_addr();
+  let relayer = "relayer".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      for user in [&allowed, &stranger, &relayer] {
+        router
+          .bank
+          .init_balance(storage, user, coins(100, "native"))
+          .unwrap();
+      }
+    }));
+  let code_id = CodeId::store_code(&app);
+  let contract = code_id
+    .instantiate(InstantiateMessage {
+      chain_id: 1,
+      chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Set MaxWithdrawalFees for Native Token
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create two Payables
+  let mut payable_ids = vec![];
+  for count in 1..=2 {
+    contract
+      .create_payable(CreatePayableMessage {
+        allowed_tokens_and_amounts: vec![],
+        expires_at: None,
+      })
+      .call(&host)
+      .unwrap();
+    payable_ids.push(
+      contract
+        .user_payable_id(FetchIdMessage {
+          reference: host.to_string(),
+          count,
+        })
+        .unwrap()
+        .id,
+    );
+  }
+  let (private_id, public_id) = (&payable_ids[0], &payable_ids[1]);
+  let pay = |payable_id: &String, payer| {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(10),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(10, "native"))
+      .call(payer)
+  };
+  let allowlist = |wallets: Vec<String>| UpdatePayablePayerAllowlistMessage {
+    payable_id: private_id.clone(),
+    payer_allowlist: wallets,
+  };
+
+  // Payables are public by default.
+  let payable = contract
+    .payable(IdMessage {
+      id: private_id.clone(),
+    })
+    .unwrap();
+  assert!(payable.payer_allowlist.is_empty());
+
+  // Only the host can update the allowlist.
+  let err = contract
+    .update_payable_payer_allowlist(allowlist(vec![allowed.to_string()]))
+    .call(&stranger)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::NotYourPayable {});
+
+  // Restrict the first Payable to the allowed wallet. Repeats are dropped.
+  contract
+    .update_payable_payer_allowlist(allowlist(vec![
+      allowed.to_string(),
+      allowed.to_string(),
+    ]))
+    .call(&host)
+    .unwrap();
+  let payable = contract
+    .payable(IdMessage {
+      id: private_id.clone(),
+    })
+    .unwrap();
+  assert_eq!(payable.payer_allowlist, vec![allowed.clone()]);
+
+  // The allowed wallet can pay.
+  pay(private_id, &allowed).unwrap();
+
+  // Other wallets are rejected.
+  let err = pay(private_id, &stranger).unwrap_err();
+  assert_eq!(err, ChainbillsError::PayerNotAllowed {});
+
+  // Relayers can't pay as an allowed wallet unless they are allowed too.
+  contract
+    .add_relayer(RelayerMessage {
+      relayer: relayer.to_string(),
+    })
+    .call(&owner)
+    .unwrap();
+  let relay_for_allowed = || {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: private_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(10),
+        payer_override: Some(allowed.to_string()),
+        memo: None,
+      })
+      .with_funds(&coins(10, "native"))
+      .call(&relayer)
+  };
+  let err = relay_for_allowed().unwrap_err();
+  assert_eq!(err, ChainbillsError::PayerNotAllowed {});
+  contract
+    .update_payable_payer_allowlist(allowlist(vec![
+      allowed.to_string(),
+      relayer.to_string(),
+    ]))
+    .call(&host)
+    .unwrap();
+  relay_for_allowed().unwrap();
+
+  // Anybody can pay into the public Payable.
+  pay(public_id, &allowed).unwrap();
+  pay(public_id, &stranger).unwrap();
+
+  // Clearing the allowlist makes the Payable public again.
+  contract
+    .update_payable_payer_allowlist(allowlist(vec![]))
+    .call(&host)
+    .unwrap();
+  pay(private_id, &stranger).unwrap();
+
+  // Invalid wallets and too long allowlists are rejected.
+  assert!(contract
+    .update_payable_payer_allowlist(allowlist(vec!["invalid".to_string()]))
+    .call(&host)
+    .is_err());
+  let err = contract
+    .update_payable_payer_allowlist(allowlist(
+      (0..=50)
+        .map(|i| format!("payer{i}").into_addr().to_string())
+        .collect(),
+    ))
+    .call(&host)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::PayerAllowlistTooLong { max: 50 });
+}
@@ -0,0 +1,156 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg};
+use cw20_base::msg::InstantiateMsg;
+use sylvia::cw_multi_test::{Contract, ContractWrapper, Executor, IntoAddr};
+use sylvia::cw_std::{to_json_binary, Addr, Empty, StdResult, Uint128};
+use sylvia::multitest::App;
+
+fn contract_cw20() -> Box<dyn Contract<Empty>> {
+  let contract = ContractWrapper::new(
+    cw20_base::contract::execute,
+    cw20_base::contract::instantiate,
+    cw20_base::contract::query,
+  );
+  Box::new(contract)
+}
+
+#[test]
+fn paying_via_cw20_send() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let payer = "payer".into_addr();
+
+  let mut app = sylvia::cw_multi_test::App::default();
+  let cw20_id = app.store_code(contract_cw20());
+  let mut new_cw20 = |name: &str| {
+    app
+      .instantiate_contract(
+        cw20_id,
+        owner.clone(),
+        &InstantiateMsg {
+          name: name.to_string(),
+          symbol: name.to_string(),
+          decimals: 6,
+          initial_balances: vec![Cw20Coin {
+            address: payer.to_string(),
+            amount: Uint128::new(100),
+          }],
+          mint: None,
+          marketing: None,
+        },
+        &[],
+        name,
+        None,
+      )
+      .unwrap()
+  };
+  let usdc_addr = new_cw20("USDC");
+  let unknown_addr = new_cw20("UNKN");
+
+  let app = App::new(app);
+  let code_id = CodeId::store_code(&app);
+  let contract = code_id
+    .instantiate(InstantiateMessage {
+      chain_id: 1,
+      chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+    })
+    .call(&owner)
+    .unwrap();
+
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: usdc_addr.to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: false,
+    })
+    .call(&owner)
+    .unwrap();
+
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+
+  let send = |token: &Addr, amount: u128, info: &TransactionInfoMessage| {
+    app.app_mut().execute_contract(
+      payer.clone(),
+      token.clone(),
+      &Cw20ExecuteMsg::Send {
+        contract: contract.contract_addr.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_json_binary(info).unwrap(),
+      },
+      &[],
+    )
+  };
+  let tx_info = TransactionInfoMessage {
+    payable_id: payable_id.clone(),
+    token: usdc_addr.to_string(),
+    amount: Uint128::new(40),
+    payer_override: None,
+    memo: None,
+  };
+
+  // The hook message must match the tokens that were sent.
+  assert!(send(&usdc_addr, 30, &tx_info).is_err());
+
+  // Tokens that are not registered can't pay through the hook.
+  let unknown_info = TransactionInfoMessage {
+    token: unknown_addr.to_string(),
+    ..tx_info.clone()
+  };
+  assert!(send(&unknown_addr, 40, &unknown_info).is_err());
+
+  // Pay with a single Send, without any prior allowance.
+  send(&usdc_addr, 40, &tx_info).unwrap();
+
+  // The payment is recorded against the original sender, not the token.
+  let payment_id = contract
+    .user_payment_id(FetchIdMessage {
+      reference: payer.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let payment = contract.user_payment(IdMessage { id: payment_id }).unwrap();
+  assert_eq!(payment.payer, payer);
+  assert_eq!(payment.details.token, usdc_addr.to_string());
+  assert_eq!(payment.details.amount, Uint128::new(40));
+  assert_eq!(
+    contract
+      .user(IdMessage {
+        id: payer.to_string()
+      })
+      .unwrap()
+      .payments_count,
+    1
+  );
+
+  let payable = contract.payable(IdMessage { id: payable_id }).unwrap();
+  assert_eq!(payable.balances[0].amount, Uint128::new(40));
+
+  let balance: StdResult<BalanceResponse> = app.querier().query_wasm_smart(
+    usdc_addr.clone(),
+    &cw20::Cw20QueryMsg::Balance {
+      address: contract.contract_addr.to_string(),
+    },
+  );
+  assert_eq!(balance.unwrap().balance, Uint128::new(40));
+}
@@ -0,0 +1,89 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::{AppResponse, IntoAddr};
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+/// Returns the value of the given attribute in the wasm event of a response.
+fn wasm_attribute(resp: &AppResponse, key: &str) -> String {
+  resp
+    .events
+    .iter()
+    .filter(|e| e.ty == "wasm")
+    .flat_map(|e| e.attributes.iter())
+    .find(|a| a.key == key)
+    .unwrap()
+    .value
+    .clone()
+}
+
+#[test]
+fn payment_balance_events() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+
+  // Each payment emits the payable's cumulative balance of the token.
+  let mut expected = 0;
+  for amount in [120, 45] {
+    expected += amount;
+    let resp = contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(amount, "native"))
+      .call(&user)
+      .unwrap();
+    assert_eq!(
+      wasm_attribute(&resp, "payable_balance"),
+      expected.to_string()
+    );
+  }
+  assert_eq!(expected, 165);
+}
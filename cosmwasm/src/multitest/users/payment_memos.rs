@@ -0,0 +1,139 @@
+use crate::contract::sv::mt::CodeId;
+use crate::contract::MAX_MEMO_LENGTH;
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::{AppResponse, IntoAddr};
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+/// Returns the value of the given attribute in the wasm event of a response.
+fn wasm_attribute(resp: &AppResponse, key: &str) -> Option<String> {
+  resp
+    .events
+    .iter()
+    .filter(|e| e.ty == "wasm")
+    .flat_map(|e| e.attributes.iter())
+    .find(|a| a.key == key)
+    .map(|a| a.value.clone())
+}
+
+#[test]
+fn payment_memos() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let pay = |memo: Option<String>| {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(10),
+        payer_override: None,
+        memo,
+      })
+      .with_funds(&coins(10, "native"))
+      .call(&user)
+  };
+
+  // Paying with a memo stores it on both payment receipts and emits it.
+  let memo = "INV-2024-0042".to_string();
+  let resp = pay(Some(memo.clone())).unwrap();
+  assert_eq!(wasm_attribute(&resp, "memo"), Some(memo.clone()));
+  let user_payment_id = contract
+    .user_payment_id(FetchIdMessage {
+      reference: user.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let user_payment = contract
+    .user_payment(IdMessage {
+      id: user_payment_id,
+    })
+    .unwrap();
+  assert_eq!(user_payment.memo, Some(memo.clone()));
+  let payable_payment_id = contract
+    .payable_payment_id(FetchIdMessage {
+      reference: payable_id.clone(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let payable_payment = contract
+    .payable_payment(IdMessage {
+      id: payable_payment_id,
+    })
+    .unwrap();
+  assert_eq!(payable_payment.memo, Some(memo));
+
+  // Paying without a memo stores and emits none.
+  let resp = pay(None).unwrap();
+  assert_eq!(wasm_attribute(&resp, "memo"), None);
+  let user_payment_id = contract
+    .user_payment_id(FetchIdMessage {
+      reference: user.to_string(),
+      count: 2,
+    })
+    .unwrap()
+    .id;
+  let user_payment = contract
+    .user_payment(IdMessage {
+      id: user_payment_id,
+    })
+    .unwrap();
+  assert_eq!(user_payment.memo, None);
+
+  // A memo of the maximum length is accepted, a longer one is rejected.
+  pay(Some("a".repeat(MAX_MEMO_LENGTH))).unwrap();
+  let err = pay(Some("a".repeat(MAX_MEMO_LENGTH + 1))).unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::MemoTooLong {
+      max: MAX_MEMO_LENGTH as u64
+    }
+  );
+  let payable = contract.payable(IdMessage { id: payable_id }).unwrap();
+  assert_eq!(payable.payments_count, 3);
+}
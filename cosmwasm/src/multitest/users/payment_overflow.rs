@@ -0,0 +1,85 @@
+use crate::contract::sv::mt::CodeId;
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn payment_overflow() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(u128::MAX, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let tx_info = |amount: u128| TransactionInfoMessage {
+    payable_id: payable_id.clone(),
+    token: "native".to_string(),
+    amount: Uint128::new(amount),
+    payer_override: None,
+    memo: None,
+  };
+
+  // Bring the token's total payments to u128::MAX, then withdraw so that
+  // the contract can receive more of the token.
+  contract
+    .pay(tx_info(u128::MAX))
+    .with_funds(&coins(u128::MAX, "native"))
+    .call(&user)
+    .unwrap();
+  contract.withdraw(tx_info(u128::MAX)).call(&host).unwrap();
+
+  // Any further payment overflows the token's totals and is rejected with
+  // an error rather than a panic.
+  let err = contract
+    .pay(tx_info(1))
+    .with_funds(&coins(1, "native"))
+    .call(&host)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::Overflow {});
+
+  // Nothing from the failed payment was recorded.
+  let payable = contract.payable(IdMessage { id: payable_id }).unwrap();
+  assert_eq!(payable.payments_count, 1);
+}
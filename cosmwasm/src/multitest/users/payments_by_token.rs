@@ -0,0 +1,162 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage, PayMultipleMessage,
+  PayablePaymentsByTokenMessage, TransactionInfoMessage,
+  UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::TokenAndAmount;
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coin, HexBinary, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn payments_by_token() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(
+          storage,
+          &user,
+          vec![coin(1000, "native"), coin(1000, "other")],
+        )
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let contract = code_id
+    .instantiate(InstantiateMessage {
+      chain_id: 1,
+      chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Support both tokens.
+  for token in ["native", "other"] {
+    contract
+      .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+        token: token.to_string(),
+        max_withdrawal_fees: Uint128::new(100),
+        is_native_token: true,
+      })
+      .call(&owner)
+      .unwrap();
+  }
+
+  // Create a Payable
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+
+  let token_page = |token: &str, start_after, limit| {
+    contract
+      .payable_payments_by_token(PayablePaymentsByTokenMessage {
+        payable_id: payable_id.clone(),
+        token: token.to_string(),
+        start_after,
+        limit,
+        from_timestamp: None,
+        to_timestamp: None,
+      })
+      .unwrap()
+      .payments
+  };
+
+  // No payments yet.
+  assert!(token_page("native", None, None).is_empty());
+
+  // Alternate payments in both tokens: native, other, native, other, native.
+  for amount in 1..=5u128 {
+    let token = if amount % 2 == 1 { "native" } else { "other" };
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: token.to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&[coin(amount, token)])
+      .call(&user)
+      .unwrap();
+  }
+
+  // A multi-token payment is listed under both of its tokens.
+  contract
+    .pay_multiple(PayMultipleMessage {
+      payable_id: payable_id.clone(),
+      tokens_and_amounts: vec![
+        TokenAndAmount {
+          token: "other".to_string(),
+          amount: Uint128::new(6),
+        },
+        TokenAndAmount {
+          token: "native".to_string(),
+          amount: Uint128::new(7),
+        },
+      ],
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&[coin(7, "native"), coin(6, "other")])
+    .call(&user)
+    .unwrap();
+
+  // Only the requested token's payments return, in order.
+  let native = token_page("native", None, None);
+  assert_eq!(
+    native.iter().map(|p| p.payable_count).collect::<Vec<_>>(),
+    vec![1, 3, 5, 6]
+  );
+  let other = token_page("other", None, None);
+  assert_eq!(
+    other.iter().map(|p| p.payable_count).collect::<Vec<_>>(),
+    vec![2, 4, 6]
+  );
+  assert!(other[..2]
+    .iter()
+    .all(|p| p.details.token == "other" && p.other_details.is_empty()));
+  assert_eq!(other[2].details.token, "other");
+  assert_eq!(other[2].other_details[0].token, "native");
+
+  // Pages follow the token's own payments.
+  assert_eq!(token_page("native", Some(1), Some(2)), native[1..3]);
+  assert_eq!(token_page("native", Some(3), Some(2)), native[3..4]);
+  assert!(token_page("native", Some(4), None).is_empty());
+
+  // Tokens the payable wasn't paid in have no payments.
+  assert!(token_page("unpaid", None, None).is_empty());
+
+  // Unknown payables are rejected
+  let err = contract
+    .payable_payments_by_token(PayablePaymentsByTokenMessage {
+      payable_id: HexBinary::from([1u8; 32]).to_hex(),
+      token: "native".to_string(),
+      start_after: None,
+      limit: None,
+      from_timestamp: None,
+      to_timestamp: None,
+    })
+    .unwrap_err();
+  // Testing the error message because a Querrier error is returned
+  // instead of a ChainbillsError.
+  assert!(err.to_string().contains("Invalid Payable ID"));
+}
@@ -0,0 +1,124 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  ChainPaymentsCount, CreatePayableMessage, FetchIdMessage, IdMessage,
+  InstantiateMessage, PerChainPayablePaymentsCountMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn payments_per_chain() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+
+  // No payments yet.
+  let per_chain = || {
+    contract
+      .payable_payments_per_chain(IdMessage {
+        id: payable_id.clone(),
+      })
+      .unwrap()
+      .counts
+  };
+  assert!(per_chain().is_empty());
+
+  // Each local payment increments this chain's count.
+  for count in 1..=2 {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(100),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(100, "native"))
+      .call(&user)
+      .unwrap();
+    assert_eq!(per_chain(), vec![ChainPaymentsCount { chain_id: 1, count }]);
+    assert_eq!(
+      contract
+        .per_chain_payable_payment_count(PerChainPayablePaymentsCountMessage {
+          payable_id: payable_id.clone(),
+          chain_id: 1,
+        })
+        .unwrap()
+        .count,
+      count
+    );
+  }
+
+  // The stats have the total alongside the per-chain counts, and the total
+  // is the sum of the per-chain counts.
+  let stats = contract
+    .payable_payment_stats(IdMessage {
+      id: payable_id.clone(),
+    })
+    .unwrap();
+  assert_eq!(stats.total, 2);
+  assert_eq!(stats.per_chain, per_chain());
+  assert_eq!(
+    stats.total,
+    stats.per_chain.iter().map(|c| c.count).sum::<u64>()
+  );
+
+  // Unknown payables are rejected.
+  for err in [
+    contract
+      .payable_payments_per_chain(IdMessage {
+        id: "00".repeat(32),
+      })
+      .unwrap_err(),
+    contract
+      .payable_payment_stats(IdMessage {
+        id: "00".repeat(32),
+      })
+      .unwrap_err(),
+  ] {
+    // Testing the error message because a Querrier error is returned
+    // instead of a ChainbillsError.
+    assert!(err.to_string().contains("Invalid Payable ID"));
+  }
+}
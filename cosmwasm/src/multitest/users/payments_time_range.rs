@@ -0,0 +1,153 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage,
+  PaginatedPaymentsMessage, PayablePaymentsByTokenMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn payments_time_range() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(100, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let contract = code_id
+    .instantiate(InstantiateMessage {
+      chain_id: 1,
+      chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Set MaxWithdrawalFees for Native Token
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+
+  // Make five payments, 100 seconds apart.
+  let start = app.block_info().time.seconds();
+  for amount in 1..=5 {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(amount, "native"))
+      .call(&user)
+      .unwrap();
+    app
+      .app_mut()
+      .update_block(|b| b.time = b.time.plus_seconds(100));
+  }
+
+  // The payment amounts of each query in the range.
+  let amounts = |from, to, start_after, limit| {
+    let user_amounts = contract
+      .user_payments(PaginatedPaymentsMessage {
+        reference: user.to_string(),
+        start_after,
+        limit,
+        from_timestamp: from,
+        to_timestamp: to,
+      })
+      .unwrap()
+      .payments
+      .iter()
+      .map(|p| p.details.amount.u128())
+      .collect::<Vec<_>>();
+    let payable_amounts = contract
+      .payable_payments(PaginatedPaymentsMessage {
+        reference: payable_id.clone(),
+        start_after,
+        limit,
+        from_timestamp: from,
+        to_timestamp: to,
+      })
+      .unwrap()
+      .payments
+      .iter()
+      .map(|p| p.details.amount.u128())
+      .collect::<Vec<_>>();
+    let token_amounts = contract
+      .payable_payments_by_token(PayablePaymentsByTokenMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        start_after,
+        limit,
+        from_timestamp: from,
+        to_timestamp: to,
+      })
+      .unwrap()
+      .payments
+      .iter()
+      .map(|p| p.details.amount.u128())
+      .collect::<Vec<_>>();
+    assert_eq!(user_amounts, payable_amounts);
+    assert_eq!(user_amounts, token_amounts);
+    user_amounts
+  };
+  // The time of the nth payment.
+  let at = |n: u64| Some(start + (n - 1) * 100);
+
+  // Without a range, all payments return.
+  assert_eq!(amounts(None, None, None, None), vec![1, 2, 3, 4, 5]);
+
+  // Both bounds are inclusive.
+  assert_eq!(amounts(at(2), at(4), None, None), vec![2, 3, 4]);
+  assert_eq!(amounts(at(2), None, None, None), vec![2, 3, 4, 5]);
+  assert_eq!(amounts(None, at(2), None, None), vec![1, 2]);
+  assert_eq!(amounts(at(3), at(3), None, None), vec![3]);
+
+  // Times just inside the payments' times exclude them.
+  let after = |n: u64| at(n).map(|t| t + 1);
+  let before = |n: u64| at(n).map(|t| t - 1);
+  assert_eq!(amounts(after(2), before(4), None, None), vec![3]);
+  assert!(amounts(after(2), before(3), None, None).is_empty());
+  assert!(amounts(after(5), None, None, None).is_empty());
+  assert!(amounts(None, before(1), None, None).is_empty());
+  assert!(amounts(at(4), at(2), None, None).is_empty());
+
+  // Pages count only the payments in the range.
+  assert_eq!(amounts(at(2), None, None, Some(2)), vec![2, 3]);
+  assert_eq!(amounts(at(2), None, Some(2), Some(2)), vec![4, 5]);
+  assert_eq!(amounts(at(2), at(4), Some(2), Some(2)), vec![4]);
+  assert!(amounts(at(2), at(4), Some(3), None).is_empty());
+}
@@ -0,0 +1,161 @@
+use crate::contract::sv::mt::CodeId;
+use crate::contract::MAX_SCAN_LIMIT;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+  UserPaymentsToHostMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn payments_to_host() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let other_host = "other_host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Set MaxWithdrawalFees for Native Token
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create two Payables for the host and one for the other host.
+  let mut payable_ids = vec![];
+  for (creator, count) in [(&host, 1), (&other_host, 1), (&host, 2)] {
+    contract
+      .create_payable(CreatePayableMessage {
+        allowed_tokens_and_amounts: vec![],
+        expires_at: None,
+      })
+      .call(creator)
+      .unwrap();
+    let payable_id = contract
+      .user_payable_id(FetchIdMessage {
+        reference: creator.to_string(),
+        count,
+      })
+      .unwrap()
+      .id;
+
+    payable_ids.push(payable_id.clone());
+
+    // Pay into each payable.
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id,
+        token: "native".to_string(),
+        amount: Uint128::new(10),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(10, "native"))
+      .call(&user)
+      .unwrap();
+  }
+
+  // Only the payments to the host's payables are returned.
+  let resp = contract
+    .user_payments_to_host(UserPaymentsToHostMessage {
+      wallet: user.to_string(),
+      host: host.to_string(),
+      start: None,
+      limit: None,
+    })
+    .unwrap();
+  assert_eq!(resp.payments.len(), 2);
+  assert_eq!(resp.payments[0].payment.payer_count, 1);
+  assert_eq!(resp.payments[1].payment.payer_count, 3);
+  assert_eq!(resp.next, None);
+
+  // Paginating with a limit of 1 returns a cursor for the next page.
+  let resp = contract
+    .user_payments_to_host(UserPaymentsToHostMessage {
+      wallet: user.to_string(),
+      host: host.to_string(),
+      start: None,
+      limit: Some(1),
+    })
+    .unwrap();
+  assert_eq!(resp.payments.len(), 1);
+  assert_eq!(resp.next, Some(2));
+  let resp = contract
+    .user_payments_to_host(UserPaymentsToHostMessage {
+      wallet: user.to_string(),
+      host: host.to_string(),
+      start: resp.next,
+      limit: Some(1),
+    })
+    .unwrap();
+  assert_eq!(resp.payments.len(), 1);
+  assert_eq!(resp.payments[0].payment.payer_count, 3);
+  assert_eq!(resp.next, None);
+
+  // A call scans at most MAX_SCAN_LIMIT payments, returning a cursor to
+  // continue from even if none of them matched.
+  let pay = |payable_id: &String| {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(1),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(1, "native"))
+      .call(&user)
+      .unwrap();
+  };
+  for _ in 0..MAX_SCAN_LIMIT {
+    pay(&payable_ids[1]);
+  }
+  pay(&payable_ids[0]);
+  let resp = contract
+    .user_payments_to_host(UserPaymentsToHostMessage {
+      wallet: user.to_string(),
+      host: host.to_string(),
+      start: Some(4),
+      limit: None,
+    })
+    .unwrap();
+  assert!(resp.payments.is_empty());
+  assert_eq!(resp.next, Some(4 + MAX_SCAN_LIMIT as u64));
+  let resp = contract
+    .user_payments_to_host(UserPaymentsToHostMessage {
+      wallet: user.to_string(),
+      host: host.to_string(),
+      start: resp.next,
+      limit: None,
+    })
+    .unwrap();
+  assert_eq!(resp.payments.len(), 1);
+  assert_eq!(
+    resp.payments[0].payment.payer_count,
+    4 + MAX_SCAN_LIMIT as u64
+  );
+  assert_eq!(resp.next, None);
+}
@@ -0,0 +1,336 @@
+use crate::contract::sv::mt::CodeId;
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::refunds::sv::mt::RefundsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  PayMultipleMessage, RefundPaymentMessage, TransactionInfoMessage,
+  UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::TokenAndAmount;
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coin, coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn refunding_payments() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(100, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Set MaxWithdrawalFees for Native Token
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable
+  let payable_resp = contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = payable_resp
+    .events
+    .iter()
+    .find(|ev| ev.ty == "wasm")
+    .unwrap()
+    .attributes
+    .iter()
+    .find(|attr| attr.key == "payable_id")
+    .unwrap()
+    .value
+    .clone();
+
+  // Make a Payment of 10 Native Tokens
+  contract
+    .pay(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(10),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&coins(10, "native"))
+    .call(&user)
+    .unwrap();
+  let payment_id = contract
+    .user_payment_id(FetchIdMessage {
+      reference: user.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+
+  // Only the host can refund
+  let err = contract
+    .refund_payment_partial(RefundPaymentMessage {
+      payment_id: payment_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(4),
+    })
+    .call(&user)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::NotYourPayable {});
+
+  // First partial refund
+  contract
+    .refund_payment_partial(RefundPaymentMessage {
+      payment_id: payment_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(4),
+    })
+    .call(&host)
+    .unwrap();
+  let refund = contract
+    .payment_refund(IdMessage {
+      id: payment_id.clone(),
+    })
+    .unwrap();
+  assert_eq!(refund.refunded_of("native"), Uint128::new(4));
+  assert!(!refund.is_fully_refunded);
+
+  // Refunds whose cumulative amount overflows are rejected
+  let err = contract
+    .refund_payment_partial(RefundPaymentMessage {
+      payment_id: payment_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::MAX,
+    })
+    .call(&host)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::Overflow {});
+
+  // Second partial refund completing the payment's amount
+  contract
+    .refund_payment_partial(RefundPaymentMessage {
+      payment_id: payment_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(6),
+    })
+    .call(&host)
+    .unwrap();
+  let refund = contract
+    .payment_refund(IdMessage {
+      id: payment_id.clone(),
+    })
+    .unwrap();
+  assert_eq!(refund.refunded_of("native"), Uint128::new(10));
+  assert!(refund.is_fully_refunded);
+
+  // The payer got their funds back and the payable's balance was deducted.
+  let balance = app.querier().query_balance(&user, "native").unwrap();
+  assert_eq!(balance.amount, Uint128::new(100));
+  let payable = contract
+    .payable(IdMessage {
+      id: payable_id.clone(),
+    })
+    .unwrap();
+  assert_eq!(payable.balances[0].amount, Uint128::zero());
+
+  // Refunds are deducted from the payable's and the token's totals.
+  let volume = contract
+    .payable_volume(IdMessage {
+      id: payable_id.clone(),
+    })
+    .unwrap()
+    .volume;
+  assert_eq!(volume[0].amount, Uint128::zero());
+  let token_details = contract
+    .token_details(IdMessage {
+      id: "native".to_string(),
+    })
+    .unwrap();
+  assert_eq!(token_details.total_user_paid, Uint128::zero());
+  assert_eq!(token_details.total_payable_received, Uint128::zero());
+
+  // Over-refunding is rejected
+  let err = contract
+    .refund_payment_partial(RefundPaymentMessage {
+      payment_id,
+      token: "native".to_string(),
+      amount: Uint128::new(1),
+    })
+    .call(&host)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::RefundExceedsPayment {});
+}
+
+#[test]
+fn refunding_multi_token_payments() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(
+          storage,
+          &user,
+          vec![coin(100, "native"), coin(100, "other")],
+        )
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  for token in ["native", "other", "third"] {
+    contract
+      .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+        token: token.to_string(),
+        max_withdrawal_fees: Uint128::new(100),
+        is_native_token: true,
+      })
+      .call(&owner)
+      .unwrap();
+  }
+
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+
+  // Pay 10 native and 20 other tokens at once.
+  contract
+    .pay_multiple(PayMultipleMessage {
+      payable_id: payable_id.clone(),
+      tokens_and_amounts: vec![
+        TokenAndAmount {
+          token: "native".to_string(),
+          amount: Uint128::new(10),
+        },
+        TokenAndAmount {
+          token: "other".to_string(),
+          amount: Uint128::new(20),
+        },
+      ],
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&[coin(10, "native"), coin(20, "other")])
+    .call(&user)
+    .unwrap();
+  let payment_id = contract
+    .user_payment_id(FetchIdMessage {
+      reference: user.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let refund_msg = |token: &str, amount: u128| RefundPaymentMessage {
+    payment_id: payment_id.clone(),
+    token: token.to_string(),
+    amount: Uint128::new(amount),
+  };
+
+  // Only the payment's tokens can be refunded.
+  let err = contract
+    .refund_payment_partial(refund_msg("third", 1))
+    .call(&host)
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::TokenNotInPayment {
+      token: "third".to_string()
+    }
+  );
+
+  // Refunds are capped by the payment's amount of each token, not of the
+  // first one.
+  contract
+    .refund_payment_partial(refund_msg("other", 15))
+    .call(&host)
+    .unwrap();
+  let err = contract
+    .refund_payment_partial(refund_msg("native", 11))
+    .call(&host)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::RefundExceedsPayment {});
+  let refund = contract
+    .payment_refund(IdMessage {
+      id: payment_id.clone(),
+    })
+    .unwrap();
+  assert_eq!(refund.refunded_of("native"), Uint128::zero());
+  assert_eq!(refund.refunded_of("other"), Uint128::new(15));
+  assert!(!refund.is_fully_refunded);
+
+  // The refunded token's totals are deducted, the other token's aren't.
+  let volume = contract
+    .payable_volume(IdMessage {
+      id: payable_id.clone(),
+    })
+    .unwrap()
+    .volume;
+  assert_eq!(volume[0].amount, Uint128::new(10));
+  assert_eq!(volume[1].amount, Uint128::new(5));
+  let other_details = contract
+    .token_details(IdMessage {
+      id: "other".to_string(),
+    })
+    .unwrap();
+  assert_eq!(other_details.total_user_paid, Uint128::new(5));
+  assert_eq!(other_details.total_payable_received, Uint128::new(5));
+  let native_details = contract
+    .token_details(IdMessage {
+      id: "native".to_string(),
+    })
+    .unwrap();
+  assert_eq!(native_details.total_user_paid, Uint128::new(10));
+
+  // Refunding the rest of both tokens fully refunds the payment.
+  contract
+    .refund_payment_partial(refund_msg("other", 5))
+    .call(&host)
+    .unwrap();
+  contract
+    .refund_payment_partial(refund_msg("native", 10))
+    .call(&host)
+    .unwrap();
+  let refund = contract
+    .payment_refund(IdMessage { id: payment_id })
+    .unwrap();
+  assert!(refund.is_fully_refunded);
+  for denom in ["native", "other"] {
+    let balance = app.querier().query_balance(&user, denom).unwrap();
+    assert_eq!(balance.amount, Uint128::new(100));
+  }
+}
@@ -0,0 +1,174 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  RelayerMessage, TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw20_base::msg::InstantiateMsg;
+use sylvia::cw_multi_test::{Contract, ContractWrapper, Executor, IntoAddr};
+use sylvia::cw_std::{Addr, Empty, Uint128};
+use sylvia::multitest::App;
+
+fn contract_cw20() -> Box<dyn Contract<Empty>> {
+  let contract = ContractWrapper::new(
+    cw20_base::contract::execute,
+    cw20_base::contract::instantiate,
+    cw20_base::contract::query,
+  );
+  Box::new(contract)
+}
+
+#[test]
+fn relayed_cw20_payments() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+  let relayer = "relayer".into_addr();
+
+  let mut app = sylvia::cw_multi_test::App::default();
+  let cw20_id = app.store_code(contract_cw20());
+  let usdc_addr = app
+    .instantiate_contract(
+      cw20_id,
+      owner.clone(),
+      &InstantiateMsg {
+        name: "USDC".to_string(),
+        symbol: "USDC".to_string(),
+        decimals: 6,
+        initial_balances: vec![
+          Cw20Coin {
+            address: user.to_string(),
+            amount: Uint128::new(100),
+          },
+          Cw20Coin {
+            address: relayer.to_string(),
+            amount: Uint128::new(100),
+          },
+        ],
+        mint: None,
+        marketing: None,
+      },
+      &[],
+      "USDC",
+      None,
+    )
+    .unwrap();
+
+  let app = App::new(app);
+  let code_id = CodeId::store_code(&app);
+  let contract = code_id
+    .instantiate(InstantiateMessage {
+      chain_id: 1,
+      chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+    })
+    .call(&owner)
+    .unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: usdc_addr.to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: false,
+    })
+    .call(&owner)
+    .unwrap();
+  contract
+    .add_relayer(RelayerMessage {
+      relayer: relayer.to_string(),
+    })
+    .call(&owner)
+    .unwrap();
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+
+  let balance = |wallet: &Addr| {
+    app
+      .querier()
+      .query_wasm_smart::<BalanceResponse>(
+        usdc_addr.clone(),
+        &Cw20QueryMsg::Balance {
+          address: wallet.to_string(),
+        },
+      )
+      .unwrap()
+      .balance
+      .u128()
+  };
+  let approve = |wallet: &Addr, amount: u128| {
+    app
+      .app_mut()
+      .execute_contract(
+        wallet.clone(),
+        usdc_addr.clone(),
+        &Cw20ExecuteMsg::IncreaseAllowance {
+          spender: contract.contract_addr.to_string(),
+          amount: Uint128::new(amount),
+          expires: None,
+        },
+        &[],
+      )
+      .unwrap();
+  };
+  // Paying directly through the app as the TransferFrom's error isn't a
+  // ChainbillsError.
+  let relay_for_user = || {
+    app.app_mut().execute_contract(
+      relayer.clone(),
+      contract.contract_addr.clone(),
+      &payments::sv::ExecMsg::Pay {
+        data: TransactionInfoMessage {
+          payable_id: payable_id.clone(),
+          token: usdc_addr.to_string(),
+          amount: Uint128::new(40),
+          payer_override: Some(user.to_string()),
+          memo: None,
+        },
+      },
+      &[],
+    )
+  };
+
+  // The user has an outstanding allowance to the contract.
+  approve(&user, 100);
+
+  // A relayer can't spend the user's allowance by overriding the payer.
+  assert!(relay_for_user().is_err());
+  assert_eq!(balance(&user), 100);
+  assert_eq!(balance(&relayer), 100);
+
+  // With its own allowance, the relayer's funds are used, and the payment is
+  // still recorded under the user.
+  approve(&relayer, 40);
+  relay_for_user().unwrap();
+  assert_eq!(balance(&user), 100);
+  assert_eq!(balance(&relayer), 60);
+  assert_eq!(balance(&contract.contract_addr), 40);
+  let payment_id = contract
+    .user_payment_id(FetchIdMessage {
+      reference: user.to_string(),
+      count: 1,
+    })
+    .unwrap();
+  assert_eq!(contract.user_payment(payment_id).unwrap().payer, user);
+  let payable = contract
+    .payable(IdMessage {
+      id: payable_id.clone(),
+    })
+    .unwrap();
+  assert_eq!(payable.balances[0].amount, Uint128::new(40));
+}
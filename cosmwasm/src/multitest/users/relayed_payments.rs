@@ -0,0 +1,127 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  RelayerMessage, TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn relayed_payments() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+  let relayer = "relayer".into_addr();
+  let stranger = "stranger".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      for wallet in [&relayer, &stranger] {
+        router
+          .bank
+          .init_balance(storage, wallet, coins(100, "native"))
+          .unwrap();
+      }
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let on_behalf_of_user = TransactionInfoMessage {
+    payable_id: payable_id.clone(),
+    token: "native".to_string(),
+    amount: Uint128::new(40),
+    payer_override: Some(user.to_string()),
+    memo: None,
+  };
+
+  // Only the owner can whitelist relayers.
+  let err = contract
+    .add_relayer(RelayerMessage {
+      relayer: relayer.to_string(),
+    })
+    .call(&stranger)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::OwnerUnauthorized {});
+  contract
+    .add_relayer(RelayerMessage {
+      relayer: relayer.to_string(),
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Non-whitelisted senders can't pay on behalf of other wallets.
+  let err = contract
+    .pay(on_behalf_of_user.clone())
+    .with_funds(&coins(40, "native"))
+    .call(&stranger)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::RelayerUnauthorized {});
+
+  // A whitelisted relayer's payment is recorded under the overridden wallet.
+  contract
+    .pay(on_behalf_of_user)
+    .with_funds(&coins(40, "native"))
+    .call(&relayer)
+    .unwrap();
+  let payment_id = contract
+    .user_payment_id(FetchIdMessage {
+      reference: user.to_string(),
+      count: 1,
+    })
+    .unwrap();
+  let payment = contract.user_payment(payment_id).unwrap();
+  assert_eq!(payment.payer, user);
+  assert_eq!(payment.details.amount, Uint128::new(40));
+  let relayer_payments_count = || {
+    contract
+      .user(IdMessage {
+        id: relayer.to_string(),
+      })
+      .map_or(0, |user| user.payments_count)
+  };
+  assert_eq!(relayer_payments_count(), 0);
+
+  // Relayers pay for themselves when no override is given.
+  contract
+    .pay(TransactionInfoMessage {
+      payable_id,
+      token: "native".to_string(),
+      amount: Uint128::new(10),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&coins(10, "native"))
+    .call(&relayer)
+    .unwrap();
+  assert_eq!(relayer_payments_count(), 1);
+}
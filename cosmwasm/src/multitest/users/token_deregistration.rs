@@ -0,0 +1,123 @@
+use crate::contract::sv::mt::CodeId;
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  SetTokenSupportedMessage, TransactionInfoMessage,
+  UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn token_deregistration() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable and pay into it.
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let pay = || {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(500),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(500, "native"))
+      .call(&user)
+  };
+  pay().unwrap();
+
+  // Only the owner can deregister a token.
+  let set_supported = |token: &str, is_supported| {
+    contract.set_token_supported(SetTokenSupportedMessage {
+      token: token.to_string(),
+      is_supported,
+    })
+  };
+  let err = set_supported("native", false).call(&host).unwrap_err();
+  assert_eq!(err, ChainbillsError::OwnerUnauthorized {});
+
+  // Unknown tokens can't be deregistered.
+  let err = set_supported("unknown", false).call(&owner).unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::InvalidToken {
+      token: "unknown".to_string()
+    }
+  );
+
+  // Payments are rejected after deregistration.
+  set_supported("native", false).call(&owner).unwrap();
+  let err = pay().unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::UnsupportedToken {
+      token: "native".to_string()
+    }
+  );
+
+  // The existing balance can still be withdrawn.
+  contract
+    .withdraw(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(500),
+      payer_override: None,
+      memo: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable = contract
+    .payable(IdMessage {
+      id: payable_id.clone(),
+    })
+    .unwrap();
+  assert_eq!(payable.balances[0].amount, Uint128::zero());
+
+  // Payments resume after the token is supported again.
+  set_supported("native", true).call(&owner).unwrap();
+  pay().unwrap();
+}
@@ -0,0 +1,102 @@
+use crate::contract::sv::mt::CodeId;
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coin, coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn unexpected_funds() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+  let usdc = "usdc".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(
+          storage,
+          &user,
+          vec![coin(1000, "native"), coin(1000, "other")],
+        )
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  for (token, is_native_token) in [("native", true), (usdc.as_str(), false)] {
+    contract
+      .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+        token: token.to_string(),
+        max_withdrawal_fees: Uint128::new(100),
+        is_native_token,
+      })
+      .call(&owner)
+      .unwrap();
+  }
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+
+  // Attaching another denom to a native payment is rejected.
+  let native_info = TransactionInfoMessage {
+    payable_id: payable_id.clone(),
+    token: "native".to_string(),
+    amount: Uint128::new(100),
+    payer_override: None,
+    memo: None,
+  };
+  let err = contract
+    .pay(native_info.clone())
+    .with_funds(&[coin(100, "native"), coin(5, "other")])
+    .call(&user)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::UnexpectedFunds {});
+
+  // Attaching any coins to a CW20 payment is rejected.
+  let err = contract
+    .pay(TransactionInfoMessage {
+      payable_id,
+      token: usdc.to_string(),
+      amount: Uint128::new(100),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&coins(5, "native"))
+    .call(&user)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::UnexpectedFunds {});
+
+  // No coins were absorbed and an exact native payment still goes through.
+  assert_eq!(
+    app.querier().query_balance(&user, "other").unwrap().amount,
+    Uint128::new(1000)
+  );
+  contract
+    .pay(native_info)
+    .with_funds(&coins(100, "native"))
+    .call(&user)
+    .unwrap();
+}
@@ -0,0 +1,141 @@
+use crate::contract::sv::mt::CodeId;
+use crate::error::ChainbillsError;
+use crate::interfaces::activities::sv::mt::ActivitiesProxy;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+  UpdatePayableTokensAndAmountsMessage,
+};
+use crate::state::{ActivityType, TokenAndAmount};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+fn native(amount: u128) -> TokenAndAmount {
+  TokenAndAmount {
+    token: "native".to_string(),
+    amount: Uint128::new(amount),
+  }
+}
+
+#[test]
+fn updating_payable_tokens() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable that accepts 40 of the Native Token.
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![native(40)],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let update = |allowed_tokens_and_amounts: Vec<TokenAndAmount>| {
+    contract.update_payable_tokens_and_amounts(
+      UpdatePayableTokensAndAmountsMessage {
+        payable_id: payable_id.clone(),
+        allowed_tokens_and_amounts,
+      },
+    )
+  };
+  let pay = |amount: u128| {
+    contract
+      .pay(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .with_funds(&coins(amount, "native"))
+      .call(&user)
+  };
+
+  // Only the host can update the allowed tokens and amounts.
+  let err = update(vec![native(60)]).call(&user).unwrap_err();
+  assert_eq!(err, ChainbillsError::NotYourPayable {});
+
+  // Unsupported tokens and zero amounts are rejected.
+  let unsupported = TokenAndAmount {
+    token: "unknown".to_string(),
+    amount: Uint128::new(10),
+  };
+  let err = update(vec![unsupported]).call(&host).unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::UnsupportedToken {
+      token: "unknown".to_string()
+    }
+  );
+  let err = update(vec![native(0)]).call(&host).unwrap_err();
+  assert_eq!(err, ChainbillsError::ZeroAmountSpecified {});
+
+  // Change the allowed set to 60 or 80 of the Native Token.
+  update(vec![native(60), native(80)]).call(&host).unwrap();
+  let payable = contract
+    .payable(IdMessage {
+      id: payable_id.clone(),
+    })
+    .unwrap();
+  assert_eq!(
+    payable.allowed_tokens_and_amounts,
+    vec![native(60), native(80)]
+  );
+
+  // The update was recorded as the payable's latest activity.
+  let activity_id = contract
+    .payable_activity_id(FetchIdMessage {
+      reference: payable_id.clone(),
+      count: payable.activities_count,
+    })
+    .unwrap();
+  let activity = contract.activity(activity_id).unwrap();
+  assert_eq!(
+    activity.activity_type,
+    ActivityType::UpdatedPayableAllowedTokensAndAmounts
+  );
+
+  // The old set no longer matches while the new one does.
+  let err = pay(40).unwrap_err();
+  assert_eq!(err, ChainbillsError::MatchingTokenAndAmountNotFound {});
+  pay(60).unwrap();
+  pay(80).unwrap();
+
+  // Clearing the set makes the payable accept any amount.
+  update(vec![]).call(&host).unwrap();
+  pay(15).unwrap();
+}
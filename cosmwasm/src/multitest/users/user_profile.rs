@@ -0,0 +1,96 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, IdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use crate::state::User;
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn user_profile() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // A wallet that has never interacted gets a zero-initialized User.
+  let fresh = contract
+    .user(IdMessage {
+      id: user.to_string(),
+    })
+    .unwrap();
+  assert_eq!(fresh, User::initialize(0));
+
+  // Create a payable and pay into it.
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  contract
+    .pay(TransactionInfoMessage {
+      payable_id,
+      token: "native".to_string(),
+      amount: Uint128::new(100),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&coins(100, "native"))
+    .call(&user)
+    .unwrap();
+
+  // The counts of the payer and host reflect what they did.
+  let payer = contract
+    .user(IdMessage {
+      id: user.to_string(),
+    })
+    .unwrap();
+  assert_eq!(payer.chain_count, 2);
+  assert_eq!(payer.payments_count, 1);
+  // Initializing the user is their first activity, paying is the second.
+  assert_eq!(payer.activities_count, 2);
+  assert_eq!(payer.payables_count, 0);
+  let host_user = contract
+    .user(IdMessage {
+      id: host.to_string(),
+    })
+    .unwrap();
+  assert_eq!(host_user.chain_count, 1);
+  assert_eq!(host_user.payables_count, 1);
+  assert_eq!(host_user.payments_count, 0);
+}
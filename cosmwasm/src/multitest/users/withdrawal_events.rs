@@ -0,0 +1,102 @@
+use crate::contract::sv::mt::CodeId;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn withdrawal_events() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(100, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  // Set MaxWithdrawalFees for Native Token
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable and pay into it
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let tx_info = TransactionInfoMessage {
+    payable_id: payable_id.clone(),
+    token: "native".to_string(),
+    amount: Uint128::new(100),
+    payer_override: None,
+    memo: None,
+  };
+  contract
+    .pay(tx_info.clone())
+    .with_funds(&coins(100, "native"))
+    .call(&user)
+    .unwrap();
+
+  // Withdraw and check the withdrawal_made event
+  let resp = contract.withdraw(tx_info).call(&host).unwrap();
+  let withdrawal_id = contract
+    .user_withdrawal_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  let event = resp
+    .events
+    .iter()
+    .find(|ev| ev.ty == "wasm-withdrawal_made")
+    .unwrap();
+  let attr = |key: &str| {
+    event
+      .attributes
+      .iter()
+      .find(|attr| attr.key == key)
+      .unwrap()
+      .value
+      .clone()
+  };
+  assert_eq!(attr("payable_id"), payable_id);
+  assert_eq!(attr("host_wallet"), host.to_string());
+  assert_eq!(attr("withdrawal_id"), withdrawal_id);
+  assert_eq!(attr("token"), "native");
+  assert_eq!(attr("amount"), "100");
+  assert_eq!(attr("fee"), "2");
+}
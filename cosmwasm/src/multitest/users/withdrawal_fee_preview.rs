@@ -0,0 +1,141 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage, WithdrawalFeeMessage,
+};
+use crate::state::TokenAndAmount;
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn withdrawal_fee_preview() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let fee_collector = "fee_collector".into_addr();
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: fee_collector.to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+
+  let set_max_withdrawal_fees = |max_withdrawal_fees| {
+    contract
+      .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+        token: "native".to_string(),
+        max_withdrawal_fees: Uint128::new(max_withdrawal_fees),
+        is_native_token: true,
+      })
+      .call(&owner)
+      .unwrap();
+  };
+  set_max_withdrawal_fees(100);
+
+  // Previewing an unknown token fails.
+  let err = contract
+    .withdrawal_fee(WithdrawalFeeMessage {
+      token: "unknown".to_string(),
+      amount: Uint128::new(100),
+    })
+    .unwrap_err();
+  // Testing the error message because a Querrier error is returned instead
+  // of a ChainbillsError.
+  assert!(err.to_string().contains("Invalid Token: unknown"));
+
+  // Create a Payable and pay into it.
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  contract
+    .pay(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(1000),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&coins(1000, "native"))
+    .call(&user)
+    .unwrap();
+
+  // Preview and withdraw, checking that the preview matches what the host
+  // and the fee collector actually got.
+  let preview_and_withdraw = |amount| {
+    let preview = contract
+      .withdrawal_fee(WithdrawalFeeMessage {
+        token: "native".to_string(),
+        amount: Uint128::new(amount),
+      })
+      .unwrap();
+    let host_before = app.querier().query_balance(&host, "native").unwrap();
+    let fees_before = app
+      .querier()
+      .query_balance(&fee_collector, "native")
+      .unwrap();
+    contract
+      .withdraw(TransactionInfoMessage {
+        payable_id: payable_id.clone(),
+        token: "native".to_string(),
+        amount: Uint128::new(amount),
+        payer_override: None,
+        memo: None,
+      })
+      .call(&host)
+      .unwrap();
+    // Fees are kept in the contract until the owner sweeps them.
+    contract
+      .owner_withdraw(TokenAndAmount {
+        token: "native".to_string(),
+        amount: preview.fee,
+      })
+      .call(&owner)
+      .unwrap();
+    let host_after = app.querier().query_balance(&host, "native").unwrap();
+    let fees_after = app
+      .querier()
+      .query_balance(&fee_collector, "native")
+      .unwrap();
+
+    assert_eq!(preview.gross, Uint128::new(amount));
+    assert_eq!(preview.net, host_after.amount - host_before.amount);
+    assert_eq!(preview.fee, fees_after.amount - fees_before.amount);
+    assert_eq!(preview.gross, preview.net + preview.fee);
+    preview
+  };
+
+  // 2% of 500 is within the max withdrawal fees.
+  let preview = preview_and_withdraw(500);
+  assert_eq!(preview.fee, Uint128::new(10));
+  assert_eq!(preview.net, Uint128::new(490));
+
+  // 2% of 400 is capped by a lower max withdrawal fees.
+  set_max_withdrawal_fees(5);
+  let preview = preview_and_withdraw(400);
+  assert_eq!(preview.fee, Uint128::new(5));
+  assert_eq!(preview.net, Uint128::new(395));
+}
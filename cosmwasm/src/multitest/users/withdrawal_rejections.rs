@@ -0,0 +1,112 @@
+use crate::contract::sv::mt::CodeId;
+use crate::error::ChainbillsError;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CreatePayableMessage, FetchIdMessage, InstantiateMessage,
+  TransactionInfoMessage, UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+#[test]
+fn withdrawal_rejections() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(1000, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  contract
+    .pay(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(100),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&coins(100, "native"))
+    .call(&user)
+    .unwrap();
+
+  let withdrawal = |token: &str, amount: u128| TransactionInfoMessage {
+    payable_id: payable_id.clone(),
+    token: token.to_string(),
+    amount: Uint128::new(amount),
+    payer_override: None,
+    memo: None,
+  };
+
+  // Only the host can withdraw.
+  let err = contract
+    .withdraw(withdrawal("native", 50))
+    .call(&user)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::NotYourPayable {});
+
+  // The host can't withdraw more than the balance.
+  let err = contract
+    .withdraw(withdrawal("native", 101))
+    .call(&host)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::InsufficientWithdrawAmount {});
+
+  // The host can't withdraw a token that was never paid.
+  let err = contract
+    .withdraw(withdrawal("other", 50))
+    .call(&host)
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::NoBalanceForWithdrawalToken {
+      token: "other".to_string()
+    }
+  );
+
+  // The whole balance can be withdrawn, after which nothing is left.
+  contract
+    .withdraw(withdrawal("native", 100))
+    .call(&host)
+    .unwrap();
+  let err = contract
+    .withdraw(withdrawal("native", 10))
+    .call(&host)
+    .unwrap_err();
+  assert_eq!(err, ChainbillsError::InsufficientWithdrawAmount {});
+}
@@ -0,0 +1,154 @@
+use crate::contract::sv::mt::{ChainbillsProxy, CodeId};
+use crate::error::ChainbillsError;
+use crate::interfaces::activities::sv::mt::ActivitiesProxy;
+use crate::interfaces::payables::sv::mt::PayablesProxy;
+use crate::interfaces::payments::sv::mt::PaymentsProxy;
+use crate::interfaces::token_details::sv::mt::TokenDetailsInterfaceProxy;
+use crate::interfaces::withdrawals::sv::mt::WithdrawalsProxy;
+use crate::messages::{
+  CountMessage, CreatePayableMessage, FetchIdMessage, InstantiateMessage,
+  PerChainPayablePaymentIdMessage, TransactionInfoMessage,
+  UpdateMaxWithdrawalFeesMessage,
+};
+use sylvia::cw_multi_test::IntoAddr;
+use sylvia::cw_std::{coins, Uint128};
+use sylvia::multitest::App;
+
+/// Asserts that a query failed with the given error rather than panicking.
+fn assert_query_err<T: std::fmt::Debug>(
+  result: Result<T, ChainbillsError>,
+  expected: ChainbillsError,
+) {
+  // Testing the error message because a Querrier error is returned instead
+  // of a ChainbillsError.
+  assert!(result
+    .unwrap_err()
+    .to_string()
+    .contains(&expected.to_string()));
+}
+
+#[test]
+fn zero_counts() {
+  let owner = "owner".into_addr();
+  let host = "host".into_addr();
+  let user = "user".into_addr();
+
+  let app =
+    App::new(sylvia::cw_multi_test::App::new(|router, _api, storage| {
+      router
+        .bank
+        .init_balance(storage, &user, coins(100, "native"))
+        .unwrap();
+    }));
+  let code_id = CodeId::store_code(&app);
+  let init_msg = InstantiateMessage {
+    chain_id: 1,
+    chainbills_fee_collector: "fee_collector".into_addr().to_string(),
+  };
+  let contract = code_id.instantiate(init_msg).call(&owner).unwrap();
+  contract
+    .update_max_withdrawal_fees(UpdateMaxWithdrawalFeesMessage {
+      token: "native".to_string(),
+      max_withdrawal_fees: Uint128::new(100),
+      is_native_token: true,
+    })
+    .call(&owner)
+    .unwrap();
+
+  // Create a Payable, pay into it, and withdraw from it so that every
+  // count is at least 1.
+  contract
+    .create_payable(CreatePayableMessage {
+      allowed_tokens_and_amounts: vec![],
+      expires_at: None,
+    })
+    .call(&host)
+    .unwrap();
+  let payable_id = contract
+    .user_payable_id(FetchIdMessage {
+      reference: host.to_string(),
+      count: 1,
+    })
+    .unwrap()
+    .id;
+  contract
+    .pay(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(50),
+      payer_override: None,
+      memo: None,
+    })
+    .with_funds(&coins(50, "native"))
+    .call(&user)
+    .unwrap();
+  contract
+    .withdraw(TransactionInfoMessage {
+      payable_id: payable_id.clone(),
+      token: "native".to_string(),
+      amount: Uint128::new(10),
+      payer_override: None,
+      memo: None,
+    })
+    .call(&host)
+    .unwrap();
+
+  let fetch = |reference: &str| FetchIdMessage {
+    reference: reference.to_string(),
+    count: 0,
+  };
+
+  // A count of zero is rejected cleanly by every 1-based count query.
+  assert_query_err(
+    contract.user_address(CountMessage { count: 0 }),
+    ChainbillsError::InvalidUserAddressCount { count: 0 },
+  );
+  assert_query_err(
+    contract.user_payable_id(fetch(host.as_str())),
+    ChainbillsError::InvalidUserPayableCount { count: 0 },
+  );
+  assert_query_err(
+    contract.user_payment_id(fetch(user.as_str())),
+    ChainbillsError::InvalidUserPaymentCount { count: 0 },
+  );
+  assert_query_err(
+    contract.payable_payment_id(fetch(&payable_id)),
+    ChainbillsError::InvalidPayablePaymentCount { count: 0 },
+  );
+  assert_query_err(
+    contract.per_chain_payable_payment_id(PerChainPayablePaymentIdMessage {
+      payable_id: payable_id.clone(),
+      chain_id: 1,
+      count: 0,
+    }),
+    ChainbillsError::InvalidPerChainPayablePaymentCount { count: 0 },
+  );
+  assert_query_err(
+    contract.user_withdrawal_id(fetch(host.as_str())),
+    ChainbillsError::InvalidUserWithdrawalCount { count: 0 },
+  );
+  assert_query_err(
+    contract.payable_withdrawal_id(fetch(&payable_id)),
+    ChainbillsError::InvalidPayableWithdrawalCount { count: 0 },
+  );
+  assert_query_err(
+    contract.chain_activity_id(CountMessage { count: 0 }),
+    ChainbillsError::InvalidChainActivityCount { count: 0 },
+  );
+  assert_query_err(
+    contract.user_activity_id(fetch(user.as_str())),
+    ChainbillsError::InvalidUserActivityCount { count: 0 },
+  );
+  assert_query_err(
+    contract.payable_activity_id(fetch(&payable_id)),
+    ChainbillsError::InvalidPayableActivityCount { count: 0 },
+  );
+
+  // While a count of 1 resolves.
+  assert!(contract
+    .user_payment_id(FetchIdMessage {
+      reference: user.to_string(),
+      count: 1,
+    })
+    .is_ok());
+}
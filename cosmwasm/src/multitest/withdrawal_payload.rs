@@ -0,0 +1,81 @@
+use crate::contract::Chainbills;
+use crate::error::ChainbillsError;
+use crate::payload::{
+  WithdrawalPayload, WormholeExecuteMsg, WITHDRAWAL_PAYLOAD_LENGTH,
+};
+use sylvia::cw_std::testing::MockStorage;
+use sylvia::cw_std::{coins, from_json, Addr, WasmMsg};
+
+fn payload() -> WithdrawalPayload {
+  WithdrawalPayload {
+    payable_id: [1; 32],
+    host: [2; 32],
+    token: [3; 32],
+    amount: 0x0102030405060708,
+    recipient_chain_id: 0x0a0b,
+    recipient: [4; 32],
+  }
+}
+
+#[test]
+fn withdrawal_payload_encoding() {
+  let encoded = payload().encode();
+  assert_eq!(encoded.len(), WITHDRAWAL_PAYLOAD_LENGTH);
+  assert_eq!(&encoded[0..32], &[1; 32]);
+  assert_eq!(&encoded[32..64], &[2; 32]);
+  assert_eq!(&encoded[64..96], &[3; 32]);
+  assert_eq!(&encoded[96..104], &[8, 7, 6, 5, 4, 3, 2, 1]);
+  assert_eq!(&encoded[104..106], &[0x0b, 0x0a]);
+  assert_eq!(&encoded[106..138], &[4; 32]);
+
+  // Round trip.
+  assert_eq!(WithdrawalPayload::decode(&encoded).unwrap(), payload());
+
+  // Payloads of the wrong length are rejected.
+  assert!(WithdrawalPayload::decode(&encoded[..137]).is_err());
+  assert!(WithdrawalPayload::decode(&[0; 139]).is_err());
+}
+
+#[test]
+fn cross_chain_withdrawal_message() {
+  let chainbills = Chainbills::new();
+  let mut storage = MockStorage::new();
+  let wormhole = Addr::unchecked("wormhole");
+
+  // The recipient chain must have a registered foreign contract.
+  let err = chainbills
+    .cross_chain_withdrawal_message(&storage, &wormhole, &payload(), 7, vec![])
+    .unwrap_err();
+  assert_eq!(
+    err,
+    ChainbillsError::UnregisteredForeignChain { chain_id: 0x0a0b }
+  );
+
+  chainbills
+    .foreign_contracts
+    .save(&mut storage, 0x0a0b, &[9; 32])
+    .unwrap();
+  let msg = chainbills
+    .cross_chain_withdrawal_message(
+      &storage,
+      &wormhole,
+      &payload(),
+      7,
+      coins(1, "native"),
+    )
+    .unwrap();
+  let WasmMsg::Execute {
+    contract_addr,
+    msg,
+    funds,
+  } = msg
+  else {
+    panic!("expected a WasmMsg::Execute");
+  };
+  assert_eq!(contract_addr, "wormhole");
+  assert_eq!(funds, coins(1, "native"));
+  let WormholeExecuteMsg::PostMessage { message, nonce } =
+    from_json(&msg).unwrap();
+  assert_eq!(nonce, 7);
+  assert_eq!(WithdrawalPayload::decode(&message).unwrap(), payload());
+}
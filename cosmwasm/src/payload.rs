@@ -0,0 +1,136 @@
+use crate::contract::Chainbills;
+use crate::error::ChainbillsError;
+use sylvia::cw_schema::cw_serde;
+use sylvia::cw_std::{
+  to_json_binary, Addr, Binary, Coin, StdError, StdResult, Storage, WasmMsg,
+};
+
+/// The length of an encoded CbTransaction.
+pub const CB_TRANSACTION_LENGTH: usize = 32 + 32 + 8 + 32;
+
+/// The length of an encoded WithdrawalPayload.
+pub const WITHDRAWAL_PAYLOAD_LENGTH: usize = 32 + 32 + 32 + 8 + 2 + 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Necessary info to pay out a withdrawal from a payable on this chain to
+/// a host's wallet on another chain. Encoded the same way (little-endian) as
+/// the payloads of the Solana program.
+pub struct WithdrawalPayload {
+  /// The Payable's ID.
+  pub payable_id: [u8; 32],
+  /// The Wormhole-normalized wallet address of the host that withdrew.
+  pub host: [u8; 32],
+  /// The Wormhole-normalized address of the involved token.
+  pub token: [u8; 32],
+  /// The Wormhole-normalized (with 8 decimals) amount due to the host.
+  pub amount: u64,
+  /// The Wormhole Chain ID of the chain to which the funds should go.
+  pub recipient_chain_id: u16,
+  /// The Wormhole-normalized wallet address to receive the funds.
+  pub recipient: [u8; 32],
+}
+
+impl WithdrawalPayload {
+  pub fn encode(&self) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(WITHDRAWAL_PAYLOAD_LENGTH);
+    buf.extend_from_slice(&self.payable_id);
+    buf.extend_from_slice(&self.host);
+    buf.extend_from_slice(&self.token);
+    buf.extend_from_slice(&self.amount.to_le_bytes());
+    buf.extend_from_slice(&self.recipient_chain_id.to_le_bytes());
+    buf.extend_from_slice(&self.recipient);
+    buf
+  }
+
+  pub fn decode(buf: &[u8]) -> StdResult<Self> {
+    if buf.len() != WITHDRAWAL_PAYLOAD_LENGTH {
+      return Err(StdError::generic_err("InvalidPayloadMessage"));
+    }
+
+    let bytes32 =
+      |start: usize| <[u8; 32]>::try_from(&buf[start..(start + 32)]).unwrap();
+    Ok(WithdrawalPayload {
+      payable_id: bytes32(0),
+      host: bytes32(32),
+      token: bytes32(64),
+      amount: u64::from_le_bytes(buf[96..104].try_into().unwrap()),
+      recipient_chain_id: u16::from_le_bytes(buf[104..106].try_into().unwrap()),
+      recipient: bytes32(106),
+    })
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A payable and the token and amount of a cross-chain transaction on it.
+/// Shares its wire format with the Solana program's CbTransaction, which
+/// unlike the other payloads, has its amount in big-endian.
+pub struct CbTransaction {
+  /// The Payable's ID.
+  pub payable_id: [u8; 32],
+  /// The Wormhole-normalized address of the involved token.
+  pub token: [u8; 32],
+  /// The Wormhole-normalized (with 8 decimals) amount of the token.
+  pub amount: u64,
+  /// The Wormhole-normalized wallet address of the payer on the source
+  /// chain.
+  pub payer: [u8; 32],
+}
+
+impl CbTransaction {
+  pub fn encode(&self) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(CB_TRANSACTION_LENGTH);
+    buf.extend_from_slice(&self.payable_id);
+    buf.extend_from_slice(&self.token);
+    buf.extend_from_slice(&self.amount.to_be_bytes());
+    buf.extend_from_slice(&self.payer);
+    buf
+  }
+
+  pub fn decode(buf: &[u8]) -> Result<Self, ChainbillsError> {
+    if buf.len() != CB_TRANSACTION_LENGTH {
+      return Err(ChainbillsError::InvalidPayload {});
+    }
+
+    Ok(CbTransaction {
+      payable_id: buf[0..32].try_into().unwrap(),
+      token: buf[32..64].try_into().unwrap(),
+      amount: u64::from_be_bytes(buf[64..72].try_into().unwrap()),
+      payer: buf[72..104].try_into().unwrap(),
+    })
+  }
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+/// The subset of the Wormhole core contract's execute messages used here.
+pub enum WormholeExecuteMsg {
+  PostMessage { message: Binary, nonce: u32 },
+}
+
+impl Chainbills {
+  /// Builds the message that posts a withdrawal payload through the
+  /// Wormhole core contract. The recipient chain must have a registered
+  /// foreign contract, as only it will act on the payload. fee should be
+  /// Wormhole's current message fee.
+  pub fn cross_chain_withdrawal_message(
+    &self,
+    storage: &dyn Storage,
+    wormhole: &Addr,
+    payload: &WithdrawalPayload,
+    nonce: u32,
+    fee: Vec<Coin>,
+  ) -> Result<WasmMsg, ChainbillsError> {
+    let chain_id = payload.recipient_chain_id;
+    if !self.foreign_contracts.has(storage, chain_id) {
+      return Err(ChainbillsError::UnregisteredForeignChain { chain_id });
+    }
+
+    Ok(WasmMsg::Execute {
+      contract_addr: wormhole.to_string(),
+      msg: to_json_binary(&WormholeExecuteMsg::PostMessage {
+        message: Binary::from(payload.encode()),
+        nonce,
+      })?,
+      funds: fee,
+    })
+  }
+}
@@ -1,5 +1,7 @@
+use crate::contract::{MIN_WITHDRAWAL_AMOUNT, MIN_WITHDRAWAL_PRECISION};
+use crate::error::ChainbillsError;
 use sylvia::cw_schema::cw_serde;
-use sylvia::cw_std::{Addr, Uint128};
+use sylvia::cw_std::{Addr, Decimal, StdError, StdResult, Uint128};
 
 #[cw_serde(crate = "sylvia::cw_schema")]
 /// Keeps track of all activities on this chain.
@@ -30,28 +32,52 @@ impl ChainStats {
     }
   }
 
-  pub fn next_user(&self) -> u64 {
-    self.users_count.checked_add(1).unwrap()
+  pub fn next_user(&self) -> Result<u64, ChainbillsError> {
+    self
+      .users_count
+      .checked_add(1)
+      .ok_or(ChainbillsError::Overflow {})
   }
 
-  pub fn next_payable(&self) -> u64 {
-    self.payables_count.checked_add(1).unwrap()
+  pub fn next_payable(&self) -> Result<u64, ChainbillsError> {
+    self
+      .payables_count
+      .checked_add(1)
+      .ok_or(ChainbillsError::Overflow {})
   }
 
-  pub fn next_user_payment(&self) -> u64 {
-    self.user_payments_count.checked_add(1).unwrap()
+  pub fn next_user_payment(&self) -> Result<u64, ChainbillsError> {
+    self
+      .user_payments_count
+      .checked_add(1)
+      .ok_or(ChainbillsError::Overflow {})
   }
 
-  pub fn next_payable_payment(&self) -> u64 {
-    self.payable_payments_count.checked_add(1).unwrap()
+  pub fn next_payable_payment(&self) -> Result<u64, ChainbillsError> {
+    self
+      .payable_payments_count
+      .checked_add(1)
+      .ok_or(ChainbillsError::Overflow {})
   }
 
-  pub fn next_withdrawal(&self) -> u64 {
-    self.withdrawals_count.checked_add(1).unwrap()
+  pub fn next_withdrawal(&self) -> Result<u64, ChainbillsError> {
+    self
+      .withdrawals_count
+      .checked_add(1)
+      .ok_or(ChainbillsError::Overflow {})
   }
 
-  pub fn next_activity(&self) -> u64 {
-    self.activities_count.checked_add(1).unwrap()
+  pub fn next_activity(&self) -> Result<u64, ChainbillsError> {
+    self.next_activities(1)
+  }
+
+  /// The activities count after recording count more activities. For
+  /// messages that record more than one activity at once.
+  pub fn next_activities(&self, count: u64) -> Result<u64, ChainbillsError> {
+    self
+      .activities_count
+      .checked_add(count)
+      .ok_or(ChainbillsError::Overflow {})
   }
 }
 
@@ -66,6 +92,20 @@ pub struct Config {
   pub chainbills_fee_collector: Addr,
   /// Percentage of withdrawal for fees with 2 decimal places. E.g. 2% is 200.
   pub withdrawal_fee_percentage: Uint128,
+  /// Percentage of collected withdrawal fees (with 2 decimal places) that is
+  /// routed to the secondary_fee_collector. The rest goes to
+  /// chainbills_fee_collector.
+  #[serde(default)]
+  pub fee_split_bps: Uint128,
+  /// A secondary pool (e.g. staking/rewards) that receives a split of fees.
+  pub secondary_fee_collector: Option<Addr>,
+  /// The maximum amount that can be withdrawn from a payable in a single
+  /// withdrawal. Zero means unlimited.
+  #[serde(default)]
+  pub max_withdrawal_amount: Uint128,
+  /// Trusted relayers that can pay on behalf of other wallets.
+  #[serde(default)]
+  pub relayers: Vec<Addr>,
 }
 
 #[cw_serde(crate = "sylvia::cw_schema")]
@@ -95,20 +135,32 @@ impl User {
     }
   }
 
-  pub fn next_payable(&self) -> u64 {
-    self.payables_count.checked_add(1).unwrap()
+  pub fn next_payable(&self) -> Result<u64, ChainbillsError> {
+    self
+      .payables_count
+      .checked_add(1)
+      .ok_or(ChainbillsError::Overflow {})
   }
 
-  pub fn next_payment(&self) -> u64 {
-    self.payments_count.checked_add(1).unwrap()
+  pub fn next_payment(&self) -> Result<u64, ChainbillsError> {
+    self
+      .payments_count
+      .checked_add(1)
+      .ok_or(ChainbillsError::Overflow {})
   }
 
-  pub fn next_withdrawal(&self) -> u64 {
-    self.withdrawals_count.checked_add(1).unwrap()
+  pub fn next_withdrawal(&self) -> Result<u64, ChainbillsError> {
+    self
+      .withdrawals_count
+      .checked_add(1)
+      .ok_or(ChainbillsError::Overflow {})
   }
 
-  pub fn next_activity(&self) -> u64 {
-    self.activities_count.checked_add(1).unwrap()
+  pub fn next_activity(&self) -> Result<u64, ChainbillsError> {
+    self
+      .activities_count
+      .checked_add(1)
+      .ok_or(ChainbillsError::Overflow {})
   }
 }
 
@@ -130,6 +182,13 @@ pub struct TokenDetails {
   pub total_withdrawn: Uint128,
   /// The total amount of fees collected from withdrawals in this token.
   pub total_withdrawal_fees_collected: Uint128,
+  /// The Cw20 decimals of this token. None for native tokens or if unknown.
+  #[serde(default)]
+  pub decimals: Option<u8>,
+  /// The smallest amount of this token that payables accepting free
+  /// payments can be paid. None means any non-zero amount.
+  #[serde(default)]
+  pub min_payment_amount: Option<Uint128>,
 }
 
 impl TokenDetails {
@@ -146,27 +205,84 @@ impl TokenDetails {
       total_payable_received: Uint128::zero(),
       total_withdrawn: Uint128::zero(),
       total_withdrawal_fees_collected: Uint128::zero(),
+      decimals: None,
+      min_payment_amount: None,
+    }
+  }
+
+  /// The smallest amount of this token that can be withdrawn: a millionth of
+  /// a whole token, but never less than MIN_WITHDRAWAL_AMOUNT. Tokens whose
+  /// decimals are unknown use MIN_WITHDRAWAL_AMOUNT.
+  pub fn min_withdrawal_amount(&self) -> Uint128 {
+    let floor = Uint128::new(MIN_WITHDRAWAL_AMOUNT);
+    match self.decimals {
+      Some(decimals) => Uint128::new(10)
+        .checked_pow(decimals.saturating_sub(MIN_WITHDRAWAL_PRECISION).into())
+        .unwrap_or(Uint128::MAX)
+        .max(floor),
+      None => floor,
     }
   }
 
-  pub fn add_user_paid(&mut self, amount: Uint128) {
-    self.total_user_paid = self.total_user_paid.checked_add(amount).unwrap()
+  pub fn add_user_paid(
+    &mut self,
+    amount: Uint128,
+  ) -> Result<(), ChainbillsError> {
+    self.total_user_paid = self
+      .total_user_paid
+      .checked_add(amount)
+      .map_err(|_| ChainbillsError::Overflow {})?;
+    Ok(())
   }
 
-  pub fn add_payable_received(&mut self, amount: Uint128) {
-    self.total_payable_received =
-      self.total_payable_received.checked_add(amount).unwrap()
+  pub fn add_payable_received(
+    &mut self,
+    amount: Uint128,
+  ) -> Result<(), ChainbillsError> {
+    self.total_payable_received = self
+      .total_payable_received
+      .checked_add(amount)
+      .map_err(|_| ChainbillsError::Overflow {})?;
+    Ok(())
   }
 
-  pub fn add_withdrawn(&mut self, amount: Uint128) {
-    self.total_withdrawn = self.total_withdrawn.checked_add(amount).unwrap()
+  /// Deducts a refunded amount from the totals of user payments and payable
+  /// payments in this token.
+  pub fn deduct_refunded(
+    &mut self,
+    amount: Uint128,
+  ) -> Result<(), ChainbillsError> {
+    self.total_user_paid = self
+      .total_user_paid
+      .checked_sub(amount)
+      .map_err(|_| ChainbillsError::Overflow {})?;
+    self.total_payable_received = self
+      .total_payable_received
+      .checked_sub(amount)
+      .map_err(|_| ChainbillsError::Overflow {})?;
+    Ok(())
+  }
+
+  pub fn add_withdrawn(
+    &mut self,
+    amount: Uint128,
+  ) -> Result<(), ChainbillsError> {
+    self.total_withdrawn = self
+      .total_withdrawn
+      .checked_add(amount)
+      .map_err(|_| ChainbillsError::Overflow {})?;
+    Ok(())
   }
 
-  pub fn add_withdrawal_fees_collected(&mut self, amount: Uint128) {
+  pub fn add_withdrawal_fees_collected(
+    &mut self,
+    amount: Uint128,
+  ) -> Result<(), ChainbillsError> {
     self.total_withdrawal_fees_collected = self
       .total_withdrawal_fees_collected
       .checked_add(amount)
-      .unwrap()
+      .map_err(|_| ChainbillsError::Overflow {})?;
+    Ok(())
   }
 }
 
@@ -185,6 +301,28 @@ pub struct TokenAndAmount {
   pub amount: Uint128,
 }
 
+impl TokenAndAmount {
+  /// Returns the amount in whole units of the token, given its decimals.
+  pub fn to_display_amount(&self, decimals: u8) -> StdResult<Decimal> {
+    Decimal::from_atomics(self.amount, decimals.into())
+      .map_err(|e| StdError::generic_err(e.to_string()))
+  }
+
+  /// Builds a TokenAndAmount from an amount in whole units of the token.
+  /// Any precision beyond the token's decimals is rounded down.
+  pub fn from_display_amount(
+    token: String,
+    display_amount: Decimal,
+    decimals: u8,
+  ) -> StdResult<Self> {
+    let amount = Uint128::new(10)
+      .checked_pow(decimals.into())?
+      .checked_mul_floor(display_amount)
+      .map_err(|e| StdError::generic_err(e.to_string()))?;
+    Ok(TokenAndAmount { token, amount })
+  }
+}
+
 #[cw_serde(crate = "sylvia::cw_schema")]
 /// A payable is like a public invoice through which anybody can pay to.
 pub struct Payable {
@@ -210,19 +348,115 @@ pub struct Payable {
   pub activities_count: u64,
   /// Whether this payable is currently accepting payments.
   pub is_closed: bool,
+  /// Whether this payable accepts payments bridged from other chains. Local
+  /// payments are always accepted.
+  #[serde(default = "default_accepts_cross_chain")]
+  pub accepts_cross_chain: bool,
+  /// The timestamp (in seconds) after which this payable stops accepting
+  /// payments. None means the payable never expires.
+  #[serde(default)]
+  pub expires_at: Option<u64>,
+  /// The cumulative amount of each token paid into this payable, less what
+  /// was refunded. Unlike balances, it isn't reduced by withdrawals.
+  #[serde(default)]
+  pub total_received_per_token: Vec<TokenAndAmount>,
+  /// The wallets allowed to pay into this payable. Empty means anybody can
+  /// pay.
+  #[serde(default)]
+  pub payer_allowlist: Vec<Addr>,
+}
+
+fn default_accepts_cross_chain() -> bool {
+  true
 }
 
 impl Payable {
-  pub fn next_payment(&self) -> u64 {
-    self.payments_count.checked_add(1).unwrap()
+  pub fn next_payment(&self) -> Result<u64, ChainbillsError> {
+    self
+      .payments_count
+      .checked_add(1)
+      .ok_or(ChainbillsError::Overflow {})
+  }
+
+  pub fn next_withdrawal(&self) -> Result<u64, ChainbillsError> {
+    self
+      .withdrawals_count
+      .checked_add(1)
+      .ok_or(ChainbillsError::Overflow {})
   }
 
-  pub fn next_withdrawal(&self) -> u64 {
-    self.withdrawals_count.checked_add(1).unwrap()
+  pub fn next_activity(&self) -> Result<u64, ChainbillsError> {
+    self
+      .activities_count
+      .checked_add(1)
+      .ok_or(ChainbillsError::Overflow {})
   }
 
-  pub fn next_activity(&self) -> u64 {
-    self.activities_count.checked_add(1).unwrap()
+  /// Whether the wallet can pay into this payable.
+  pub fn is_payer_allowed(&self, payer: &Addr) -> bool {
+    self.payer_allowlist.is_empty() || self.payer_allowlist.contains(payer)
+  }
+
+  /// Adds the amount to the payable's total received of the token.
+  pub fn add_received(
+    &mut self,
+    token: &str,
+    amount: Uint128,
+  ) -> Result<(), ChainbillsError> {
+    match self
+      .total_received_per_token
+      .iter_mut()
+      .find(|total| total.token == token)
+    {
+      Some(total) => {
+        total.amount = total
+          .amount
+          .checked_add(amount)
+          .map_err(|_| ChainbillsError::Overflow {})?;
+      }
+      None => self.total_received_per_token.push(TokenAndAmount {
+        token: token.to_string(),
+        amount,
+      }),
+    }
+    Ok(())
+  }
+
+  /// Deducts a refunded amount from the payable's total received of the
+  /// token.
+  pub fn deduct_received(
+    &mut self,
+    token: &str,
+    amount: Uint128,
+  ) -> Result<(), ChainbillsError> {
+    let total = self
+      .total_received_per_token
+      .iter_mut()
+      .find(|total| total.token == token)
+      .ok_or(ChainbillsError::Overflow {})?;
+    total.amount = total
+      .amount
+      .checked_sub(amount)
+      .map_err(|_| ChainbillsError::Overflow {})?;
+    Ok(())
+  }
+
+  /// Whether this payable accepts any supported token in any non-zero amount.
+  /// This is the case when its host didn't specify the tokens and amounts
+  /// that it accepts.
+  pub fn accepts_free_payments(&self) -> bool {
+    self.allowed_tokens_and_amounts.is_empty()
+  }
+
+  /// Whether this payable accepts a payment of the given token and amount.
+  /// The token's support and the amount being non-zero are checked
+  /// separately.
+  pub fn accepts_token_and_amount(&self, token: &str, amount: Uint128) -> bool {
+    self.accepts_free_payments()
+      || self
+        .allowed_tokens_and_amounts
+        .iter()
+        .any(|taa| taa.token == token && taa.amount == amount)
   }
 }
 
@@ -246,6 +480,13 @@ pub struct UserPayment {
   pub timestamp: u64,
   /// The amount and token that the payer paid
   pub details: TokenAndAmount,
+  /// Other tokens and amounts that the payer paid together with details,
+  /// in a multi-token payment. Empty for single-token payments.
+  #[serde(default)]
+  pub other_details: Vec<TokenAndAmount>,
+  /// The free-text reference that the payer attached to this payment.
+  #[serde(default)]
+  pub memo: Option<String>,
 }
 
 #[cw_serde(crate = "sylvia::cw_schema")]
@@ -273,6 +514,54 @@ pub struct PayablePayment {
   pub timestamp: u64,
   /// The amount and token that the payer paid
   pub details: TokenAndAmount,
+  /// Other tokens and amounts that the payer paid together with details,
+  /// in a multi-token payment. Empty for single-token payments.
+  #[serde(default)]
+  pub other_details: Vec<TokenAndAmount>,
+  /// The free-text reference that the payer attached to this payment.
+  #[serde(default)]
+  pub memo: Option<String>,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+/// A payable's balance of a token from the point at which it changed.
+pub struct BalanceCheckpoint {
+  /// When the balance changed.
+  pub timestamp: u64,
+  /// The balance after the change.
+  pub balance: Uint128,
+}
+
+#[cw_serde(crate = "sylvia::cw_schema")]
+/// Tracks how much of a user's payment has been refunded by the host.
+pub struct PaymentRefund {
+  /// The cumulative amount of each of the payment's tokens that has been
+  /// refunded. Tokens that haven't been refunded are left out.
+  pub refunded: Vec<TokenAndAmount>,
+  /// Whether every token of the payment has been refunded in full.
+  pub is_fully_refunded: bool,
+}
+
+impl PaymentRefund {
+  /// The cumulative amount of the token that has been refunded. Zero if none.
+  pub fn refunded_of(&self, token: &str) -> Uint128 {
+    self
+      .refunded
+      .iter()
+      .find(|refunded| refunded.token == token)
+      .map_or(Uint128::zero(), |refunded| refunded.amount)
+  }
+
+  /// Sets the cumulative amount of the token that has been refunded.
+  pub fn set_refunded(&mut self, token: &str, amount: Uint128) {
+    match self.refunded.iter_mut().find(|refunded| refunded.token == token) {
+      Some(refunded) => refunded.amount = amount,
+      None => self.refunded.push(TokenAndAmount {
+        token: token.to_string(),
+        amount,
+      }),
+    }
+  }
 }
 
 #[cw_serde(crate = "sylvia::cw_schema")]
@@ -316,6 +605,8 @@ pub enum ActivityType {
   ReopenedPayable,
   /// The payable's allowed tokens and amounts were updated.
   UpdatedPayableAllowedTokensAndAmounts,
+  /// A payment to the payable was (partially or fully) refunded.
+  RefundedPayment,
 }
 
 #[cw_serde(crate = "sylvia::cw_schema")]
@@ -338,3 +629,131 @@ pub struct ActivityRecord {
   /// The type of activity.
   pub activity_type: ActivityType,
 }
+
+#[cfg(test)]
+impl Payable {
+  /// An open payable with no tokens, balances or counts, for tests to
+  /// override the fields they need.
+  pub fn test_default() -> Self {
+    Payable {
+      chain_count: 1,
+      host: Addr::unchecked("host"),
+      host_count: 1,
+      allowed_tokens_and_amounts: vec![],
+      balances: vec![],
+      created_at: 0,
+      payments_count: 0,
+      withdrawals_count: 0,
+      activities_count: 0,
+      is_closed: false,
+      accepts_cross_chain: true,
+      expires_at: None,
+      total_received_per_token: vec![],
+      payer_allowlist: vec![],
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn chain_stats(count: u64) -> ChainStats {
+    ChainStats {
+      users_count: count,
+      payables_count: count,
+      user_payments_count: count,
+      payable_payments_count: count,
+      withdrawals_count: count,
+      activities_count: count,
+    }
+  }
+
+  fn user(count: u64) -> User {
+    User {
+      payables_count: count,
+      payments_count: count,
+      withdrawals_count: count,
+      activities_count: count,
+      ..User::initialize(1)
+    }
+  }
+
+  fn payable(count: u64) -> Payable {
+    Payable {
+      payments_count: count,
+      withdrawals_count: count,
+      activities_count: count,
+      ..Payable::test_default()
+    }
+  }
+
+  #[test]
+  fn returns_the_incremented_counts() {
+    let stats = chain_stats(u64::MAX - 2);
+    assert_eq!(stats.next_user().unwrap(), u64::MAX - 1);
+    assert_eq!(stats.next_payable().unwrap(), u64::MAX - 1);
+    assert_eq!(stats.next_user_payment().unwrap(), u64::MAX - 1);
+    assert_eq!(stats.next_payable_payment().unwrap(), u64::MAX - 1);
+    assert_eq!(stats.next_withdrawal().unwrap(), u64::MAX - 1);
+    assert_eq!(stats.next_activity().unwrap(), u64::MAX - 1);
+    assert_eq!(stats.next_activities(2).unwrap(), u64::MAX);
+
+    let user = user(u64::MAX - 1);
+    assert_eq!(user.next_payable().unwrap(), u64::MAX);
+    assert_eq!(user.next_payment().unwrap(), u64::MAX);
+    assert_eq!(user.next_withdrawal().unwrap(), u64::MAX);
+    assert_eq!(user.next_activity().unwrap(), u64::MAX);
+
+    let payable = payable(u64::MAX - 1);
+    assert_eq!(payable.next_payment().unwrap(), u64::MAX);
+    assert_eq!(payable.next_withdrawal().unwrap(), u64::MAX);
+    assert_eq!(payable.next_activity().unwrap(), u64::MAX);
+  }
+
+  #[test]
+  fn derives_min_withdrawal_amount_from_decimals() {
+    let min = |decimals| {
+      TokenDetails {
+        decimals,
+        ..TokenDetails::initialize(true, false, Uint128::zero())
+      }
+      .min_withdrawal_amount()
+      .u128()
+    };
+    assert_eq!(min(None), MIN_WITHDRAWAL_AMOUNT);
+    assert_eq!(min(Some(0)), MIN_WITHDRAWAL_AMOUNT);
+    assert_eq!(min(Some(6)), MIN_WITHDRAWAL_AMOUNT);
+    assert_eq!(min(Some(8)), 100);
+    assert_eq!(min(Some(18)), 1_000_000_000_000);
+    assert_eq!(min(Some(u8::MAX)), u128::MAX);
+  }
+
+  #[test]
+  fn fails_past_the_overflow_boundary() {
+    let overflow = ChainbillsError::Overflow {};
+
+    let stats = chain_stats(u64::MAX);
+    assert_eq!(stats.next_user().unwrap_err(), overflow);
+    assert_eq!(stats.next_payable().unwrap_err(), overflow);
+    assert_eq!(stats.next_user_payment().unwrap_err(), overflow);
+    assert_eq!(stats.next_payable_payment().unwrap_err(), overflow);
+    assert_eq!(stats.next_withdrawal().unwrap_err(), overflow);
+    assert_eq!(stats.next_activity().unwrap_err(), overflow);
+    assert_eq!(
+      chain_stats(u64::MAX - 1).next_activities(2).unwrap_err(),
+      overflow
+    );
+
+    let user = user(u64::MAX);
+    assert_eq!(user.next_payable().unwrap_err(), overflow);
+    assert_eq!(user.next_payment().unwrap_err(), overflow);
+    assert_eq!(user.next_withdrawal().unwrap_err(), overflow);
+    assert_eq!(user.next_activity().unwrap_err(), overflow);
+
+    let payable = payable(u64::MAX);
+    assert_eq!(payable.next_payment().unwrap_err(), overflow);
+    assert_eq!(payable.next_withdrawal().unwrap_err(), overflow);
+    assert_eq!(payable.next_activity().unwrap_err(), overflow);
+  }
+}
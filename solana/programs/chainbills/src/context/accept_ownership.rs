@@ -0,0 +1,14 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+/// Context used to accept a proposed ownership of this program.
+pub struct AcceptOwnership<'info> {
+  #[account(mut, seeds = [Config::SEED_PREFIX], bump)]
+  /// Config Account that holds the owner and the pending owner.
+  pub config: AccountLoader<'info, Config>,
+
+  /// Signer for this instruction. Should be the pending owner. The check is
+  /// carried out inside the instruction.
+  pub new_owner: Signer<'info>,
+}
@@ -0,0 +1,12 @@
+use crate::{error::ChainbillsError, state::Payable};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+/// Context used to close a payable's account and return its rent to its host.
+pub struct ClosePayableAccount<'info> {
+  #[account(mut, close = signer, constraint = payable.host == *signer.key @ ChainbillsError::NotYourPayable)]
+  pub payable: Box<Account<'info, Payable>>,
+
+  #[account(mut)]
+  pub signer: Signer<'info>,
+}
@@ -10,7 +10,7 @@ pub struct CreatePayable<'info> {
         seeds = [
             signer.key().as_ref(),
             Payable::SEED_PREFIX,
-            &host.next_payable().to_le_bytes()[..],
+            &host.next_payable()?.to_le_bytes()[..],
         ],
         bump,
         payer = signer,
@@ -36,7 +36,7 @@ pub struct CreatePayable<'info> {
 
   #[account(
     init,
-    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity().to_le_bytes()[..]],
+    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = ActivityRecord::SPACE
@@ -46,7 +46,7 @@ pub struct CreatePayable<'info> {
 
   #[account(
     init,
-    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &host.next_activity().to_le_bytes()[..]],
+    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &host.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = UserActivityInfo::SPACE
@@ -56,7 +56,7 @@ pub struct CreatePayable<'info> {
 
   #[account(
     init,
-    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity().to_le_bytes()[..]],
+    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = PayableActivityInfo::SPACE
@@ -0,0 +1,15 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(chain_count: u64)]
+/// Context used to read an ActivityRecord by its chain count.
+pub struct GetActivity<'info> {
+  #[account(seeds = [ActivityRecord::SEED_PREFIX, &chain_count.to_le_bytes()[..]], bump)]
+  /// The ActivityRecord with the given chain count.
+  pub activity: Box<Account<'info, ActivityRecord>>,
+
+  #[account(seeds = [ChainStats::SEED_PREFIX], bump)]
+  /// Keeps track of entities on this chain. Used to validate the count.
+  pub chain_stats: Box<Account<'info, ChainStats>>,
+}
@@ -0,0 +1,11 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+/// Context used to read the foreign contract registered for a chain.
+pub struct GetForeignContract<'info> {
+  #[account(seeds = [ForeignContract::SEED_PREFIX, &chain.to_le_bytes()[..]], bump)]
+  /// The ForeignContract registered for the given Wormhole Chain ID.
+  pub foreign_contract: Account<'info, ForeignContract>,
+}
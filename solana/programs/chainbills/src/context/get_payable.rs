@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+/// Context used to resolve a payable from its ID (its address).
+pub struct GetPayable<'info> {
+  /// CHECK: Not loaded as an Account so that unknown payable IDs fail with
+  /// InvalidPayableId. Its owner and discriminator are checked in the handler.
+  pub payable: UncheckedAccount<'info>,
+}
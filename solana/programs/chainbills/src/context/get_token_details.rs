@@ -0,0 +1,12 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(token: Pubkey)]
+/// Context used to read the details of a token.
+pub struct GetTokenDetails<'info> {
+  #[account(seeds = [TokenDetails::SEED_PREFIX, &token.key().as_ref()], bump)]
+  /// The TokenDetails of the given token. Fails to load for tokens that were
+  /// never registered.
+  pub token_details: Box<Account<'info, TokenDetails>>,
+}
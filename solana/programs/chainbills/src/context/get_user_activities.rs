@@ -0,0 +1,16 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+/// Context used to read the latest activities of a user. The
+/// UserActivityInfo and ActivityRecord of each activity are passed in pairs
+/// as remaining_accounts, newest first.
+pub struct GetUserActivities<'info> {
+  /// CHECK: Only used as the seed of the user and their activities.
+  pub wallet: UncheckedAccount<'info>,
+
+  #[account(seeds = [wallet.key().as_ref()], bump)]
+  /// The user whose activities are read. Its activities_count is where the
+  /// feed starts.
+  pub user: Box<Account<'info, User>>,
+}
@@ -0,0 +1,57 @@
+use crate::{payload::CreatePayablePayload, state::*};
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+/// Context used to mirror a payable created on another chain from the VAA
+/// of its creation.
+pub struct InitializePayableReceived<'info> {
+  #[account(
+        init,
+        payer = signer,
+        seeds = [ForeignPayable::SEED_PREFIX, &posted.data().payable_id],
+        bump,
+        space = ForeignPayable::SPACE
+    )]
+  /// The mirror of the foreign payable.
+  pub foreign_payable: Box<Account<'info, ForeignPayable>>,
+
+  #[account(
+        init,
+        payer = signer,
+        seeds = [WormholeReceived::SEED_PREFIX, &vaa_hash],
+        bump,
+        space = WormholeReceived::SPACE
+    )]
+  /// Records that the VAA was consumed. Initializing it fails if the VAA is
+  /// replayed.
+  pub wormhole_received: Box<Account<'info, WormholeReceived>>,
+
+  #[account(
+        seeds = [wormhole::SEED_PREFIX_POSTED_VAA, &vaa_hash],
+        bump,
+        seeds::program = wormhole_program.key()
+    )]
+  /// The VAA verified and posted by the Wormhole program.
+  pub posted: Box<Account<'info, wormhole::PostedVaa<CreatePayablePayload>>>,
+
+  /// The ForeignContract registered for the VAA's emitter chain. Checked
+  /// against the VAA's emitter in the handler.
+  pub foreign_contract: Box<Account<'info, ForeignContract>>,
+
+  #[account(seeds = [Config::SEED_PREFIX], bump)]
+  /// Config Account that stores important constant addresses that are used
+  /// across program instructions.
+  pub config: AccountLoader<'info, Config>,
+
+  #[account(mut)]
+  /// Pays for the new accounts. Can be anyone, such as a relayer.
+  pub signer: Signer<'info>,
+
+  /// Wormhole program.
+  pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+  /// System program.
+  pub system_program: Program<'info, System>,
+}
@@ -17,17 +17,17 @@ pub struct InitializeUser<'info> {
 
   #[account(
     init,
-    seeds = [UserAddress::SEED_PREFIX, &chain_stats.next_user().to_le_bytes()[..]],
+    seeds = [UserAddress::SEED_PREFIX, &chain_stats.next_user()?.to_le_bytes()[..]],
     bump,
     payer = signer,
-    space = User::SPACE
+    space = UserAddress::SPACE
   )]
   /// Keeps the wallet address of the user.
   pub user_address: Box<Account<'info, UserAddress>>,
 
   #[account(
     init,
-    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity().to_le_bytes()[..]],
+    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = ActivityRecord::SPACE
@@ -37,7 +37,7 @@ pub struct InitializeUser<'info> {
 
   #[account(
     init,
-    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &user.next_activity().to_le_bytes()[..]],
+    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &user.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = UserActivityInfo::SPACE
@@ -0,0 +1,21 @@
+use crate::{error::ChainbillsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+/// Context used to grow the Config account to the current Config::SPACE
+/// after an upgrade added fields to it.
+pub struct MigrateConfig<'info> {
+  #[account(mut, seeds = [Config::SEED_PREFIX], bump)]
+  /// CHECK: Config Account to grow. Not loaded with an AccountLoader as that
+  /// fails while the account is smaller than Config::SPACE. Its seeds, and
+  /// the discriminator checked in Config::read_owner, ensure it is the
+  /// Config.
+  pub config: UncheckedAccount<'info>,
+
+  #[account(mut, address = Config::read_owner(&config)? @ ChainbillsError::OwnerUnauthorized)]
+  /// Signer for this instruction. Should be the owner of this program. Pays
+  /// the rent of the added space.
+  pub owner: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+/// Context used to grow a Payable account to the current layout after an
+/// upgrade added fields to Payable.
+pub struct MigratePayable<'info> {
+  #[account(mut, owner = crate::ID)]
+  /// CHECK: Payable Account to migrate. Not loaded as an Account as that
+  /// fails while the account lacks the added fields. Its discriminator is
+  /// checked in Payable::read_compat.
+  pub payable: UncheckedAccount<'info>,
+
+  #[account(mut)]
+  /// Signer for this instruction. Pays the rent of the added space.
+  pub signer: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+}
@@ -1,25 +1,67 @@
 pub mod initialize;
+pub mod accept_ownership;
+pub mod close_payable_account;
 pub mod create_payable;
+pub mod get_activity;
+pub mod get_foreign_contract;
+pub mod get_payable;
+pub mod get_token_details;
+pub mod get_user_activities;
+pub mod initialize_payable_received;
 pub mod initialize_user;
+pub mod migrate_config;
+pub mod migrate_payable;
 pub mod owner_withdraw;
 pub mod pay;
 pub mod pay_native;
+pub mod pay_received;
+pub mod propose_new_owner;
+pub mod refund_payment;
 pub mod register_foreign_contract;
+pub mod set_fee_recipient;
+pub mod set_max_withdrawal_amount;
+pub mod set_min_vaa_finality;
+pub mod set_paused;
+pub mod set_relayer;
+pub mod set_token_supported;
+pub mod sweep_dust;
 pub mod update_max_withdrawal_fees;
 pub mod update_max_withdrawal_fees_native;
 pub mod update_payable;
+pub mod vaa_consumed;
 pub mod withdraw;
 pub mod withdraw_native;
 
 pub use initialize::*;
+pub use accept_ownership::*;
+pub use close_payable_account::*;
 pub use create_payable::*;
+pub use get_activity::*;
+pub use get_foreign_contract::*;
+pub use get_payable::*;
+pub use get_token_details::*;
+pub use get_user_activities::*;
+pub use initialize_payable_received::*;
 pub use initialize_user::*;
+pub use migrate_config::*;
+pub use migrate_payable::*;
 pub use owner_withdraw::*;
 pub use pay::*;
 pub use pay_native::*;
+pub use pay_received::*;
+pub use propose_new_owner::*;
+pub use refund_payment::*;
 pub use register_foreign_contract::*;
+pub use set_fee_recipient::*;
+pub use set_max_withdrawal_amount::*;
+pub use set_min_vaa_finality::*;
+pub use set_paused::*;
+pub use set_relayer::*;
+pub use set_token_supported::*;
+pub use sweep_dust::*;
 pub use update_max_withdrawal_fees::*;
 pub use update_max_withdrawal_fees_native::*;
 pub use update_payable::*;
+pub use vaa_consumed::*;
 pub use withdraw::*;
 pub use withdraw_native::*;
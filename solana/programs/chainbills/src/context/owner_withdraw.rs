@@ -1,11 +1,11 @@
 use crate::{error::ChainbillsError, state::*};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 
 #[derive(Accounts)]
 pub struct OwnerWithdraw<'info> {
-  pub mint: Box<Account<'info, Mint>>,
+  pub mint: Box<InterfaceAccount<'info, Mint>>,
 
   #[account(mut, seeds = [ChainStats::SEED_PREFIX], bump)]
   pub chain_stats: Box<Account<'info, ChainStats>>,
@@ -14,15 +14,17 @@ pub struct OwnerWithdraw<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = chain_stats,
+        associated_token::token_program = token_program,
     )]
-  pub chain_token_account: Box<Account<'info, TokenAccount>>,
+  pub chain_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
   #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = owner,
+        associated_token::token_program = token_program,
     )]
-  pub owner_token_account: Box<Account<'info, TokenAccount>>,
+  pub owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
   #[account(seeds = [Config::SEED_PREFIX], bump)]
   pub config: AccountLoader<'info, Config>,
@@ -30,5 +32,5 @@ pub struct OwnerWithdraw<'info> {
   #[account(mut, address = config.load()?.owner @ ChainbillsError::OwnerUnauthorized)]
   pub owner: Signer<'info>,
 
-  pub token_program: Program<'info, Token>,
+  pub token_program: Interface<'info, TokenInterface>,
 }
@@ -1,6 +1,6 @@
 use crate::state::*;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct Pay<'info> {
@@ -9,7 +9,7 @@ pub struct Pay<'info> {
         seeds = [
             signer.key().as_ref(),
             UserPayment::SEED_PREFIX,
-            &payer.next_payment().to_le_bytes()[..]
+            &payer.next_payment()?.to_le_bytes()[..]
         ],
         bump,
         payer = signer,
@@ -22,7 +22,7 @@ pub struct Pay<'info> {
         seeds = [
             payable.key().as_ref(),
             PayablePayment::SEED_PREFIX,
-            &payable.next_payment().to_le_bytes()[..]
+            &payable.next_payment()?.to_le_bytes()[..]
         ],
         bump,
         payer = signer,
@@ -35,7 +35,7 @@ pub struct Pay<'info> {
         seeds = [
             payable.key().as_ref(),
             &config.load()?.chain_id.to_le_bytes()[..],
-            &payable_per_chain_payments_counter.next_payment().to_le_bytes()[..]
+            &payable_per_chain_payments_counter.next_payment()?.to_le_bytes()[..]
         ],
         bump,
         payer = signer,
@@ -57,7 +57,7 @@ pub struct Pay<'info> {
 
   #[account(
     init,
-    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity().to_le_bytes()[..]],
+    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = ActivityRecord::SPACE
@@ -67,7 +67,7 @@ pub struct Pay<'info> {
 
   #[account(
     init,
-    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &payer.next_activity().to_le_bytes()[..]],
+    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &payer.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = UserActivityInfo::SPACE
@@ -77,8 +77,8 @@ pub struct Pay<'info> {
 
   #[account(
     init,
-    // added 1 to chain_stats.next_activity() because the previous addition in this same transaction is for the user activity
-    seeds = [ActivityRecord::SEED_PREFIX, &(chain_stats.next_activity().checked_add(1).unwrap()).to_le_bytes()[..]], 
+    // the second next activity because the first one in this same transaction is for the user activity
+    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activities(2)?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = ActivityRecord::SPACE
@@ -88,7 +88,7 @@ pub struct Pay<'info> {
 
   #[account(
     init,
-    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity().to_le_bytes()[..]],
+    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = PayableActivityInfo::SPACE
@@ -108,7 +108,7 @@ pub struct Pay<'info> {
   #[account(seeds = [Config::SEED_PREFIX], bump)]
   pub config: AccountLoader<'info, Config>,
 
-  pub mint: Box<Account<'info, Mint>>,
+  pub mint: Box<InterfaceAccount<'info, Mint>>,
 
   #[account(seeds = [TokenDetails::SEED_PREFIX, mint.key().as_ref()], bump)]
   pub token_details: Box<Account<'info, TokenDetails>>,
@@ -117,20 +117,22 @@ pub struct Pay<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = signer,
+        associated_token::token_program = token_program,
     )]
-  pub payer_token_account: Box<Account<'info, TokenAccount>>,
+  pub payer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
   #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = chain_stats,
+        associated_token::token_program = token_program,
     )]
-  pub chain_token_account: Box<Account<'info, TokenAccount>>,
+  pub chain_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
   #[account(mut)]
   pub signer: Signer<'info>,
 
-  pub token_program: Program<'info, Token>,
+  pub token_program: Interface<'info, TokenInterface>,
 
   pub system_program: Program<'info, System>,
 }
@@ -8,7 +8,7 @@ pub struct PayNative<'info> {
         seeds = [
             signer.key().as_ref(),
             UserPayment::SEED_PREFIX,
-            &payer.next_payment().to_le_bytes()[..]
+            &payer.next_payment()?.to_le_bytes()[..]
         ],
         bump,
         payer = signer,
@@ -21,7 +21,7 @@ pub struct PayNative<'info> {
         seeds = [
             payable.key().as_ref(),
             PayablePayment::SEED_PREFIX,
-            &payable.next_payment().to_le_bytes()[..]
+            &payable.next_payment()?.to_le_bytes()[..]
         ],
         bump,
         payer = signer,
@@ -34,7 +34,7 @@ pub struct PayNative<'info> {
         seeds = [
             payable.key().as_ref(),
             &config.load()?.chain_id.to_le_bytes()[..],
-            &payable_per_chain_payments_counter.next_payment().to_le_bytes()[..]
+            &payable_per_chain_payments_counter.next_payment()?.to_le_bytes()[..]
         ],
         bump,
         payer = signer,
@@ -56,7 +56,7 @@ pub struct PayNative<'info> {
 
   #[account(
     init,
-    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity().to_le_bytes()[..]],
+    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = ActivityRecord::SPACE
@@ -66,7 +66,7 @@ pub struct PayNative<'info> {
 
   #[account(
     init,
-    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &payer.next_activity().to_le_bytes()[..]],
+    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &payer.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = UserActivityInfo::SPACE
@@ -76,8 +76,8 @@ pub struct PayNative<'info> {
 
   #[account(
     init,
-    // added 1 to chain_stats.next_activity() because the previous addition in this same transaction is for the user activity
-    seeds = [ActivityRecord::SEED_PREFIX, &(chain_stats.next_activity().checked_add(1).unwrap()).to_le_bytes()[..]], 
+    // the second next activity because the first one in this same transaction is for the user activity
+    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activities(2)?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = ActivityRecord::SPACE
@@ -87,7 +87,7 @@ pub struct PayNative<'info> {
 
   #[account(
     init,
-    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity().to_le_bytes()[..]],
+    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = PayableActivityInfo::SPACE
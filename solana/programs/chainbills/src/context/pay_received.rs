@@ -0,0 +1,181 @@
+use crate::{payload::CbTransfer, state::*, token_bridge};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use wormhole_anchor_sdk::wormhole;
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+/// Context used to record a payment made to a payable on this chain from
+/// another chain, redeeming its Token Bridge transfer into the
+/// chain_token_account.
+pub struct PayReceived<'info> {
+  #[account(
+        init_if_needed,
+        seeds = [
+            payable.key().as_ref(),
+            &posted.emitter_chain().to_le_bytes()[..],
+        ],
+        bump,
+        payer = signer,
+        space = PayablePerChainPaymentsCounter::SPACE
+    )]
+  /// Counts the payable's payments from the payment's source chain.
+  /// Created with the first payment from that chain.
+  pub payable_per_chain_payments_counter:
+    Box<Account<'info, PayablePerChainPaymentsCounter>>,
+
+  #[account(
+        init,
+        seeds = [
+            payable.key().as_ref(),
+            PayablePayment::SEED_PREFIX,
+            &payable.next_payment()?.to_le_bytes()[..]
+        ],
+        bump,
+        payer = signer,
+        space = PayablePayment::SPACE
+    )]
+  pub payable_payment: Box<Account<'info, PayablePayment>>,
+
+  #[account(
+        init,
+        seeds = [
+            payable.key().as_ref(),
+            &posted.emitter_chain().to_le_bytes()[..],
+            &payable_per_chain_payments_counter.next_payment()?.to_le_bytes()[..]
+        ],
+        bump,
+        payer = signer,
+        space = PayablePerChainPaymentInfo::SPACE
+    )]
+  pub payable_per_chain_payment_info:
+    Box<Account<'info, PayablePerChainPaymentInfo>>,
+
+  #[account(
+    init,
+    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity()?.to_le_bytes()[..]],
+    bump,
+    payer = signer,
+    space = ActivityRecord::SPACE
+  )]
+  /// Houses Details of this activity as one of PayableReceived.
+  pub payable_activity: Box<Account<'info, ActivityRecord>>,
+
+  #[account(
+    init,
+    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity()?.to_le_bytes()[..]],
+    bump,
+    payer = signer,
+    space = PayableActivityInfo::SPACE
+  )]
+  /// Houses Chain Count of activities for this activity.
+  pub payable_activity_info: Box<Account<'info, PayableActivityInfo>>,
+
+  #[account(
+        init,
+        payer = signer,
+        seeds = [WormholeReceived::SEED_PREFIX, &vaa_hash],
+        bump,
+        space = WormholeReceived::SPACE
+    )]
+  /// Records that the VAA was consumed. Initializing it fails if the VAA is
+  /// replayed.
+  pub wormhole_received: Box<Account<'info, WormholeReceived>>,
+
+  #[account(
+        seeds = [wormhole::SEED_PREFIX_POSTED_VAA, &vaa_hash],
+        bump,
+        seeds::program = wormhole_program.key()
+    )]
+  /// The Token Bridge transfer VAA verified and posted by the Wormhole
+  /// program. Its payload carries the CbTransaction of the payment.
+  pub posted: Box<Account<'info, wormhole::PostedVaa<CbTransfer>>>,
+
+  /// The ForeignContract registered for the payment's source chain. Checked
+  /// against the transfer's sender in the handler.
+  pub foreign_contract: Box<Account<'info, ForeignContract>>,
+
+  #[account(mut, realloc = payable.space_update_balance(mint.key()), realloc::payer = signer, realloc::zero = false)]
+  pub payable: Box<Account<'info, Payable>>,
+
+  #[account(mut, seeds = [ChainStats::SEED_PREFIX], bump)]
+  /// Redeems the transfer, as the owner of chain_token_account.
+  pub chain_stats: Box<Account<'info, ChainStats>>,
+
+  #[account(seeds = [Config::SEED_PREFIX], bump)]
+  pub config: AccountLoader<'info, Config>,
+
+  pub mint: Box<Account<'info, Mint>>,
+
+  #[account(mut, seeds = [TokenDetails::SEED_PREFIX, mint.key().as_ref()], bump)]
+  pub token_details: Box<Account<'info, TokenDetails>>,
+
+  #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = chain_stats,
+    )]
+  /// Receives the bridged tokens.
+  pub chain_token_account: Box<Account<'info, TokenAccount>>,
+
+  #[account(
+        seeds = [token_bridge::SEED_PREFIX_CONFIG],
+        bump,
+        seeds::program = token_bridge_program.key()
+    )]
+  /// CHECK: Token Bridge's config. Checked by the Token Bridge program.
+  pub token_bridge_config: UncheckedAccount<'info>,
+
+  #[account(mut)]
+  /// CHECK: Token Bridge's claim account of the VAA. Created and checked by
+  /// the Token Bridge program. Also prevents redeeming the transfer twice.
+  pub token_bridge_claim: UncheckedAccount<'info>,
+
+  #[account(
+        seeds = [
+            &posted.emitter_chain().to_be_bytes(),
+            posted.emitter_address()
+        ],
+        bump,
+        seeds::program = token_bridge_program.key()
+    )]
+  /// CHECK: The Token Bridge of the payment's source chain, as registered
+  /// with this chain's Token Bridge. Checked by the Token Bridge program.
+  pub token_bridge_foreign_endpoint: UncheckedAccount<'info>,
+
+  #[account(
+        mut,
+        seeds = [mint.key().as_ref()],
+        bump,
+        seeds::program = token_bridge_program.key()
+    )]
+  /// Token Bridge's custody of the mint, from which the tokens are released.
+  pub token_bridge_custody: Box<Account<'info, TokenAccount>>,
+
+  #[account(
+        seeds = [token_bridge::SEED_PREFIX_CUSTODY_SIGNER],
+        bump,
+        seeds::program = token_bridge_program.key()
+    )]
+  /// CHECK: Token Bridge's custody signer. Only used as a seeded address.
+  pub token_bridge_custody_signer: UncheckedAccount<'info>,
+
+  #[account(mut)]
+  /// Pays for the new accounts. Can be anyone, such as a relayer.
+  pub signer: Signer<'info>,
+
+  /// Wormhole program.
+  pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+  /// Token Bridge program.
+  pub token_bridge_program: Program<'info, token_bridge::TokenBridge>,
+
+  /// Token Program.
+  pub token_program: Program<'info, Token>,
+
+  /// System Program.
+  pub system_program: Program<'info, System>,
+
+  /// Rent sysvar. Needed by the Token Bridge program.
+  pub rent: Sysvar<'info, Rent>,
+}
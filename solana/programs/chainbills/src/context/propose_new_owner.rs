@@ -0,0 +1,14 @@
+use crate::{error::ChainbillsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+/// Context used to propose a new owner of this program.
+pub struct ProposeNewOwner<'info> {
+  #[account(mut, seeds = [Config::SEED_PREFIX], bump)]
+  /// Config Account that holds the owner and the pending owner.
+  pub config: AccountLoader<'info, Config>,
+
+  #[account(address = config.load()?.owner @ ChainbillsError::OwnerUnauthorized)]
+  /// Signer for this instruction. Should be the current owner.
+  pub owner: Signer<'info>,
+}
@@ -0,0 +1,165 @@
+use crate::{error::ChainbillsError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct RefundPayment<'info> {
+  #[account(
+    init_if_needed,
+    seeds = [user_payment.key().as_ref(), PaymentRefund::SEED_PREFIX],
+    bump,
+    payer = signer,
+    space = PaymentRefund::SPACE
+  )]
+  /// Marks the payment as refunded.
+  pub payment_refund: Box<Account<'info, PaymentRefund>>,
+
+  #[account(
+    init,
+    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity()?.to_le_bytes()[..]],
+    bump,
+    payer = signer,
+    space = ActivityRecord::SPACE
+  )]
+  /// Houses Details of this activity as RefundedPayment.
+  pub activity: Box<Account<'info, ActivityRecord>>,
+
+  #[account(
+    init,
+    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &host.next_activity()?.to_le_bytes()[..]],
+    bump,
+    payer = signer,
+    space = UserActivityInfo::SPACE
+  )]
+  /// Houses Chain Count of activities for this activity.
+  pub user_activity_info: Box<Account<'info, UserActivityInfo>>,
+
+  #[account(
+    init,
+    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity()?.to_le_bytes()[..]],
+    bump,
+    payer = signer,
+    space = PayableActivityInfo::SPACE
+  )]
+  /// Houses Chain Count of activities for this activity.
+  pub payable_activity_info: Box<Account<'info, PayableActivityInfo>>,
+
+  #[account(
+    seeds = [
+      user_payment.payer.as_ref(),
+      UserPayment::SEED_PREFIX,
+      &user_payment.payer_count.to_le_bytes()[..]
+    ],
+    bump
+  )]
+  pub user_payment: Box<Account<'info, UserPayment>>,
+
+  #[account(mut, constraint = payable.host == *signer.key @ ChainbillsError::NotYourPayable)]
+  pub payable: Box<Account<'info, Payable>>,
+
+  #[account(mut, seeds = [signer.key().as_ref()], bump)]
+  pub host: Box<Account<'info, User>>,
+
+  #[account(mut, seeds = [ChainStats::SEED_PREFIX], bump)]
+  pub chain_stats: Box<Account<'info, ChainStats>>,
+
+  #[account(address = user_payment.details.token)]
+  pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+  #[account(address = user_payment.payer)]
+  pub payer_wallet: SystemAccount<'info>,
+
+  #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = payer_wallet,
+        associated_token::token_program = token_program,
+    )]
+  pub payer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = chain_stats,
+        associated_token::token_program = token_program,
+    )]
+  pub chain_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  #[account(mut)]
+  pub signer: Signer<'info>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+
+  pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefundPaymentNative<'info> {
+  #[account(
+    init_if_needed,
+    seeds = [user_payment.key().as_ref(), PaymentRefund::SEED_PREFIX],
+    bump,
+    payer = signer,
+    space = PaymentRefund::SPACE
+  )]
+  /// Marks the payment as refunded.
+  pub payment_refund: Box<Account<'info, PaymentRefund>>,
+
+  #[account(
+    init,
+    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity()?.to_le_bytes()[..]],
+    bump,
+    payer = signer,
+    space = ActivityRecord::SPACE
+  )]
+  /// Houses Details of this activity as RefundedPayment.
+  pub activity: Box<Account<'info, ActivityRecord>>,
+
+  #[account(
+    init,
+    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &host.next_activity()?.to_le_bytes()[..]],
+    bump,
+    payer = signer,
+    space = UserActivityInfo::SPACE
+  )]
+  /// Houses Chain Count of activities for this activity.
+  pub user_activity_info: Box<Account<'info, UserActivityInfo>>,
+
+  #[account(
+    init,
+    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity()?.to_le_bytes()[..]],
+    bump,
+    payer = signer,
+    space = PayableActivityInfo::SPACE
+  )]
+  /// Houses Chain Count of activities for this activity.
+  pub payable_activity_info: Box<Account<'info, PayableActivityInfo>>,
+
+  #[account(
+    seeds = [
+      user_payment.payer.as_ref(),
+      UserPayment::SEED_PREFIX,
+      &user_payment.payer_count.to_le_bytes()[..]
+    ],
+    bump,
+    constraint = user_payment.details.token == crate::ID @ ChainbillsError::NotNativePayment
+  )]
+  pub user_payment: Box<Account<'info, UserPayment>>,
+
+  #[account(mut, constraint = payable.host == *signer.key @ ChainbillsError::NotYourPayable)]
+  pub payable: Box<Account<'info, Payable>>,
+
+  #[account(mut, seeds = [signer.key().as_ref()], bump)]
+  pub host: Box<Account<'info, User>>,
+
+  #[account(mut, seeds = [ChainStats::SEED_PREFIX], bump)]
+  pub chain_stats: Box<Account<'info, ChainStats>>,
+
+  #[account(mut, address = user_payment.payer)]
+  pub payer_wallet: SystemAccount<'info>,
+
+  #[account(mut)]
+  pub signer: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+}
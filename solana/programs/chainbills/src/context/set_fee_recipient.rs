@@ -0,0 +1,16 @@
+use crate::{error::ChainbillsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+/// Context used to set where a payable's withdrawal fees go.
+pub struct SetFeeRecipient<'info> {
+  #[account(mut)]
+  pub payable: Box<Account<'info, Payable>>,
+
+  #[account(seeds = [Config::SEED_PREFIX], bump)]
+  pub config: AccountLoader<'info, Config>,
+
+  #[account(address = config.load()?.owner @ ChainbillsError::OwnerUnauthorized)]
+  /// Signer for this instruction. Should be the owner of this program.
+  pub owner: Signer<'info>,
+}
@@ -0,0 +1,14 @@
+use crate::{error::ChainbillsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+/// Context used to set the maximum amount of a single withdrawal.
+pub struct SetMaxWithdrawalAmount<'info> {
+  #[account(mut, seeds = [Config::SEED_PREFIX], bump)]
+  /// Config Account that holds the maximum withdrawal amount.
+  pub config: AccountLoader<'info, Config>,
+
+  #[account(address = config.load()?.owner @ ChainbillsError::OwnerUnauthorized)]
+  /// Signer for this instruction. Should be the owner of this program.
+  pub owner: Signer<'info>,
+}
@@ -0,0 +1,14 @@
+use crate::{error::ChainbillsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+/// Context used to set the minimum finality of received VAAs.
+pub struct SetMinVaaFinality<'info> {
+  #[account(mut, seeds = [Config::SEED_PREFIX], bump)]
+  /// Config Account that holds the minimum finality.
+  pub config: AccountLoader<'info, Config>,
+
+  #[account(address = config.load()?.owner @ ChainbillsError::OwnerUnauthorized)]
+  /// Signer for this instruction. Should be the owner of this program.
+  pub owner: Signer<'info>,
+}
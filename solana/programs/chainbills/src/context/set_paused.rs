@@ -0,0 +1,15 @@
+use crate::{error::ChainbillsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+/// Context used to pause or unpause the program, its cross-chain operations,
+/// its payments, or its withdrawals.
+pub struct SetPaused<'info> {
+  #[account(mut, seeds = [Config::SEED_PREFIX], bump)]
+  /// Config Account that holds the paused state.
+  pub config: AccountLoader<'info, Config>,
+
+  #[account(address = config.load()?.owner @ ChainbillsError::OwnerUnauthorized)]
+  /// Signer for this instruction. Should be the owner of this program.
+  pub owner: Signer<'info>,
+}
@@ -0,0 +1,31 @@
+use crate::{error::ChainbillsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(relayer: Pubkey)]
+/// Context used to authorize or deauthorize a relayer.
+pub struct SetRelayer<'info> {
+  #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [Relayer::SEED_PREFIX, relayer.as_ref()],
+        bump,
+        space = Relayer::SPACE
+    )]
+  /// The Relayer account of the wallet. Created the first time the wallet is
+  /// authorized. There is only one per wallet, so authorizing a relayer twice
+  /// doesn't duplicate it.
+  pub relayer_account: Account<'info, Relayer>,
+
+  #[account(seeds = [Config::SEED_PREFIX], bump)]
+  /// Config Account that stores important constant addresses that are used
+  /// across program instructions.
+  pub config: AccountLoader<'info, Config>,
+
+  #[account(mut, address = config.load()?.owner @ ChainbillsError::OwnerUnauthorized)]
+  /// Signer for this instruction. Should be the owner of this program.
+  pub owner: Signer<'info>,
+
+  /// System program.
+  pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,26 @@
+use crate::{error::ChainbillsError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(token: Pubkey, is_supported: bool)]
+/// Context used to start or stop accepting payments in a token.
+pub struct SetTokenSupported<'info> {
+  #[account(mut, seeds = [TokenDetails::SEED_PREFIX, &token.key().as_ref()], bump)]
+  /// Account that stores the details of the token.
+  pub token_details: Box<Account<'info, TokenDetails>>,
+
+  #[account(mut, seeds = [SupportedTokens::SEED_PREFIX], bump, realloc = supported_tokens.space_update(token, is_supported), realloc::payer = owner, realloc::zero = false)]
+  /// Lists the supported tokens. Resized to list or unlist the token.
+  pub supported_tokens: Box<Account<'info, SupportedTokens>>,
+
+  #[account(seeds = [Config::SEED_PREFIX], bump)]
+  /// Config Account that stores important constant addresses that are used
+  /// across program instructions.
+  pub config: AccountLoader<'info, Config>,
+
+  #[account(mut, address = config.load()?.owner @ ChainbillsError::OwnerUnauthorized)]
+  /// Signer for this instruction. Should be the owner of this program.
+  pub owner: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+}
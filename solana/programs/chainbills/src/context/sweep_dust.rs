@@ -0,0 +1,39 @@
+use crate::{error::ChainbillsError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+/// Context used to sweep a payable's dust balance of a token to its host.
+pub struct SweepDust<'info> {
+  #[account(mut, constraint = payable.host == *signer.key @ ChainbillsError::NotYourPayable)]
+  pub payable: Box<Account<'info, Payable>>,
+
+  pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+  #[account(seeds = [ChainStats::SEED_PREFIX], bump)]
+  pub chain_stats: Box<Account<'info, ChainStats>>,
+
+  #[account(seeds = [Config::SEED_PREFIX], bump)]
+  pub config: AccountLoader<'info, Config>,
+
+  #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = chain_stats,
+        associated_token::token_program = token_program,
+    )]
+  pub chain_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program,
+    )]
+  pub host_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+  #[account(mut)]
+  pub signer: Signer<'info>,
+
+  pub token_program: Interface<'info, TokenInterface>,
+}
@@ -7,6 +7,11 @@ pub struct UpdateMaxWithdrawalFeesNative<'info> {
   /// Account that stores the details of the token to updates its max withdrawal fees.
   pub token_details: Box<Account<'info, TokenDetails>>,
 
+  #[account(init_if_needed, payer = owner, seeds = [SupportedTokens::SEED_PREFIX], bump, space = SupportedTokens::space_for(0))]
+  /// Lists the supported tokens. Grown inside the instruction when the native
+  /// token is newly listed.
+  pub supported_tokens: Box<Account<'info, SupportedTokens>>,
+
   #[account(seeds = [Config::SEED_PREFIX], bump)]
   pub config: AccountLoader<'info, Config>,
 
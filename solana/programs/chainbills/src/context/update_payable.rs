@@ -14,7 +14,7 @@ pub struct UpdatePayable<'info> {
 
   #[account(
     init,
-    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity().to_le_bytes()[..]],
+    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = ActivityRecord::SPACE
@@ -24,7 +24,7 @@ pub struct UpdatePayable<'info> {
 
   #[account(
     init,
-    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &host.next_activity().to_le_bytes()[..]],
+    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &host.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = UserActivityInfo::SPACE
@@ -34,7 +34,7 @@ pub struct UpdatePayable<'info> {
 
   #[account(
     init,
-    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity().to_le_bytes()[..]],
+    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = PayableActivityInfo::SPACE
@@ -42,7 +42,7 @@ pub struct UpdatePayable<'info> {
   /// Houses Chain Count of activities for this activity.
   pub payable_activity_info: Box<Account<'info, PayableActivityInfo>>,
 
-  #[account(seeds = [signer.key().as_ref()], bump)]
+  #[account(mut, seeds = [signer.key().as_ref()], bump)]
   pub host: Box<Account<'info, User>>,
 
   #[account(mut, seeds = [ChainStats::SEED_PREFIX], bump)]
@@ -57,15 +57,16 @@ pub struct UpdatePayable<'info> {
 #[derive(Accounts)]
 #[instruction(allowed_tokens_and_amounts: Vec<TokenAndAmount>)]
 pub struct UpdatePayableAllowedTokensAndAmounts<'info> {
-  // Allowing realloc::zero to be true if in case the allowed tokens and
-  // amounts vec's len is lower than the previous one. This will allow the
-  // program to refresh zeroing out discarded space as needed.
+  // Shrinking to fewer allowed tokens and amounts truncates the account to
+  // exactly the payable's new space and refunds the freed rent to the signer.
+  // realloc::zero is true so that, if the account grows again after being
+  // shrunk in the same transaction, the discarded bytes come back zeroed.
   #[account(mut, constraint = payable.host == *signer.key @ ChainbillsError::NotYourPayable, realloc = payable.space_update_ataa(allowed_tokens_and_amounts.len()), realloc::payer = signer, realloc::zero = true)]
   pub payable: Box<Account<'info, Payable>>,
 
   #[account(
     init,
-    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity().to_le_bytes()[..]],
+    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = ActivityRecord::SPACE
@@ -75,7 +76,7 @@ pub struct UpdatePayableAllowedTokensAndAmounts<'info> {
 
   #[account(
     init,
-    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &host.next_activity().to_le_bytes()[..]],
+    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &host.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = UserActivityInfo::SPACE
@@ -85,7 +86,7 @@ pub struct UpdatePayableAllowedTokensAndAmounts<'info> {
 
   #[account(
     init,
-    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity().to_le_bytes()[..]],
+    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = PayableActivityInfo::SPACE
@@ -93,7 +94,7 @@ pub struct UpdatePayableAllowedTokensAndAmounts<'info> {
   /// Houses Chain Count of activities for this activity.
   pub payable_activity_info: Box<Account<'info, PayableActivityInfo>>,
 
-  #[account(seeds = [signer.key().as_ref()], bump)]
+  #[account(mut, seeds = [signer.key().as_ref()], bump)]
   pub host: Box<Account<'info, User>>,
 
   #[account(mut, seeds = [ChainStats::SEED_PREFIX], bump)]
@@ -104,3 +105,11 @@ pub struct UpdatePayableAllowedTokensAndAmounts<'info> {
 
   pub system_program: Program<'info, System>,
 }
+
+#[derive(Accounts)]
+pub struct UpdatePayableAcceptsCrossChain<'info> {
+  #[account(mut, constraint = payable.host == *signer.key @ ChainbillsError::NotYourPayable)]
+  pub payable: Box<Account<'info, Payable>>,
+
+  pub signer: Signer<'info>,
+}
@@ -0,0 +1,12 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+/// Context used to check whether a VAA has been consumed.
+pub struct VaaConsumed<'info> {
+  #[account(seeds = [WormholeReceived::SEED_PREFIX, &vaa_hash], bump)]
+  /// CHECK: Not loaded as an Account because it doesn't exist until the VAA
+  /// is consumed. Its owner and data are checked in the handler.
+  pub wormhole_received: UncheckedAccount<'info>,
+}
@@ -1,6 +1,6 @@
 use crate::{error::ChainbillsError, state::*};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
@@ -8,7 +8,9 @@ pub struct Withdraw<'info> {
         init,
         seeds = [signer.key().as_ref(),
             Withdrawal::SEED_PREFIX,
-            &host.next_withdrawal().to_le_bytes()[..]],
+            &User::read(host.owner, &host.try_borrow_data()?)?
+              .next_withdrawal()?
+              .to_le_bytes()[..]],
         bump,
         payer = signer,
         space = Withdrawal::SPACE
@@ -19,7 +21,7 @@ pub struct Withdraw<'info> {
         init,
         seeds = [payable.key().as_ref(),
             PayableWithdrawalInfo::SEED_PREFIX,
-            &payable.next_withdrawal().to_le_bytes()[..]],
+            &payable.next_withdrawal()?.to_le_bytes()[..]],
         bump,
         payer = signer,
         space = PayableWithdrawalInfo::SPACE
@@ -28,7 +30,7 @@ pub struct Withdraw<'info> {
 
   #[account(
     init,
-    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity().to_le_bytes()[..]],
+    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = ActivityRecord::SPACE
@@ -38,7 +40,7 @@ pub struct Withdraw<'info> {
 
   #[account(
     init,
-    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &host.next_activity().to_le_bytes()[..]],
+    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &User::read(host.owner, &host.try_borrow_data()?)?.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = UserActivityInfo::SPACE
@@ -48,7 +50,7 @@ pub struct Withdraw<'info> {
 
   #[account(
     init,
-    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity().to_le_bytes()[..]],
+    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = PayableActivityInfo::SPACE
@@ -60,7 +62,9 @@ pub struct Withdraw<'info> {
   pub payable: Box<Account<'info, Payable>>,
 
   #[account(mut, seeds = [signer.key().as_ref()], bump)]
-  pub host: Box<Account<'info, User>>,
+  /// CHECK: Not loaded as an Account so that hosts who never initialized a
+  /// User fail with UserNotInitialized. It is read with User::read.
+  pub host: UncheckedAccount<'info>,
 
   #[account(mut, seeds = [ChainStats::SEED_PREFIX], bump)]
   pub chain_stats: Box<Account<'info, ChainStats>>,
@@ -68,7 +72,7 @@ pub struct Withdraw<'info> {
   #[account(seeds = [Config::SEED_PREFIX], bump)]
   pub config: AccountLoader<'info, Config>,
 
-  pub mint: Box<Account<'info, Mint>>,
+  pub mint: Box<InterfaceAccount<'info, Mint>>,
 
   #[account(seeds = [TokenDetails::SEED_PREFIX, mint.key().as_ref()], bump)]
   pub token_details: Box<Account<'info, TokenDetails>>,
@@ -77,30 +81,38 @@ pub struct Withdraw<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = signer,
+        associated_token::token_program = token_program,
     )]
-  pub host_token_account: Box<Account<'info, TokenAccount>>,
+  pub host_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
   #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = chain_stats,
+        associated_token::token_program = token_program,
     )]
-  pub chain_token_account: Box<Account<'info, TokenAccount>>,
+  pub chain_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
   #[account(
         mut,
         associated_token::mint = mint,
         associated_token::authority = fee_collector,
+        associated_token::token_program = token_program,
     )]
-  pub fees_token_account: Box<Account<'info, TokenAccount>>,
+  pub fees_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-  #[account(address = config.load()?.chainbills_fee_collector)]
+  #[account(
+        address = payable.fee_collector(config.load()?.chainbills_fee_collector)
+          @ ChainbillsError::WrongFeeCollectorAddress
+    )]
+  /// Receives the withdrawal fees. The payable's fee_recipient if set,
+  /// otherwise the chainbills_fee_collector in Config.
   pub fee_collector: SystemAccount<'info>,
 
   #[account(mut)]
   pub signer: Signer<'info>,
 
-  pub token_program: Program<'info, Token>,
+  pub token_program: Interface<'info, TokenInterface>,
 
   pub system_program: Program<'info, System>,
 }
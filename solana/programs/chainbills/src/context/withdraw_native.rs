@@ -7,7 +7,9 @@ pub struct WithdrawNative<'info> {
         init,
         seeds = [signer.key().as_ref(),
             Withdrawal::SEED_PREFIX,
-            &host.next_withdrawal().to_le_bytes()[..]],
+            &User::read(host.owner, &host.try_borrow_data()?)?
+              .next_withdrawal()?
+              .to_le_bytes()[..]],
         bump,
         payer = signer,
         space = Withdrawal::SPACE
@@ -18,7 +20,7 @@ pub struct WithdrawNative<'info> {
         init,
         seeds = [payable.key().as_ref(),
             PayableWithdrawalInfo::SEED_PREFIX,
-            &payable.next_withdrawal().to_le_bytes()[..]],
+            &payable.next_withdrawal()?.to_le_bytes()[..]],
         bump,
         payer = signer,
         space = PayableWithdrawalInfo::SPACE
@@ -27,7 +29,7 @@ pub struct WithdrawNative<'info> {
 
   #[account(
     init,
-    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity().to_le_bytes()[..]],
+    seeds = [ActivityRecord::SEED_PREFIX, &chain_stats.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = ActivityRecord::SPACE
@@ -37,7 +39,7 @@ pub struct WithdrawNative<'info> {
 
   #[account(
     init,
-    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &host.next_activity().to_le_bytes()[..]],
+    seeds = [signer.key().as_ref(), ActivityRecord::SEED_PREFIX, &User::read(host.owner, &host.try_borrow_data()?)?.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = UserActivityInfo::SPACE
@@ -47,7 +49,7 @@ pub struct WithdrawNative<'info> {
 
   #[account(
     init,
-    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity().to_le_bytes()[..]],
+    seeds = [payable.key().as_ref(), ActivityRecord::SEED_PREFIX, &payable.next_activity()?.to_le_bytes()[..]],
     bump,
     payer = signer,
     space = PayableActivityInfo::SPACE
@@ -59,7 +61,9 @@ pub struct WithdrawNative<'info> {
   pub payable: Box<Account<'info, Payable>>,
 
   #[account(mut, seeds = [signer.key().as_ref()], bump)]
-  pub host: Box<Account<'info, User>>,
+  /// CHECK: Not loaded as an Account so that hosts who never initialized a
+  /// User fail with UserNotInitialized. It is read with User::read.
+  pub host: UncheckedAccount<'info>,
 
   #[account(mut, seeds = [ChainStats::SEED_PREFIX], bump)]
   pub chain_stats: Box<Account<'info, ChainStats>>,
@@ -67,7 +71,13 @@ pub struct WithdrawNative<'info> {
   #[account(seeds = [Config::SEED_PREFIX], bump)]
   pub config: AccountLoader<'info, Config>,
 
-  #[account(address = config.load()?.chainbills_fee_collector)]
+  #[account(
+        mut,
+        address = payable.fee_collector(config.load()?.chainbills_fee_collector)
+          @ ChainbillsError::WrongFeeCollectorAddress
+    )]
+  /// Receives the withdrawal fees. The payable's fee_recipient if set,
+  /// otherwise the chainbills_fee_collector in Config.
   pub fee_collector: SystemAccount<'info>,
 
   #[account(seeds = [TokenDetails::SEED_PREFIX, crate::ID.as_ref()], bump)]
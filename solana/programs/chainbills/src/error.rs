@@ -7,6 +7,10 @@ pub enum ChainbillsError {
   /// of the allowed tokens and amounts vector.
   InvalidRemainingAccountsLength,
 
+  #[msg("MismatchedTokenDetailsMint")]
+  /// The token details account provided is not for the mint in use.
+  MismatchedTokenDetailsMint,
+
   #[msg("NonTokenDetailsAccountProvided")]
   /// A non-token details account was provided in the remaining accounts.
   NonTokenDetailsAccountProvided,
@@ -19,14 +23,35 @@ pub enum ChainbillsError {
   /// The token is not supported.
   UnsupportedToken,
 
+  #[msg("MaxPayableTokensCapacityReached")]
+  /// The allowed tokens and amounts are more than the maximum allowed.
+  MaxPayableTokensCapacityReached,
+
+  #[msg("DuplicateTokenAndAmountInPayable")]
+  /// The same token and amount was specified more than once in allowed tokens
+  /// and amounts.
+  DuplicateTokenAndAmountInPayable,
+
   #[msg("ZeroAmountSpecified")]
   /// The amount specified is zero.
   ZeroAmountSpecified,
 
+  #[msg("WithdrawalAmountTooSmall")]
+  /// The amount to withdraw is below the minimum withdrawal amount.
+  WithdrawalAmountTooSmall,
+
   #[msg("PayableIsClosed")]
   /// The payable is closed, so it can accept payments.
   PayableIsClosed,
 
+  #[msg("PayableExpired")]
+  /// The payable's expiry time has passed, so it can't accept payments.
+  PayableExpired,
+
+  #[msg("IncompleteGoal")]
+  /// Only one of goal_token and goal_amount was provided.
+  IncompleteGoal,
+
   #[msg("PayableIsAlreadyClosed")]
   /// The payable is already closed, so no need to close it again.
   PayableIsAlreadyClosed,
@@ -36,9 +61,14 @@ pub enum ChainbillsError {
   PayableIsNotClosed,
 
   #[msg("MatchingTokenAndAmountNotFound")]
-  /// No matching token and amount found for payment.
+  /// None of the allowed tokens and amounts matches the payment's token and
+  /// amount.
   MatchingTokenAndAmountNotFound,
 
+  #[msg("PaymentAmountTooLarge")]
+  /// The payment amount is greater than the payable's max_payment_amount.
+  PaymentAmountTooLarge,
+
   #[msg("NotYourPayable")]
   /// The payable is not owned by the caller.
   NotYourPayable,
@@ -51,10 +81,26 @@ pub enum ChainbillsError {
   /// No balance found for the specified withdrawal token.
   NoBalanceForWithdrawalToken,
 
+  #[msg("PaymentNotForPayable")]
+  /// The payment to refund was not made to the provided payable.
+  PaymentNotForPayable,
+
+  #[msg("PaymentAlreadyRefunded")]
+  /// The payment has already been refunded.
+  PaymentAlreadyRefunded,
+
+  #[msg("InsufficientPayableBalance")]
+  /// The payable doesn't have enough balance of the payment's token.
+  InsufficientPayableBalance,
+
   #[msg("OwnerUnauthorized")]
   /// The caller is not the owner of the program.
   OwnerUnauthorized,
 
+  #[msg("NotPendingOwner")]
+  /// The caller is not the proposed new owner of the program.
+  NotPendingOwner,
+
   #[msg("InvalidWormholeBridge")]
   /// Specified Wormhole bridge data PDA is wrong.
   InvalidWormholeBridge,
@@ -66,4 +112,108 @@ pub enum ChainbillsError {
   #[msg("WrongFeeCollectorAddress")]
   /// The provided fee collector address is wrong.
   WrongFeeCollectorAddress,
+
+  #[msg("ContractPaused")]
+  /// The program is paused, so payments, withdrawals, and payable creation
+  /// are not allowed.
+  ContractPaused,
+
+  #[msg("CrossChainPaused")]
+  /// Cross-chain (Wormhole) operations are paused.
+  CrossChainPaused,
+
+  #[msg("InvalidActivityCount")]
+  /// The requested activity count is zero or greater than the count of
+  /// activities on this chain.
+  InvalidActivityCount,
+
+  #[msg("Overflow")]
+  /// An arithmetic operation overflowed or underflowed.
+  Overflow,
+
+  #[msg("InvalidForeignEmitter")]
+  /// The VAA wasn't emitted by the foreign contract registered for its
+  /// emitter chain.
+  InvalidForeignEmitter,
+
+  #[msg("InsufficientVaaFinality")]
+  /// The VAA's consistency level (finality) is below the configured minimum.
+  InsufficientVaaFinality,
+
+  #[msg("WithdrawalExceedsMax")]
+  /// The withdrawal amount is above the configured maximum of a single
+  /// withdrawal.
+  WithdrawalExceedsMax,
+
+  #[msg("InvalidPayload")]
+  /// A cross-chain payload is malformed or doesn't have the expected length.
+  InvalidPayload,
+
+  #[msg("InvalidPayableId")]
+  /// The payable ID is all zeros and can't refer to an existing payable.
+  InvalidPayableId,
+
+  #[msg("RelayerUnauthorized")]
+  /// The signer isn't an authorized relayer.
+  RelayerUnauthorized,
+
+  #[msg("TooManyPayableBalances")]
+  /// A payment in a new token would take the payable's balances past
+  /// MAX_PAYABLE_BALANCES.
+  TooManyPayableBalances,
+
+  #[msg("PaymentsPaused")]
+  /// Payments are paused, though withdrawals may still go on.
+  PaymentsPaused,
+
+  #[msg("WithdrawalsPaused")]
+  /// Withdrawals are paused, though payments may still go on.
+  WithdrawalsPaused,
+
+  #[msg("NotDust")]
+  /// The payable's balance of the token is too large to be swept as dust.
+  NotDust,
+
+  #[msg("InvalidUserActivityAccount")]
+  /// An account passed to read a user's activities isn't the UserActivityInfo
+  /// or ActivityRecord derived for its position.
+  InvalidUserActivityAccount,
+
+  #[msg("MemoTooLong")]
+  /// The payment's memo is longer than UserPayment::MAX_MEMO_LENGTH.
+  MemoTooLong,
+
+  #[msg("UserNotInitialized")]
+  /// The host withdrawing has never initialized a User on this chain.
+  UserNotInitialized,
+
+  #[msg("FeeTooHigh")]
+  /// The withdrawal fee percentage exceeds
+  /// Config::MAX_WITHDRAWAL_FEE_PERCENTAGE.
+  FeeTooHigh,
+
+  #[msg("PayableHasBalance")]
+  /// The payable still has a balance in some token, so its account can't be
+  /// closed.
+  PayableHasBalance,
+
+  #[msg("ExpiryNotInFuture")]
+  /// The payable's expires_at isn't after the time of its creation.
+  ExpiryNotInFuture,
+
+  #[msg("InvalidConfigAccount")]
+  /// The account passed as config isn't this program's Config account.
+  InvalidConfigAccount,
+
+  #[msg("NotNativePayment")]
+  /// The payment wasn't made in native SOL. Refund it with refund_payment.
+  NotNativePayment,
+
+  #[msg("PayableRejectsCrossChain")]
+  /// The payable doesn't accept payments bridged from other chains.
+  PayableRejectsCrossChain,
+
+  #[msg("ZeroMaxPayments")]
+  /// A payable's max_payments must be greater than zero when set.
+  ZeroMaxPayments,
 }
@@ -27,11 +27,15 @@ pub struct UserPaid {
   pub payable_chain_id: u16,
   pub chain_count: u64,
   pub payer_count: u64,
+  pub memo: Option<String>,
 }
 
 #[event]
 /// Emitted when a payment is made to a payable. payer_wallet is [u8; 32] to
-/// take into account payments from other chains.
+/// take into account payments from other chains. balance is the payable's
+/// balance of the paid token after this payment. remaining_to_goal and
+/// remaining_slots are what is left after this payment of the payable's goal
+/// and max_payments, and are only set if the payable has them.
 pub struct PayableReceived {
   pub payable_id: Pubkey,
   pub payer_wallet: [u8; 32],
@@ -39,6 +43,9 @@ pub struct PayableReceived {
   pub payer_chain_id: u16,
   pub chain_count: u64,
   pub payable_count: u64,
+  pub balance: u64,
+  pub remaining_to_goal: Option<u64>,
+  pub remaining_slots: Option<u64>,
 }
 
 #[event]
@@ -51,12 +58,36 @@ pub struct Withdrew {
   pub host_count: u64,
 }
 
+#[event]
+pub struct RefundedPayment {
+  pub payable_id: Pubkey,
+  pub host_wallet: Pubkey,
+  pub payment_id: Pubkey,
+  pub payer_wallet: Pubkey,
+  pub token: Pubkey,
+  pub amount: u64,
+}
+
+#[event]
+pub struct GoalReached {
+  pub payable_id: Pubkey,
+  pub goal_token: Pubkey,
+  pub goal_amount: u64,
+}
+
 #[event]
 pub struct ClosedPayable {
   pub payable_id: Pubkey,
   pub host_wallet: Pubkey,
 }
 
+#[event]
+pub struct ClosedPayableAccount {
+  pub payable_id: Pubkey,
+  pub host_wallet: Pubkey,
+  pub reclaimed_lamports: u64,
+}
+
 #[event]
 pub struct ReopenedPayable {
   pub payable_id: Pubkey,
@@ -67,12 +98,15 @@ pub struct ReopenedPayable {
 pub struct UpdatedPayableAllowedTokensAndAmounts {
   pub payable_id: Pubkey,
   pub host_wallet: Pubkey,
+  pub allowed_tokens_count: u64,
 }
 
 #[event]
 pub struct UpdatedMaxWithdrawalFees {
   pub token: Pubkey,
+  pub previous_max_withdrawal_fees: u64,
   pub max_withdrawal_fees: u64,
+  pub effective_at: u64,
 }
 
 #[event]
@@ -85,4 +119,94 @@ pub struct OwnerWithdrew {
 pub struct RegisteredForeignContract {
   pub chain_id: u16,
   pub emitter: [u8; 32],
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct UpdatedForeignContract {
+  pub chain_id: u16,
+  pub previous_emitter: [u8; 32],
+  pub emitter: [u8; 32],
+}
+
+#[event]
+pub struct UpdatedPausedState {
+  pub paused: bool,
+}
+
+#[event]
+pub struct UpdatedTokenSupported {
+  pub token: Pubkey,
+  pub is_supported: bool,
+}
+
+#[event]
+pub struct UpdatedMinVaaFinality {
+  pub min_vaa_finality: u8,
+}
+
+#[event]
+pub struct UpdatedMaxWithdrawalAmount {
+  pub max_withdrawal_amount: u64,
+}
+
+#[event]
+pub struct UpdatedCrossChainPausedState {
+  pub paused: bool,
+}
+
+#[event]
+pub struct ProposedNewOwner {
+  pub owner: Pubkey,
+  pub new_owner: Pubkey,
+}
+
+#[event]
+pub struct AcceptedOwnership {
+  pub previous_owner: Pubkey,
+  pub new_owner: Pubkey,
+}
+
+#[event]
+pub struct UpdatedRelayer {
+  pub relayer: Pubkey,
+  pub is_authorized: bool,
+}
+
+#[event]
+pub struct UpdatedPaymentsPausedState {
+  pub paused: bool,
+}
+
+#[event]
+pub struct UpdatedWithdrawalsPausedState {
+  pub paused: bool,
+}
+
+#[event]
+pub struct UpdatedPayableAcceptsCrossChain {
+  pub payable_id: Pubkey,
+  pub host_wallet: Pubkey,
+  pub accepts_cross_chain: bool,
+}
+
+#[event]
+pub struct UpdatedFeeRecipient {
+  pub payable_id: Pubkey,
+  pub fee_recipient: Option<Pubkey>,
+}
+
+#[event]
+pub struct SweptDust {
+  pub payable_id: Pubkey,
+  pub token: Pubkey,
+  pub amount: u64,
+}
+
+#[event]
+/// Emitted when a payable created on another chain is mirrored on this one.
+/// host_wallet is the Wormhole-normalized address of the payable's host.
+pub struct InitializedPayableReceived {
+  pub payable_id: [u8; 32],
+  pub chain_id: u16,
+  pub host_wallet: [u8; 32],
+}
@@ -0,0 +1,84 @@
+use crate::{
+  context::ClosePayableAccount, error::ChainbillsError, events::*,
+  state::Payable,
+};
+use anchor_lang::prelude::*;
+
+/// Ensures that the payable's account can be closed: the payable must be
+/// closed and must have no balance left in any token.
+fn check_payable_account_closable(payable: &Payable) -> Result<()> {
+  require!(payable.is_closed, ChainbillsError::PayableIsNotClosed);
+  require!(
+    payable.balances.iter().all(|balance| balance.amount == 0),
+    ChainbillsError::PayableHasBalance
+  );
+  Ok(())
+}
+
+/// Closes a closed and fully withdrawn payable's account, returning its rent
+/// to the host. Unlike close_payable, this can't be undone. Can be called
+/// only by the host (user) that owns the payable.
+#[inline(never)]
+pub fn close_payable_account(ctx: Context<ClosePayableAccount>) -> Result<()> {
+  /* CHECKS */
+  let payable = ctx.accounts.payable.as_ref();
+  check_payable_account_closable(payable)?;
+
+  /* STATE CHANGES */
+  // The account is closed (and its lamports moved to the signer) by the
+  // close constraint once the instruction succeeds.
+  let reclaimed_lamports = ctx.accounts.payable.to_account_info().lamports();
+
+  // Emit log and event.
+  msg!("Closed Payable Account.");
+  emit!(ClosedPayableAccount {
+    payable_id: payable.key(),
+    host_wallet: ctx.accounts.signer.key(),
+    reclaimed_lamports
+  });
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::state::TokenAndAmount;
+
+  fn payable(is_closed: bool, balances: Vec<u64>) -> Payable {
+    Payable {
+      is_closed,
+      balances: balances
+        .into_iter()
+        .map(|amount| TokenAndAmount {
+          token: Pubkey::new_unique(),
+          amount,
+        })
+        .collect(),
+      ..Payable::test_default()
+    }
+  }
+
+  #[test]
+  fn closes_empty_closed_payable() {
+    // Never paid.
+    assert!(check_payable_account_closable(&payable(true, vec![])).is_ok());
+    // Fully withdrawn.
+    assert!(check_payable_account_closable(&payable(true, vec![0, 0])).is_ok());
+  }
+
+  #[test]
+  fn rejects_payable_with_balance() {
+    assert_eq!(
+      check_payable_account_closable(&payable(true, vec![0, 1])).unwrap_err(),
+      ChainbillsError::PayableHasBalance.into()
+    );
+  }
+
+  #[test]
+  fn rejects_open_payable() {
+    assert_eq!(
+      check_payable_account_closable(&payable(false, vec![])).unwrap_err(),
+      ChainbillsError::PayableIsNotClosed.into()
+    );
+  }
+}
@@ -1,30 +1,45 @@
-use crate::{context::*, error::ChainbillsError, events::*, state::*};
+use crate::{
+  context::*, error::ChainbillsError, events::*, handlers::check_not_paused,
+  state::*,
+};
 use anchor_lang::{prelude::*, solana_program::clock};
 
-/// Create a Payable
-///
-/// ### args
-/// * allowed_tokens_and_amounts<Vec<TokenAndAmount>>: The allowed tokens
-///         (and their amounts) on this payable. If this vector is empty,
-///         then the payable will accept payments in any token.
-#[inline(never)]
-pub fn create_payable_handler<'info>(
-  ctx: Context<'_, '_, 'info, 'info, CreatePayable>,
-  allowed_tokens_and_amounts: Vec<TokenAndAmount>,
+/// Validates the allowed tokens and amounts (ataas) that a host provides for
+/// a payable. Each ataa must have its TokenDetails account in the same
+/// position in remaining_accounts.
+pub fn check_allowed_tokens_and_amounts<'info>(
+  remaining_accounts: &'info [AccountInfo<'info>],
+  allowed_tokens_and_amounts: &[TokenAndAmount],
 ) -> Result<()> {
-  /* CHECKS */
+  // Ensure that the ataas are within the allowed capacity.
+  require!(
+    allowed_tokens_and_amounts.len() <= Payable::MAX_PAYABLES_TOKENS,
+    ChainbillsError::MaxPayableTokensCapacityReached
+  );
+
+  // Ensure that no token and amount pair appears more than once. A token can
+  // still be listed with different amounts (e.g. tiered payments).
+  for (i, taa) in allowed_tokens_and_amounts.iter().enumerate() {
+    require!(
+      !allowed_tokens_and_amounts[..i]
+        .iter()
+        .any(|other| other.token == taa.token && other.amount == taa.amount),
+      ChainbillsError::DuplicateTokenAndAmountInPayable
+    );
+  }
+
   // Ensure that length of remaining_accounts in context matches that of the
   // allowed_tokens_and_amounts (ataas) vector. This is necessary inorder to
   // use remaining_accounts to get the token details.
   require!(
-    ctx.remaining_accounts.len() == allowed_tokens_and_amounts.len(),
+    remaining_accounts.len() == allowed_tokens_and_amounts.len(),
     ChainbillsError::InvalidRemainingAccountsLength
   );
 
   for (i, taa) in allowed_tokens_and_amounts.iter().enumerate() {
     // Get the token details for the specified token.
     let token_details =
-      Account::<'info, TokenDetails>::try_from(&ctx.remaining_accounts[i])
+      Account::<'info, TokenDetails>::try_from(&remaining_accounts[i])
         .map_err(|_| ChainbillsError::NonTokenDetailsAccountProvided)?;
 
     // Ensure that the token is supported.
@@ -41,19 +56,87 @@ pub fn create_payable_handler<'info>(
     require!(taa.amount > 0, ChainbillsError::ZeroAmountSpecified);
   }
 
+  Ok(())
+}
+
+/// Validates a payable's goal. The goal_token and goal_amount must be either
+/// both set or both unset, and the goal_amount must be greater than zero.
+pub fn check_goal(
+  goal_token: Option<Pubkey>,
+  goal_amount: Option<u64>,
+) -> Result<()> {
+  require!(
+    goal_token.is_some() == goal_amount.is_some(),
+    ChainbillsError::IncompleteGoal
+  );
+  if let Some(goal_amount) = goal_amount {
+    require!(goal_amount > 0, ChainbillsError::ZeroAmountSpecified);
+  }
+  Ok(())
+}
+
+/// Validates a payable's payments cap. If set, max_payments must be greater
+/// than zero.
+pub fn check_max_payments(max_payments: Option<u64>) -> Result<()> {
+  require!(max_payments != Some(0), ChainbillsError::ZeroMaxPayments);
+  Ok(())
+}
+
+/// Validates a payable's expiry. If set, expires_at must be after now.
+pub fn check_expiry(expires_at: Option<u64>, now: u64) -> Result<()> {
+  if let Some(expires_at) = expires_at {
+    require!(expires_at > now, ChainbillsError::ExpiryNotInFuture);
+  }
+  Ok(())
+}
+
+/// Create a Payable
+///
+/// ### args
+/// * allowed_tokens_and_amounts<Vec<TokenAndAmount>>: The allowed tokens
+///   (and their amounts) on this payable. If this vector is empty,
+///   then the payable will accept payments in any token.
+/// * expires_at<Option<u64>>: The timestamp (in seconds) after which this
+///   payable stops accepting payments. If None, the payable never expires.
+/// * max_payment_amount<Option<u64>>: The maximum amount of a single payment
+///   if this payable accepts free payments. Ignored otherwise.
+/// * goal_token<Option<Pubkey>>: The token in which the goal is set.
+/// * goal_amount<Option<u64>>: The balance of goal_token at which the payable
+///   gets closed. Must be set together with goal_token.
+/// * max_payments<Option<u64>>: The number of payments after which the
+///   payable gets closed. If None, it accepts any number of payments.
+#[inline(never)]
+pub fn create_payable_handler<'info>(
+  ctx: Context<'_, '_, 'info, 'info, CreatePayable>,
+  allowed_tokens_and_amounts: Vec<TokenAndAmount>,
+  expires_at: Option<u64>,
+  max_payment_amount: Option<u64>,
+  goal_token: Option<Pubkey>,
+  goal_amount: Option<u64>,
+  max_payments: Option<u64>,
+) -> Result<()> {
+  /* CHECKS */
+  check_not_paused(&*ctx.accounts.config.load()?)?;
+  check_allowed_tokens_and_amounts(
+    ctx.remaining_accounts,
+    &allowed_tokens_and_amounts,
+  )?;
+  check_goal(goal_token, goal_amount)?;
+  check_max_payments(max_payments)?;
+  let timestamp = clock::Clock::get()?.unix_timestamp as u64;
+  check_expiry(expires_at, timestamp)?;
+
   /* STATE CHANGES */
   // Increment the chain stats for payables_count and activities_count.
   let chain_stats = ctx.accounts.chain_stats.as_mut();
-  chain_stats.payables_count = chain_stats.next_payable();
-  chain_stats.activities_count = chain_stats.next_activity();
+  chain_stats.payables_count = chain_stats.next_payable()?;
+  chain_stats.activities_count = chain_stats.next_activity()?;
 
   // Increment payables_count and activities_count on the host initializing
   // this payable.
   let host = ctx.accounts.host.as_mut();
-  host.payables_count = host.next_payable();
-  host.activities_count = host.next_activity();
-
-  let timestamp = clock::Clock::get()?.unix_timestamp as u64;
+  host.payables_count = host.next_payable()?;
+  host.activities_count = host.next_activity()?;
 
   // Initialize the payable.
   let payable = ctx.accounts.payable.as_mut();
@@ -67,6 +150,13 @@ pub fn create_payable_handler<'info>(
   payable.withdrawals_count = 0;
   payable.activities_count = 1; // Start at 1 to record the initialization.
   payable.is_closed = false;
+  payable.fee_recipient = None;
+  payable.expires_at = expires_at;
+  payable.max_payment_amount = max_payment_amount;
+  payable.goal_token = goal_token;
+  payable.goal_amount = goal_amount;
+  payable.rejects_cross_chain = false;
+  payable.max_payments = max_payments;
 
   // Initialize the payable_per_chain_payments_counter for Solana.
   let ppcpc = ctx.accounts.payable_per_chain_payments_counter.as_mut();
@@ -103,3 +193,114 @@ pub fn create_payable_handler<'info>(
   });
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn validates_goals() {
+    assert!(check_goal(None, None).is_ok());
+    assert!(check_goal(Some(Pubkey::new_unique()), Some(100)).is_ok());
+    assert_eq!(
+      check_goal(Some(Pubkey::new_unique()), None).unwrap_err(),
+      ChainbillsError::IncompleteGoal.into()
+    );
+    assert_eq!(
+      check_goal(None, Some(100)).unwrap_err(),
+      ChainbillsError::IncompleteGoal.into()
+    );
+    assert_eq!(
+      check_goal(Some(Pubkey::new_unique()), Some(0)).unwrap_err(),
+      ChainbillsError::ZeroAmountSpecified.into()
+    );
+  }
+
+  #[test]
+  fn validates_max_payments() {
+    assert!(check_max_payments(None).is_ok());
+    assert!(check_max_payments(Some(1)).is_ok());
+    assert_eq!(
+      check_max_payments(Some(0)).unwrap_err(),
+      ChainbillsError::ZeroMaxPayments.into()
+    );
+  }
+
+  #[test]
+  fn validates_expiry() {
+    let now = 1_000;
+    assert!(check_expiry(None, now).is_ok());
+    assert!(check_expiry(Some(now + 1), now).is_ok());
+    for expires_at in [0, now - 1, now] {
+      assert_eq!(
+        check_expiry(Some(expires_at), now).unwrap_err(),
+        ChainbillsError::ExpiryNotInFuture.into()
+      );
+    }
+  }
+
+  #[test]
+  fn rejects_ataas_over_capacity() {
+    let ataas = vec![
+      TokenAndAmount {
+        token: Pubkey::new_unique(),
+        amount: 1,
+      };
+      Payable::MAX_PAYABLES_TOKENS + 1
+    ];
+    let result = check_allowed_tokens_and_amounts(&[], &ataas);
+    assert_eq!(
+      result.unwrap_err(),
+      ChainbillsError::MaxPayableTokensCapacityReached.into()
+    );
+  }
+
+  #[test]
+  fn rejects_duplicate_tokens_and_amounts() {
+    let token = Pubkey::new_unique();
+    let ataas = vec![
+      TokenAndAmount { token, amount: 10 },
+      TokenAndAmount {
+        token: Pubkey::new_unique(),
+        amount: 10,
+      },
+      TokenAndAmount { token, amount: 10 },
+    ];
+    let result = check_allowed_tokens_and_amounts(&[], &ataas);
+    assert_eq!(
+      result.unwrap_err(),
+      ChainbillsError::DuplicateTokenAndAmountInPayable.into()
+    );
+  }
+
+  #[test]
+  fn accepts_multiple_amounts_per_token() {
+    let token = Pubkey::new_unique();
+    let ataas = vec![
+      TokenAndAmount { token, amount: 10 },
+      TokenAndAmount { token, amount: 25 },
+    ];
+    // Passes the capacity and duplicate checks and fails on the next one.
+    let result = check_allowed_tokens_and_amounts(&[], &ataas);
+    assert_eq!(
+      result.unwrap_err(),
+      ChainbillsError::InvalidRemainingAccountsLength.into()
+    );
+  }
+
+  #[test]
+  fn accepts_ataas_at_capacity() {
+    let ataas = (0..Payable::MAX_PAYABLES_TOKENS)
+      .map(|_| TokenAndAmount {
+        token: Pubkey::new_unique(),
+        amount: 1,
+      })
+      .collect::<Vec<_>>();
+    // Passes the capacity and duplicate checks and fails on the next one.
+    let result = check_allowed_tokens_and_amounts(&[], &ataas);
+    assert_eq!(
+      result.unwrap_err(),
+      ChainbillsError::InvalidRemainingAccountsLength.into()
+    );
+  }
+}
@@ -0,0 +1,108 @@
+use crate::{context::GetActivity, error::ChainbillsError, state::*};
+use anchor_lang::prelude::*;
+
+/// Ensures that the count is of an existing activity on this chain.
+fn check_activity_count(
+  chain_count: u64,
+  chain_stats: &ChainStats,
+) -> Result<()> {
+  require!(
+    chain_count > 0 && chain_count <= chain_stats.activities_count,
+    ChainbillsError::InvalidActivityCount
+  );
+  Ok(())
+}
+
+/// Logs the ActivityRecord with the given chain count.
+///
+/// ### args
+/// * chain_count<u64>: The nth count of the activity on this chain.
+#[inline(never)]
+pub fn get_activity_handler(
+  ctx: Context<GetActivity>,
+  chain_count: u64,
+) -> Result<()> {
+  check_activity_count(chain_count, &ctx.accounts.chain_stats)?;
+
+  let activity = &ctx.accounts.activity;
+  msg!(
+    "Activity {}: type {}, entity {}, user count {}, payable count {}, timestamp {}",
+    activity.chain_count,
+    activity.activity_type as u8,
+    activity.entity,
+    activity.user_count,
+    activity.payable_count,
+    activity.timestamp
+  );
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn chain_stats(activities_count: u64) -> ChainStats {
+    ChainStats {
+      users_count: 1,
+      payables_count: 1,
+      user_payments_count: 0,
+      payable_payments_count: 0,
+      withdrawals_count: 0,
+      activities_count,
+    }
+  }
+
+  #[test]
+  fn derives_activity_addresses_by_chain_count() {
+    let (first, _) = ActivityRecord::pda(1);
+    let (second, _) = ActivityRecord::pda(2);
+    assert_ne!(first, second);
+    assert_eq!(
+      first,
+      Pubkey::find_program_address(
+        &[ActivityRecord::SEED_PREFIX, &1u64.to_le_bytes()[..]],
+        &crate::ID
+      )
+      .0
+    );
+  }
+
+  #[test]
+  fn reads_back_activities_of_different_operations() {
+    // A user was initialized and then created a payable.
+    let stats = chain_stats(2);
+    let records = [
+      (1, ActivityType::InitializedUser),
+      (2, ActivityType::CreatedPayable),
+    ]
+    .map(|(chain_count, activity_type)| ActivityRecord {
+      chain_count,
+      user_count: chain_count,
+      payable_count: chain_count - 1,
+      timestamp: 0,
+      entity: Pubkey::new_unique(),
+      activity_type,
+    });
+
+    for record in records {
+      assert!(check_activity_count(record.chain_count, &stats).is_ok());
+
+      // The record stored at the derived address reads back as it was.
+      let mut data = vec![];
+      record.try_serialize(&mut data).unwrap();
+      assert_eq!(data.len(), ActivityRecord::SPACE);
+      let read = ActivityRecord::try_deserialize(&mut &data[..]).unwrap();
+      assert_eq!(read.chain_count, record.chain_count);
+      assert_eq!(read.entity, record.entity);
+      assert_eq!(read.activity_type as u8, record.activity_type as u8);
+    }
+
+    // Counts past the recorded activities are invalid.
+    for count in [0, 3] {
+      assert_eq!(
+        check_activity_count(count, &stats).unwrap_err(),
+        ChainbillsError::InvalidActivityCount.into()
+      );
+    }
+  }
+}
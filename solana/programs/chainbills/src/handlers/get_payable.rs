@@ -0,0 +1,88 @@
+use crate::{context::GetPayable, error::ChainbillsError, state::Payable};
+use anchor_lang::prelude::*;
+
+/// Reads a Payable from its account's owner and data. Fails with
+/// InvalidPayableId if the account isn't an initialized Payable of this
+/// program.
+pub fn read_payable(owner: &Pubkey, data: &[u8]) -> Result<Payable> {
+  require!(*owner == crate::ID, ChainbillsError::InvalidPayableId);
+  Payable::try_deserialize(&mut &data[..])
+    .map_err(|_| error!(ChainbillsError::InvalidPayableId))
+}
+
+/// Logs the host, accepted tokens and amounts, and counts of the payable,
+/// for payers' wallets to show what is being paid for before paying.
+#[inline(never)]
+pub fn get_payable_handler(ctx: Context<GetPayable>) -> Result<()> {
+  let info = ctx.accounts.payable.to_account_info();
+  let payable = read_payable(info.owner, &info.try_borrow_data()?)?;
+  msg!(
+    "Payable {}: host {}, closed {}, payments {}, balances {}",
+    info.key(),
+    payable.host,
+    payable.is_closed,
+    payable.payments_count,
+    payable.balances.len()
+  );
+  for taa in payable.allowed_tokens_and_amounts.iter() {
+    msg!("Accepts {} of {}", taa.amount, taa.token);
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::state::TokenAndAmount;
+
+  fn payable() -> Payable {
+    Payable {
+      payments_count: 2,
+      activities_count: 3,
+      allowed_tokens_and_amounts: vec![TokenAndAmount {
+        token: Pubkey::new_unique(),
+        amount: 500,
+      }],
+      ..Payable::test_default()
+    }
+  }
+
+  #[test]
+  fn reads_back_the_created_payable() {
+    let created = payable();
+    let mut data = Vec::new();
+    created.try_serialize(&mut data).unwrap();
+
+    let read = read_payable(&crate::ID, &data).unwrap();
+    assert_eq!(read.host, created.host);
+    assert_eq!(read.payments_count, 2);
+    assert_eq!(read.allowed_tokens_and_amounts.len(), 1);
+    assert_eq!(
+      read.allowed_tokens_and_amounts[0].token,
+      created.allowed_tokens_and_amounts[0].token
+    );
+    assert_eq!(read.allowed_tokens_and_amounts[0].amount, 500);
+  }
+
+  #[test]
+  fn rejects_unknown_payable_ids() {
+    let mut data = Vec::new();
+    payable().try_serialize(&mut data).unwrap();
+
+    // Not owned by this program.
+    assert_eq!(
+      read_payable(&Pubkey::new_unique(), &data).err().unwrap(),
+      error!(ChainbillsError::InvalidPayableId)
+    );
+    // Uninitialized account.
+    assert_eq!(
+      read_payable(&crate::ID, &[]).err().unwrap(),
+      error!(ChainbillsError::InvalidPayableId)
+    );
+    // Not a Payable.
+    assert_eq!(
+      read_payable(&crate::ID, &[0; 64]).err().unwrap(),
+      error!(ChainbillsError::InvalidPayableId)
+    );
+  }
+}
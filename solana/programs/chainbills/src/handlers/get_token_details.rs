@@ -0,0 +1,33 @@
+use crate::context::GetTokenDetails;
+use anchor_lang::{prelude::*, solana_program::clock};
+
+/// Logs the support status and maximum withdrawal fees of the token, for
+/// clients to show fees before a withdrawal. Both the fees in effect now and
+/// any pending increase (with when it applies) are logged.
+///
+/// ### args
+/// * token<Pubkey>: The token mint whose details are being read.
+#[inline(never)]
+pub fn get_token_details_handler(
+  ctx: Context<GetTokenDetails>,
+  token: Pubkey,
+) -> Result<()> {
+  let token_details = &ctx.accounts.token_details;
+  let now = clock::Clock::get()?.unix_timestamp as u64;
+  let effective_max_withdrawal_fees =
+    token_details.effective_max_withdrawal_fees(now);
+  msg!(
+    "Token {}: supported {}, max withdrawal fees {}",
+    token,
+    token_details.is_supported,
+    effective_max_withdrawal_fees
+  );
+  if now < token_details.pending_fees_effective_at {
+    msg!(
+      "Pending max withdrawal fees {} from {}",
+      token_details.pending_max_withdrawal_fees,
+      token_details.pending_fees_effective_at
+    );
+  }
+  Ok(())
+}
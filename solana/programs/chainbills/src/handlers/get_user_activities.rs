@@ -0,0 +1,333 @@
+use crate::{context::GetUserActivities, error::ChainbillsError, state::*};
+use anchor_lang::prelude::*;
+
+/// Returns the user counts of the latest `limit` activities of a user with
+/// the given activities_count, newest first.
+pub fn latest_user_activity_counts(
+  activities_count: u64,
+  limit: u64,
+) -> Vec<u64> {
+  let oldest = activities_count.saturating_sub(limit);
+  (oldest + 1..=activities_count).rev().collect()
+}
+
+/// Derives the UserActivityInfo addresses of the latest `limit` activities
+/// of the given wallet, newest first. Each one holds the chain count from
+/// which the ActivityRecord's address is derived.
+pub fn latest_user_activity_info_addresses(
+  wallet: Pubkey,
+  activities_count: u64,
+  limit: u64,
+) -> Vec<Pubkey> {
+  latest_user_activity_counts(activities_count, limit)
+    .into_iter()
+    .map(|user_count| UserActivityInfo::pda(wallet, user_count).0)
+    .collect()
+}
+
+/// Reads the latest `limit` activities of the given wallet from accounts
+/// given as (address, owner, data) in UserActivityInfo and ActivityRecord
+/// pairs, newest first. Fails with InvalidUserActivityAccount if any account
+/// isn't the one derived for its position.
+pub fn read_user_activities(
+  wallet: Pubkey,
+  activities_count: u64,
+  limit: u64,
+  accounts: &[(Pubkey, Pubkey, &[u8])],
+) -> Result<Vec<ActivityRecord>> {
+  let user_counts = latest_user_activity_counts(activities_count, limit);
+  require!(
+    accounts.len() == user_counts.len() * 2,
+    ChainbillsError::InvalidRemainingAccountsLength
+  );
+
+  let mut activities = Vec::with_capacity(user_counts.len());
+  for (user_count, pair) in user_counts.into_iter().zip(accounts.chunks(2)) {
+    let (info_key, info_owner, info_data) = pair[0];
+    let (record_key, record_owner, record_data) = pair[1];
+
+    require!(
+      info_key == UserActivityInfo::pda(wallet, user_count).0
+        && info_owner == crate::ID,
+      ChainbillsError::InvalidUserActivityAccount
+    );
+    let info = UserActivityInfo::try_deserialize(&mut &info_data[..])
+      .map_err(|_| error!(ChainbillsError::InvalidUserActivityAccount))?;
+
+    require!(
+      record_key == ActivityRecord::pda(info.chain_count).0
+        && record_owner == crate::ID,
+      ChainbillsError::InvalidUserActivityAccount
+    );
+    let activity = ActivityRecord::try_deserialize(&mut &record_data[..])
+      .map_err(|_| error!(ChainbillsError::InvalidUserActivityAccount))?;
+    activities.push(activity);
+  }
+  Ok(activities)
+}
+
+/// Logs the latest activities of a user, newest first.
+///
+/// ### args
+/// * limit<u64>: The maximum number of activities to read.
+#[inline(never)]
+pub fn get_user_activities_handler(
+  ctx: Context<GetUserActivities>,
+  limit: u64,
+) -> Result<()> {
+  let data = ctx
+    .remaining_accounts
+    .iter()
+    .map(|info| info.try_borrow_data())
+    .collect::<std::result::Result<Vec<_>, _>>()?;
+  let accounts = ctx
+    .remaining_accounts
+    .iter()
+    .zip(data.iter())
+    .map(|(info, data)| (info.key(), *info.owner, &data[..]))
+    .collect::<Vec<_>>();
+
+  let activities = read_user_activities(
+    ctx.accounts.wallet.key(),
+    ctx.accounts.user.activities_count,
+    limit,
+    &accounts,
+  )?;
+  for activity in activities.iter() {
+    msg!(
+      "Activity {}: type {}, entity {}, user count {}, payable count {}, timestamp {}",
+      activity.chain_count,
+      activity.activity_type as u8,
+      activity.entity,
+      activity.user_count,
+      activity.payable_count,
+      activity.timestamp
+    );
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  struct Recorded {
+    info_key: Pubkey,
+    info_data: Vec<u8>,
+    record_key: Pubkey,
+    record_data: Vec<u8>,
+  }
+
+  /// Records an activity of the user as the handlers do, with its
+  /// UserActivityInfo pointing at the ActivityRecord on this chain.
+  fn record(
+    wallet: Pubkey,
+    user: &mut User,
+    chain_stats: &mut ChainStats,
+    activity_type: ActivityType,
+  ) -> Recorded {
+    chain_stats.activities_count = chain_stats.next_activity().unwrap();
+    user.activities_count = user.next_activity().unwrap();
+
+    let mut info_data = vec![];
+    UserActivityInfo {
+      chain_count: chain_stats.activities_count,
+    }
+    .try_serialize(&mut info_data)
+    .unwrap();
+    let mut record_data = vec![];
+    ActivityRecord {
+      chain_count: chain_stats.activities_count,
+      user_count: user.activities_count,
+      payable_count: 0,
+      timestamp: 0,
+      entity: Pubkey::new_unique(),
+      activity_type,
+    }
+    .try_serialize(&mut record_data)
+    .unwrap();
+
+    Recorded {
+      info_key: UserActivityInfo::pda(wallet, user.activities_count).0,
+      info_data,
+      record_key: ActivityRecord::pda(chain_stats.activities_count).0,
+      record_data,
+    }
+  }
+
+  fn accounts<'a>(
+    recorded: &[&'a Recorded],
+  ) -> Vec<(Pubkey, Pubkey, &'a [u8])> {
+    recorded
+      .iter()
+      .flat_map(|r| {
+        [
+          (r.info_key, crate::ID, &r.info_data[..]),
+          (r.record_key, crate::ID, &r.record_data[..]),
+        ]
+      })
+      .collect()
+  }
+
+  #[test]
+  fn counts_the_latest_activities_newest_first() {
+    assert_eq!(latest_user_activity_counts(5, 3), vec![5, 4, 3]);
+    assert_eq!(latest_user_activity_counts(2, 10), vec![2, 1]);
+    assert!(latest_user_activity_counts(0, 3).is_empty());
+    assert!(latest_user_activity_counts(4, 0).is_empty());
+  }
+
+  #[test]
+  fn reads_the_latest_activities_of_a_user() {
+    let wallet = Pubkey::new_unique();
+    let other = Pubkey::new_unique();
+    let mut user = User {
+      chain_count: 1,
+      payables_count: 0,
+      payments_count: 0,
+      withdrawals_count: 0,
+      activities_count: 0,
+    };
+    let mut chain_stats = ChainStats {
+      users_count: 2,
+      payables_count: 0,
+      user_payments_count: 0,
+      payable_payments_count: 0,
+      withdrawals_count: 0,
+      activities_count: 0,
+    };
+
+    let initialized = record(
+      wallet,
+      &mut user,
+      &mut chain_stats,
+      ActivityType::InitializedUser,
+    );
+    let created = record(
+      wallet,
+      &mut user,
+      &mut chain_stats,
+      ActivityType::CreatedPayable,
+    );
+    // Another user's activity in between, so user and chain counts differ.
+    let mut other_user = User { ..user };
+    record(
+      other,
+      &mut other_user,
+      &mut chain_stats,
+      ActivityType::UserPaid,
+    );
+    let paid =
+      record(wallet, &mut user, &mut chain_stats, ActivityType::UserPaid);
+    let withdrew =
+      record(wallet, &mut user, &mut chain_stats, ActivityType::Withdrew);
+
+    // The derived addresses are those at which the activities were recorded.
+    assert_eq!(
+      latest_user_activity_info_addresses(wallet, user.activities_count, 3),
+      [&withdrew, &paid, &created].map(|r| r.info_key).to_vec()
+    );
+
+    let activities = read_user_activities(
+      wallet,
+      user.activities_count,
+      3,
+      &accounts(&[&withdrew, &paid, &created]),
+    )
+    .unwrap();
+    assert_eq!(
+      activities
+        .iter()
+        .map(|a| (a.user_count, a.chain_count, a.activity_type as u8))
+        .collect::<Vec<_>>(),
+      vec![
+        (4, 5, ActivityType::Withdrew as u8),
+        (3, 4, ActivityType::UserPaid as u8),
+        (2, 2, ActivityType::CreatedPayable as u8),
+      ]
+    );
+
+    // Asking for more than exist returns all of them.
+    let all = read_user_activities(
+      wallet,
+      user.activities_count,
+      10,
+      &accounts(&[&withdrew, &paid, &created, &initialized]),
+    )
+    .unwrap();
+    assert_eq!(all.len(), 4);
+    assert_eq!(
+      all[3].activity_type as u8,
+      ActivityType::InitializedUser as u8
+    );
+  }
+
+  #[test]
+  fn rejects_accounts_out_of_order_or_missing() {
+    let wallet = Pubkey::new_unique();
+    let mut user = User {
+      chain_count: 1,
+      payables_count: 0,
+      payments_count: 0,
+      withdrawals_count: 0,
+      activities_count: 0,
+    };
+    let mut chain_stats = ChainStats {
+      users_count: 1,
+      payables_count: 0,
+      user_payments_count: 0,
+      payable_payments_count: 0,
+      withdrawals_count: 0,
+      activities_count: 0,
+    };
+    let first = record(
+      wallet,
+      &mut user,
+      &mut chain_stats,
+      ActivityType::InitializedUser,
+    );
+    let second = record(
+      wallet,
+      &mut user,
+      &mut chain_stats,
+      ActivityType::CreatedPayable,
+    );
+
+    // Oldest first instead of newest first.
+    assert_eq!(
+      read_user_activities(wallet, 2, 2, &accounts(&[&first, &second]))
+        .err()
+        .unwrap(),
+      ChainbillsError::InvalidUserActivityAccount.into()
+    );
+
+    // Another wallet's derivations don't match.
+    assert_eq!(
+      read_user_activities(
+        Pubkey::new_unique(),
+        2,
+        2,
+        &accounts(&[&second, &first])
+      )
+      .err()
+      .unwrap(),
+      ChainbillsError::InvalidUserActivityAccount.into()
+    );
+
+    // Accounts not owned by the program are rejected.
+    let mut foreign = accounts(&[&second]);
+    foreign[1].1 = Pubkey::new_unique();
+    assert_eq!(
+      read_user_activities(wallet, 2, 1, &foreign).err().unwrap(),
+      ChainbillsError::InvalidUserActivityAccount.into()
+    );
+
+    // Each activity needs both of its accounts.
+    assert_eq!(
+      read_user_activities(wallet, 2, 2, &accounts(&[&second]))
+        .err()
+        .unwrap(),
+      ChainbillsError::InvalidRemainingAccountsLength.into()
+    );
+  }
+}
@@ -1,7 +1,17 @@
-use crate::{context::Initialize, events::*};
+use crate::{context::Initialize, error::ChainbillsError, events::*, state::*};
 use anchor_lang::prelude::*;
 use wormhole_anchor_sdk::wormhole;
 
+/// Ensures that a withdrawal fee percentage doesn't exceed
+/// [MAX_WITHDRAWAL_FEE_PERCENTAGE](Config::MAX_WITHDRAWAL_FEE_PERCENTAGE).
+pub fn check_withdrawal_fee_percentage(percentage: u16) -> Result<()> {
+  require!(
+    percentage <= Config::MAX_WITHDRAWAL_FEE_PERCENTAGE,
+    ChainbillsError::FeeTooHigh
+  );
+  Ok(())
+}
+
 /// Initialize the Config and Solana's ChainStats.
 ///
 /// Should be run once by the deployer of the program
@@ -12,6 +22,7 @@ pub fn initialize_handler(ctx: Context<Initialize>) -> Result<()> {
   let config = &mut ctx.accounts.config.load_init()?;
   config.chain_id = wormhole::CHAIN_ID_SOLANA;
   config.withdrawal_fee_percentage = 200u16; // 2.00%
+  check_withdrawal_fee_percentage(config.withdrawal_fee_percentage)?;
   config.owner = *ctx.accounts.owner.to_account_info().key;
   config.chainbills_fee_collector =
     *ctx.accounts.chainbills_fee_collector.to_account_info().key;
@@ -74,3 +85,28 @@ pub fn initialize_handler(ctx: Context<Initialize>) -> Result<()> {
   emit!(Initialized {});
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn caps_the_withdrawal_fee_percentage() {
+    assert!(check_withdrawal_fee_percentage(200).is_ok());
+    assert!(check_withdrawal_fee_percentage(
+      Config::MAX_WITHDRAWAL_FEE_PERCENTAGE
+    )
+    .is_ok());
+    assert_eq!(
+      check_withdrawal_fee_percentage(
+        Config::MAX_WITHDRAWAL_FEE_PERCENTAGE + 1
+      )
+      .unwrap_err(),
+      ChainbillsError::FeeTooHigh.into()
+    );
+    assert_eq!(
+      check_withdrawal_fee_percentage(10000).unwrap_err(),
+      ChainbillsError::FeeTooHigh.into()
+    );
+  }
+}
@@ -0,0 +1,218 @@
+use crate::{
+  context::InitializePayableReceived,
+  error::ChainbillsError,
+  events::*,
+  handlers::{
+    check_cross_chain_not_paused, check_foreign_emitter, check_vaa_finality,
+  },
+  payload::{is_zero_bytes32, CreatePayablePayload},
+  state::*,
+};
+use anchor_lang::{prelude::*, solana_program::clock};
+use wormhole_anchor_sdk::wormhole;
+
+/// Checks the VAA of a payable's creation on another chain and saves its
+/// mirror, recording the VAA as consumed.
+#[allow(clippy::too_many_arguments)]
+fn receive_created_payable(
+  config: &Config,
+  foreign_contract_key: Pubkey,
+  foreign_contract: &ForeignContract,
+  posted: &wormhole::PostedVaa<CreatePayablePayload>,
+  vaa_hash: [u8; 32],
+  now: u64,
+  foreign_payable: &mut ForeignPayable,
+  wormhole_received: &mut WormholeReceived,
+) -> Result<()> {
+  /* CHECKS */
+  check_cross_chain_not_paused(config)?;
+  check_vaa_finality(config, posted.finality())?;
+  check_foreign_emitter(
+    foreign_contract_key,
+    foreign_contract,
+    posted.emitter_chain(),
+    posted.emitter_address(),
+  )?;
+  let CreatePayablePayload { payable_id, host } = *posted.data();
+  require!(
+    !is_zero_bytes32(&payable_id),
+    ChainbillsError::InvalidPayableId
+  );
+  require!(!is_zero_bytes32(&host), ChainbillsError::InvalidPayload);
+
+  /* STATE CHANGES */
+  wormhole_received.batch_id = posted.batch_id();
+  wormhole_received.vaa_hash = vaa_hash;
+
+  foreign_payable.payable_id = payable_id;
+  foreign_payable.chain_id = posted.emitter_chain();
+  foreign_payable.host = host;
+  foreign_payable.created_at = now;
+  Ok(())
+}
+
+/// Mirrors a payable created on another chain from the VAA of its creation,
+/// so that payments on this chain can target it. The VAA must have been
+/// emitted by the registered foreign contract of its chain and can only be
+/// consumed once.
+///
+/// ### args
+/// * vaa_hash<[u8; 32]>: The Keccak256 hash of the VAA.
+#[inline(never)]
+pub fn initialize_payable_received_handler(
+  ctx: Context<InitializePayableReceived>,
+  vaa_hash: [u8; 32],
+) -> Result<()> {
+  let foreign_contract_key = ctx.accounts.foreign_contract.key();
+  receive_created_payable(
+    &*ctx.accounts.config.load()?,
+    foreign_contract_key,
+    &ctx.accounts.foreign_contract,
+    &ctx.accounts.posted,
+    vaa_hash,
+    clock::Clock::get()?.unix_timestamp as u64,
+    ctx.accounts.foreign_payable.as_mut(),
+    ctx.accounts.wormhole_received.as_mut(),
+  )?;
+
+  let foreign_payable = &ctx.accounts.foreign_payable;
+  msg!(
+    "Initialized Payable Received from chain {}.",
+    foreign_payable.chain_id
+  );
+  emit!(InitializedPayableReceived {
+    payable_id: foreign_payable.payable_id,
+    chain_id: foreign_payable.chain_id,
+    host_wallet: foreign_payable.host,
+  });
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::handlers::is_vaa_consumed;
+  use wormhole_anchor_sdk::wormhole::PostedVaaMeta;
+
+  const CHAIN: u16 = 7;
+  const EMITTER: [u8; 32] = [9; 32];
+
+  fn config() -> Config {
+    Config {
+      min_vaa_finality: 1,
+      ..Config::test_default()
+    }
+  }
+
+  fn posted(
+    emitter_address: [u8; 32],
+    payload: CreatePayablePayload,
+  ) -> wormhole::PostedVaa<CreatePayablePayload> {
+    wormhole::PostedVaa {
+      meta: PostedVaaMeta {
+        finality: 1,
+        emitter_chain: CHAIN,
+        emitter_address,
+        ..Default::default()
+      },
+      payload: (CreatePayablePayload::LENGTH as u32, payload),
+    }
+  }
+
+  fn new_foreign_payable() -> ForeignPayable {
+    ForeignPayable {
+      payable_id: [0; 32],
+      chain_id: 0,
+      host: [0; 32],
+      created_at: 0,
+    }
+  }
+
+  #[test]
+  fn mirrors_payables_from_valid_vaas_once() {
+    let vaa_hash = [5u8; 32];
+    let payload = CreatePayablePayload {
+      payable_id: [1; 32],
+      host: [2; 32],
+    };
+    let mut foreign_payable = new_foreign_payable();
+    let mut received = WormholeReceived::default();
+    receive_created_payable(
+      &config(),
+      ForeignContract::pda(CHAIN).0,
+      &ForeignContract { address: EMITTER },
+      &posted(EMITTER, payload),
+      vaa_hash,
+      100,
+      &mut foreign_payable,
+      &mut received,
+    )
+    .unwrap();
+    assert_eq!(foreign_payable.payable_id, [1; 32]);
+    assert_eq!(foreign_payable.chain_id, CHAIN);
+    assert_eq!(foreign_payable.host, [2; 32]);
+    assert_eq!(foreign_payable.created_at, 100);
+
+    // The VAA is now consumed. A replay of it fails as the context can't
+    // initialize its WormholeReceived account again.
+    let mut data = vec![];
+    received.try_serialize(&mut data).unwrap();
+    assert!(is_vaa_consumed(&crate::ID, &data, vaa_hash));
+    assert!(!is_vaa_consumed(&crate::ID, &data, [6u8; 32]));
+  }
+
+  #[test]
+  fn rejects_vaas_from_unregistered_emitters() {
+    let payload = CreatePayablePayload {
+      payable_id: [1; 32],
+      host: [2; 32],
+    };
+    let received = |emitter, key, payload| {
+      receive_created_payable(
+        &config(),
+        key,
+        &ForeignContract { address: EMITTER },
+        &posted(emitter, payload),
+        [5u8; 32],
+        100,
+        &mut new_foreign_payable(),
+        &mut WormholeReceived::default(),
+      )
+      .unwrap_err()
+    };
+
+    // Another emitter on the registered chain.
+    assert_eq!(
+      received([8; 32], ForeignContract::pda(CHAIN).0, payload),
+      ChainbillsError::InvalidForeignEmitter.into()
+    );
+    // The ForeignContract of another chain.
+    assert_eq!(
+      received(EMITTER, ForeignContract::pda(CHAIN + 1).0, payload),
+      ChainbillsError::InvalidForeignEmitter.into()
+    );
+    // Zero payable IDs and hosts.
+    assert_eq!(
+      received(
+        EMITTER,
+        ForeignContract::pda(CHAIN).0,
+        CreatePayablePayload {
+          payable_id: [0; 32],
+          host: [2; 32],
+        }
+      ),
+      ChainbillsError::InvalidPayableId.into()
+    );
+    assert_eq!(
+      received(
+        EMITTER,
+        ForeignContract::pda(CHAIN).0,
+        CreatePayablePayload {
+          payable_id: [1; 32],
+          host: [0; 32],
+        }
+      ),
+      ChainbillsError::InvalidPayload.into()
+    );
+  }
+}
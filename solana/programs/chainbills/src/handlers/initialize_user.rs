@@ -1,24 +1,46 @@
-use crate::{context::InitializeUser, events::*, state::ActivityType};
+use crate::{
+  context::InitializeUser,
+  events::*,
+  state::{ActivityType, ChainStats, User},
+};
 use anchor_lang::prelude::*;
 use solana_program::clock;
 
+/// Increments the chain's users and activities counts and initializes the
+/// user's counts with them.
+fn initialize_user_counts(
+  chain_stats: &mut ChainStats,
+  user: &mut User,
+) -> Result<()> {
+  chain_stats.users_count = chain_stats.next_user()?;
+  chain_stats.activities_count = chain_stats.next_activity()?;
+
+  user.chain_count = chain_stats.users_count;
+  user.payables_count = 0;
+  user.payments_count = 0;
+  user.withdrawals_count = 0;
+  user.activities_count = 1; // Start at 1 to record the initialization.
+  Ok(())
+}
+
+/// The event emitted when a user is initialized. Its chain_count is the
+/// chain's users_count after the initialization, as on CosmWasm.
+fn initialized_user_event(wallet: Pubkey, user: &User) -> InitializedUser {
+  InitializedUser {
+    wallet,
+    chain_count: user.chain_count,
+  }
+}
+
 /// Initialize a User
 ///
 /// Should be run once for the first time that anyone uses their wallet to
 /// interact with this program.
 pub fn initialize_user_handler(ctx: Context<InitializeUser>) -> Result<()> {
-  // Increment chain count for users and activities.
+  // Increment chain count for users and activities and initialize the user.
   let chain_stats = ctx.accounts.chain_stats.as_mut();
-  chain_stats.users_count = chain_stats.next_user();
-  chain_stats.activities_count = chain_stats.next_activity();
-
-  // Initialize the user.
   let user = ctx.accounts.user.as_mut();
-  user.chain_count = chain_stats.users_count;
-  user.payables_count = 0;
-  user.payments_count = 0;
-  user.withdrawals_count = 0;
-  user.activities_count = 1; // Start at 1 to record the initialization.
+  initialize_user_counts(chain_stats, user)?;
 
   // Initialize the user address.
   let user_address = ctx.accounts.user_address.as_mut();
@@ -39,9 +61,47 @@ pub fn initialize_user_handler(ctx: Context<InitializeUser>) -> Result<()> {
 
   // Emit log and event.
   msg!("Initialized User with chain_count: {}.", user.chain_count);
-  emit!(InitializedUser {
-    wallet: ctx.accounts.signer.key(),
-    chain_count: user.chain_count
-  });
+  emit!(initialized_user_event(ctx.accounts.signer.key(), user));
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn new_user() -> User {
+    User {
+      chain_count: 0,
+      payables_count: 0,
+      payments_count: 0,
+      withdrawals_count: 0,
+      activities_count: 0,
+    }
+  }
+
+  #[test]
+  fn emits_the_incremented_users_count() {
+    let mut chain_stats = ChainStats {
+      users_count: 0,
+      payables_count: 0,
+      user_payments_count: 0,
+      payable_payments_count: 0,
+      withdrawals_count: 0,
+      activities_count: 0,
+    };
+
+    for expected_count in 1..=2 {
+      let wallet = Pubkey::new_unique();
+      let mut user = new_user();
+      initialize_user_counts(&mut chain_stats, &mut user).unwrap();
+      assert_eq!(chain_stats.users_count, expected_count);
+      assert_eq!(chain_stats.activities_count, expected_count);
+      assert_eq!(user.chain_count, expected_count);
+      assert_eq!(user.activities_count, 1);
+
+      let event = initialized_user_event(wallet, &user);
+      assert_eq!(event.wallet, wallet);
+      assert_eq!(event.chain_count, chain_stats.users_count);
+    }
+  }
+}
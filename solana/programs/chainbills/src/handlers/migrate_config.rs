@@ -0,0 +1,146 @@
+use crate::{context::*, state::*};
+use anchor_lang::{
+  prelude::*,
+  system_program::{self, Transfer},
+};
+
+/// Grows the account to the given space, with the payer topping up its rent.
+/// The added bytes are zeroed. Does nothing if the account is big enough.
+pub fn grow_account<'info>(
+  account: &AccountInfo<'info>,
+  payer: &AccountInfo<'info>,
+  system_program: &AccountInfo<'info>,
+  space: usize,
+) -> Result<()> {
+  if account.data_len() >= space {
+    return Ok(());
+  }
+  let rent = Rent::get()?.minimum_balance(space);
+  let shortfall = rent.saturating_sub(account.lamports());
+  if shortfall > 0 {
+    system_program::transfer(
+      CpiContext::new(
+        system_program.clone(),
+        Transfer {
+          from: payer.clone(),
+          to: account.clone(),
+        },
+      ),
+      shortfall,
+    )?;
+  }
+  account.realloc(space, true)?;
+  Ok(())
+}
+
+/// Grows the Config account to the current Config::SPACE after an upgrade
+/// added fields to it. The added bytes are zeroed, which is the default of
+/// every added field. The owner pays the rent of the added space. Should be
+/// called only by the owner of this program.
+#[inline(never)]
+pub fn migrate_config_handler(ctx: Context<MigrateConfig>) -> Result<()> {
+  grow_account(
+    &ctx.accounts.config.to_account_info(),
+    &ctx.accounts.owner.to_account_info(),
+    &ctx.accounts.system_program.to_account_info(),
+    Config::SPACE,
+  )?;
+
+  msg!("Migrated Config.");
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::ChainbillsError;
+  use anchor_lang::Discriminator;
+
+  /// The size of the Config account before any fields were added to it.
+  const LEGACY_SPACE: usize =
+    space::DISCRIMINATOR + (2 * space::U16) + (6 * space::PUBKEY);
+
+  /// The config's data as laid out in an account of its current size.
+  fn account_data(config: &Config) -> Vec<u8> {
+    let mut data = Config::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(config));
+    data
+  }
+
+  fn read_owner(owner: &Pubkey, data: &mut [u8]) -> Result<Pubkey> {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let info =
+      AccountInfo::new(&key, false, true, &mut lamports, data, owner, false, 0);
+    Config::read_owner(&info)
+  }
+
+  #[test]
+  fn keeps_the_legacy_fields_in_place() {
+    let config = Config {
+      chain_id: 30,
+      withdrawal_fee_percentage: 150,
+      ..Config::test_default()
+    };
+    let data = account_data(&config);
+    assert_eq!(data.len(), Config::SPACE);
+
+    // Legacy accounts hold only the leading fields. Zeroing the rest, as
+    // migrate_config's realloc does, keeps their values and defaults the
+    // added fields.
+    let mut migrated = data[..LEGACY_SPACE].to_vec();
+    migrated.resize(Config::SPACE, 0);
+    let read: Config =
+      bytemuck::pod_read_unaligned(&migrated[space::DISCRIMINATOR..]);
+    assert_eq!(read.chain_id, 30);
+    assert_eq!(read.withdrawal_fee_percentage, 150);
+    assert_eq!(read.owner, config.owner);
+    assert_eq!(
+      read.chainbills_fee_collector,
+      config.chainbills_fee_collector
+    );
+    assert_eq!(read.wormhole_bridge, config.wormhole_bridge);
+    assert_eq!(read.wormhole_emitter, config.wormhole_emitter);
+    assert_eq!(read.wormhole_fee_collector, config.wormhole_fee_collector);
+    assert_eq!(read.wormhole_sequence, config.wormhole_sequence);
+    assert!(!read.is_paused());
+    assert!(!read.is_cross_chain_paused());
+    assert!(!read.is_payments_paused());
+    assert!(!read.is_withdrawals_paused());
+    assert_eq!(read.min_vaa_finality, 0);
+    assert_eq!(read.max_withdrawal_amount, 0);
+    assert_eq!(read.pending_owner, Pubkey::default());
+  }
+
+  #[test]
+  fn reads_the_owner_of_legacy_and_current_configs() {
+    let config = Config::test_default();
+    let mut data = account_data(&config);
+    assert_eq!(read_owner(&crate::ID, &mut data).unwrap(), config.owner);
+
+    let mut legacy = data[..LEGACY_SPACE].to_vec();
+    assert_eq!(read_owner(&crate::ID, &mut legacy).unwrap(), config.owner);
+  }
+
+  #[test]
+  fn rejects_other_accounts_as_config() {
+    let mut data = account_data(&Config::test_default());
+    assert_eq!(
+      read_owner(&Pubkey::new_unique(), &mut data).unwrap_err(),
+      ChainbillsError::InvalidConfigAccount.into()
+    );
+
+    let mut data = account_data(&Config::test_default());
+    data[..space::DISCRIMINATOR].copy_from_slice(&[0; 8]);
+    assert_eq!(
+      read_owner(&crate::ID, &mut data).unwrap_err(),
+      ChainbillsError::InvalidConfigAccount.into()
+    );
+
+    let mut data = account_data(&Config::test_default());
+    assert_eq!(
+      read_owner(&crate::ID, &mut data[..20]).unwrap_err(),
+      ChainbillsError::InvalidConfigAccount.into()
+    );
+  }
+}
@@ -0,0 +1,107 @@
+use crate::{context::*, handlers::grow_account, state::*};
+use anchor_lang::prelude::*;
+
+/// Writes the payable over the account data, zeroing whatever follows it so
+/// that fields added to Payable later read their defaults from there.
+fn rewrite_payable(payable: &Payable, data: &mut [u8]) -> Result<()> {
+  data.fill(0);
+  payable.try_serialize(&mut &mut data[..])
+}
+
+/// Grows a Payable account to the current layout after an upgrade added
+/// fields to Payable. The added fields take their defaults. Anyone can call
+/// it as it doesn't change any of the payable's existing fields.
+#[inline(never)]
+pub fn migrate_payable_handler(ctx: Context<MigratePayable>) -> Result<()> {
+  let info = ctx.accounts.payable.to_account_info();
+  let payable = Payable::read_compat(&info.try_borrow_data()?)?;
+  grow_account(
+    &info,
+    &ctx.accounts.signer.to_account_info(),
+    &ctx.accounts.system_program.to_account_info(),
+    payable.space(),
+  )?;
+  rewrite_payable(&payable, &mut info.try_borrow_mut_data()?)?;
+
+  msg!("Migrated Payable.");
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn serialize(payable: &Payable) -> Vec<u8> {
+    let mut data = vec![];
+    payable.try_serialize(&mut data).unwrap();
+    data
+  }
+
+  #[test]
+  fn migrates_payables_without_the_optional_fields() {
+    let payable = Payable {
+      allowed_tokens_and_amounts: vec![TokenAndAmount {
+        token: Pubkey::new_unique(),
+        amount: 100,
+      }],
+      balances: vec![TokenAndAmount {
+        token: Pubkey::new_unique(),
+        amount: 50,
+      }],
+      payments_count: 3,
+      ..Payable::test_default()
+    };
+
+    // Before the optional fields and rejects_cross_chain were added, the
+    // payable ended right after its balances. That is each of their None
+    // tags and the rejects_cross_chain byte short.
+    let full = serialize(&payable);
+    let legacy = &full[..full.len() - 7];
+    assert!(Payable::try_deserialize(&mut &legacy[..]).is_err());
+
+    let read = Payable::read_compat(legacy).unwrap();
+    assert_eq!(read.payments_count, 3);
+    assert_eq!(read.allowed_tokens_and_amounts[0].amount, 100);
+    assert_eq!(read.balances[0].amount, 50);
+    assert_eq!(read.fee_recipient, None);
+    assert_eq!(read.expires_at, None);
+    assert_eq!(read.goal_amount, None);
+    assert!(read.accepts_cross_chain());
+    assert_eq!(read.max_payments, None);
+
+    let mut data = legacy.to_vec();
+    data.resize(read.space(), 0);
+    rewrite_payable(&read, &mut data).unwrap();
+    assert_eq!(&data[..full.len()], &full[..]);
+  }
+
+  #[test]
+  fn keeps_migrated_payables_and_clears_their_stale_bytes() {
+    let payable = Payable {
+      expires_at: Some(1_000),
+      goal_token: Some(Pubkey::new_unique()),
+      goal_amount: Some(500),
+      ..Payable::test_default()
+    };
+    let mut data = serialize(&payable);
+    let len = data.len();
+    data.resize(payable.space(), 0xff);
+
+    let read = Payable::read_compat(&data).unwrap();
+    assert_eq!(read.expires_at, Some(1_000));
+    assert_eq!(read.goal_token, payable.goal_token);
+    assert_eq!(read.goal_amount, Some(500));
+
+    rewrite_payable(&read, &mut data).unwrap();
+    assert!(data[len..].iter().all(|byte| *byte == 0));
+    let read = Payable::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(read.goal_amount, Some(500));
+  }
+
+  #[test]
+  fn rejects_other_accounts() {
+    let mut data = serialize(&Payable::test_default());
+    data[..8].copy_from_slice(&[0; 8]);
+    assert!(Payable::read_compat(&data).is_err());
+  }
+}
@@ -1,22 +1,58 @@
-use crate::{context::*, error::ChainbillsError, events::*, state::*};
+use crate::{
+  context::*,
+  error::ChainbillsError,
+  events::*,
+  handlers::{check_payments_not_paused, check_token_supported},
+  state::*,
+};
 use anchor_lang::{
   prelude::*,
   solana_program::clock,
   system_program::{self, Transfer},
 };
-use anchor_spl::token::{self, Transfer as SplTransfer};
+use anchor_spl::token_interface::{
+  self,
+  spl_token_2022::{
+    extension::{
+      transfer_fee::TransferFeeConfig, BaseStateWithExtensions,
+      StateWithExtensions,
+    },
+    state::Mint as SplMint,
+  },
+  TransferChecked,
+};
+
+/// Ensures that the payable hasn't expired by the given time (in seconds).
+/// Payments at exactly the expiry time are still accepted.
+pub fn check_not_expired(payable: &Payable, now: u64) -> Result<()> {
+  if let Some(expires_at) = payable.expires_at {
+    require!(now <= expires_at, ChainbillsError::PayableExpired);
+  }
+  Ok(())
+}
+
+/// Computes the fee that the mint withholds when transferring the amount in
+/// the given epoch. Only Token-2022 mints with the transfer fee extension
+/// withhold fees, so it is zero for other mints.
+pub fn transfer_fee(mint_data: &[u8], epoch: u64, amount: u64) -> Result<u64> {
+  let mint = StateWithExtensions::<SplMint>::unpack(mint_data)?;
+  match mint.get_extension::<TransferFeeConfig>() {
+    Ok(config) => config
+      .calculate_epoch_fee(epoch, amount)
+      .ok_or(error!(ChainbillsError::Overflow)),
+    Err(_) => Ok(0),
+  }
+}
 
 fn check_pay_inputs(
   amount: u64,
   mint: Pubkey,
+  memo: &Option<String>,
   payable: &Account<Payable>,
   token_details: &Account<TokenDetails>,
 ) -> Result<()> {
   // Ensure that payments are currently accepted in the provided token.
-  require!(
-    token_details.is_supported,
-    ChainbillsError::UnsupportedToken
-  );
+  check_token_supported(token_details)?;
 
   // Ensure that amount is greater than zero
   require!(amount > 0, ChainbillsError::ZeroAmountSpecified);
@@ -24,27 +60,150 @@ fn check_pay_inputs(
   // Ensure that the payable is not closed
   require!(!payable.is_closed, ChainbillsError::PayableIsClosed);
 
-  // If this payable specified the tokens and amounts it can accept, ensure
-  // that the token and amount are matching.
+  // Ensure that the payable has not expired
+  check_not_expired(payable, clock::Clock::get()?.unix_timestamp as u64)?;
+
+  check_payment_amount(payable, mint, amount)?;
+
+  check_memo(memo)?;
+
+  check_balances_capacity(payable, mint)
+}
+
+/// Ensures that the payment's memo, if any, isn't too long.
+fn check_memo(memo: &Option<String>) -> Result<()> {
+  if let Some(memo) = memo {
+    require!(
+      memo.len() <= UserPayment::MAX_MEMO_LENGTH,
+      ChainbillsError::MemoTooLong
+    );
+  }
+  Ok(())
+}
+
+/// The event emitted when a user pays into a payable on this chain.
+fn user_paid_event(
+  payable_id: Pubkey,
+  payment_id: Pubkey,
+  user_payment: &UserPayment,
+) -> UserPaid {
+  UserPaid {
+    payable_id: payable_id.to_bytes(),
+    payer_wallet: user_payment.payer,
+    payment_id,
+    payable_chain_id: user_payment.payable_chain_id,
+    chain_count: user_payment.chain_count,
+    payer_count: user_payment.payer_count,
+    memo: user_payment.memo.clone(),
+  }
+}
+
+/// The event emitted when a payable on this chain receives a payment, after
+/// the payment was credited to it.
+pub fn payable_received_event(
+  payable: &Payable,
+  payable_id: Pubkey,
+  payment_id: Pubkey,
+  payable_payment: &PayablePayment,
+) -> PayableReceived {
+  PayableReceived {
+    payable_id,
+    payer_wallet: payable_payment.payer,
+    payment_id,
+    payer_chain_id: payable_payment.payer_chain_id,
+    chain_count: payable_payment.chain_count,
+    payable_count: payable_payment.payable_count,
+    balance: payable.balance_of(payable_payment.details.token),
+    remaining_to_goal: payable.remaining_to_goal(),
+    remaining_slots: payable.remaining_slots(),
+  }
+}
+
+/// Ensures that a payment in a new token doesn't take the payable's balances
+/// past the maximum. Tokens it already holds can still be paid.
+pub fn check_balances_capacity(payable: &Payable, mint: Pubkey) -> Result<()> {
+  require!(
+    payable.balances.len() < Payable::MAX_PAYABLE_BALANCES
+      || payable.balances.iter().any(|bal| bal.token == mint),
+    ChainbillsError::TooManyPayableBalances
+  );
+  Ok(())
+}
+
+/// If this payable specified the tokens and amounts it can accept, ensures
+/// that any of them matches the token and amount. A token can be listed
+/// more than once with different amounts. Otherwise (free payments), ensures
+/// that the amount doesn't exceed the payable's max_payment_amount if set.
+pub fn check_payment_amount(
+  payable: &Payable,
+  mint: Pubkey,
+  amount: u64,
+) -> Result<()> {
   if !payable.allowed_tokens_and_amounts.is_empty() {
-    let mut ataa_it = payable.allowed_tokens_and_amounts.iter().peekable();
-    while let Some(taa) = ataa_it.next() {
-      if taa.token == mint && taa.amount == amount {
+    require!(
+      payable
+        .allowed_tokens_and_amounts
+        .iter()
+        .any(|taa| taa.token == mint && taa.amount == amount),
+      ChainbillsError::MatchingTokenAndAmountNotFound
+    );
+  } else if let Some(max_payment_amount) = payable.max_payment_amount {
+    require!(
+      amount <= max_payment_amount,
+      ChainbillsError::PaymentAmountTooLarge
+    );
+  }
+
+  Ok(())
+}
+
+/// Adds the amount of the token (mint) to the payable's balances. If that
+/// makes the payable reach its goal, closes it and returns true. Also closes
+/// it (returning false) if the payment took its last slot, so the payable's
+/// payments_count should already count this payment.
+pub fn credit_payable(
+  payable: &mut Payable,
+  mint: Pubkey,
+  amount: u64,
+) -> Result<bool> {
+  // This boolean and the following two scopes was used (instead of peekable)
+  // to solve the borrowing twice bug with rust on the payable variable.
+  let mut was_matching_balance_updated = false;
+  {
+    for balance in payable.balances.iter_mut() {
+      if balance.token == mint {
+        balance.amount = balance
+          .amount
+          .checked_add(amount)
+          .ok_or(ChainbillsError::Overflow)?;
+        was_matching_balance_updated = true;
         break;
       }
-      if ataa_it.peek().is_none() {
-        return err!(ChainbillsError::MatchingTokenAndAmountNotFound);
-      }
+    }
+  }
+  {
+    if !was_matching_balance_updated {
+      payable.balances.push(TokenAndAmount {
+        token: mint,
+        amount,
+      });
     }
   }
 
-  Ok(())
+  // Close the payable if this payment made it reach its goal.
+  let has_reached_goal = !payable.is_closed && payable.has_reached_goal();
+  if has_reached_goal || payable.remaining_slots() == Some(0) {
+    payable.is_closed = true;
+  }
+  Ok(has_reached_goal)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn update_state_for_payment(
   chain_id: u16,
   amount: u64,
   mint: Pubkey,
+  memo: Option<String>,
   signer: Pubkey,
   chain_stats: &mut Account<ChainStats>,
   payable: &mut Account<Payable>,
@@ -62,54 +221,33 @@ fn update_state_for_payment(
   payable_activity_info: &mut Account<PayableActivityInfo>,
 ) -> Result<()> {
   // Increment the chain stats for payments counts.
-  chain_stats.user_payments_count = chain_stats.next_user_payment();
-  chain_stats.payable_payments_count = chain_stats.next_payable_payment();
+  chain_stats.user_payments_count = chain_stats.next_user_payment()?;
+  chain_stats.payable_payments_count = chain_stats.next_payable_payment()?;
 
   // Increment the chain stats for activities_count.
   //
-  // Incrementing twice to account for recording two activities: one for the
+  // Incrementing by two to account for recording two activities: one for the
   // user and one for the payable.
-  chain_stats.activities_count = chain_stats.next_activity();
-  chain_stats.activities_count = chain_stats.next_activity();
+  chain_stats.activities_count = chain_stats.next_activities(2)?;
 
   // Increment payments_count and activities_count in the payer that just paid.
-  payer.payments_count = payer.next_payment();
-  payer.activities_count = payer.next_activity();
+  payer.payments_count = payer.next_payment()?;
+  payer.activities_count = payer.next_activity()?;
 
   // Increment payments_count and activities_count on involved payable.
-  payable.payments_count = payable.next_payment();
-  payable.activities_count = payable.next_activity();
+  payable.payments_count = payable.next_payment()?;
+  payable.activities_count = payable.next_activity()?;
 
-  // Update payable's balances to add this token and its amount.
-  //
-  // This boolean and the following two scopes was used (instead of peekable)
-  // to solve the borrowing twice bug with rust on the payable variable.
-  let mut was_matching_balance_updated = false;
-  {
-    for balance in payable.balances.iter_mut() {
-      if balance.token == mint {
-        balance.amount = balance.amount.checked_add(amount).unwrap();
-        was_matching_balance_updated = true;
-        break;
-      }
-    }
-  }
-  {
-    if !was_matching_balance_updated {
-      payable.balances.push(TokenAndAmount {
-        token: mint,
-        amount,
-      });
-    }
-  }
+  // Update payable's balances and close it if it reached its goal.
+  let has_reached_goal = credit_payable(payable, mint, amount)?;
 
   // Increment payments_count on the payable_chain_counter for Solana.
   payable_per_chain_payments_counter.payments_count =
-    payable_per_chain_payments_counter.next_payment();
+    payable_per_chain_payments_counter.next_payment()?;
 
   // Increase the supported token's totals from this payment.
-  token_details.add_user_paid(amount);
-  token_details.add_payable_received(amount);
+  token_details.add_user_paid(amount)?;
+  token_details.add_payable_received(amount)?;
 
   let timestamp = clock::Clock::get()?.unix_timestamp as u64;
   let payment_details = TokenAndAmount {
@@ -125,6 +263,7 @@ fn update_state_for_payment(
   user_payment.payer_count = payer.payments_count;
   user_payment.timestamp = timestamp;
   user_payment.details = payment_details;
+  user_payment.memo = memo.clone();
 
   // Initialize the Payable Payment.
   payable_payment.payable_id = payable.key();
@@ -136,6 +275,7 @@ fn update_state_for_payment(
   payable_payment.payable_count = payable.payments_count;
   payable_payment.timestamp = timestamp;
   payable_payment.details = payment_details;
+  payable_payment.memo = memo;
 
   // Initialize the Payable Per Chain Payment. This is used for retrieving
   // payments per chain. The stored payable_count can then be used to get the
@@ -144,8 +284,10 @@ fn update_state_for_payment(
 
   // Initialize the User Activity.
   // subtracting 1 because we incremented the activities_count twice.
-  user_activity.chain_count =
-    chain_stats.activities_count.checked_sub(1).unwrap();
+  user_activity.chain_count = chain_stats
+    .activities_count
+    .checked_sub(1)
+    .ok_or(ChainbillsError::Overflow)?;
   user_activity.user_count = payer.activities_count;
   // Setting 0 because it's not a payable activity.
   user_activity.payable_count = 0;
@@ -179,22 +321,25 @@ fn update_state_for_payment(
     payable_payment.chain_count,
     payable_payment.payable_count
   );
-  emit!(UserPaid {
-    payable_id: payable.key().to_bytes(),
-    payer_wallet: signer,
-    payment_id: user_payment.key(),
-    payable_chain_id: user_payment.payable_chain_id,
-    chain_count: user_payment.chain_count,
-    payer_count: user_payment.payer_count,
-  });
-  emit!(PayableReceived {
-    payable_id: payable.key(),
-    payer_wallet: signer.to_bytes(),
-    payment_id: payable_payment.key(),
-    payer_chain_id: payable_payment.payer_chain_id,
-    chain_count: payable_payment.chain_count,
-    payable_count: payable_payment.payable_count,
-  });
+  emit!(user_paid_event(
+    payable.key(),
+    user_payment.key(),
+    user_payment
+  ));
+  emit!(payable_received_event(
+    payable,
+    payable.key(),
+    payable_payment.key(),
+    payable_payment
+  ));
+  if has_reached_goal {
+    msg!("Payable reached its goal and was closed.");
+    emit!(GoalReached {
+      payable_id: payable.key(),
+      goal_token: payable.goal_token.unwrap(),
+      goal_amount: payable.goal_amount.unwrap(),
+    });
+  }
   Ok(())
 }
 
@@ -202,32 +347,45 @@ fn update_state_for_payment(
 ///
 /// ### args
 /// * amount<u64>: The Wormhole-normalized amount to be paid
+/// * memo<Option<String>>: An optional reference stored with the payment
 #[inline(never)]
-pub fn pay(ctx: Context<Pay>, amount: u64) -> Result<()> {
+pub fn pay(ctx: Context<Pay>, amount: u64, memo: Option<String>) -> Result<()> {
   /* CHECKS */
+  check_payments_not_paused(&*ctx.accounts.config.load()?)?;
   let mint = &ctx.accounts.mint;
   let payable = ctx.accounts.payable.as_mut();
   let token_details = ctx.accounts.token_details.as_mut();
-  check_pay_inputs(amount, mint.key(), payable, token_details)?;
+  check_pay_inputs(amount, mint.key(), &memo, payable, token_details)?;
 
   /* TRANSFER */
-  token::transfer(
+  token_interface::transfer_checked(
     CpiContext::new(
       ctx.accounts.token_program.to_account_info(),
-      SplTransfer {
+      TransferChecked {
         from: ctx.accounts.payer_token_account.to_account_info(),
+        mint: mint.to_account_info(),
         to: ctx.accounts.chain_token_account.to_account_info(),
         authority: ctx.accounts.signer.to_account_info(),
       },
     ),
     amount,
+    mint.decimals,
+  )?;
+
+  // Only credit what was received, in case the mint withheld transfer fees.
+  let fee = transfer_fee(
+    &mint.to_account_info().try_borrow_data()?,
+    clock::Clock::get()?.epoch,
+    amount,
   )?;
+  let received = amount.checked_sub(fee).ok_or(ChainbillsError::Overflow)?;
 
   /* STATE CHANGES */
   update_state_for_payment(
     ctx.accounts.config.load()?.chain_id,
-    amount,
+    received,
     mint.key(),
+    memo,
     ctx.accounts.signer.key(),
     ctx.accounts.chain_stats.as_mut(),
     payable,
@@ -248,12 +406,18 @@ pub fn pay(ctx: Context<Pay>, amount: u64) -> Result<()> {
 ///
 /// ### args
 /// * amount<u64>: The Wormhole-normalized amount to be paid
+/// * memo<Option<String>>: An optional reference stored with the payment
 #[inline(never)]
-pub fn pay_native(ctx: Context<PayNative>, amount: u64) -> Result<()> {
+pub fn pay_native(
+  ctx: Context<PayNative>,
+  amount: u64,
+  memo: Option<String>,
+) -> Result<()> {
   /* CHECKS */
+  check_payments_not_paused(&*ctx.accounts.config.load()?)?;
   let payable = ctx.accounts.payable.as_mut();
   let token_details = ctx.accounts.token_details.as_mut();
-  check_pay_inputs(amount, crate::ID, payable, token_details)?;
+  check_pay_inputs(amount, crate::ID, &memo, payable, token_details)?;
 
   /* TRANSFER */
   system_program::transfer(
@@ -272,6 +436,7 @@ pub fn pay_native(ctx: Context<PayNative>, amount: u64) -> Result<()> {
     ctx.accounts.config.load()?.chain_id,
     amount,
     crate::ID,
+    memo,
     ctx.accounts.signer.key(),
     ctx.accounts.chain_stats.as_mut(),
     payable,
@@ -287,3 +452,385 @@ pub fn pay_native(ctx: Context<PayNative>, amount: u64) -> Result<()> {
     ctx.accounts.payable_activity_info.as_mut(),
   )
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn free_payable(expires_at: Option<u64>) -> Payable {
+    Payable {
+      expires_at,
+      ..Payable::test_default()
+    }
+  }
+
+  #[test]
+  fn accepts_payments_until_expiry() {
+    let payable = free_payable(Some(1_000));
+    assert!(check_not_expired(&payable, 999).is_ok());
+    assert!(check_not_expired(&payable, 1_000).is_ok());
+    assert_eq!(
+      check_not_expired(&payable, 1_001).unwrap_err(),
+      ChainbillsError::PayableExpired.into()
+    );
+  }
+
+  #[test]
+  fn caps_free_payment_amounts() {
+    let mint = Pubkey::new_unique();
+    let mut payable = free_payable(None);
+    payable.max_payment_amount = Some(100);
+    assert!(check_payment_amount(&payable, mint, 99).is_ok());
+    assert!(check_payment_amount(&payable, mint, 100).is_ok());
+    assert_eq!(
+      check_payment_amount(&payable, mint, 101).unwrap_err(),
+      ChainbillsError::PaymentAmountTooLarge.into()
+    );
+
+    // Without a cap, any amount goes.
+    payable.max_payment_amount = None;
+    assert!(check_payment_amount(&payable, mint, u64::MAX).is_ok());
+  }
+
+  #[test]
+  fn caps_distinct_balances() {
+    let mut payable = free_payable(None);
+    let mints: Vec<Pubkey> = (0..=Payable::MAX_PAYABLE_BALANCES)
+      .map(|_| Pubkey::new_unique())
+      .collect();
+
+    // Fill the payable's balances up to the maximum.
+    for mint in mints.iter().take(Payable::MAX_PAYABLE_BALANCES) {
+      assert!(check_balances_capacity(&payable, *mint).is_ok());
+      credit_payable(&mut payable, *mint, 10).unwrap();
+    }
+
+    // A payment in a new token is then rejected.
+    assert_eq!(
+      check_balances_capacity(&payable, mints[Payable::MAX_PAYABLE_BALANCES])
+        .unwrap_err(),
+      ChainbillsError::TooManyPayableBalances.into()
+    );
+
+    // While tokens that the payable already holds can still be paid.
+    assert!(check_balances_capacity(&payable, mints[0]).is_ok());
+  }
+
+  #[test]
+  fn accepts_arbitrary_free_payments() {
+    let payable = free_payable(None);
+    for amount in [1, 7, 123, u64::MAX] {
+      assert!(
+        check_payment_amount(&payable, Pubkey::new_unique(), amount).is_ok()
+      );
+    }
+  }
+
+  #[test]
+  fn accepts_only_matching_fixed_payments() {
+    let (mint, other_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut payable = free_payable(None);
+    payable.allowed_tokens_and_amounts = vec![
+      TokenAndAmount {
+        token: mint,
+        amount: 10,
+      },
+      TokenAndAmount {
+        token: mint,
+        amount: 25,
+      },
+    ];
+
+    // Any listed amount of the token matches.
+    assert!(check_payment_amount(&payable, mint, 10).is_ok());
+    assert!(check_payment_amount(&payable, mint, 25).is_ok());
+
+    // Other amounts, or listed amounts of other tokens, don't.
+    for (token, amount) in [(mint, 9), (mint, 11), (mint, 35), (other_mint, 10)]
+    {
+      assert_eq!(
+        check_payment_amount(&payable, token, amount).unwrap_err(),
+        ChainbillsError::MatchingTokenAndAmountNotFound.into()
+      );
+    }
+  }
+
+  #[test]
+  fn ignores_cap_for_fixed_payments() {
+    let mint = Pubkey::new_unique();
+    let mut payable = free_payable(None);
+    payable.max_payment_amount = Some(100);
+    payable.allowed_tokens_and_amounts = vec![TokenAndAmount {
+      token: mint,
+      amount: 500,
+    }];
+    assert!(check_payment_amount(&payable, mint, 500).is_ok());
+  }
+
+  #[test]
+  fn closes_payable_on_reaching_goal() {
+    let (goal_token, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut payable = free_payable(None);
+    payable.goal_token = Some(goal_token);
+    payable.goal_amount = Some(100);
+
+    // Payments below the goal or in other tokens keep the payable open.
+    assert!(!credit_payable(&mut payable, goal_token, 60).unwrap());
+    assert!(!credit_payable(&mut payable, other, 500).unwrap());
+    assert!(!payable.is_closed);
+
+    // The payment that crosses the goal closes the payable.
+    assert!(credit_payable(&mut payable, goal_token, 50).unwrap());
+    assert!(payable.is_closed);
+    assert_eq!(payable.balances[0].amount, 110);
+  }
+
+  #[test]
+  fn tracks_cumulative_balance_per_token() {
+    let (token, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut payable = free_payable(None);
+    assert_eq!(payable.balance_of(token), 0);
+
+    credit_payable(&mut payable, token, 40).unwrap();
+    assert_eq!(payable.balance_of(token), 40);
+    credit_payable(&mut payable, other, 7).unwrap();
+    credit_payable(&mut payable, token, 25).unwrap();
+    assert_eq!(payable.balance_of(token), 65);
+    assert_eq!(payable.balance_of(other), 7);
+  }
+
+  #[test]
+  fn never_closes_payable_without_goal() {
+    let mut payable = free_payable(None);
+    assert!(!credit_payable(&mut payable, Pubkey::new_unique(), 1_000).unwrap());
+    assert!(!payable.is_closed);
+  }
+
+  /// Credits a payment to the payable as update_state_for_payment does and
+  /// returns the PayableReceived event it emits.
+  fn receive(
+    payable: &mut Payable,
+    mint: Pubkey,
+    amount: u64,
+  ) -> PayableReceived {
+    payable.payments_count = payable.next_payment().unwrap();
+    credit_payable(payable, mint, amount).unwrap();
+    let payable_payment = PayablePayment {
+      payable_id: Pubkey::new_unique(),
+      payer: [1; 32],
+      chain_count: 1,
+      payer_chain_id: 1,
+      local_chain_count: 1,
+      payable_count: payable.payments_count,
+      timestamp: 0,
+      details: TokenAndAmount {
+        token: mint,
+        amount,
+      },
+      memo: None,
+    };
+    payable_received_event(
+      payable,
+      payable_payment.payable_id,
+      Pubkey::new_unique(),
+      &payable_payment,
+    )
+  }
+
+  #[test]
+  fn closes_payable_on_taking_its_last_slot() {
+    let mint = Pubkey::new_unique();
+    let mut payable = free_payable(None);
+    payable.max_payments = Some(2);
+
+    receive(&mut payable, mint, 10);
+    assert!(!payable.is_closed);
+    receive(&mut payable, mint, 10);
+    assert!(payable.is_closed);
+    assert_eq!(payable.remaining_slots(), Some(0));
+  }
+
+  #[test]
+  fn reports_remaining_goal_and_slots_in_pay_events() {
+    let (goal_token, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut capped = free_payable(None);
+    capped.max_payments = Some(3);
+    capped.goal_token = Some(goal_token);
+    capped.goal_amount = Some(100);
+
+    // Each payment takes a slot, and only goal_token payments count
+    // towards the goal.
+    let event = receive(&mut capped, goal_token, 40);
+    assert_eq!(event.balance, 40);
+    assert_eq!(event.remaining_to_goal, Some(60));
+    assert_eq!(event.remaining_slots, Some(2));
+    let event = receive(&mut capped, other, 500);
+    assert_eq!(event.balance, 500);
+    assert_eq!(event.remaining_to_goal, Some(60));
+    assert_eq!(event.remaining_slots, Some(1));
+
+    // Overshooting the goal leaves nothing to it.
+    let event = receive(&mut capped, goal_token, 70);
+    assert_eq!(event.remaining_to_goal, Some(0));
+    assert_eq!(event.remaining_slots, Some(0));
+
+    // Payables without a goal or a cap omit them.
+    let mut uncapped = free_payable(None);
+    let event = receive(&mut uncapped, goal_token, 40);
+    assert_eq!(event.balance, 40);
+    assert_eq!(event.remaining_to_goal, None);
+    assert_eq!(event.remaining_slots, None);
+  }
+
+  #[test]
+  fn never_expires_without_expiry() {
+    let payable = free_payable(None);
+    assert!(check_not_expired(&payable, u64::MAX).is_ok());
+  }
+
+  fn mint_data(transfer_fee_basis_points: Option<u16>) -> Vec<u8> {
+    use anchor_spl::token_interface::spl_token_2022::extension::{
+      transfer_fee::TransferFee, ExtensionType, StateWithExtensionsMut,
+    };
+
+    let extensions = match transfer_fee_basis_points {
+      Some(_) => vec![ExtensionType::TransferFeeConfig],
+      None => vec![],
+    };
+    let len =
+      ExtensionType::try_calculate_account_len::<SplMint>(&extensions).unwrap();
+    let mut data = vec![0; len];
+    let mut state =
+      StateWithExtensionsMut::<SplMint>::unpack_uninitialized(&mut data)
+        .unwrap();
+    if let Some(basis_points) = transfer_fee_basis_points {
+      let fee = TransferFee {
+        epoch: 0.into(),
+        maximum_fee: 50.into(),
+        transfer_fee_basis_points: basis_points.into(),
+      };
+      let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+      config.older_transfer_fee = fee;
+      config.newer_transfer_fee = fee;
+    }
+    state.base = SplMint {
+      decimals: 6,
+      is_initialized: true,
+      ..Default::default()
+    };
+    state.pack_base();
+    if transfer_fee_basis_points.is_some() {
+      state.init_account_type().unwrap();
+    }
+    data
+  }
+
+  #[test]
+  fn withholds_nothing_without_transfer_fees() {
+    // Legacy mints and Token-2022 mints without the extension are alike.
+    assert_eq!(transfer_fee(&mint_data(None), 1, 1_000).unwrap(), 0);
+  }
+
+  #[test]
+  fn computes_transfer_fees_of_token_2022_mints() {
+    // 1% of 1,000 is 10.
+    let data = mint_data(Some(100));
+    assert_eq!(transfer_fee(&data, 1, 1_000).unwrap(), 10);
+
+    // The fee is capped at the maximum fee.
+    assert_eq!(transfer_fee(&data, 1, 100_000).unwrap(), 50);
+  }
+
+  #[test]
+  fn fails_cleanly_on_transfer_fee_overflow() {
+    // Fees above 100% of a huge amount don't fit in a u64.
+    let data = mint_data(Some(u16::MAX));
+    assert_eq!(
+      transfer_fee(&data, 1, u64::MAX).unwrap_err(),
+      ChainbillsError::Overflow.into()
+    );
+  }
+
+  #[test]
+  fn fails_cleanly_on_overflow() {
+    // Crediting a balance past u64::MAX overflows and leaves it unchanged.
+    let token = Pubkey::new_unique();
+    let mut payable = free_payable(None);
+    credit_payable(&mut payable, token, u64::MAX - 1).unwrap();
+    assert_eq!(
+      credit_payable(&mut payable, token, 2).unwrap_err(),
+      ChainbillsError::Overflow.into()
+    );
+    assert_eq!(payable.balance_of(token), u64::MAX - 1);
+
+    // So does adding to the token's payment totals past u64::MAX.
+    let mut token_details = TokenDetails {
+      mint: token,
+      total_user_paid: u64::MAX - 1,
+      total_payable_received: u64::MAX - 1,
+      ..TokenDetails::test_default()
+    };
+    assert_eq!(
+      token_details.add_user_paid(2).unwrap_err(),
+      ChainbillsError::Overflow.into()
+    );
+    assert_eq!(
+      token_details.add_payable_received(2).unwrap_err(),
+      ChainbillsError::Overflow.into()
+    );
+  }
+
+  fn user_payment(memo: Option<String>) -> UserPayment {
+    UserPayment {
+      payable_id: Pubkey::new_unique().to_bytes(),
+      payer: Pubkey::new_unique(),
+      payable_chain_id: 1,
+      chain_count: 1,
+      payer_count: 1,
+      timestamp: 0,
+      details: TokenAndAmount {
+        token: crate::ID,
+        amount: 10,
+      },
+      memo,
+    }
+  }
+
+  #[test]
+  fn stores_and_emits_payment_memos() {
+    let payable_id = Pubkey::new_unique();
+    let payment_id = Pubkey::new_unique();
+
+    for memo in [None, Some("INV-2024-0042".to_string())] {
+      assert!(check_memo(&memo).is_ok());
+
+      // The memo reads back from the stored payment and is in its event.
+      let mut data = vec![];
+      user_payment(memo.clone()).try_serialize(&mut data).unwrap();
+      assert!(data.len() <= UserPayment::SPACE);
+      let stored = UserPayment::try_deserialize(&mut &data[..]).unwrap();
+      assert_eq!(stored.memo, memo);
+
+      let event = user_paid_event(payable_id, payment_id, &stored);
+      assert_eq!(event.memo, memo);
+      assert_eq!(event.payer_wallet, stored.payer);
+      assert_eq!(event.payable_id, payable_id.to_bytes());
+    }
+  }
+
+  #[test]
+  fn rejects_memos_that_are_too_long() {
+    let max = UserPayment::MAX_MEMO_LENGTH;
+    assert!(check_memo(&Some("a".repeat(max))).is_ok());
+    assert_eq!(
+      check_memo(&Some("a".repeat(max + 1))).unwrap_err(),
+      ChainbillsError::MemoTooLong.into()
+    );
+
+    // The length is in bytes, not characters.
+    assert_eq!(
+      check_memo(&Some("\u{e9}".repeat(max / 2 + 1))).unwrap_err(),
+      ChainbillsError::MemoTooLong.into()
+    );
+  }
+}
@@ -0,0 +1,462 @@
+use crate::{
+  context::PayReceived,
+  error::ChainbillsError,
+  events::*,
+  handlers::{
+    check_balances_capacity, check_cross_chain_not_paused,
+    check_foreign_emitter, check_not_expired, check_payment_amount,
+    check_payments_not_paused, check_token_supported, check_vaa_finality,
+    credit_payable, payable_received_event,
+  },
+  payload::{is_zero_bytes32, CbTransfer},
+  state::*,
+  token_bridge,
+};
+use anchor_lang::{prelude::*, solana_program::clock};
+use wormhole_anchor_sdk::wormhole;
+
+/// Checks the Token Bridge transfer of a payment made on another chain to the
+/// payable and returns the amount (with the mint's decimals) to credit it.
+///
+/// The transfer must have been sent by the registered foreign contract of its
+/// chain, and its CbTransaction must target this payable in the transferred
+/// token and amount, on behalf of a payer. The payable must accept payments
+/// from other chains.
+#[allow(clippy::too_many_arguments)]
+fn check_received_payment(
+  config: &Config,
+  foreign_contract_key: Pubkey,
+  foreign_contract: &ForeignContract,
+  posted: &wormhole::PostedVaa<CbTransfer>,
+  payable_key: Pubkey,
+  payable: &Payable,
+  token_details: &TokenDetails,
+  mint: Pubkey,
+  mint_decimals: u8,
+  now: u64,
+) -> Result<u64> {
+  check_payments_not_paused(config)?;
+  check_cross_chain_not_paused(config)?;
+  check_vaa_finality(config, posted.finality())?;
+
+  // The VAA's emitter is the source chain's Token Bridge, so the foreign
+  // contract is the sender of the transfer.
+  let transfer = posted.data();
+  check_foreign_emitter(
+    foreign_contract_key,
+    foreign_contract,
+    posted.emitter_chain(),
+    &transfer.from_address,
+  )?;
+
+  let payload = &transfer.transaction;
+  require!(
+    payload.checked_payable_id()? == payable_key.to_bytes(),
+    ChainbillsError::PaymentNotForPayable
+  );
+  require!(
+    payload.details.token == mint
+      && transfer.mint() == mint
+      && payload.details.amount == transfer.amount
+      && !is_zero_bytes32(&payload.payer),
+    ChainbillsError::InvalidPayload
+  );
+  require!(
+    payable.accepts_cross_chain(),
+    ChainbillsError::PayableRejectsCrossChain
+  );
+
+  let amount =
+    token_bridge::denormalize_amount(transfer.amount, mint_decimals)?;
+  check_token_supported(token_details)?;
+  require!(amount > 0, ChainbillsError::ZeroAmountSpecified);
+  require!(!payable.is_closed, ChainbillsError::PayableIsClosed);
+  check_not_expired(payable, now)?;
+  check_payment_amount(payable, mint, amount)?;
+  check_balances_capacity(payable, mint)?;
+  Ok(amount)
+}
+
+/// Records a payment made on another chain to a payable on this chain. It is
+/// the payable's side of update_state_for_payment in pay, as the payer's side
+/// was recorded on the payer's chain.
+#[allow(clippy::too_many_arguments)]
+fn update_state_for_received_payment(
+  payer_chain_id: u16,
+  payer: [u8; 32],
+  amount: u64,
+  mint: Pubkey,
+  now: u64,
+  chain_stats: &mut Account<ChainStats>,
+  payable: &mut Account<Payable>,
+  payable_per_chain_payments_counter: &mut Account<
+    PayablePerChainPaymentsCounter,
+  >,
+  token_details: &mut Account<TokenDetails>,
+  payable_payment: &mut Account<PayablePayment>,
+  payable_per_chain_payment_info: &mut Account<PayablePerChainPaymentInfo>,
+  payable_activity: &mut Account<ActivityRecord>,
+  payable_activity_info: &mut Account<PayableActivityInfo>,
+) -> Result<()> {
+  // Increment the chain stats for payable payments and activities counts.
+  chain_stats.payable_payments_count = chain_stats.next_payable_payment()?;
+  chain_stats.activities_count = chain_stats.next_activity()?;
+
+  // Increment payments_count and activities_count on involved payable.
+  payable.payments_count = payable.next_payment()?;
+  payable.activities_count = payable.next_activity()?;
+
+  // Update payable's balances and close it if it reached its goal.
+  let has_reached_goal = credit_payable(payable, mint, amount)?;
+
+  // Increment payments_count on the payable_chain_counter for the payer's
+  // chain.
+  payable_per_chain_payments_counter.payments_count =
+    payable_per_chain_payments_counter.next_payment()?;
+
+  // Increase the supported token's totals from this payment.
+  token_details.add_payable_received(amount)?;
+
+  // Initialize the Payable Payment.
+  payable_payment.payable_id = payable.key();
+  payable_payment.payer = payer;
+  payable_payment.chain_count = chain_stats.payable_payments_count;
+  payable_payment.payer_chain_id = payer_chain_id;
+  payable_payment.local_chain_count =
+    payable_per_chain_payments_counter.payments_count;
+  payable_payment.payable_count = payable.payments_count;
+  payable_payment.timestamp = now;
+  payable_payment.details = TokenAndAmount {
+    token: mint,
+    amount,
+  };
+  payable_payment.memo = None;
+
+  // Initialize the Payable Per Chain Payment.
+  payable_per_chain_payment_info.payable_count = payable.payments_count;
+
+  // Initialize the Payable Activity.
+  payable_activity.chain_count = chain_stats.activities_count;
+  // Setting 0 because it's not a user activity.
+  payable_activity.user_count = 0;
+  payable_activity.payable_count = payable.activities_count;
+  payable_activity.timestamp = now;
+  payable_activity.entity = payable_payment.key();
+  payable_activity.activity_type = ActivityType::PayableReceived;
+
+  // Initialize the Payable Activity Info.
+  payable_activity_info.chain_count = chain_stats.activities_count;
+
+  // Emit logs and events.
+  msg!(
+    "Payable Payment was received from chain {} with chain_count: {}, and payable_count: {}.",
+    payer_chain_id,
+    payable_payment.chain_count,
+    payable_payment.payable_count
+  );
+  emit!(payable_received_event(
+    payable,
+    payable.key(),
+    payable_payment.key(),
+    payable_payment
+  ));
+  if has_reached_goal {
+    msg!("Payable reached its goal and was closed.");
+    emit!(GoalReached {
+      payable_id: payable.key(),
+      goal_token: payable.goal_token.unwrap(),
+      goal_amount: payable.goal_amount.unwrap(),
+    });
+  }
+  Ok(())
+}
+
+/// Records a payment made on another chain to a payable on this chain,
+/// redeeming its Token Bridge transfer into the chain_token_account. Only
+/// Solana-native tokens can be received.
+///
+/// The transfer's CbTransaction payload must target the payable. The payment
+/// is recorded with the payload's payer as its payer and the VAA can only be
+/// consumed once.
+///
+/// ### args
+/// * vaa_hash<[u8; 32]>: The Keccak256 hash of the VAA.
+#[inline(never)]
+pub fn pay_received_handler(
+  ctx: Context<PayReceived>,
+  vaa_hash: [u8; 32],
+) -> Result<()> {
+  /* CHECKS */
+  let now = clock::Clock::get()?.unix_timestamp as u64;
+  let mint = &ctx.accounts.mint;
+  let posted = &ctx.accounts.posted;
+  let amount = check_received_payment(
+    &*ctx.accounts.config.load()?,
+    ctx.accounts.foreign_contract.key(),
+    &ctx.accounts.foreign_contract,
+    posted,
+    ctx.accounts.payable.key(),
+    &ctx.accounts.payable,
+    &ctx.accounts.token_details,
+    mint.key(),
+    mint.decimals,
+    now,
+  )?;
+
+  /* TRANSFER */
+  token_bridge::complete_transfer_native_with_payload(
+    CpiContext::new_with_signer(
+      ctx.accounts.token_bridge_program.to_account_info(),
+      token_bridge::CompleteTransferNativeWithPayload {
+        payer: ctx.accounts.signer.to_account_info(),
+        config: ctx.accounts.token_bridge_config.to_account_info(),
+        vaa: posted.to_account_info(),
+        claim: ctx.accounts.token_bridge_claim.to_account_info(),
+        foreign_endpoint: ctx
+          .accounts
+          .token_bridge_foreign_endpoint
+          .to_account_info(),
+        to: ctx.accounts.chain_token_account.to_account_info(),
+        redeemer: ctx.accounts.chain_stats.to_account_info(),
+        custody: ctx.accounts.token_bridge_custody.to_account_info(),
+        mint: mint.to_account_info(),
+        custody_signer: ctx
+          .accounts
+          .token_bridge_custody_signer
+          .to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        wormhole_program: ctx.accounts.wormhole_program.to_account_info(),
+      },
+      &[&[ChainStats::SEED_PREFIX, &[ctx.bumps.chain_stats]]],
+    ),
+  )?;
+
+  /* STATE CHANGES */
+  let wormhole_received = ctx.accounts.wormhole_received.as_mut();
+  wormhole_received.batch_id = posted.batch_id();
+  wormhole_received.vaa_hash = vaa_hash;
+
+  update_state_for_received_payment(
+    posted.emitter_chain(),
+    posted.data().transaction.payer,
+    amount,
+    mint.key(),
+    now,
+    ctx.accounts.chain_stats.as_mut(),
+    ctx.accounts.payable.as_mut(),
+    ctx.accounts.payable_per_chain_payments_counter.as_mut(),
+    ctx.accounts.token_details.as_mut(),
+    ctx.accounts.payable_payment.as_mut(),
+    ctx.accounts.payable_per_chain_payment_info.as_mut(),
+    ctx.accounts.payable_activity.as_mut(),
+    ctx.accounts.payable_activity_info.as_mut(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::payload::CbTransaction;
+  use wormhole::{PostedVaa, PostedVaaMeta, CHAIN_ID_SOLANA};
+
+  const CHAIN: u16 = 7;
+  const EMITTER: [u8; 32] = [9; 32];
+  const PAYER: [u8; 32] = [5; 32];
+
+  fn config() -> Config {
+    Config {
+      min_vaa_finality: 1,
+      ..Config::test_default()
+    }
+  }
+
+  fn token_details(mint: Pubkey) -> TokenDetails {
+    TokenDetails {
+      mint,
+      ..TokenDetails::test_default()
+    }
+  }
+
+  fn posted(
+    payable_id: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+  ) -> PostedVaa<CbTransfer> {
+    let transfer = CbTransfer {
+      amount,
+      token_address: mint.to_bytes(),
+      token_chain: CHAIN_ID_SOLANA,
+      to_address: Pubkey::new_unique().to_bytes(),
+      to_chain: CHAIN_ID_SOLANA,
+      from_address: EMITTER,
+      transaction: CbTransaction {
+        payable_id: payable_id.to_bytes(),
+        details: TokenAndAmount {
+          token: mint,
+          amount,
+        },
+        payer: PAYER,
+      },
+    };
+    PostedVaa {
+      meta: PostedVaaMeta {
+        finality: 1,
+        emitter_chain: CHAIN,
+        emitter_address: [3; 32],
+        ..Default::default()
+      },
+      payload: (CbTransfer::LENGTH as u32, transfer),
+    }
+  }
+
+  #[test]
+  fn accepts_valid_inbound_payments() {
+    let payable_key = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let amount = check_received_payment(
+      &config(),
+      ForeignContract::pda(CHAIN).0,
+      &ForeignContract { address: EMITTER },
+      &posted(payable_key, mint, 1_500),
+      payable_key,
+      &Payable::test_default(),
+      &token_details(mint),
+      mint,
+      9,
+      100,
+    )
+    .unwrap();
+    // Wormhole-normalized amounts have 8 decimals.
+    assert_eq!(amount, 15_000);
+
+    // Transfers not sent by the registered foreign contract are rejected.
+    assert_eq!(
+      check_received_payment(
+        &config(),
+        ForeignContract::pda(CHAIN).0,
+        &ForeignContract { address: [8; 32] },
+        &posted(payable_key, mint, 1_500),
+        payable_key,
+        &Payable::test_default(),
+        &token_details(mint),
+        mint,
+        9,
+        100,
+      )
+      .unwrap_err(),
+      ChainbillsError::InvalidForeignEmitter.into()
+    );
+  }
+
+  #[test]
+  fn rejects_payments_for_other_payables() {
+    let payable_key = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    assert_eq!(
+      check_received_payment(
+        &config(),
+        ForeignContract::pda(CHAIN).0,
+        &ForeignContract { address: EMITTER },
+        &posted(Pubkey::new_unique(), mint, 1_500),
+        payable_key,
+        &Payable::test_default(),
+        &token_details(mint),
+        mint,
+        9,
+        100,
+      )
+      .unwrap_err(),
+      ChainbillsError::PaymentNotForPayable.into()
+    );
+  }
+
+  #[test]
+  fn rejects_payments_to_local_only_payables() {
+    let payable_key = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let payable = Payable {
+      rejects_cross_chain: true,
+      ..Payable::test_default()
+    };
+    assert_eq!(
+      check_received_payment(
+        &config(),
+        ForeignContract::pda(CHAIN).0,
+        &ForeignContract { address: EMITTER },
+        &posted(payable_key, mint, 1_500),
+        payable_key,
+        &payable,
+        &token_details(mint),
+        mint,
+        9,
+        100,
+      )
+      .unwrap_err(),
+      ChainbillsError::PayableRejectsCrossChain.into()
+    );
+  }
+
+  #[test]
+  fn rejects_payments_without_a_payer() {
+    let payable_key = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let mut posted = posted(payable_key, mint, 1_500);
+    posted.payload.1.transaction.payer = [0; 32];
+    assert_eq!(
+      check_received_payment(
+        &config(),
+        ForeignContract::pda(CHAIN).0,
+        &ForeignContract { address: EMITTER },
+        &posted,
+        payable_key,
+        &Payable::test_default(),
+        &token_details(mint),
+        mint,
+        9,
+        100,
+      )
+      .unwrap_err(),
+      ChainbillsError::InvalidPayload.into()
+    );
+  }
+
+  #[test]
+  fn fails_cleanly_on_denormalization_overflow() {
+    assert_eq!(token_bridge::denormalize_amount(7, 6).unwrap(), 7);
+    assert_eq!(
+      token_bridge::denormalize_amount(7, 18).unwrap(),
+      70_000_000_000
+    );
+    let overflow: Error = ChainbillsError::Overflow.into();
+    assert_eq!(
+      token_bridge::denormalize_amount(u64::MAX, 9).unwrap_err(),
+      overflow
+    );
+    // 10^(u8::MAX - 8) doesn't fit in a u64 either.
+    assert_eq!(
+      token_bridge::denormalize_amount(1, u8::MAX).unwrap_err(),
+      overflow
+    );
+
+    let payable_key = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    assert_eq!(
+      check_received_payment(
+        &config(),
+        ForeignContract::pda(CHAIN).0,
+        &ForeignContract { address: EMITTER },
+        &posted(payable_key, mint, u64::MAX),
+        payable_key,
+        &Payable::test_default(),
+        &token_details(mint),
+        mint,
+        18,
+        100,
+      )
+      .unwrap_err(),
+      overflow
+    );
+  }
+}
@@ -0,0 +1,427 @@
+use crate::{
+  context::*, error::ChainbillsError, events::*, handlers::transfer_lamports,
+  state::*,
+};
+use anchor_lang::{prelude::*, solana_program::clock};
+use anchor_spl::token_interface::{self, TransferChecked};
+
+fn check_refund_inputs(
+  payable_id: Pubkey,
+  payable: &Payable,
+  user_payment: &UserPayment,
+  payment_refund: &PaymentRefund,
+) -> Result<()> {
+  // Ensure that the payment was made to this payable.
+  require!(
+    user_payment.payable_id == payable_id.to_bytes(),
+    ChainbillsError::PaymentNotForPayable
+  );
+
+  // Ensure that the payment hasn't been refunded before.
+  require!(
+    !payment_refund.is_refunded,
+    ChainbillsError::PaymentAlreadyRefunded
+  );
+
+  // Ensure that the payable still holds enough of the payment's token.
+  let TokenAndAmount { token, amount } = user_payment.details;
+  require!(
+    payable
+      .balances
+      .iter()
+      .any(|balance| balance.token == token && balance.amount >= amount),
+    ChainbillsError::InsufficientPayableBalance
+  );
+
+  Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_state_for_refund(
+  payable_id: Pubkey,
+  payment_id: Pubkey,
+  host_wallet: Pubkey,
+  timestamp: u64,
+  user_payment: &UserPayment,
+  chain_stats: &mut ChainStats,
+  host: &mut User,
+  payable: &mut Payable,
+  payment_refund: &mut PaymentRefund,
+  activity: &mut ActivityRecord,
+  user_activity_info: &mut UserActivityInfo,
+  payable_activity_info: &mut PayableActivityInfo,
+) -> Result<()> {
+  let TokenAndAmount { token, amount } = user_payment.details;
+
+  // Increment the activities_count in the chain stats, host, and payable.
+  chain_stats.activities_count = chain_stats.next_activity()?;
+  host.activities_count = host.next_activity()?;
+  payable.activities_count = payable.next_activity()?;
+
+  // Deduct the balances on the involved payable.
+  for balance in payable.balances.iter_mut() {
+    if balance.token == token {
+      balance.amount = balance
+        .amount
+        .checked_sub(amount)
+        .ok_or(ChainbillsError::Overflow)?;
+      break;
+    }
+  }
+
+  // Mark the payment as refunded.
+  payment_refund.is_refunded = true;
+  payment_refund.amount = amount;
+  payment_refund.timestamp = timestamp;
+
+  // Initialize the activity.
+  activity.chain_count = chain_stats.activities_count;
+  activity.user_count = host.activities_count;
+  activity.payable_count = payable.activities_count;
+  activity.timestamp = timestamp;
+  activity.entity = payment_id;
+  activity.activity_type = ActivityType::RefundedPayment;
+
+  // Initialize the user activity info.
+  user_activity_info.chain_count = chain_stats.activities_count;
+
+  // Initialize the payable activity info.
+  payable_activity_info.chain_count = chain_stats.activities_count;
+
+  // Emit log and event.
+  msg!(
+    "Payment with payer_count: {} was refunded with amount: {}.",
+    user_payment.payer_count,
+    amount
+  );
+  emit!(RefundedPayment {
+    payable_id,
+    host_wallet,
+    payment_id,
+    payer_wallet: user_payment.payer,
+    token,
+    amount,
+  });
+  Ok(())
+}
+
+/// Transfers the amount of a payment from a payable back to its payer.
+#[inline(never)]
+pub fn refund_payment(ctx: Context<RefundPayment>) -> Result<()> {
+  /* CHECKS */
+  let payable_id = ctx.accounts.payable.key();
+  check_refund_inputs(
+    payable_id,
+    &ctx.accounts.payable,
+    &ctx.accounts.user_payment,
+    &ctx.accounts.payment_refund,
+  )?;
+  let amount = ctx.accounts.user_payment.details.amount;
+
+  /* TRANSFERS */
+  // Transfer the payment's amount back to the payer.
+  let cpi_accounts = TransferChecked {
+    from: ctx.accounts.chain_token_account.to_account_info().clone(),
+    mint: ctx.accounts.mint.to_account_info().clone(),
+    to: ctx.accounts.payer_token_account.to_account_info().clone(),
+    authority: ctx.accounts.chain_stats.to_account_info().clone(),
+  };
+  token_interface::transfer_checked(
+    CpiContext::new_with_signer(
+      ctx.accounts.token_program.to_account_info(),
+      cpi_accounts,
+      &[&[ChainStats::SEED_PREFIX, &[ctx.bumps.chain_stats]]],
+    ),
+    amount,
+    ctx.accounts.mint.decimals,
+  )?;
+
+  /* STATE CHANGES */
+  update_state_for_refund(
+    payable_id,
+    ctx.accounts.user_payment.key(),
+    ctx.accounts.signer.key(),
+    clock::Clock::get()?.unix_timestamp as u64,
+    &ctx.accounts.user_payment,
+    &mut ctx.accounts.chain_stats,
+    &mut ctx.accounts.host,
+    &mut ctx.accounts.payable,
+    &mut ctx.accounts.payment_refund,
+    &mut ctx.accounts.activity,
+    &mut ctx.accounts.user_activity_info,
+    &mut ctx.accounts.payable_activity_info,
+  )
+}
+
+/// Transfers the amount of a native (SOL) payment from a payable back to its
+/// payer. The lamports are moved out of the chain_stats account that holds
+/// native payments, as withdraw_native does.
+#[inline(never)]
+pub fn refund_payment_native(ctx: Context<RefundPaymentNative>) -> Result<()> {
+  /* CHECKS */
+  let payable_id = ctx.accounts.payable.key();
+  check_refund_inputs(
+    payable_id,
+    &ctx.accounts.payable,
+    &ctx.accounts.user_payment,
+    &ctx.accounts.payment_refund,
+  )?;
+
+  /* TRANSFERS */
+  // Transfer the payment's amount back to the payer.
+  transfer_lamports(
+    &ctx.accounts.chain_stats.to_account_info(),
+    &ctx.accounts.payer_wallet.to_account_info(),
+    ctx.accounts.user_payment.details.amount,
+  )?;
+
+  /* STATE CHANGES */
+  update_state_for_refund(
+    payable_id,
+    ctx.accounts.user_payment.key(),
+    ctx.accounts.signer.key(),
+    clock::Clock::get()?.unix_timestamp as u64,
+    &ctx.accounts.user_payment,
+    &mut ctx.accounts.chain_stats,
+    &mut ctx.accounts.host,
+    &mut ctx.accounts.payable,
+    &mut ctx.accounts.payment_refund,
+    &mut ctx.accounts.activity,
+    &mut ctx.accounts.user_activity_info,
+    &mut ctx.accounts.payable_activity_info,
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn payable_with_balance(token: Pubkey, amount: u64) -> Payable {
+    Payable {
+      payments_count: 1,
+      activities_count: 2,
+      balances: vec![TokenAndAmount { token, amount }],
+      ..Payable::test_default()
+    }
+  }
+
+  fn user_payment_to(payable_id: Pubkey, token: Pubkey) -> UserPayment {
+    UserPayment {
+      payable_id: payable_id.to_bytes(),
+      payer: Pubkey::new_unique(),
+      payable_chain_id: 1,
+      chain_count: 1,
+      payer_count: 1,
+      timestamp: 0,
+      details: TokenAndAmount { token, amount: 10 },
+      memo: None,
+    }
+  }
+
+  fn payment_refund(is_refunded: bool) -> PaymentRefund {
+    PaymentRefund {
+      is_refunded,
+      amount: 0,
+      timestamp: 0,
+    }
+  }
+
+  #[test]
+  fn accepts_a_refundable_payment() {
+    let (payable_id, token) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let payable = payable_with_balance(token, 10);
+    let user_payment = user_payment_to(payable_id, token);
+    let result = check_refund_inputs(
+      payable_id,
+      &payable,
+      &user_payment,
+      &payment_refund(false),
+    );
+    assert!(result.is_ok());
+  }
+
+  #[test]
+  fn rejects_payments_to_other_payables() {
+    let (payable_id, token) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let payable = payable_with_balance(token, 10);
+    let user_payment = user_payment_to(Pubkey::new_unique(), token);
+    let result = check_refund_inputs(
+      payable_id,
+      &payable,
+      &user_payment,
+      &payment_refund(false),
+    );
+    assert_eq!(
+      result.unwrap_err(),
+      ChainbillsError::PaymentNotForPayable.into()
+    );
+  }
+
+  #[test]
+  fn rejects_double_refunds() {
+    let (payable_id, token) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let payable = payable_with_balance(token, 10);
+    let user_payment = user_payment_to(payable_id, token);
+    let result = check_refund_inputs(
+      payable_id,
+      &payable,
+      &user_payment,
+      &payment_refund(true),
+    );
+    assert_eq!(
+      result.unwrap_err(),
+      ChainbillsError::PaymentAlreadyRefunded.into()
+    );
+  }
+
+  #[test]
+  fn rejects_insufficient_payable_balance() {
+    let (payable_id, token) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let user_payment = user_payment_to(payable_id, token);
+
+    // Some of the payment has been withdrawn.
+    let payable = payable_with_balance(token, 9);
+    let result = check_refund_inputs(
+      payable_id,
+      &payable,
+      &user_payment,
+      &payment_refund(false),
+    );
+    assert_eq!(
+      result.unwrap_err(),
+      ChainbillsError::InsufficientPayableBalance.into()
+    );
+
+    // The payable has no balance in the payment's token.
+    let payable = payable_with_balance(Pubkey::new_unique(), 10);
+    let result = check_refund_inputs(
+      payable_id,
+      &payable,
+      &user_payment,
+      &payment_refund(false),
+    );
+    assert_eq!(
+      result.unwrap_err(),
+      ChainbillsError::InsufficientPayableBalance.into()
+    );
+  }
+
+  fn chain_stats() -> ChainStats {
+    ChainStats {
+      users_count: 2,
+      payables_count: 1,
+      user_payments_count: 1,
+      payable_payments_count: 1,
+      withdrawals_count: 0,
+      activities_count: 4,
+    }
+  }
+
+  fn host() -> User {
+    User {
+      chain_count: 1,
+      payables_count: 1,
+      payments_count: 0,
+      withdrawals_count: 0,
+      activities_count: 2,
+    }
+  }
+
+  fn activity() -> ActivityRecord {
+    ActivityRecord {
+      chain_count: 0,
+      user_count: 0,
+      payable_count: 0,
+      timestamp: 0,
+      entity: Pubkey::default(),
+      activity_type: ActivityType::UserPaid,
+    }
+  }
+
+  #[test]
+  fn refunds_native_payments() {
+    let (payable_id, payment_id) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let host_wallet = Pubkey::new_unique();
+    let mut payable = payable_with_balance(crate::ID, 10);
+    let user_payment = user_payment_to(payable_id, crate::ID);
+    let mut payment_refund = payment_refund(false);
+    let mut chain_stats = chain_stats();
+    let mut host = host();
+    let mut activity = activity();
+    let mut user_activity_info = UserActivityInfo { chain_count: 0 };
+    let mut payable_activity_info = PayableActivityInfo { chain_count: 0 };
+
+    // The chain_stats account holds native payments.
+    let chain_stats_id = Pubkey::new_unique();
+    let (mut escrow_lamports, mut payer_lamports) = (1_000, 5);
+    let (mut escrow_data, mut payer_data) = (vec![], vec![]);
+    let escrow = AccountInfo::new(
+      &chain_stats_id,
+      false,
+      true,
+      &mut escrow_lamports,
+      &mut escrow_data,
+      &crate::ID,
+      false,
+      0,
+    );
+    let system_program = anchor_lang::system_program::ID;
+    let payer = AccountInfo::new(
+      &user_payment.payer,
+      false,
+      true,
+      &mut payer_lamports,
+      &mut payer_data,
+      &system_program,
+      false,
+      0,
+    );
+
+    // As refund_payment_native does.
+    check_refund_inputs(payable_id, &payable, &user_payment, &payment_refund)
+      .unwrap();
+    transfer_lamports(&escrow, &payer, user_payment.details.amount).unwrap();
+    update_state_for_refund(
+      payable_id,
+      payment_id,
+      host_wallet,
+      1_234,
+      &user_payment,
+      &mut chain_stats,
+      &mut host,
+      &mut payable,
+      &mut payment_refund,
+      &mut activity,
+      &mut user_activity_info,
+      &mut payable_activity_info,
+    )
+    .unwrap();
+
+    assert_eq!(escrow.lamports(), 990);
+    assert_eq!(payer.lamports(), 15);
+    assert_eq!(payable.balances[0].amount, 0);
+    assert_eq!(payable.activities_count, 3);
+    assert_eq!(host.activities_count, 3);
+    assert_eq!(chain_stats.activities_count, 5);
+    assert!(payment_refund.is_refunded);
+    assert_eq!(payment_refund.amount, 10);
+    assert_eq!(payment_refund.timestamp, 1_234);
+    assert_eq!(activity.chain_count, 5);
+    assert_eq!(activity.user_count, 3);
+    assert_eq!(activity.payable_count, 3);
+    assert_eq!(activity.entity, payment_id);
+    assert!(matches!(
+      activity.activity_type,
+      ActivityType::RefundedPayment
+    ));
+    assert_eq!(user_activity_info.chain_count, 5);
+    assert_eq!(payable_activity_info.chain_count, 5);
+
+    // The payment can't be refunded again.
+    assert_eq!(
+      check_refund_inputs(payable_id, &payable, &user_payment, &payment_refund)
+        .unwrap_err(),
+      ChainbillsError::PaymentAlreadyRefunded.into()
+    );
+  }
+}
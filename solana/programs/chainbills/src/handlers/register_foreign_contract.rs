@@ -1,8 +1,48 @@
 use crate::{
-  context::RegisterForeignContract, error::ChainbillsError, events::*,
+  context::{GetForeignContract, RegisterForeignContract},
+  error::ChainbillsError,
+  events::*,
+  payload::is_zero_bytes32,
+  state::ForeignContract,
 };
 use anchor_lang::prelude::*;
 
+/// Ensures that a VAA was emitted by the foreign contract registered for its
+/// emitter chain. The ForeignContract account must be the one derived from
+/// the emitter chain, and its address must be the emitter address. Should be
+/// checked by handlers of received messages.
+///
+/// ### Arguments
+/// * `key`              - Address of the provided ForeignContract account
+/// * `foreign_contract` - The provided ForeignContract account's data
+/// * `emitter_chain`    - The VAA's emitter chain
+/// * `emitter_address`  - The VAA's emitter address
+pub fn check_foreign_emitter(
+  key: Pubkey,
+  foreign_contract: &ForeignContract,
+  emitter_chain: u16,
+  emitter_address: &[u8; 32],
+) -> Result<()> {
+  require!(
+    key == ForeignContract::pda(emitter_chain).0
+      && foreign_contract.address == *emitter_address,
+    ChainbillsError::InvalidForeignEmitter
+  );
+  Ok(())
+}
+
+/// Saves the address into the ForeignContract. Returns the previously saved
+/// address if the contract was already registered (and is being updated).
+fn save_foreign_contract(
+  foreign_contract: &mut ForeignContract,
+  address: [u8; 32],
+) -> Option<[u8; 32]> {
+  let previous = foreign_contract.address;
+  foreign_contract.address = address;
+  // A zero address means that the account was just initialized.
+  (previous != [0u8; 32]).then_some(previous)
+}
+
 /// This instruction registers a new foreign contract (from another network)
 /// and saves the contract information in a ForeignContract account. This
 /// instruction is owner-only, meaning that only the owner of the program
@@ -23,19 +63,129 @@ pub fn register_foreign_contract_handler(
   require!(
     chain > 0
       && chain != ctx.accounts.config.load()?.chain_id
-      && !address.iter().all(|&x| x == 0),
+      && !is_zero_bytes32(&address),
     ChainbillsError::InvalidForeignContract,
   );
 
-  // Save the contract info into the ForeignContract account.
+  // Save the contract info into the ForeignContract account. If a contract
+  // was already registered for the chain, its address is overwritten (as in
+  // key rotations on the other chain).
   let contract = &mut ctx.accounts.foreign_contract;
-  contract.address = address;
-
-  // Emit log and event.
-  msg!("Registered Foreign Contract and its ChainStats");
-  emit!(RegisteredForeignContract {
-    chain_id: chain,
-    emitter: address
-  });
+  match save_foreign_contract(contract, address) {
+    Some(previous_emitter) => {
+      msg!("Updated Foreign Contract");
+      emit!(UpdatedForeignContract {
+        chain_id: chain,
+        previous_emitter,
+        emitter: address
+      });
+    }
+    None => {
+      msg!("Registered Foreign Contract and its ChainStats");
+      emit!(RegisteredForeignContract {
+        chain_id: chain,
+        emitter: address
+      });
+    }
+  }
+  Ok(())
+}
+
+/// Logs the Wormhole Chain ID and the normalized (32-byte) address of the
+/// foreign contract registered for the chain.
+///
+/// ### Arguments
+/// * `ctx`   - `GetForeignContract` context
+/// * `chain` - Wormhole Chain ID
+#[inline(never)]
+pub fn get_foreign_contract_handler(
+  ctx: Context<GetForeignContract>,
+  chain: u16,
+) -> Result<()> {
+  msg!(
+    "Foreign Contract of chain {}: {:?}",
+    chain,
+    ctx.accounts.foreign_contract.address
+  );
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn updates_registered_contracts() {
+    let mut foreign_contract = ForeignContract::default();
+
+    // Registering for the first time.
+    assert_eq!(
+      save_foreign_contract(&mut foreign_contract, [1u8; 32]),
+      None
+    );
+    assert_eq!(foreign_contract.address, [1u8; 32]);
+
+    // Updating the same chain's contract returns the previous address.
+    assert_eq!(
+      save_foreign_contract(&mut foreign_contract, [2u8; 32]),
+      Some([1u8; 32])
+    );
+    assert_eq!(foreign_contract.address, [2u8; 32]);
+
+    // Emitters are checked against the new address.
+    let (key, _) = ForeignContract::pda(2);
+    assert!(
+      check_foreign_emitter(key, &foreign_contract, 2, &[2u8; 32]).is_ok()
+    );
+    assert!(
+      check_foreign_emitter(key, &foreign_contract, 2, &[1u8; 32]).is_err()
+    );
+  }
+
+  #[test]
+  fn reads_back_contracts_of_each_chain() {
+    let registered = [(2u16, [1u8; 32]), (30u16, [2u8; 32])];
+    let keys = registered.map(|(chain, _)| ForeignContract::pda(chain).0);
+    assert_ne!(keys[0], keys[1]);
+
+    for (chain, address) in registered {
+      let mut data = vec![];
+      ForeignContract { address }
+        .try_serialize(&mut data)
+        .unwrap();
+      assert_eq!(data.len(), ForeignContract::SPACE);
+      let read = ForeignContract::try_deserialize(&mut &data[..]).unwrap();
+      assert_eq!(read.address, address);
+      assert!(check_foreign_emitter(
+        ForeignContract::pda(chain).0,
+        &read,
+        chain,
+        &address
+      )
+      .is_ok());
+    }
+  }
+
+  #[test]
+  fn accepts_only_registered_emitters() {
+    let address = [7u8; 32];
+    let (key, _) = ForeignContract::pda(2);
+    let foreign_contract = ForeignContract { address };
+
+    // A VAA from the registered emitter.
+    assert!(check_foreign_emitter(key, &foreign_contract, 2, &address).is_ok());
+
+    // A VAA from another emitter on the registered chain.
+    assert_eq!(
+      check_foreign_emitter(key, &foreign_contract, 2, &[8u8; 32]).unwrap_err(),
+      ChainbillsError::InvalidForeignEmitter.into()
+    );
+
+    // A VAA from an unregistered chain, checked against the contract
+    // registered for another chain.
+    assert_eq!(
+      check_foreign_emitter(key, &foreign_contract, 4, &address).unwrap_err(),
+      ChainbillsError::InvalidForeignEmitter.into()
+    );
+  }
+}
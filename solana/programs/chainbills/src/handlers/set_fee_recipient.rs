@@ -0,0 +1,33 @@
+use crate::{context::*, events::*};
+use anchor_lang::prelude::*;
+
+/// Sets where withdrawal fees from a payable go. Should be called only by
+/// the owner of this program, so that hosts can't redirect the protocol's
+/// fees.
+///
+/// ### args
+/// * fee_recipient<Option<Pubkey>>: Where the fees should go. If None, they
+///   go to the chainbills_fee_collector in Config.
+#[inline(never)]
+pub fn set_fee_recipient_handler(
+  ctx: Context<SetFeeRecipient>,
+  fee_recipient: Option<Pubkey>,
+) -> Result<()> {
+  // Clearing the fee_recipient shortens the payable. Zeroing the old data
+  // keeps its last bytes from lingering past the payable's end once it is
+  // written back, where fields added to Payable later would read them.
+  ctx
+    .accounts
+    .payable
+    .to_account_info()
+    .try_borrow_mut_data()?
+    .fill(0);
+  ctx.accounts.payable.fee_recipient = fee_recipient;
+
+  msg!("Updated Fee Recipient.");
+  emit!(UpdatedFeeRecipient {
+    payable_id: ctx.accounts.payable.key(),
+    fee_recipient
+  });
+  Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::{
+  context::SetMaxWithdrawalAmount, error::ChainbillsError, events::*, state::*,
+};
+use anchor_lang::prelude::*;
+
+/// Ensures that a withdrawal doesn't exceed the configured maximum amount
+/// of a single withdrawal. A maximum of zero means unlimited.
+pub fn check_max_withdrawal_amount(config: &Config, amount: u64) -> Result<()> {
+  require!(
+    config.max_withdrawal_amount == 0 || amount <= config.max_withdrawal_amount,
+    ChainbillsError::WithdrawalExceedsMax
+  );
+  Ok(())
+}
+
+/// Sets the maximum amount of a single withdrawal. Should be called only by
+/// the owner of this program.
+///
+/// ### args
+/// * max_withdrawal_amount<u64>: The maximum amount. Zero means unlimited.
+#[inline(never)]
+pub fn set_max_withdrawal_amount_handler(
+  ctx: Context<SetMaxWithdrawalAmount>,
+  max_withdrawal_amount: u64,
+) -> Result<()> {
+  let config = &mut ctx.accounts.config.load_mut()?;
+  config.max_withdrawal_amount = max_withdrawal_amount;
+
+  msg!("Updated Max Withdrawal Amount.");
+  emit!(UpdatedMaxWithdrawalAmount {
+    max_withdrawal_amount
+  });
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn caps_withdrawal_amounts() {
+    let mut config = Config::test_default();
+
+    // Zero means unlimited.
+    assert!(check_max_withdrawal_amount(&config, u64::MAX).is_ok());
+
+    // Amounts below and at the cap are allowed, above it aren't.
+    config.max_withdrawal_amount = 1_000;
+    assert!(check_max_withdrawal_amount(&config, 999).is_ok());
+    assert!(check_max_withdrawal_amount(&config, 1_000).is_ok());
+    assert_eq!(
+      check_max_withdrawal_amount(&config, 1_001).unwrap_err(),
+      ChainbillsError::WithdrawalExceedsMax.into()
+    );
+  }
+}
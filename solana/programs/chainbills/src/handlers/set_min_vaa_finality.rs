@@ -0,0 +1,55 @@
+use crate::{
+  context::SetMinVaaFinality, error::ChainbillsError, events::*, state::*,
+};
+use anchor_lang::prelude::*;
+
+/// Ensures that a received VAA was emitted with at least the configured
+/// minimum consistency level (finality), so that messages that could still
+/// be re-orged on their source chain aren't acted upon. Should be checked by
+/// handlers of received messages with the VAA's `finality()`.
+pub fn check_vaa_finality(config: &Config, finality: u8) -> Result<()> {
+  require!(
+    finality >= config.min_vaa_finality,
+    ChainbillsError::InsufficientVaaFinality
+  );
+  Ok(())
+}
+
+/// Sets the minimum Wormhole consistency level (finality) of received VAAs.
+/// Should be called only by the owner of this program.
+///
+/// ### args
+/// * min_vaa_finality<u8>: The minimum consistency level.
+#[inline(never)]
+pub fn set_min_vaa_finality_handler(
+  ctx: Context<SetMinVaaFinality>,
+  min_vaa_finality: u8,
+) -> Result<()> {
+  let config = &mut ctx.accounts.config.load_mut()?;
+  config.min_vaa_finality = min_vaa_finality;
+
+  msg!("Updated Min VAA Finality.");
+  emit!(UpdatedMinVaaFinality { min_vaa_finality });
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rejects_vaas_below_the_min_finality() {
+    let mut config = Config::test_default();
+
+    // Without a minimum, any VAA is accepted.
+    assert!(check_vaa_finality(&config, 0).is_ok());
+
+    // With a minimum of finalized (1), confirmed (0) VAAs are rejected.
+    config.min_vaa_finality = 1;
+    assert!(check_vaa_finality(&config, 1).is_ok());
+    assert_eq!(
+      check_vaa_finality(&config, 0).unwrap_err(),
+      ChainbillsError::InsufficientVaaFinality.into()
+    );
+  }
+}
@@ -0,0 +1,185 @@
+use crate::{context::SetPaused, error::ChainbillsError, events::*, state::*};
+use anchor_lang::prelude::*;
+
+/// Ensures that the program isn't paused.
+pub fn check_not_paused(config: &Config) -> Result<()> {
+  require!(!config.is_paused(), ChainbillsError::ContractPaused);
+  Ok(())
+}
+
+/// Ensures that cross-chain (Wormhole) operations aren't paused. Should be
+/// checked by handlers of received messages and cross-chain withdrawals.
+pub fn check_cross_chain_not_paused(config: &Config) -> Result<()> {
+  require!(
+    !config.is_cross_chain_paused(),
+    ChainbillsError::CrossChainPaused
+  );
+  Ok(())
+}
+
+/// Ensures that payments aren't paused, either on their own or as part of
+/// the whole program.
+pub fn check_payments_not_paused(config: &Config) -> Result<()> {
+  check_not_paused(config)?;
+  require!(
+    !config.is_payments_paused(),
+    ChainbillsError::PaymentsPaused
+  );
+  Ok(())
+}
+
+/// Ensures that withdrawals aren't paused, either on their own or as part of
+/// the whole program.
+pub fn check_withdrawals_not_paused(config: &Config) -> Result<()> {
+  check_not_paused(config)?;
+  require!(
+    !config.is_withdrawals_paused(),
+    ChainbillsError::WithdrawalsPaused
+  );
+  Ok(())
+}
+
+/// Pauses or unpauses payments, withdrawals, and payable creation.
+/// Should be called only by the owner of this program.
+///
+/// ### args
+/// * paused<bool>: Whether the program should be paused.
+#[inline(never)]
+pub fn set_paused_handler(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+  let config = &mut ctx.accounts.config.load_mut()?;
+  config.paused = paused.into();
+
+  msg!("Updated Paused State.");
+  emit!(UpdatedPausedState { paused });
+  Ok(())
+}
+
+/// Pauses or unpauses cross-chain (Wormhole) operations. Local operations are
+/// not affected. Should be called only by the owner of this program.
+///
+/// ### args
+/// * paused<bool>: Whether cross-chain operations should be paused.
+#[inline(never)]
+pub fn set_cross_chain_paused_handler(
+  ctx: Context<SetPaused>,
+  paused: bool,
+) -> Result<()> {
+  let config = &mut ctx.accounts.config.load_mut()?;
+  config.cross_chain_paused = paused.into();
+
+  msg!("Updated Cross-Chain Paused State.");
+  emit!(UpdatedCrossChainPausedState { paused });
+  Ok(())
+}
+
+/// Pauses or unpauses payments only. Withdrawals are not affected.
+/// Should be called only by the owner of this program.
+///
+/// ### args
+/// * paused<bool>: Whether payments should be paused.
+#[inline(never)]
+pub fn set_payments_paused_handler(
+  ctx: Context<SetPaused>,
+  paused: bool,
+) -> Result<()> {
+  let config = &mut ctx.accounts.config.load_mut()?;
+  config.payments_paused = paused.into();
+
+  msg!("Updated Payments Paused State.");
+  emit!(UpdatedPaymentsPausedState { paused });
+  Ok(())
+}
+
+/// Pauses or unpauses withdrawals only. Payments are not affected.
+/// Should be called only by the owner of this program.
+///
+/// ### args
+/// * paused<bool>: Whether withdrawals should be paused.
+#[inline(never)]
+pub fn set_withdrawals_paused_handler(
+  ctx: Context<SetPaused>,
+  paused: bool,
+) -> Result<()> {
+  let config = &mut ctx.accounts.config.load_mut()?;
+  config.withdrawals_paused = paused.into();
+
+  msg!("Updated Withdrawals Paused State.");
+  emit!(UpdatedWithdrawalsPausedState { paused });
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn blocks_only_when_paused() {
+    let mut config = Config::test_default();
+    assert!(check_not_paused(&config).is_ok());
+
+    config.paused = true.into();
+    assert_eq!(
+      check_not_paused(&config).unwrap_err(),
+      ChainbillsError::ContractPaused.into()
+    );
+
+    config.paused = false.into();
+    assert!(check_not_paused(&config).is_ok());
+  }
+
+  #[test]
+  fn pauses_cross_chain_independently() {
+    let mut config = Config {
+      cross_chain_paused: 1,
+      ..Config::test_default()
+    };
+
+    // Received messages are rejected while local operations still go on.
+    assert_eq!(
+      check_cross_chain_not_paused(&config).unwrap_err(),
+      ChainbillsError::CrossChainPaused.into()
+    );
+    assert!(check_not_paused(&config).is_ok());
+
+    config.cross_chain_paused = 0;
+    assert!(check_cross_chain_not_paused(&config).is_ok());
+  }
+
+  #[test]
+  fn pauses_payments_and_withdrawals_independently() {
+    let mut config = Config::test_default();
+    assert!(check_payments_not_paused(&config).is_ok());
+    assert!(check_withdrawals_not_paused(&config).is_ok());
+
+    // Only payments are blocked.
+    config.payments_paused = true.into();
+    assert_eq!(
+      check_payments_not_paused(&config).unwrap_err(),
+      ChainbillsError::PaymentsPaused.into()
+    );
+    assert!(check_withdrawals_not_paused(&config).is_ok());
+    assert!(check_not_paused(&config).is_ok());
+
+    // Only withdrawals are blocked.
+    config.payments_paused = false.into();
+    config.withdrawals_paused = true.into();
+    assert!(check_payments_not_paused(&config).is_ok());
+    assert_eq!(
+      check_withdrawals_not_paused(&config).unwrap_err(),
+      ChainbillsError::WithdrawalsPaused.into()
+    );
+    assert!(check_not_paused(&config).is_ok());
+
+    // The overall pause still blocks both.
+    config.withdrawals_paused = false.into();
+    config.paused = true.into();
+    assert_eq!(
+      check_payments_not_paused(&config).unwrap_err(),
+      ChainbillsError::ContractPaused.into()
+    );
+    assert_eq!(
+      check_withdrawals_not_paused(&config).unwrap_err(),
+      ChainbillsError::ContractPaused.into()
+    );
+  }
+}
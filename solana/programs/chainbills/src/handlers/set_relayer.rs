@@ -0,0 +1,85 @@
+use crate::{context::SetRelayer, error::ChainbillsError, events::*, state::*};
+use anchor_lang::prelude::*;
+
+/// Ensures that the Relayer account is of an authorized relayer. Should be
+/// checked by instructions that act on behalf of other wallets.
+///
+/// ### Arguments
+/// * `key`             - Address of the provided Relayer account
+/// * `relayer_account` - The provided Relayer account's data
+/// * `relayer`         - The wallet acting as a relayer
+pub fn check_relayer(
+  key: Pubkey,
+  relayer_account: &Relayer,
+  relayer: Pubkey,
+) -> Result<()> {
+  require!(
+    key == Relayer::pda(relayer).0 && relayer_account.is_authorized,
+    ChainbillsError::RelayerUnauthorized
+  );
+  Ok(())
+}
+
+/// Authorizes or deauthorizes a relayer. Should be called only by the owner
+/// of this program.
+///
+/// ### args
+/// * relayer<Pubkey>: The wallet of the relayer.
+/// * is_authorized<bool>: Whether the relayer should be trusted.
+#[inline(never)]
+pub fn set_relayer_handler(
+  ctx: Context<SetRelayer>,
+  relayer: Pubkey,
+  is_authorized: bool,
+) -> Result<()> {
+  ctx.accounts.relayer_account.is_authorized = is_authorized;
+
+  msg!("Updated Relayer.");
+  emit!(UpdatedRelayer {
+    relayer,
+    is_authorized
+  });
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn accepts_only_authorized_relayers() {
+    let relayer = Pubkey::new_unique();
+    let (key, _) = Relayer::pda(relayer);
+    let mut relayer_account = Relayer::default();
+
+    // Accounts start out unauthorized.
+    assert_eq!(
+      check_relayer(key, &relayer_account, relayer).unwrap_err(),
+      ChainbillsError::RelayerUnauthorized.into()
+    );
+
+    relayer_account.is_authorized = true;
+    assert!(check_relayer(key, &relayer_account, relayer).is_ok());
+
+    // The account must be the one derived from the relayer's wallet.
+    let other = Pubkey::new_unique();
+    assert_eq!(
+      check_relayer(key, &relayer_account, other).unwrap_err(),
+      ChainbillsError::RelayerUnauthorized.into()
+    );
+
+    // Deauthorized relayers are rejected.
+    relayer_account.is_authorized = false;
+    assert!(check_relayer(key, &relayer_account, relayer).is_err());
+  }
+
+  #[test]
+  fn derives_one_account_per_relayer() {
+    let relayer = Pubkey::new_unique();
+    assert_eq!(Relayer::pda(relayer), Relayer::pda(relayer));
+    assert_ne!(
+      Relayer::pda(relayer).0,
+      Relayer::pda(Pubkey::new_unique()).0
+    );
+  }
+}
@@ -0,0 +1,107 @@
+use crate::{
+  context::SetTokenSupported, error::ChainbillsError, events::*, state::*,
+};
+use anchor_lang::prelude::*;
+
+/// Ensures that payments are currently accepted in the token.
+pub fn check_token_supported(token_details: &TokenDetails) -> Result<()> {
+  require!(
+    token_details.is_supported,
+    ChainbillsError::UnsupportedToken
+  );
+  Ok(())
+}
+
+/// Sets whether payments are accepted in the token, listing or unlisting it
+/// among the supported tokens.
+fn set_token_supported(
+  token_details: &mut TokenDetails,
+  supported_tokens: &mut SupportedTokens,
+  token: Pubkey,
+  is_supported: bool,
+) {
+  token_details.is_supported = is_supported;
+  supported_tokens.set_supported(token, is_supported);
+}
+
+/// Starts or stops accepting payments in the given token. Existing balances
+/// in the token can still be withdrawn when it is no longer supported.
+/// Should be called only by the owner of this program.
+///
+/// ### args
+/// * token<Pubkey>: The token mint whose support status is being set.
+/// * is_supported<bool>: Whether payments should be accepted in the token.
+#[inline(never)]
+pub fn set_token_supported_handler(
+  ctx: Context<SetTokenSupported>,
+  token: Pubkey,
+  is_supported: bool,
+) -> Result<()> {
+  set_token_supported(
+    ctx.accounts.token_details.as_mut(),
+    ctx.accounts.supported_tokens.as_mut(),
+    token,
+    is_supported,
+  );
+
+  msg!("Updated Token Supported State.");
+  emit!(UpdatedTokenSupported {
+    token,
+    is_supported
+  });
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::handlers::register_token;
+
+  #[test]
+  fn rejects_payments_in_unsupported_tokens() {
+    let mut token_details = TokenDetails {
+      max_withdrawal_fees: 10,
+      ..TokenDetails::test_default()
+    };
+    assert!(check_token_supported(&token_details).is_ok());
+
+    token_details.is_supported = false;
+    assert_eq!(
+      check_token_supported(&token_details).unwrap_err(),
+      ChainbillsError::UnsupportedToken.into()
+    );
+  }
+
+  #[test]
+  fn lists_registered_tokens_until_they_are_unsupported() {
+    let mut supported_tokens = SupportedTokens { tokens: vec![] };
+    let mut first = TokenDetails {
+      mint: Pubkey::default(),
+      is_supported: false,
+      ..TokenDetails::test_default()
+    };
+    let mut second = TokenDetails {
+      mint: Pubkey::default(),
+      is_supported: false,
+      ..TokenDetails::test_default()
+    };
+    let (first_mint, second_mint) = (Pubkey::new_unique(), crate::ID);
+
+    // Both registered tokens are listed.
+    register_token(&mut first, &mut supported_tokens, first_mint, 10, 0)
+      .unwrap();
+    register_token(&mut second, &mut supported_tokens, second_mint, 20, 0)
+      .unwrap();
+    assert_eq!(supported_tokens.tokens, vec![first_mint, second_mint]);
+
+    // The unsupported one is unlisted, and only it.
+    set_token_supported(&mut first, &mut supported_tokens, first_mint, false);
+    assert!(!first.is_supported);
+    assert!(second.is_supported);
+    assert_eq!(supported_tokens.tokens, vec![second_mint]);
+
+    // Supporting it again lists it back.
+    set_token_supported(&mut first, &mut supported_tokens, first_mint, true);
+    assert_eq!(supported_tokens.tokens, vec![second_mint, first_mint]);
+  }
+}
@@ -0,0 +1,136 @@
+use crate::{
+  context::SweepDust, error::ChainbillsError, events::*,
+  handlers::check_withdrawals_not_paused, state::*,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, TransferChecked};
+
+/// The number of decimals that Wormhole normalizes token amounts to.
+pub const WORMHOLE_DECIMALS: u8 = 8;
+
+/// The smallest amount of a token with the given decimals that survives
+/// Wormhole's normalization. Amounts below it are dust. Tokens with at most
+/// 8 decimals can't have dust, so their threshold is the smallest unit.
+pub fn dust_threshold(decimals: u8) -> u64 {
+  10u64.pow(decimals.saturating_sub(WORMHOLE_DECIMALS).into())
+}
+
+/// Removes the payable's balance of the token, if it is dust, and returns it.
+fn take_dust(
+  payable: &mut Payable,
+  token: Pubkey,
+  decimals: u8,
+) -> Result<u64> {
+  let position = payable
+    .balances
+    .iter()
+    .position(|balance| balance.token == token)
+    .ok_or(ChainbillsError::InsufficientPayableBalance)?;
+  let amount = payable.balances[position].amount;
+  require!(amount < dust_threshold(decimals), ChainbillsError::NotDust);
+
+  payable.balances.remove(position);
+  Ok(amount)
+}
+
+/// Moves a payable's dust balance of a token to its host, clearing the
+/// balance entry. Can be called only by the host (user) that owns the
+/// payable.
+#[inline(never)]
+pub fn sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+  /* CHECKS */
+  check_withdrawals_not_paused(&*ctx.accounts.config.load()?)?;
+  let mint = &ctx.accounts.mint;
+  let payable = ctx.accounts.payable.as_mut();
+
+  /* STATE CHANGES */
+  let amount = take_dust(payable, mint.key(), mint.decimals)?;
+
+  /* TRANSFER */
+  if amount > 0 {
+    token_interface::transfer_checked(
+      CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+          from: ctx.accounts.chain_token_account.to_account_info(),
+          mint: mint.to_account_info(),
+          to: ctx.accounts.host_token_account.to_account_info(),
+          authority: ctx.accounts.chain_stats.to_account_info(),
+        },
+        &[&[ChainStats::SEED_PREFIX, &[ctx.bumps.chain_stats]]],
+      ),
+      amount,
+      mint.decimals,
+    )?;
+  }
+
+  msg!("Swept Dust.");
+  emit!(SweptDust {
+    payable_id: payable.key(),
+    token: mint.key(),
+    amount
+  });
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn payable_with_balances(balances: Vec<TokenAndAmount>) -> Payable {
+    Payable {
+      payments_count: 1,
+      balances,
+      ..Payable::test_default()
+    }
+  }
+
+  #[test]
+  fn computes_dust_threshold_from_decimals() {
+    assert_eq!(dust_threshold(6), 1);
+    assert_eq!(dust_threshold(8), 1);
+    assert_eq!(dust_threshold(9), 10);
+    assert_eq!(dust_threshold(18), 10_000_000_000);
+  }
+
+  #[test]
+  fn sweeps_dust_left_by_normalization() {
+    let token = Pubkey::new_unique();
+    let other = Pubkey::new_unique();
+    let decimals = 9;
+    let mut payable = payable_with_balances(vec![
+      TokenAndAmount {
+        token,
+        amount: 1_000_000_005,
+      },
+      TokenAndAmount {
+        token: other,
+        amount: 7,
+      },
+    ]);
+
+    // A non-dust balance can't be swept.
+    assert_eq!(
+      take_dust(&mut payable, token, decimals).unwrap_err(),
+      ChainbillsError::NotDust.into()
+    );
+
+    // Withdrawing the whole Wormhole-normalized amount leaves dust behind.
+    let threshold = dust_threshold(decimals);
+    let normalized = payable.balances[0].amount / threshold;
+    payable.balances[0].amount -= normalized * threshold;
+    assert_eq!(payable.balances[0].amount, 5);
+
+    // Sweeping the dust clears the balance entry.
+    assert_eq!(take_dust(&mut payable, token, decimals).unwrap(), 5);
+    assert_eq!(payable.balance_of(token), 0);
+    assert_eq!(payable.balances.len(), 1);
+    assert_eq!(payable.balance_of(other), 7);
+
+    // Nothing is left to sweep afterwards.
+    assert_eq!(
+      take_dust(&mut payable, token, decimals).unwrap_err(),
+      ChainbillsError::InsufficientPayableBalance.into()
+    );
+  }
+}
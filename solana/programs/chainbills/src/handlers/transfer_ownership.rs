@@ -0,0 +1,107 @@
+use crate::{context::*, error::ChainbillsError, events::*, state::*};
+use anchor_lang::prelude::*;
+
+/// Promotes the signer to owner if they are the pending owner. Returns the
+/// previous owner.
+fn promote_pending_owner(
+  config: &mut Config,
+  signer: Pubkey,
+) -> Result<Pubkey> {
+  require!(
+    config.pending_owner != Pubkey::default() && config.pending_owner == signer,
+    ChainbillsError::NotPendingOwner
+  );
+  let previous_owner = config.owner;
+  config.owner = signer;
+  config.pending_owner = Pubkey::default();
+  Ok(previous_owner)
+}
+
+/// Proposes a new owner for this program. The proposed owner has to call
+/// accept_ownership to become the owner. Proposing the default Pubkey
+/// cancels any pending proposal.
+///
+/// ### args
+/// * new_owner<Pubkey>: The account to become the next owner.
+#[inline(never)]
+pub fn propose_new_owner_handler(
+  ctx: Context<ProposeNewOwner>,
+  new_owner: Pubkey,
+) -> Result<()> {
+  let config = &mut ctx.accounts.config.load_mut()?;
+  config.pending_owner = new_owner;
+
+  msg!("Proposed New Owner.");
+  emit!(ProposedNewOwner {
+    owner: config.owner,
+    new_owner
+  });
+  Ok(())
+}
+
+/// Makes the pending owner the owner of this program.
+/// Should be called only by the pending owner.
+#[inline(never)]
+pub fn accept_ownership_handler(ctx: Context<AcceptOwnership>) -> Result<()> {
+  let config = &mut ctx.accounts.config.load_mut()?;
+  let new_owner = ctx.accounts.new_owner.key();
+  let previous_owner = promote_pending_owner(config, new_owner)?;
+
+  msg!("Accepted Ownership.");
+  emit!(AcceptedOwnership {
+    previous_owner,
+    new_owner
+  });
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn config_with_owner(owner: Pubkey) -> Config {
+    Config {
+      owner,
+      ..Config::test_default()
+    }
+  }
+
+  #[test]
+  fn rejects_acceptance_without_proposal() {
+    let owner = Pubkey::new_unique();
+    let mut config = config_with_owner(owner);
+    assert_eq!(
+      promote_pending_owner(&mut config, Pubkey::default()).unwrap_err(),
+      ChainbillsError::NotPendingOwner.into()
+    );
+    assert_eq!(config.owner, owner);
+  }
+
+  #[test]
+  fn transfers_ownership_to_pending_owner_only() {
+    let owner = Pubkey::new_unique();
+    let new_owner = Pubkey::new_unique();
+    let mut config = config_with_owner(owner);
+    config.pending_owner = new_owner;
+
+    // Accepting with any other key fails and changes nothing.
+    assert_eq!(
+      promote_pending_owner(&mut config, Pubkey::new_unique()).unwrap_err(),
+      ChainbillsError::NotPendingOwner.into()
+    );
+    assert_eq!(
+      promote_pending_owner(&mut config, owner).unwrap_err(),
+      ChainbillsError::NotPendingOwner.into()
+    );
+    assert_eq!(config.owner, owner);
+    assert_eq!(config.pending_owner, new_owner);
+
+    // The pending owner becomes the owner and the proposal is cleared.
+    assert_eq!(
+      promote_pending_owner(&mut config, new_owner).unwrap(),
+      owner
+    );
+    assert_eq!(config.owner, new_owner);
+    assert_eq!(config.pending_owner, Pubkey::default());
+  }
+}
@@ -1,11 +1,50 @@
-use crate::{context::*, error::ChainbillsError, events::*};
-use anchor_lang::prelude::*;
+use crate::{
+  context::*, error::ChainbillsError, events::*, handlers::grow_account,
+  state::*,
+};
+use anchor_lang::{prelude::*, solana_program::clock};
 
-/// Updates the maximum withdrawal fees of the given token.
+/// Sets the mint and maximum withdrawal fees of the token details (marking
+/// the token as supported). Returns the fees in effect before the update
+/// and the timestamp from which the new fees apply. The previous fees of
+/// newly added tokens are zero.
+fn save_max_withdrawal_fees(
+  token_details: &mut TokenDetails,
+  mint: Pubkey,
+  max_withdrawal_fees: u64,
+  now: u64,
+) -> Result<(u64, u64)> {
+  let previous_max_withdrawal_fees =
+    token_details.effective_max_withdrawal_fees(now);
+  let effective_at =
+    token_details.schedule_max_withdrawal_fees(max_withdrawal_fees, now)?;
+  token_details.mint = mint;
+  token_details.is_supported = true;
+  Ok((previous_max_withdrawal_fees, effective_at))
+}
+
+/// Saves the maximum withdrawal fees of the token and lists it among the
+/// supported tokens. supported_tokens should have been grown to
+/// space_update(mint, true) beforehand.
+pub fn register_token(
+  token_details: &mut TokenDetails,
+  supported_tokens: &mut SupportedTokens,
+  mint: Pubkey,
+  max_withdrawal_fees: u64,
+  now: u64,
+) -> Result<(u64, u64)> {
+  let saved =
+    save_max_withdrawal_fees(token_details, mint, max_withdrawal_fees, now)?;
+  supported_tokens.set_supported(mint, true);
+  Ok(saved)
+}
+
+/// Updates the maximum withdrawal fees of the given token. Increases only
+/// apply after TokenDetails::FEES_TIMELOCK.
 ///
 /// ### Args
 /// * token<Pubkey>: The token mint for which its maximum withdrawal fees is
-///                  been set.
+///   been set.
 /// * max_withdrawal_fees<u64>: The maximum withdrawal fees to set.
 #[inline(never)]
 pub fn update_max_withdrawal_fees(
@@ -25,20 +64,33 @@ pub fn update_max_withdrawal_fees(
     return Err(ChainbillsError::WrongFeeCollectorAddress.into());
   }
 
-  let token_details = ctx.accounts.token_details.as_mut();
-  token_details.mint = token;
-  token_details.is_supported = true;
-  token_details.max_withdrawal_fees = max_withdrawal_fees;
+  let now = clock::Clock::get()?.unix_timestamp as u64;
+  grow_account(
+    &ctx.accounts.supported_tokens.to_account_info(),
+    &ctx.accounts.owner.to_account_info(),
+    &ctx.accounts.system_program.to_account_info(),
+    ctx.accounts.supported_tokens.space_update(token, true),
+  )?;
+  let (previous_max_withdrawal_fees, effective_at) = register_token(
+    ctx.accounts.token_details.as_mut(),
+    ctx.accounts.supported_tokens.as_mut(),
+    token,
+    max_withdrawal_fees,
+    now,
+  )?;
 
   msg!("Updated Max Withdrawal Fees.");
   emit!(UpdatedMaxWithdrawalFees {
     token,
-    max_withdrawal_fees
+    previous_max_withdrawal_fees,
+    max_withdrawal_fees,
+    effective_at
   });
   Ok(())
 }
 
 /// Updates the maximum withdrawal fees of the native token (Solana).
+/// Increases only apply after TokenDetails::FEES_TIMELOCK.
 ///
 /// ### Args
 /// * max_withdrawal_fees<u64>: The maximum withdrawal fees to set.
@@ -46,15 +98,117 @@ pub fn update_max_withdrawal_fees_native(
   ctx: Context<UpdateMaxWithdrawalFeesNative>,
   max_withdrawal_fees: u64,
 ) -> Result<()> {
-  let token_details = ctx.accounts.token_details.as_mut();
-  token_details.mint = crate::ID;
-  token_details.is_supported = true;
-  token_details.max_withdrawal_fees = max_withdrawal_fees;
+  let now = clock::Clock::get()?.unix_timestamp as u64;
+  grow_account(
+    &ctx.accounts.supported_tokens.to_account_info(),
+    &ctx.accounts.owner.to_account_info(),
+    &ctx.accounts.system_program.to_account_info(),
+    ctx.accounts.supported_tokens.space_update(crate::ID, true),
+  )?;
+  let (previous_max_withdrawal_fees, effective_at) = register_token(
+    ctx.accounts.token_details.as_mut(),
+    ctx.accounts.supported_tokens.as_mut(),
+    crate::ID,
+    max_withdrawal_fees,
+    now,
+  )?;
 
   msg!("Updated Max Withdrawal Fees.");
   emit!(UpdatedMaxWithdrawalFees {
     token: crate::ID,
-    max_withdrawal_fees
+    previous_max_withdrawal_fees,
+    max_withdrawal_fees,
+    effective_at
   });
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn new_token_details() -> TokenDetails {
+    TokenDetails {
+      mint: Pubkey::default(),
+      is_supported: false,
+      ..TokenDetails::test_default()
+    }
+  }
+
+  #[test]
+  fn returns_the_previous_max_withdrawal_fees() {
+    let mint = Pubkey::new_unique();
+    let mut token_details = new_token_details();
+
+    // Newly added tokens had no fees.
+    assert_eq!(
+      save_max_withdrawal_fees(&mut token_details, mint, 100, 5).unwrap(),
+      (0, 5)
+    );
+    assert_eq!(token_details.mint, mint);
+    assert!(token_details.is_supported);
+    assert_eq!(token_details.max_withdrawal_fees, 100);
+
+    // Updates report the fees they replaced.
+    assert_eq!(
+      save_max_withdrawal_fees(&mut token_details, mint, 40, 6).unwrap(),
+      (100, 6)
+    );
+    assert_eq!(token_details.max_withdrawal_fees, 40);
+  }
+
+  #[test]
+  fn delays_increased_fees_by_the_timelock() {
+    let mint = Pubkey::new_unique();
+    let mut token_details = new_token_details();
+    save_max_withdrawal_fees(&mut token_details, mint, 100, 0).unwrap();
+
+    let now = 1_000;
+    let effective_at = now + TokenDetails::FEES_TIMELOCK;
+    assert_eq!(
+      save_max_withdrawal_fees(&mut token_details, mint, 500, now).unwrap(),
+      (100, effective_at)
+    );
+    assert_eq!(token_details.max_withdrawal_fees, 100);
+    assert_eq!(token_details.pending_max_withdrawal_fees, 500);
+
+    // The increase doesn't apply before the delay.
+    assert_eq!(token_details.effective_max_withdrawal_fees(now), 100);
+    assert_eq!(
+      token_details.effective_max_withdrawal_fees(effective_at - 1),
+      100
+    );
+    token_details.promote_pending_fees(effective_at - 1);
+    assert_eq!(token_details.max_withdrawal_fees, 100);
+
+    // It does once the delay has elapsed, and is then promoted.
+    assert_eq!(
+      token_details.effective_max_withdrawal_fees(effective_at),
+      500
+    );
+    token_details.promote_pending_fees(effective_at);
+    assert_eq!(token_details.max_withdrawal_fees, 500);
+    assert_eq!(token_details.pending_max_withdrawal_fees, 0);
+    assert_eq!(token_details.pending_fees_effective_at, 0);
+  }
+
+  #[test]
+  fn applies_decreases_immediately_and_cancels_pending_increases() {
+    let mint = Pubkey::new_unique();
+    let mut token_details = new_token_details();
+    save_max_withdrawal_fees(&mut token_details, mint, 100, 0).unwrap();
+    save_max_withdrawal_fees(&mut token_details, mint, 500, 10).unwrap();
+
+    assert_eq!(
+      save_max_withdrawal_fees(&mut token_details, mint, 50, 20).unwrap(),
+      (100, 20)
+    );
+    assert_eq!(token_details.max_withdrawal_fees, 50);
+    assert_eq!(token_details.pending_fees_effective_at, 0);
+    assert_eq!(
+      token_details
+        .effective_max_withdrawal_fees(10 + TokenDetails::FEES_TIMELOCK),
+      50
+    );
+  }
+}
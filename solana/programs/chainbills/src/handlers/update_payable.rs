@@ -2,13 +2,35 @@ use crate::{
   context::*,
   error::ChainbillsError,
   events::*,
+  handlers::check_allowed_tokens_and_amounts,
   state::{
     ActivityRecord, ActivityType, ChainStats, Payable, PayableActivityInfo,
-    TokenAndAmount, TokenDetails, User, UserActivityInfo,
+    TokenAndAmount, User, UserActivityInfo,
   },
 };
 use anchor_lang::prelude::*;
 
+fn increment_activities_counts(
+  chain_stats: &mut ChainStats,
+  host: &mut User,
+  payable: &mut Payable,
+) -> Result<()> {
+  chain_stats.activities_count = chain_stats.next_activity()?;
+  host.activities_count = host.next_activity()?;
+  payable.activities_count = payable.next_activity()?;
+  Ok(())
+}
+
+/// Replaces the payable's allowed_tokens_and_amounts and returns how many
+/// tokens it now accepts.
+fn set_allowed_tokens_and_amounts(
+  payable: &mut Payable,
+  allowed_tokens_and_amounts: Vec<TokenAndAmount>,
+) -> u64 {
+  payable.allowed_tokens_and_amounts = allowed_tokens_and_amounts;
+  payable.allowed_tokens_and_amounts.len() as u64
+}
+
 fn record_update_payable_activity(
   chain_stats: &mut Account<ChainStats>,
   host: &mut Account<User>,
@@ -18,14 +40,8 @@ fn record_update_payable_activity(
   payable_activity_info: &mut Account<PayableActivityInfo>,
   activity_type: ActivityType,
 ) -> Result<()> {
-  // Increment the chain stats for activities count.
-  chain_stats.activities_count = chain_stats.next_activity();
-
-  // Increment the host's activities count.
-  host.activities_count = host.next_activity();
-
-  // Increment the payable's activities count.
-  payable.activities_count = payable.next_activity();
+  // Increment the chain stats, host, and payable activities counts.
+  increment_activities_counts(chain_stats, host, payable)?;
 
   // Initialize the activity.
   activity.chain_count = chain_stats.activities_count;
@@ -110,45 +126,23 @@ pub fn reopen_payable(ctx: Context<UpdatePayable>) -> Result<()> {
 ///
 /// ### args
 /// * allowed_tokens_and_amounts: the new set of tokens and amounts that the payable
-/// will accept.
+///   will accept.
 #[inline(never)]
 pub fn update_payable_allowed_tokens_and_amounts<'info>(
   ctx: Context<'_, '_, 'info, 'info, UpdatePayableAllowedTokensAndAmounts>,
   allowed_tokens_and_amounts: Vec<TokenAndAmount>,
 ) -> Result<()> {
   /* CHECKS */
-  // Ensure that length of remaining_accounts in context matches that of the
-  // allowed_tokens_and_amounts (ataas) vector. This is necessary inorder to
-  // use remaining_accounts to get the token details.
-  require!(
-    ctx.remaining_accounts.len() == allowed_tokens_and_amounts.len(),
-    ChainbillsError::InvalidRemainingAccountsLength
-  );
-
-  for (i, taa) in allowed_tokens_and_amounts.iter().enumerate() {
-    // Get the token details for the specified token.
-    let token_details =
-      Account::<'info, TokenDetails>::try_from(&ctx.remaining_accounts[i])
-        .map_err(|_| ChainbillsError::NonTokenDetailsAccountProvided)?;
-
-    // Ensure that the token is supported.
-    require!(
-      taa.token == token_details.mint,
-      ChainbillsError::InvalidTokenDetailsAccount
-    );
-    require!(
-      token_details.is_supported,
-      ChainbillsError::UnsupportedToken
-    );
-
-    // Ensure that all specified acceptable amounts are greater than zero.
-    require!(taa.amount > 0, ChainbillsError::ZeroAmountSpecified);
-  }
+  check_allowed_tokens_and_amounts(
+    ctx.remaining_accounts,
+    &allowed_tokens_and_amounts,
+  )?;
 
   /* STATE CHANGES */
   // Update the payable's allowed_tokens_and_amounts.
   let payable = ctx.accounts.payable.as_mut();
-  payable.allowed_tokens_and_amounts = allowed_tokens_and_amounts;
+  let allowed_tokens_count =
+    set_allowed_tokens_and_amounts(payable, allowed_tokens_and_amounts);
 
   // Record the activity.
   record_update_payable_activity(
@@ -165,7 +159,197 @@ pub fn update_payable_allowed_tokens_and_amounts<'info>(
   msg!("Updated Payable's allowedTokensAndAmounts.");
   emit!(UpdatedPayableAllowedTokensAndAmounts {
     payable_id: payable.key(),
-    host_wallet: ctx.accounts.signer.key()
+    host_wallet: ctx.accounts.signer.key(),
+    allowed_tokens_count
   });
   Ok(())
 }
+
+/// Allows a payable's host to set whether the payable accepts payments
+/// bridged from other chains. Local payments are always accepted.
+///
+/// ### args
+/// * accepts_cross_chain<bool>: Whether to accept payments from other chains.
+#[inline(never)]
+pub fn update_payable_accepts_cross_chain(
+  ctx: Context<UpdatePayableAcceptsCrossChain>,
+  accepts_cross_chain: bool,
+) -> Result<()> {
+  let payable = ctx.accounts.payable.as_mut();
+  payable.rejects_cross_chain = !accepts_cross_chain;
+
+  msg!("Updated Payable's acceptsCrossChain.");
+  emit!(UpdatedPayableAcceptsCrossChain {
+    payable_id: payable.key(),
+    host_wallet: ctx.accounts.signer.key(),
+    accepts_cross_chain
+  });
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn chain_stats() -> ChainStats {
+    ChainStats {
+      users_count: 1,
+      payables_count: 1,
+      user_payments_count: 0,
+      payable_payments_count: 0,
+      withdrawals_count: 0,
+      activities_count: 5,
+    }
+  }
+
+  fn host() -> User {
+    User {
+      chain_count: 1,
+      payables_count: 1,
+      payments_count: 0,
+      withdrawals_count: 0,
+      activities_count: 2,
+    }
+  }
+
+  fn tokens_and_amounts(len: usize) -> Vec<TokenAndAmount> {
+    (1..=len)
+      .map(|i| TokenAndAmount {
+        token: Pubkey::new_unique(),
+        amount: i as u64 * 100,
+      })
+      .collect()
+  }
+
+  /// Serializes the payable into an account of the size that realloc would
+  /// give it and reads it back.
+  fn realloc_and_read_back(payable: &Payable, len: usize) -> Payable {
+    let mut data = vec![0u8; payable.space_update_ataa(len)];
+    payable.try_serialize(&mut data.as_mut_slice()).unwrap();
+    Payable::try_deserialize(&mut data.as_slice()).unwrap()
+  }
+
+  #[test]
+  fn increments_all_activities_counts() {
+    let mut chain_stats = chain_stats();
+    let mut host = host();
+    let mut payable = Payable::test_default();
+
+    increment_activities_counts(&mut chain_stats, &mut host, &mut payable)
+      .unwrap();
+    assert_eq!(chain_stats.activities_count, 6);
+    assert_eq!(host.activities_count, 3);
+    assert_eq!(payable.activities_count, 2);
+  }
+
+  #[test]
+  fn grows_and_shrinks_allowed_tokens_and_amounts() {
+    let mut payable = Payable::test_default();
+
+    // Growing from none to the maximum.
+    let grown = tokens_and_amounts(Payable::MAX_PAYABLES_TOKENS);
+    let count = set_allowed_tokens_and_amounts(&mut payable, grown.clone());
+    assert_eq!(count, Payable::MAX_PAYABLES_TOKENS as u64);
+    let read = realloc_and_read_back(&payable, grown.len());
+    assert_eq!(read.allowed_tokens_and_amounts.len(), grown.len());
+    for (read, expected) in read.allowed_tokens_and_amounts.iter().zip(&grown) {
+      assert_eq!(read.token, expected.token);
+      assert_eq!(read.amount, expected.amount);
+    }
+
+    // Shrinking down to a single token.
+    let shrunk = tokens_and_amounts(1);
+    let count = set_allowed_tokens_and_amounts(&mut payable, shrunk.clone());
+    assert_eq!(count, 1);
+    assert!(
+      payable.space_update_ataa(shrunk.len())
+        < payable.space_update_ataa(grown.len())
+    );
+    let read = realloc_and_read_back(&payable, shrunk.len());
+    assert_eq!(read.allowed_tokens_and_amounts.len(), 1);
+    assert_eq!(read.allowed_tokens_and_amounts[0].token, shrunk[0].token);
+    assert_eq!(read.allowed_tokens_and_amounts[0].amount, 100);
+
+    // Shrinking to none (free payments).
+    let count = set_allowed_tokens_and_amounts(&mut payable, vec![]);
+    assert_eq!(count, 0);
+    let read = realloc_and_read_back(&payable, 0);
+    assert!(read.allowed_tokens_and_amounts.is_empty());
+  }
+
+  /// A payable with all its optional fields set, so that it fills all the
+  /// space reserved for it.
+  fn full_payable(balances_len: usize) -> Payable {
+    Payable {
+      balances: tokens_and_amounts(balances_len),
+      fee_recipient: Some(Pubkey::new_unique()),
+      expires_at: Some(u64::MAX),
+      max_payment_amount: Some(u64::MAX),
+      goal_token: Some(Pubkey::new_unique()),
+      goal_amount: Some(u64::MAX),
+      max_payments: Some(u64::MAX),
+      ..Payable::test_default()
+    }
+  }
+
+  /// Resizes account data as AccountInfo::realloc does. Bytes past the old
+  /// length are zeroed only when growing with zero_init.
+  fn realloc(data: &mut Vec<u8>, new_len: usize, zero_init: bool) {
+    let old_len = data.len();
+    if new_len > old_len && !zero_init {
+      // The runtime may hand back bytes from before an earlier shrink.
+      data.resize(new_len, 0xff);
+    } else {
+      data.resize(new_len, 0);
+    }
+  }
+
+  #[test]
+  fn space_update_ataa_matches_serialized_length() {
+    for balances_len in [0, 2] {
+      for len in [0, 1, Payable::MAX_PAYABLES_TOKENS] {
+        let mut payable = full_payable(balances_len);
+        set_allowed_tokens_and_amounts(&mut payable, tokens_and_amounts(len));
+
+        let mut data = vec![];
+        payable.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), payable.space_update_ataa(len));
+        assert_eq!(data.len(), payable.space());
+      }
+    }
+  }
+
+  #[test]
+  fn zeroes_space_regrown_after_shrinking() {
+    let mut payable = full_payable(1);
+    let max = Payable::MAX_PAYABLES_TOKENS;
+    set_allowed_tokens_and_amounts(&mut payable, tokens_and_amounts(max));
+    let mut data = vec![];
+    payable.try_serialize(&mut data).unwrap();
+
+    // Shrinking truncates the account to the new space, which the smaller
+    // payable exactly fills.
+    set_allowed_tokens_and_amounts(&mut payable, tokens_and_amounts(1));
+    realloc(&mut data, payable.space_update_ataa(1), true);
+    payable.try_serialize(&mut data.as_mut_slice()).unwrap();
+    let read = Payable::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(read.allowed_tokens_and_amounts.len(), 1);
+    assert_eq!(read.balances.len(), 1);
+    assert_eq!(read.goal_amount, Some(u64::MAX));
+
+    // Growing again in the same transaction hands back the discarded bytes
+    // unless they are zeroed, as realloc::zero = true does.
+    let shrunk_len = data.len();
+    let grown_space = payable.space_update_ataa(max);
+    let mut stale = data.clone();
+    realloc(&mut stale, grown_space, false);
+    assert!(stale[shrunk_len..].iter().any(|byte| *byte != 0));
+    realloc(&mut data, grown_space, true);
+    assert!(data[shrunk_len..].iter().all(|byte| *byte == 0));
+
+    set_allowed_tokens_and_amounts(&mut payable, tokens_and_amounts(max));
+    payable.try_serialize(&mut data.as_mut_slice()).unwrap();
+    let read = Payable::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(read.allowed_tokens_and_amounts.len(), max);
+  }
+}
@@ -0,0 +1,60 @@
+use crate::{context::VaaConsumed, state::WormholeReceived};
+use anchor_lang::prelude::*;
+
+/// Whether the account at a VAA's WormholeReceived address, given its owner
+/// and data, records that the VAA was consumed.
+pub fn is_vaa_consumed(
+  owner: &Pubkey,
+  data: &[u8],
+  vaa_hash: [u8; 32],
+) -> bool {
+  *owner == crate::ID
+    && WormholeReceived::try_deserialize(&mut &data[..])
+      .is_ok_and(|received| received.vaa_hash == vaa_hash)
+}
+
+/// Returns (and logs) whether the VAA with the given hash has already been
+/// consumed, so that relayers don't resubmit it.
+///
+/// ### args
+/// * vaa_hash<[u8; 32]>: The Keccak256 hash of the VAA.
+#[inline(never)]
+pub fn vaa_consumed_handler(
+  ctx: Context<VaaConsumed>,
+  vaa_hash: [u8; 32],
+) -> Result<bool> {
+  let info = ctx.accounts.wormhole_received.to_account_info();
+  let consumed =
+    is_vaa_consumed(info.owner, &info.try_borrow_data()?, vaa_hash);
+  msg!("VAA consumed: {}", consumed);
+  Ok(consumed)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reports_vaas_consumed_once_received() {
+    let vaa_hash = [7u8; 32];
+    let (address, _) = WormholeReceived::pda(vaa_hash);
+    assert_ne!(address, WormholeReceived::pda([8u8; 32]).0);
+
+    // Before the VAA is processed, its address is an empty system account.
+    assert!(!is_vaa_consumed(&System::id(), &[], vaa_hash));
+
+    // Processing the VAA initializes the WormholeReceived account.
+    let mut data = vec![];
+    WormholeReceived {
+      batch_id: 0,
+      vaa_hash,
+    }
+    .try_serialize(&mut data)
+    .unwrap();
+    assert!(is_vaa_consumed(&crate::ID, &data, vaa_hash));
+
+    // Accounts of other programs or of other VAAs don't count.
+    assert!(!is_vaa_consumed(&Pubkey::new_unique(), &data, vaa_hash));
+    assert!(!is_vaa_consumed(&crate::ID, &data, [8u8; 32]));
+  }
+}
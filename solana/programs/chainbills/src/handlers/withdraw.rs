@@ -1,39 +1,69 @@
-use crate::{context::*, error::ChainbillsError, events::*, state::*};
+use crate::{
+  context::*,
+  error::ChainbillsError,
+  events::*,
+  handlers::{check_max_withdrawal_amount, check_withdrawals_not_paused},
+  state::*,
+};
 use anchor_lang::{prelude::*, solana_program::clock};
-use anchor_spl::token::{self, Transfer as SplTransfer};
+use anchor_spl::token_interface::{self, TransferChecked};
 use std::cmp::min;
 
+/// The decimals of native SOL (lamports).
+const NATIVE_DECIMALS: u8 = 9;
+
+/// Returns the payable's balance of the specified token (mint). A zeroed
+/// balance (after everything was withdrawn) counts as no balance.
+fn balance_of(payable: &Payable, mint: Pubkey) -> Result<u64> {
+  match payable
+    .balances
+    .iter()
+    .find(|balance| balance.token == mint)
+  {
+    Some(balance) if balance.amount > 0 => Ok(balance.amount),
+    _ => err!(ChainbillsError::NoBalanceForWithdrawalToken),
+  }
+}
+
 fn check_withdraw_inputs(
   amount: u64,
   mint: Pubkey,
-  payable: &Account<Payable>,
+  decimals: u8,
+  payable: &Payable,
 ) -> Result<()> {
   // Ensure that amount is greater than zero
   require!(amount > 0, ChainbillsError::ZeroAmountSpecified);
 
-  // - Ensure that this payable has enough of the amount in its balance.
   // - Ensure that the specified token (mint) for withdrawal exists in the
   //   payable's balances.
-  if payable.balances.is_empty() {
-    return err!(ChainbillsError::NoBalanceForWithdrawalToken);
-  }
-  let mut bals_it = payable.balances.iter().peekable();
-  while let Some(balance) = bals_it.next() {
-    if balance.token == mint {
-      if balance.amount < amount {
-        return err!(ChainbillsError::InsufficientWithdrawAmount);
-      } else {
-        break;
-      }
-    }
-    if bals_it.peek().is_none() {
-      return err!(ChainbillsError::NoBalanceForWithdrawalToken);
-    }
-  }
+  // - Ensure that this payable has enough of the amount in its balance.
+  require!(
+    balance_of(payable, mint)? >= amount,
+    ChainbillsError::InsufficientWithdrawAmount
+  );
+
+  // Ensure that the amount isn't dust.
+  require!(
+    amount >= Withdrawal::min_amount(decimals),
+    ChainbillsError::WithdrawalAmountTooSmall
+  );
 
   Ok(())
 }
 
+/// Ensures that the token details are for the mint being withdrawn, so fees
+/// can't be computed with another token's max_withdrawal_fees.
+fn check_token_details_mint(
+  token_details: &TokenDetails,
+  mint: Pubkey,
+) -> Result<()> {
+  require!(
+    token_details.mint == mint,
+    ChainbillsError::MismatchedTokenDetailsMint
+  );
+  Ok(())
+}
+
 struct WithdrawalAmounts {
   amount_due: u64,
   fees: u64,
@@ -43,17 +73,66 @@ fn compute_amounts(
   amount: u64,
   token_details: &TokenDetails,
   config: &Config,
-) -> WithdrawalAmounts {
+) -> Result<WithdrawalAmounts> {
   let percent = amount
     .checked_mul(config.withdrawal_fee_percentage.into())
-    .unwrap()
+    .ok_or(ChainbillsError::Overflow)?
     .checked_div(10000) // 10000 is 100%
-    .unwrap();
+    .ok_or(ChainbillsError::Overflow)?;
   let fees = min(percent, token_details.max_withdrawal_fees);
-  let amount_due = amount.checked_sub(fees).unwrap();
-  WithdrawalAmounts { amount_due, fees }
+  let amount_due = amount.checked_sub(fees).ok_or(ChainbillsError::Overflow)?;
+  Ok(WithdrawalAmounts { amount_due, fees })
+}
+
+/// Deducts the amount from the balance of the token (mint) and drops the
+/// balance entry if nothing is left of it.
+fn deduct_balance(
+  balances: &mut Vec<TokenAndAmount>,
+  mint: Pubkey,
+  amount: u64,
+) -> Result<()> {
+  if let Some(index) = balances.iter().position(|bal| bal.token == mint) {
+    balances[index].amount = balances[index]
+      .amount
+      .checked_sub(amount)
+      .ok_or(ChainbillsError::Overflow)?;
+    if balances[index].amount == 0 {
+      balances.remove(index);
+    }
+  }
+  Ok(())
 }
 
+/// Shrinks the payable's account if balance entries were dropped from it and
+/// returns the freed rent to the receiver.
+fn shrink_payable<'info>(
+  payable: &Account<'info, Payable>,
+  receiver: &AccountInfo<'info>,
+) -> Result<()> {
+  let payable_info = payable.to_account_info();
+  let new_space = payable.space();
+  if new_space >= payable_info.data_len() {
+    return Ok(());
+  }
+  payable_info.realloc(new_space, false)?;
+
+  let rent = Rent::get()?.minimum_balance(new_space);
+  let excess = payable_info
+    .lamports()
+    .checked_sub(rent)
+    .ok_or(ChainbillsError::Overflow)?;
+  **payable_info.try_borrow_mut_lamports()? -= excess;
+  **receiver.try_borrow_mut_lamports()? += excess;
+  Ok(())
+}
+
+/// Writes the host back to its account, which the withdraw contexts don't
+/// load as an Account.
+fn save_host(host_info: &AccountInfo, host: &User) -> Result<()> {
+  host.try_serialize(&mut &mut host_info.try_borrow_mut_data()?[..])
+}
+
+#[allow(clippy::too_many_arguments)]
 fn update_state_for_withdrawal(
   amount: u64,
   fees: u64,
@@ -61,7 +140,7 @@ fn update_state_for_withdrawal(
   signer: Pubkey,
   chain_stats: &mut Account<ChainStats>,
   payable: &mut Account<Payable>,
-  host: &mut Account<User>,
+  host: &mut User,
   token_details: &mut Account<TokenDetails>,
   withdrawal: &mut Account<Withdrawal>,
   payable_withdrawal_info: &mut Account<PayableWithdrawalInfo>,
@@ -70,29 +149,24 @@ fn update_state_for_withdrawal(
   payable_activity_info: &mut Account<PayableActivityInfo>,
 ) -> Result<()> {
   // Increment the chain stats for payables_count and activities_count.
-  chain_stats.withdrawals_count = chain_stats.next_withdrawal();
-  chain_stats.activities_count = chain_stats.next_activity();
+  chain_stats.withdrawals_count = chain_stats.next_withdrawal()?;
+  chain_stats.activities_count = chain_stats.next_activity()?;
 
   // Increment withdrawals_count and activities_count in the host that just
   // withdrew.
-  host.withdrawals_count = host.next_withdrawal();
-  host.activities_count = host.next_activity();
+  host.withdrawals_count = host.next_withdrawal()?;
+  host.activities_count = host.next_activity()?;
 
   // Increment withdrawals_count and activities_count on the involved payable.
-  payable.withdrawals_count = payable.next_withdrawal();
-  payable.activities_count = payable.next_activity();
+  payable.withdrawals_count = payable.next_withdrawal()?;
+  payable.activities_count = payable.next_activity()?;
 
   // Deduct the balances on the involved payable.
-  for balance in payable.balances.iter_mut() {
-    if balance.token == mint {
-      balance.amount = balance.amount.checked_sub(amount).unwrap();
-      break;
-    }
-  }
+  deduct_balance(&mut payable.balances, mint, amount)?;
 
   // Increase the supported token's totals from this withdrawal.
-  token_details.add_withdrawn(amount);
-  token_details.add_withdrawal_fees_collected(fees);
+  token_details.add_withdrawn(amount)?;
+  token_details.add_withdrawal_fees_collected(fees)?;
 
   let timestamp = clock::Clock::get()?.unix_timestamp as u64;
 
@@ -152,16 +226,22 @@ fn update_state_for_withdrawal(
 #[inline(never)]
 pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
   /* CHECKS */
+  check_withdrawals_not_paused(&*ctx.accounts.config.load()?)?;
+  check_max_withdrawal_amount(&*ctx.accounts.config.load()?, amount)?;
   let payable = ctx.accounts.payable.as_mut();
   let mint = &ctx.accounts.mint;
-  check_withdraw_inputs(amount, mint.key(), payable)?;
+  check_withdraw_inputs(amount, mint.key(), mint.decimals, payable)?;
+  check_token_details_mint(&ctx.accounts.token_details, mint.key())?;
 
   /* TRANSFERS */
   // Prepare withdraw amounts and fees
   let config = ctx.accounts.config.load()?;
   let token_details = ctx.accounts.token_details.as_mut();
+  // Apply any increased fees whose timelock has elapsed.
+  token_details
+    .promote_pending_fees(clock::Clock::get()?.unix_timestamp as u64);
   let WithdrawalAmounts { amount_due, fees } =
-    compute_amounts(amount, token_details, &config);
+    compute_amounts(amount, token_details, &config)?;
 
   // Extract Accounts needed for transferring
   let host_ta = &ctx.accounts.host_token_account;
@@ -171,38 +251,45 @@ pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
   let authority = &ctx.accounts.chain_stats;
 
   // Prepare accounts for withdrawing and for fees
-  let cpi_accounts_host = SplTransfer {
+  let cpi_accounts_host = TransferChecked {
     from: source.to_account_info().clone(),
+    mint: mint.to_account_info().clone(),
     to: host_ta.to_account_info().clone(),
     authority: authority.to_account_info().clone(),
   };
-  let cpi_accounts_fees = SplTransfer {
+  let cpi_accounts_fees = TransferChecked {
     from: source.to_account_info().clone(),
+    mint: mint.to_account_info().clone(),
     to: fees_ta.to_account_info().clone(),
     authority: authority.to_account_info().clone(),
   };
 
-  // Transfer the amount minus fees to the host.
-  token::transfer(
+  // Transfer the amount minus fees to the host. If the mint withholds
+  // transfer fees (Token-2022), the host and fee collector bear them.
+  token_interface::transfer_checked(
     CpiContext::new_with_signer(
       token_program.to_account_info(),
       cpi_accounts_host,
       &[&[ChainStats::SEED_PREFIX, &[ctx.bumps.chain_stats]]],
     ),
     amount_due,
+    mint.decimals,
   )?;
 
   // Transfer the fees to the fees collector.
-  token::transfer(
+  token_interface::transfer_checked(
     CpiContext::new_with_signer(
       token_program.to_account_info(),
       cpi_accounts_fees,
       &[&[ChainStats::SEED_PREFIX, &[ctx.bumps.chain_stats]]],
     ),
     fees,
+    mint.decimals,
   )?;
 
   /* STATE CHANGES */
+  let host_info = ctx.accounts.host.to_account_info();
+  let mut host = User::read(host_info.owner, &host_info.try_borrow_data()?)?;
   update_state_for_withdrawal(
     amount,
     fees,
@@ -210,16 +297,39 @@ pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
     ctx.accounts.signer.key(),
     ctx.accounts.chain_stats.as_mut(),
     payable,
-    ctx.accounts.host.as_mut(),
+    &mut host,
     token_details,
     ctx.accounts.withdrawal.as_mut(),
     ctx.accounts.payable_withdrawal_info.as_mut(),
     ctx.accounts.activity.as_mut(),
     ctx.accounts.user_activity_info.as_mut(),
     ctx.accounts.payable_activity_info.as_mut(),
+  )?;
+  save_host(&host_info, &host)?;
+  shrink_payable(
+    &ctx.accounts.payable,
+    &ctx.accounts.signer.to_account_info(),
   )
 }
 
+/// Moves lamports between accounts. The debited account must be owned by
+/// this program.
+pub fn transfer_lamports(
+  from: &AccountInfo,
+  to: &AccountInfo,
+  amount: u64,
+) -> Result<()> {
+  **from.try_borrow_mut_lamports()? = from
+    .lamports()
+    .checked_sub(amount)
+    .ok_or(ChainbillsError::Overflow)?;
+  **to.try_borrow_mut_lamports()? = to
+    .lamports()
+    .checked_add(amount)
+    .ok_or(ChainbillsError::Overflow)?;
+  Ok(())
+}
+
 /// Transfers the amount of native tokens (Solana) from a payable to a host
 ///
 /// ### args
@@ -230,15 +340,21 @@ pub fn withdraw_native(
   amount: u64,
 ) -> Result<()> {
   /* CHECKS */
+  check_withdrawals_not_paused(&*ctx.accounts.config.load()?)?;
+  check_max_withdrawal_amount(&*ctx.accounts.config.load()?, amount)?;
   let payable = ctx.accounts.payable.as_mut();
-  check_withdraw_inputs(amount, crate::ID, payable)?;
+  check_withdraw_inputs(amount, crate::ID, NATIVE_DECIMALS, payable)?;
+  check_token_details_mint(&ctx.accounts.token_details, crate::ID)?;
 
   /* TRANSFERS */
   // Prepare withdraw amounts and fees
   let config = ctx.accounts.config.load()?;
   let token_details = ctx.accounts.token_details.as_mut();
+  // Apply any increased fees whose timelock has elapsed.
+  token_details
+    .promote_pending_fees(clock::Clock::get()?.unix_timestamp as u64);
   let WithdrawalAmounts { amount_due, fees } =
-    compute_amounts(amount, token_details, &config);
+    compute_amounts(amount, token_details, &config)?;
 
   // Extract Accounts needed for transferring
   let chain_stats = ctx.accounts.chain_stats.to_account_info();
@@ -246,26 +362,14 @@ pub fn withdraw_native(
   let fees_collector = ctx.accounts.fee_collector.to_account_info();
 
   // Transfer the amount minus fees to the host.
-  chain_stats
-    .try_borrow_mut_lamports()?
-    .checked_sub(amount_due)
-    .unwrap();
-  signer
-    .try_borrow_mut_lamports()?
-    .checked_add(amount_due)
-    .unwrap();
+  transfer_lamports(&chain_stats, &signer, amount_due)?;
 
   // Transfer the fees to the fees collector.
-  chain_stats
-    .try_borrow_mut_lamports()?
-    .checked_sub(fees)
-    .unwrap();
-  fees_collector
-    .try_borrow_mut_lamports()?
-    .checked_add(fees)
-    .unwrap();
+  transfer_lamports(&chain_stats, &fees_collector, fees)?;
 
   /* STATE CHANGES */
+  let host_info = ctx.accounts.host.to_account_info();
+  let mut host = User::read(host_info.owner, &host_info.try_borrow_data()?)?;
   update_state_for_withdrawal(
     amount,
     fees,
@@ -273,12 +377,317 @@ pub fn withdraw_native(
     ctx.accounts.signer.key(),
     ctx.accounts.chain_stats.as_mut(),
     payable,
-    ctx.accounts.host.as_mut(),
+    &mut host,
     token_details,
     ctx.accounts.withdrawal.as_mut(),
     ctx.accounts.payable_withdrawal_info.as_mut(),
     ctx.accounts.activity.as_mut(),
     ctx.accounts.user_activity_info.as_mut(),
     ctx.accounts.payable_activity_info.as_mut(),
+  )?;
+  save_host(&host_info, &host)?;
+  shrink_payable(
+    &ctx.accounts.payable,
+    &ctx.accounts.signer.to_account_info(),
   )
 }
+
+/// Transfers the entire balance of a token from a payable to a host
+#[inline(never)]
+pub fn withdraw_all(ctx: Context<Withdraw>) -> Result<()> {
+  let amount = balance_of(&ctx.accounts.payable, ctx.accounts.mint.key())?;
+  withdraw(ctx, amount)
+}
+
+/// Transfers the entire balance of native tokens (Solana) from a payable to
+/// a host
+#[inline(never)]
+pub fn withdraw_all_native(ctx: Context<WithdrawNative>) -> Result<()> {
+  let amount = balance_of(&ctx.accounts.payable, crate::ID)?;
+  withdraw_native(ctx, amount)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn payable_with_balances(balances: Vec<TokenAndAmount>) -> Payable {
+    Payable {
+      payments_count: 1,
+      activities_count: 2,
+      balances,
+      ..Payable::test_default()
+    }
+  }
+
+  #[test]
+  fn drops_fully_withdrawn_balances() {
+    let (token, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut payable = payable_with_balances(vec![
+      TokenAndAmount { token, amount: 10 },
+      TokenAndAmount {
+        token: other,
+        amount: 5,
+      },
+    ]);
+    let space_before = payable.space();
+
+    // A partial withdrawal keeps the entry.
+    deduct_balance(&mut payable.balances, token, 4).unwrap();
+    assert_eq!(payable.balances.len(), 2);
+    assert_eq!(payable.balances[0].amount, 6);
+
+    // Withdrawing the rest drops the entry and leaves the other one.
+    deduct_balance(&mut payable.balances, token, 6).unwrap();
+    assert_eq!(payable.balances.len(), 1);
+    assert_eq!(payable.balances[0].token, other);
+    assert_eq!(payable.balances[0].amount, 5);
+    assert_eq!(payable.space(), space_before - TokenAndAmount::SPACE);
+    assert_eq!(
+      check_withdraw_inputs(1, token, 6, &payable).unwrap_err(),
+      ChainbillsError::NoBalanceForWithdrawalToken.into()
+    );
+  }
+
+  #[test]
+  fn sizes_payables_as_serialized() {
+    let mut payable = payable_with_balances(vec![
+      TokenAndAmount {
+        token: Pubkey::new_unique(),
+        amount: 1,
+      };
+      3
+    ]);
+    // Space is reserved for the optional fields when they are set.
+    payable.fee_recipient = Some(Pubkey::new_unique());
+    payable.expires_at = Some(1);
+    payable.max_payment_amount = Some(1);
+    payable.goal_token = Some(Pubkey::new_unique());
+    payable.goal_amount = Some(1);
+    payable.max_payments = Some(1);
+    let mut data = vec![];
+    payable.try_serialize(&mut data).unwrap();
+    assert_eq!(payable.space(), data.len());
+  }
+
+  #[test]
+  fn routes_fees_to_the_fee_recipient_if_set() {
+    let default_collector = Pubkey::new_unique();
+    let mut payable = payable_with_balances(vec![]);
+    assert_eq!(payable.fee_collector(default_collector), default_collector);
+
+    let recipient = Pubkey::new_unique();
+    payable.fee_recipient = Some(recipient);
+    assert_eq!(payable.fee_collector(default_collector), recipient);
+  }
+
+  #[test]
+  fn rejects_token_details_of_another_mint() {
+    let mint = Pubkey::new_unique();
+    let mut token_details = TokenDetails {
+      mint,
+      max_withdrawal_fees: 10,
+      ..TokenDetails::test_default()
+    };
+    assert!(check_token_details_mint(&token_details, mint).is_ok());
+
+    token_details.mint = Pubkey::new_unique();
+    assert_eq!(
+      check_token_details_mint(&token_details, mint).unwrap_err(),
+      ChainbillsError::MismatchedTokenDetailsMint.into()
+    );
+  }
+
+  #[test]
+  fn withdraws_balances_of_unsupported_tokens() {
+    let mint = Pubkey::new_unique();
+    let token_details = TokenDetails {
+      mint,
+      is_supported: false,
+      max_withdrawal_fees: 10,
+      ..TokenDetails::test_default()
+    };
+    let payable = payable_with_balances(vec![TokenAndAmount {
+      token: mint,
+      amount: 50,
+    }]);
+
+    // Deregistering a token only stops new payments in it.
+    assert!(check_token_details_mint(&token_details, mint).is_ok());
+    assert!(check_withdraw_inputs(50, mint, 6, &payable).is_ok());
+  }
+
+  #[test]
+  fn withdraws_the_full_balance() {
+    let token = Pubkey::new_unique();
+    let payable = payable_with_balances(vec![
+      TokenAndAmount {
+        token: Pubkey::new_unique(),
+        amount: 5,
+      },
+      TokenAndAmount { token, amount: 10 },
+    ]);
+    let amount = balance_of(&payable, token).unwrap();
+    assert_eq!(amount, 10);
+    assert!(check_withdraw_inputs(amount, token, 6, &payable).is_ok());
+  }
+
+  #[test]
+  fn rejects_withdrawals_after_the_full_balance() {
+    // The balance entry is zeroed after withdrawing everything.
+    let token = Pubkey::new_unique();
+    let payable =
+      payable_with_balances(vec![TokenAndAmount { token, amount: 0 }]);
+    assert_eq!(
+      balance_of(&payable, token).unwrap_err(),
+      ChainbillsError::NoBalanceForWithdrawalToken.into()
+    );
+    assert_eq!(
+      check_withdraw_inputs(1, token, 6, &payable).unwrap_err(),
+      ChainbillsError::NoBalanceForWithdrawalToken.into()
+    );
+  }
+
+  #[test]
+  fn rejects_withdrawals_of_unknown_tokens() {
+    let payable = payable_with_balances(vec![]);
+    assert_eq!(
+      check_withdraw_inputs(1, Pubkey::new_unique(), 6, &payable).unwrap_err(),
+      ChainbillsError::NoBalanceForWithdrawalToken.into()
+    );
+  }
+
+  #[test]
+  fn rejects_withdrawals_above_the_balance() {
+    let token = Pubkey::new_unique();
+    let payable =
+      payable_with_balances(vec![TokenAndAmount { token, amount: 10 }]);
+    assert_eq!(
+      check_withdraw_inputs(11, token, 6, &payable).unwrap_err(),
+      ChainbillsError::InsufficientWithdrawAmount.into()
+    );
+  }
+
+  #[test]
+  fn rejects_withdrawals_below_the_minimum() {
+    let token = Pubkey::new_unique();
+    let payable = payable_with_balances(vec![TokenAndAmount {
+      token,
+      amount: 10_000,
+    }]);
+    for amount in [1, Withdrawal::MIN_AMOUNT - 1] {
+      assert_eq!(
+        check_withdraw_inputs(amount, token, 6, &payable).unwrap_err(),
+        ChainbillsError::WithdrawalAmountTooSmall.into()
+      );
+    }
+
+    // The minimum scales with the token's decimals.
+    assert_eq!(
+      check_withdraw_inputs(999, token, NATIVE_DECIMALS, &payable).unwrap_err(),
+      ChainbillsError::WithdrawalAmountTooSmall.into()
+    );
+    assert!(
+      check_withdraw_inputs(1_000, token, NATIVE_DECIMALS, &payable).is_ok()
+    );
+  }
+
+  #[test]
+  fn derives_the_minimum_from_decimals() {
+    assert_eq!(Withdrawal::min_amount(0), Withdrawal::MIN_AMOUNT);
+    assert_eq!(Withdrawal::min_amount(6), Withdrawal::MIN_AMOUNT);
+    assert_eq!(Withdrawal::min_amount(8), 100);
+    assert_eq!(Withdrawal::min_amount(NATIVE_DECIMALS), 1_000);
+    assert_eq!(Withdrawal::min_amount(u8::MAX), u64::MAX);
+  }
+
+  #[test]
+  fn never_short_changes_the_host() {
+    let token_details = TokenDetails {
+      max_withdrawal_fees: u64::MAX,
+      ..TokenDetails::test_default()
+    };
+    let config = Config::test_default();
+
+    // Fees round down, so the host always gets at least 98% (at 2% fees).
+    for amount in Withdrawal::MIN_AMOUNT..Withdrawal::MIN_AMOUNT + 200 {
+      let WithdrawalAmounts { amount_due, fees } =
+        compute_amounts(amount, &token_details, &config).unwrap();
+      assert_eq!(amount_due + fees, amount);
+      assert!(amount_due * 100 >= amount * 98);
+    }
+  }
+
+  #[test]
+  fn fails_cleanly_on_overflow() {
+    let mut token_details = TokenDetails {
+      max_withdrawal_fees: u64::MAX,
+      total_withdrawn: u64::MAX - 1,
+      ..TokenDetails::test_default()
+    };
+    let config = Config::test_default();
+
+    // Computing the fees of a near-u64::MAX amount overflows.
+    assert_eq!(
+      compute_amounts(u64::MAX - 1, &token_details, &config)
+        .err()
+        .unwrap(),
+      ChainbillsError::Overflow.into()
+    );
+
+    // So does adding to the token's totals past u64::MAX.
+    assert_eq!(
+      token_details.add_withdrawn(2).unwrap_err(),
+      ChainbillsError::Overflow.into()
+    );
+
+    // Deducting more than a balance underflows.
+    let token = Pubkey::new_unique();
+    let mut balances = vec![TokenAndAmount { token, amount: 1 }];
+    assert_eq!(
+      deduct_balance(&mut balances, token, 2).unwrap_err(),
+      ChainbillsError::Overflow.into()
+    );
+  }
+
+  #[test]
+  fn transfers_lamports() {
+    let (from_key, to_key, owner) =
+      (Pubkey::new_unique(), Pubkey::new_unique(), crate::ID);
+    let (mut from_lamports, mut to_lamports) = (100, 5);
+    let (mut from_data, mut to_data) = (vec![], vec![]);
+    let from = AccountInfo::new(
+      &from_key,
+      false,
+      true,
+      &mut from_lamports,
+      &mut from_data,
+      &owner,
+      false,
+      0,
+    );
+    let to = AccountInfo::new(
+      &to_key,
+      false,
+      true,
+      &mut to_lamports,
+      &mut to_data,
+      &owner,
+      false,
+      0,
+    );
+
+    // The lamports actually move.
+    transfer_lamports(&from, &to, 60).unwrap();
+    assert_eq!(from.lamports(), 40);
+    assert_eq!(to.lamports(), 65);
+
+    // Moving more than the account has fails without moving any lamports.
+    assert_eq!(
+      transfer_lamports(&from, &to, 41).unwrap_err(),
+      ChainbillsError::Overflow.into()
+    );
+    assert_eq!(from.lamports(), 40);
+    assert_eq!(to.lamports(), 65);
+  }
+}
@@ -4,6 +4,7 @@ pub mod events;
 pub mod handlers;
 pub mod payload;
 pub mod state;
+pub mod token_bridge;
 
 use crate::{context::*, state::TokenAndAmount};
 use anchor_lang::prelude::*;
@@ -51,30 +52,78 @@ pub mod chainbills {
   /// * allowed_tokens_and_amounts<Vec<TokenAndAmount>>: The allowed tokens
   ///         (and their amounts) on this payable. If this vector is empty,
   ///         then the payable will accept payments in any token.
+  /// * expires_at<Option<u64>>: The timestamp (in seconds) after which this
+  ///         payable stops accepting payments. If None, it never expires.
+  /// * max_payment_amount<Option<u64>>: The maximum amount of a single
+  ///         payment if this payable accepts free payments.
+  /// * goal_token<Option<Pubkey>>: The token in which the goal is set.
+  /// * goal_amount<Option<u64>>: The balance of goal_token at which the
+  ///         payable gets closed. Must be set together with goal_token.
+  /// * max_payments<Option<u64>>: The number of payments after which the
+  ///         payable gets closed. If None, it accepts any number of payments.
   #[inline(never)]
   pub fn create_payable<'info>(
     ctx: Context<'_, '_, 'info, 'info, CreatePayable>,
     allowed_tokens_and_amounts: Vec<TokenAndAmount>,
+    expires_at: Option<u64>,
+    max_payment_amount: Option<u64>,
+    goal_token: Option<Pubkey>,
+    goal_amount: Option<u64>,
+    max_payments: Option<u64>,
   ) -> Result<()> {
-    handlers::create_payable_handler(ctx, allowed_tokens_and_amounts)
+    handlers::create_payable_handler(
+      ctx,
+      allowed_tokens_and_amounts,
+      expires_at,
+      max_payment_amount,
+      goal_token,
+      goal_amount,
+      max_payments,
+    )
   }
 
   /// Transfers the amount of tokens from a payer to a payable
   ///
   /// ### args
   /// * amount<u64>: The amount to be paid
+  /// * memo<Option<String>>: An optional reference (e.g. an order number)
+  ///   stored with the payment. At most UserPayment::MAX_MEMO_LENGTH bytes.
   #[inline(never)]
-  pub fn pay(ctx: Context<Pay>, amount: u64) -> Result<()> {
-    handlers::pay(ctx, amount)
+  pub fn pay(
+    ctx: Context<Pay>,
+    amount: u64,
+    memo: Option<String>,
+  ) -> Result<()> {
+    handlers::pay(ctx, amount, memo)
   }
 
   /// Transfers the amount of native tokens (Solana) to a payable
   ///
   /// ### args
   /// * amount<u64>: The Wormhole-normalized amount to be paid
+  /// * memo<Option<String>>: An optional reference (e.g. an order number)
+  ///   stored with the payment. At most UserPayment::MAX_MEMO_LENGTH bytes.
   #[inline(never)]
-  pub fn pay_native(ctx: Context<PayNative>, amount: u64) -> Result<()> {
-    handlers::pay_native(ctx, amount)
+  pub fn pay_native(
+    ctx: Context<PayNative>,
+    amount: u64,
+    memo: Option<String>,
+  ) -> Result<()> {
+    handlers::pay_native(ctx, amount, memo)
+  }
+
+  /// Records a payment made on another chain to a payable on this chain,
+  /// redeeming its Token Bridge transfer into the chain_token_account. Only
+  /// Solana-native tokens can be received.
+  ///
+  /// ### args
+  /// * vaa_hash<[u8; 32]>: The Keccak256 hash of the VAA.
+  #[inline(never)]
+  pub fn pay_received(
+    ctx: Context<PayReceived>,
+    vaa_hash: [u8; 32],
+  ) -> Result<()> {
+    handlers::pay_received_handler(ctx, vaa_hash)
   }
 
   /// Transfers the amount of tokens from a payable to a host
@@ -98,6 +147,45 @@ pub mod chainbills {
     handlers::withdraw_native(ctx, amount)
   }
 
+  /// Transfers the amount of a payment from a payable back to its payer.
+  /// Can be called only by the host (user) that owns the payable, and only
+  /// once per payment.
+  #[inline(never)]
+  pub fn refund_payment(ctx: Context<RefundPayment>) -> Result<()> {
+    handlers::refund_payment(ctx)
+  }
+
+  /// Transfers the amount of a native (SOL) payment from a payable back to
+  /// its payer. Can be called only by the host (user) that owns the payable,
+  /// and only once per payment.
+  #[inline(never)]
+  pub fn refund_payment_native(
+    ctx: Context<RefundPaymentNative>,
+  ) -> Result<()> {
+    handlers::refund_payment_native(ctx)
+  }
+
+  /// Transfers the entire balance of a token from a payable to a host
+  #[inline(never)]
+  pub fn withdraw_all(ctx: Context<Withdraw>) -> Result<()> {
+    handlers::withdraw_all(ctx)
+  }
+
+  /// Transfers the entire balance of native tokens (Solana) from a payable
+  /// to a host
+  #[inline(never)]
+  pub fn withdraw_all_native(ctx: Context<WithdrawNative>) -> Result<()> {
+    handlers::withdraw_all_native(ctx)
+  }
+
+  /// Moves a payable's dust balance of a token (an amount too small to
+  /// survive Wormhole's 8-decimal normalization) to its host, clearing the
+  /// balance entry. Can be called only by the host that owns the payable.
+  #[inline(never)]
+  pub fn sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+    handlers::sweep_dust(ctx)
+  }
+
   /// Stop a payable from accepting payments. Can be called only
   /// by the host (user) that owns the payable.
   #[inline(never)]
@@ -112,6 +200,16 @@ pub mod chainbills {
     handlers::reopen_payable(ctx)
   }
 
+  /// Closes a closed and fully withdrawn payable's account, returning its
+  /// rent to the host. Can be called only by the host (user) that owns the
+  /// payable.
+  #[inline(never)]
+  pub fn close_payable_account(
+    ctx: Context<ClosePayableAccount>,
+  ) -> Result<()> {
+    handlers::close_payable_account(ctx)
+  }
+
   /// Allows a payable's host to update the payable's allowed_tokens_and_amounts.
   ///
   /// ### args
@@ -128,7 +226,21 @@ pub mod chainbills {
     )
   }
 
-  /// Updates the maximum withdrawal fees of the given token.
+  /// Allows a payable's host to set whether the payable accepts payments
+  /// bridged from other chains. Local payments are always accepted.
+  ///
+  /// ### args
+  /// * accepts_cross_chain: whether to accept payments from other chains.
+  #[inline(never)]
+  pub fn update_payable_accepts_cross_chain(
+    ctx: Context<UpdatePayableAcceptsCrossChain>,
+    accepts_cross_chain: bool,
+  ) -> Result<()> {
+    handlers::update_payable_accepts_cross_chain(ctx, accepts_cross_chain)
+  }
+
+  /// Updates the maximum withdrawal fees of the given token, listing it in
+  /// SupportedTokens. Increases only apply after TokenDetails::FEES_TIMELOCK.
   ///
   /// ### Args
   /// * token<Pubkey>: The address of the token for which its maximum
@@ -143,7 +255,9 @@ pub mod chainbills {
     handlers::update_max_withdrawal_fees(ctx, token, max_withdrawal_fees)
   }
 
-  /// Updates the maximum withdrawal fees of the native token (Solana).
+  /// Updates the maximum withdrawal fees of the native token (Solana), listing
+  /// it in SupportedTokens. Increases only apply after
+  /// TokenDetails::FEES_TIMELOCK.
   ///
   /// ### Args
   /// * max_withdrawal_fees<u64>: The maximum withdrawal fees to set.
@@ -155,6 +269,44 @@ pub mod chainbills {
     handlers::update_max_withdrawal_fees_native(ctx, max_withdrawal_fees)
   }
 
+  /// Starts or stops accepting payments in the given token, listing or
+  /// unlisting it in SupportedTokens. Existing balances in the token can still
+  /// be withdrawn when it is no longer supported.
+  /// Should be called only by the owner of this program.
+  ///
+  /// ### args
+  /// * token<Pubkey>: The token mint whose support status is being set.
+  /// * is_supported<bool>: Whether payments should be accepted in the token.
+  #[inline(never)]
+  pub fn set_token_supported(
+    ctx: Context<SetTokenSupported>,
+    token: Pubkey,
+    is_supported: bool,
+  ) -> Result<()> {
+    handlers::set_token_supported_handler(ctx, token, is_supported)
+  }
+
+  /// Logs whether payments are accepted in the given token, its maximum
+  /// withdrawal fees in effect, and any pending increase of them. Doesn't
+  /// change any state.
+  ///
+  /// ### args
+  /// * token<Pubkey>: The token mint whose details are being read.
+  #[inline(never)]
+  pub fn get_token_details(
+    ctx: Context<GetTokenDetails>,
+    token: Pubkey,
+  ) -> Result<()> {
+    handlers::get_token_details_handler(ctx, token)
+  }
+
+  /// Logs the host, accepted tokens and amounts, and counts of the payable
+  /// with the given ID (address). Doesn't change any state.
+  #[inline(never)]
+  pub fn get_payable(ctx: Context<GetPayable>) -> Result<()> {
+    handlers::get_payable_handler(ctx)
+  }
+
   /// Withdraws fees from this program.
   /// Should be called only by upgrade authority holder of this program.
   ///
@@ -168,6 +320,71 @@ pub mod chainbills {
     handlers::owner_withdraw_handler(ctx, amount)
   }
 
+  /// Proposes a new owner for this program. The proposed owner has to call
+  /// accept_ownership to take over. Should be called only by the owner.
+  ///
+  /// ### args
+  /// * new_owner<Pubkey>: The account to become the next owner.
+  #[inline(never)]
+  pub fn propose_new_owner(
+    ctx: Context<ProposeNewOwner>,
+    new_owner: Pubkey,
+  ) -> Result<()> {
+    handlers::propose_new_owner_handler(ctx, new_owner)
+  }
+
+  /// Makes the pending owner the owner of this program.
+  /// Should be called only by the pending owner.
+  #[inline(never)]
+  pub fn accept_ownership(ctx: Context<AcceptOwnership>) -> Result<()> {
+    handlers::accept_ownership_handler(ctx)
+  }
+
+  /// Grows the Config account to its current size after an upgrade added
+  /// fields to it. The added fields start zeroed. Should be called only by
+  /// the owner of this program, once after such an upgrade.
+  #[inline(never)]
+  pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+    handlers::migrate_config_handler(ctx)
+  }
+
+  /// Grows a Payable account to the current layout after an upgrade added
+  /// fields to Payable. The added fields take their defaults. Anyone can
+  /// call it, paying the rent of the added space.
+  #[inline(never)]
+  pub fn migrate_payable(ctx: Context<MigratePayable>) -> Result<()> {
+    handlers::migrate_payable_handler(ctx)
+  }
+
+  /// Sets where withdrawal fees from a payable go. Should be called only by
+  /// the owner of this program.
+  ///
+  /// ### args
+  /// * fee_recipient<Option<Pubkey>>: Where the fees should go. If None,
+  ///         they go to the chainbills_fee_collector.
+  #[inline(never)]
+  pub fn set_fee_recipient(
+    ctx: Context<SetFeeRecipient>,
+    fee_recipient: Option<Pubkey>,
+  ) -> Result<()> {
+    handlers::set_fee_recipient_handler(ctx, fee_recipient)
+  }
+
+  /// Authorizes or deauthorizes a relayer, a wallet that can act on behalf
+  /// of other wallets. Should be called only by the owner of this program.
+  ///
+  /// ### args
+  /// * relayer<Pubkey>: The wallet of the relayer.
+  /// * is_authorized<bool>: Whether the relayer should be trusted.
+  #[inline(never)]
+  pub fn set_relayer(
+    ctx: Context<SetRelayer>,
+    relayer: Pubkey,
+    is_authorized: bool,
+  ) -> Result<()> {
+    handlers::set_relayer_handler(ctx, relayer, is_authorized)
+  }
+
   /// Register (or update) a trusted contract or Wormhole emitter from another
   /// chain. Also initialize that chain's ChainStats if need be.
   ///
@@ -183,4 +400,152 @@ pub mod chainbills {
   ) -> Result<()> {
     handlers::register_foreign_contract_handler(ctx, chain, address)
   }
+
+  /// Logs the Wormhole Chain ID and normalized address of the foreign
+  /// contract registered for the chain. Doesn't change any state.
+  ///
+  /// ### Arguments
+  /// * `ctx`   - `GetForeignContract` context
+  /// * `chain` - Wormhole Chain ID
+  #[inline(never)]
+  pub fn get_foreign_contract(
+    ctx: Context<GetForeignContract>,
+    chain: u16,
+  ) -> Result<()> {
+    handlers::get_foreign_contract_handler(ctx, chain)
+  }
+
+  /// Returns whether the VAA with the given hash has already been consumed
+  /// (its WormholeReceived account exists). Meant to be simulated by
+  /// relayers before submitting a VAA. Doesn't change any state.
+  ///
+  /// ### args
+  /// * vaa_hash<[u8; 32]>: The Keccak256 hash of the VAA.
+  #[inline(never)]
+  pub fn vaa_consumed(
+    ctx: Context<VaaConsumed>,
+    vaa_hash: [u8; 32],
+  ) -> Result<bool> {
+    handlers::vaa_consumed_handler(ctx, vaa_hash)
+  }
+
+  /// Mirrors a payable created on another chain from the VAA of its
+  /// creation, so that payments on this chain can target it. Fails if the
+  /// VAA wasn't emitted by the registered foreign contract of its chain or
+  /// was already consumed.
+  ///
+  /// ### args
+  /// * vaa_hash<[u8; 32]>: The Keccak256 hash of the VAA.
+  #[inline(never)]
+  pub fn initialize_payable_received(
+    ctx: Context<InitializePayableReceived>,
+    vaa_hash: [u8; 32],
+  ) -> Result<()> {
+    handlers::initialize_payable_received_handler(ctx, vaa_hash)
+  }
+
+  /// Pauses or unpauses payments, withdrawals, and payable creation.
+  /// Should be called only by the owner of this program. Doesn't affect
+  /// owner_withdraw.
+  ///
+  /// ### args
+  /// * paused<bool>: Whether the program should be paused.
+  #[inline(never)]
+  pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    handlers::set_paused_handler(ctx, paused)
+  }
+
+  /// Sets the minimum Wormhole consistency level (finality) that VAAs
+  /// received from other chains must have. Should be called only by the
+  /// owner of this program.
+  ///
+  /// ### args
+  /// * min_vaa_finality<u8>: The minimum consistency level.
+  #[inline(never)]
+  pub fn set_min_vaa_finality(
+    ctx: Context<SetMinVaaFinality>,
+    min_vaa_finality: u8,
+  ) -> Result<()> {
+    handlers::set_min_vaa_finality_handler(ctx, min_vaa_finality)
+  }
+
+  /// Sets the maximum amount that can be withdrawn from a payable in a single
+  /// withdrawal. Should be called only by the owner of this program.
+  ///
+  /// ### args
+  /// * max_withdrawal_amount<u64>: The maximum amount. Zero means unlimited.
+  #[inline(never)]
+  pub fn set_max_withdrawal_amount(
+    ctx: Context<SetMaxWithdrawalAmount>,
+    max_withdrawal_amount: u64,
+  ) -> Result<()> {
+    handlers::set_max_withdrawal_amount_handler(ctx, max_withdrawal_amount)
+  }
+
+  /// Pauses or unpauses cross-chain (Wormhole) operations independently of
+  /// local ones. Should be called only by the owner of this program.
+  ///
+  /// ### args
+  /// * paused<bool>: Whether cross-chain operations should be paused.
+  #[inline(never)]
+  pub fn set_cross_chain_paused(
+    ctx: Context<SetPaused>,
+    paused: bool,
+  ) -> Result<()> {
+    handlers::set_cross_chain_paused_handler(ctx, paused)
+  }
+
+  /// Pauses or unpauses payments only, so withdrawals can continue during an
+  /// incident. Should be called only by the owner of this program.
+  ///
+  /// ### args
+  /// * paused<bool>: Whether payments should be paused.
+  #[inline(never)]
+  pub fn set_payments_paused(
+    ctx: Context<SetPaused>,
+    paused: bool,
+  ) -> Result<()> {
+    handlers::set_payments_paused_handler(ctx, paused)
+  }
+
+  /// Pauses or unpauses withdrawals only, so payments can continue during an
+  /// incident. Should be called only by the owner of this program.
+  ///
+  /// ### args
+  /// * paused<bool>: Whether withdrawals should be paused.
+  #[inline(never)]
+  pub fn set_withdrawals_paused(
+    ctx: Context<SetPaused>,
+    paused: bool,
+  ) -> Result<()> {
+    handlers::set_withdrawals_paused_handler(ctx, paused)
+  }
+
+  /// Logs the ActivityRecord with the given chain count. Doesn't change any
+  /// state. Clients can page through activities on this chain by
+  /// incrementing the count up to `chain_stats.activities_count`.
+  ///
+  /// ### args
+  /// * chain_count<u64>: The nth count of the activity on this chain.
+  #[inline(never)]
+  pub fn get_activity(
+    ctx: Context<GetActivity>,
+    chain_count: u64,
+  ) -> Result<()> {
+    handlers::get_activity_handler(ctx, chain_count)
+  }
+
+  /// Logs the latest activities of a user, newest first. The UserActivityInfo
+  /// and ActivityRecord of each activity are passed in pairs as
+  /// remaining_accounts, counting down from the user's activities_count.
+  ///
+  /// ### args
+  /// * limit<u64>: The maximum number of activities to read.
+  #[inline(never)]
+  pub fn get_user_activities(
+    ctx: Context<GetUserActivities>,
+    limit: u64,
+  ) -> Result<()> {
+    handlers::get_user_activities_handler(ctx, limit)
+  }
 }
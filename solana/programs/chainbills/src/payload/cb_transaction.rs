@@ -0,0 +1,179 @@
+use crate::error::ChainbillsError;
+use crate::state::TokenAndAmount;
+use anchor_lang::prelude::*;
+use std::io;
+
+/// Whether all the 32 bytes are zero. Such a bytes32 can't be a valid
+/// caller, payable ID, or emitter, as it would only match accounts (or
+/// records) that were never initialized.
+pub fn is_zero_bytes32(bytes: &[u8; 32]) -> bool {
+  bytes.iter().all(|&x| x == 0)
+}
+
+#[derive(Clone, Copy)]
+/// A payable and the token details (token and amount) of a cross-chain
+/// transaction on it. Shares its wire format with CosmWasm's CbTransaction.
+pub struct CbTransaction {
+  /// The Payable's ID.
+  pub payable_id: [u8; 32],
+
+  /// The involved token and its Wormhole-normalized amount.
+  pub details: TokenAndAmount,
+
+  /// The Wormhole-normalized wallet address of the payer on the source
+  /// chain.
+  pub payer: [u8; 32],
+}
+
+impl CbTransaction {
+  /// payable_id (32) + token (32) + amount (8) + payer (32).
+  pub const LENGTH: usize = 32 + 32 + 8 + 32;
+
+  /// Returns the payable_id, failing with InvalidPayableId if it is all
+  /// zeros. Should be used by handlers of received transactions.
+  pub fn checked_payable_id(&self) -> Result<[u8; 32]> {
+    require!(
+      !is_zero_bytes32(&self.payable_id),
+      ChainbillsError::InvalidPayableId
+    );
+    Ok(self.payable_id)
+  }
+
+  /// Returns the wire bytes of this transaction.
+  pub fn encode(&self) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(Self::LENGTH);
+    self.serialize(&mut buf).unwrap();
+    buf
+  }
+
+  /// Parses a CbTransaction from its wire bytes. Fails with InvalidPayload
+  /// if the bytes are not exactly of the expected length.
+  pub fn decode(buf: &[u8]) -> Result<Self> {
+    Self::deserialize(&mut &buf[..])
+      .map_err(|_| error!(ChainbillsError::InvalidPayload))
+  }
+}
+
+impl AnchorSerialize for CbTransaction {
+  fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+    self.payable_id.serialize(writer)?;
+    self.details.token.to_bytes().serialize(writer)?;
+    self.details.amount.to_be_bytes().serialize(writer)?;
+    self.payer.serialize(writer)?;
+    Ok(())
+  }
+}
+
+impl AnchorDeserialize for CbTransaction {
+  fn deserialize(buf: &mut &[u8]) -> io::Result<Self> {
+    if buf.len() != Self::LENGTH {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "InvalidPayloadMessage",
+      ));
+    }
+
+    let mut index = 0usize;
+    let payable_id = <[u8; 32]>::deserialize(&mut &buf[index..(index + 32)])?;
+    index += 32;
+
+    let token = <[u8; 32]>::deserialize(&mut &buf[index..(index + 32)])?;
+    index += 32;
+
+    let amount = {
+      let mut out = [0u8; 8];
+      out.copy_from_slice(&buf[index..(index + 8)]);
+      u64::from_be_bytes(out)
+    };
+    index += 8;
+
+    let payer = <[u8; 32]>::deserialize(&mut &buf[index..(index + 32)])?;
+
+    Ok(CbTransaction {
+      payable_id,
+      details: TokenAndAmount {
+        token: Pubkey::new_from_array(token),
+        amount,
+      },
+      payer,
+    })
+  }
+
+  fn deserialize_reader<R: io::prelude::Read>(
+    _reader: &mut R,
+  ) -> io::Result<Self> {
+    todo!()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> CbTransaction {
+    CbTransaction {
+      payable_id: [1; 32],
+      details: TokenAndAmount {
+        token: Pubkey::new_from_array([2; 32]),
+        amount: 0x0102030405060708,
+      },
+      payer: [3; 32],
+    }
+  }
+
+  #[test]
+  fn test_serialize_deserialize() {
+    let transaction = sample();
+    let buf = transaction.encode();
+    assert_eq!(buf.len(), CbTransaction::LENGTH);
+
+    let decoded = CbTransaction::decode(&buf).unwrap();
+    assert_eq!(transaction.payable_id, decoded.payable_id);
+    assert_eq!(transaction.details.token, decoded.details.token);
+    assert_eq!(transaction.details.amount, decoded.details.amount);
+    assert_eq!(transaction.payer, decoded.payer);
+  }
+
+  #[test]
+  fn test_wire_format() {
+    // Must match the bytes asserted for CosmWasm's CbTransaction.
+    let mut expected = vec![1u8; 32];
+    expected.extend([2u8; 32]);
+    expected.extend([1, 2, 3, 4, 5, 6, 7, 8]);
+    expected.extend([3u8; 32]);
+    assert_eq!(sample().encode(), expected);
+  }
+
+  #[test]
+  fn test_rejects_zero_payable_id() {
+    assert!(is_zero_bytes32(&[0; 32]));
+    assert!(!is_zero_bytes32(&sample().payable_id));
+    assert_eq!(sample().checked_payable_id().unwrap(), [1; 32]);
+
+    let zero_id = CbTransaction {
+      payable_id: [0; 32],
+      ..sample()
+    };
+    // A zero payable ID still decodes, but is rejected when used.
+    let decoded = CbTransaction::decode(&zero_id.encode()).unwrap();
+    assert_eq!(
+      decoded.checked_payable_id().err().unwrap(),
+      error!(ChainbillsError::InvalidPayableId)
+    );
+  }
+
+  #[test]
+  fn test_deserialize_invalid_input() {
+    for len in [0, 40, 72, 103, 105] {
+      let buf = vec![0; len];
+      let result = CbTransaction::deserialize(&mut &buf[..]);
+      assert!(result.is_err());
+      assert_eq!(result.err().unwrap().kind(), io::ErrorKind::InvalidInput);
+
+      assert_eq!(
+        CbTransaction::decode(&buf).err().unwrap(),
+        error!(ChainbillsError::InvalidPayload)
+      );
+    }
+  }
+}
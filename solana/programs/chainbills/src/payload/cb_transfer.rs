@@ -0,0 +1,177 @@
+use crate::payload::CbTransaction;
+use anchor_lang::prelude::*;
+use std::io;
+use wormhole_anchor_sdk::wormhole::CHAIN_ID_SOLANA;
+
+/// The Token Bridge's payload ID of transfers with payload.
+pub const PAYLOAD_ID_TRANSFER_WITH_PAYLOAD: u8 = 3;
+
+#[derive(Clone, Copy)]
+/// A Token Bridge transfer (with payload) whose payload is the CbTransaction
+/// of a cross-chain payment. Found as the payload of Token Bridge VAAs.
+pub struct CbTransfer {
+  /// The Wormhole-normalized (with 8 decimals) amount transferred.
+  pub amount: u64,
+
+  /// The Wormhole-normalized address of the token on its chain.
+  pub token_address: [u8; 32],
+
+  /// The Wormhole Chain ID of the token's chain.
+  pub token_chain: u16,
+
+  /// The Wormhole-normalized address of the transfer's recipient.
+  pub to_address: [u8; 32],
+
+  /// The Wormhole Chain ID of the recipient's chain.
+  pub to_chain: u16,
+
+  /// The Wormhole-normalized address that sent the transfer.
+  pub from_address: [u8; 32],
+
+  /// The payment the transfer was made for.
+  pub transaction: CbTransaction,
+}
+
+impl CbTransfer {
+  /// payload_id (1) + amount (32) + token_address (32) + token_chain (2)
+  /// + to_address (32) + to_chain (2) + from_address (32)
+  /// + CbTransaction::LENGTH.
+  pub const LENGTH: usize =
+    1 + 32 + 32 + 2 + 32 + 2 + 32 + CbTransaction::LENGTH;
+
+  /// The transferred token's mint on this chain if it is a Solana-native
+  /// token. Otherwise, the default Pubkey.
+  pub fn mint(&self) -> Pubkey {
+    if self.token_chain == CHAIN_ID_SOLANA {
+      Pubkey::new_from_array(self.token_address)
+    } else {
+      Pubkey::default()
+    }
+  }
+}
+
+impl AnchorSerialize for CbTransfer {
+  fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+    PAYLOAD_ID_TRANSFER_WITH_PAYLOAD.serialize(writer)?;
+    [0u8; 24].serialize(writer)?;
+    self.amount.to_be_bytes().serialize(writer)?;
+    self.token_address.serialize(writer)?;
+    self.token_chain.to_be_bytes().serialize(writer)?;
+    self.to_address.serialize(writer)?;
+    self.to_chain.to_be_bytes().serialize(writer)?;
+    self.from_address.serialize(writer)?;
+    self.transaction.serialize(writer)?;
+    Ok(())
+  }
+}
+
+impl AnchorDeserialize for CbTransfer {
+  fn deserialize(buf: &mut &[u8]) -> io::Result<Self> {
+    // The amount is a uint256 of which only the last 8 bytes can be set.
+    if buf.len() != Self::LENGTH
+      || buf[0] != PAYLOAD_ID_TRANSFER_WITH_PAYLOAD
+      || buf[1..25].iter().any(|&x| x != 0)
+    {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "InvalidPayloadMessage",
+      ));
+    }
+
+    let u16_at =
+      |index: usize| u16::from_be_bytes([buf[index], buf[index + 1]]);
+    let bytes32_at = |index: usize| {
+      let mut out = [0u8; 32];
+      out.copy_from_slice(&buf[index..(index + 32)]);
+      out
+    };
+
+    let amount = {
+      let mut out = [0u8; 8];
+      out.copy_from_slice(&buf[25..33]);
+      u64::from_be_bytes(out)
+    };
+    let transaction = CbTransaction::deserialize(&mut &buf[133..])?;
+
+    Ok(CbTransfer {
+      amount,
+      token_address: bytes32_at(33),
+      token_chain: u16_at(65),
+      to_address: bytes32_at(67),
+      to_chain: u16_at(99),
+      from_address: bytes32_at(101),
+      transaction,
+    })
+  }
+
+  fn deserialize_reader<R: io::prelude::Read>(
+    _reader: &mut R,
+  ) -> io::Result<Self> {
+    todo!()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::state::TokenAndAmount;
+
+  fn sample() -> CbTransfer {
+    CbTransfer {
+      amount: 0x0102030405060708,
+      token_address: [1; 32],
+      token_chain: CHAIN_ID_SOLANA,
+      to_address: [2; 32],
+      to_chain: CHAIN_ID_SOLANA,
+      from_address: [3; 32],
+      transaction: CbTransaction {
+        payable_id: [4; 32],
+        details: TokenAndAmount {
+          token: Pubkey::new_from_array([1; 32]),
+          amount: 0x0102030405060708,
+        },
+        payer: [5; 32],
+      },
+    }
+  }
+
+  #[test]
+  fn serialize_deserialize() {
+    let transfer = sample();
+    let mut buf = Vec::new();
+    transfer.serialize(&mut buf).unwrap();
+    assert_eq!(buf.len(), CbTransfer::LENGTH);
+    assert_eq!(buf[0], PAYLOAD_ID_TRANSFER_WITH_PAYLOAD);
+
+    let decoded = CbTransfer::deserialize(&mut &buf[..]).unwrap();
+    assert_eq!(decoded.amount, transfer.amount);
+    assert_eq!(decoded.token_address, transfer.token_address);
+    assert_eq!(decoded.token_chain, transfer.token_chain);
+    assert_eq!(decoded.to_address, transfer.to_address);
+    assert_eq!(decoded.to_chain, transfer.to_chain);
+    assert_eq!(decoded.from_address, transfer.from_address);
+    assert_eq!(decoded.transaction.payable_id, [4; 32]);
+    assert_eq!(decoded.transaction.payer, [5; 32]);
+    assert_eq!(decoded.mint(), Pubkey::new_from_array([1; 32]));
+  }
+
+  #[test]
+  fn deserialize_invalid_input() {
+    let mut buf = Vec::new();
+    sample().serialize(&mut buf).unwrap();
+
+    // Other payload IDs (e.g. transfers without payload).
+    let mut other_id = buf.clone();
+    other_id[0] = 1;
+    // Amounts that don't fit in a u64.
+    let mut large_amount = buf.clone();
+    large_amount[24] = 1;
+    // Truncated payloads.
+    let truncated = buf[..buf.len() - 1].to_vec();
+
+    for buf in [other_id, large_amount, truncated] {
+      let result = CbTransfer::deserialize(&mut &buf[..]);
+      assert_eq!(result.err().unwrap().kind(), io::ErrorKind::InvalidInput);
+    }
+  }
+}
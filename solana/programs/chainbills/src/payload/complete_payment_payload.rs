@@ -52,21 +52,21 @@ impl AnchorDeserialize for CompletePaymentPayload {
     let amount = {
       let mut out = [0u8; 8];
       out.copy_from_slice(&buf[index..(index + 8)]);
-      u64::from_le_bytes(out) as u64
+      u64::from_le_bytes(out)
     };
     index += 8;
 
     let payable_count = {
       let mut out = [0u8; 8];
       out.copy_from_slice(&buf[index..(index + 8)]);
-      u64::from_le_bytes(out) as u64
+      u64::from_le_bytes(out)
     };
     index += 8;
 
     let timestamp = {
       let mut out = [0u8; 8];
       out.copy_from_slice(&buf[index..(index + 8)]);
-      u64::from_le_bytes(out) as u64
+      u64::from_le_bytes(out)
     };
     index += 8;
 
@@ -123,7 +123,7 @@ mod tests {
 
   #[test]
   fn test_deserialize_invalid_input() {
-    let buf = vec![0; 121]; // Too long buffer length
+    let buf = [0; 121]; // Too long buffer length
     let result = CompletePaymentPayload::deserialize(&mut &buf[..]);
 
     assert!(result.is_err());
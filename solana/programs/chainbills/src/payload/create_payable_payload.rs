@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use std::io;
+
+#[derive(Clone, Copy, Debug)]
+/// Necessary info to mirror a payable created on another chain, so that
+/// payments on this chain can target it. The payable's chain is the VAA's
+/// emitter chain.
+pub struct CreatePayablePayload {
+  /// The Payable's ID.
+  pub payable_id: [u8; 32],
+  /// The Wormhole-normalized wallet address of the payable's host.
+  pub host: [u8; 32],
+}
+
+impl CreatePayablePayload {
+  /// payable_id (32) + host (32).
+  pub const LENGTH: usize = 32 + 32;
+}
+
+impl AnchorSerialize for CreatePayablePayload {
+  fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+    self.payable_id.serialize(writer)?;
+    self.host.serialize(writer)?;
+    Ok(())
+  }
+}
+
+impl AnchorDeserialize for CreatePayablePayload {
+  fn deserialize(buf: &mut &[u8]) -> io::Result<Self> {
+    if buf.len() != Self::LENGTH {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "InvalidPayloadMessage",
+      ));
+    }
+
+    let payable_id = <[u8; 32]>::deserialize(&mut &buf[..32])?;
+    let host = <[u8; 32]>::deserialize(&mut &buf[32..])?;
+    Ok(CreatePayablePayload { payable_id, host })
+  }
+
+  fn deserialize_reader<R: io::prelude::Read>(
+    _reader: &mut R,
+  ) -> io::Result<Self> {
+    todo!()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn serialize_deserialize() {
+    let payload = CreatePayablePayload {
+      payable_id: [1; 32],
+      host: [2; 32],
+    };
+    let mut buf = Vec::new();
+    payload.serialize(&mut buf).unwrap();
+    assert_eq!(buf.len(), CreatePayablePayload::LENGTH);
+
+    let deserialized =
+      CreatePayablePayload::deserialize(&mut &buf[..]).unwrap();
+    assert_eq!(deserialized.payable_id, payload.payable_id);
+    assert_eq!(deserialized.host, payload.host);
+  }
+
+  #[test]
+  fn deserialize_invalid_input() {
+    for len in [63, 65] {
+      let buf = vec![0; len];
+      let result = CreatePayablePayload::deserialize(&mut &buf[..]);
+      assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+  }
+}
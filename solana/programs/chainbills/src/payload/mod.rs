@@ -1,5 +1,11 @@
+pub mod cb_transaction;
+pub mod cb_transfer;
 pub mod complete_payment_payload;
+pub mod create_payable_payload;
 pub mod start_payment_payload;
 
+pub use cb_transaction::*;
+pub use cb_transfer::*;
 pub use complete_payment_payload::*;
+pub use create_payable_payload::*;
 pub use start_payment_payload::*;
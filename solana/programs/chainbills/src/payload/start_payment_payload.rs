@@ -30,7 +30,7 @@ impl AnchorDeserialize for StartPaymentPayload {
     let payer_count = {
       let mut out = [0u8; 8];
       out.copy_from_slice(&buf[index..(index + 8)]);
-      u64::from_le_bytes(out) as u64
+      u64::from_le_bytes(out)
     };
     index += 8;
 
@@ -75,7 +75,7 @@ mod tests {
 
   #[test]
   fn deserialize_invalid_input() {
-    let buf = vec![0; 41]; // Too long buffer length
+    let buf = [0; 41]; // Too long buffer length
     let result = StartPaymentPayload::deserialize(&mut &buf[..]);
 
     assert!(result.is_err());
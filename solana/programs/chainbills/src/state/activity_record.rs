@@ -1,3 +1,4 @@
+use crate::state::space;
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
@@ -26,6 +27,9 @@ pub enum ActivityType {
 
   /// The payable's allowed tokens and amounts were updated.
   UpdatedPayableAllowedTokensAndAmounts,
+
+  /// A payment to the payable was refunded to the payer by the host.
+  RefundedPayment,
 }
 
 #[account]
@@ -55,10 +59,20 @@ pub struct ActivityRecord {
 }
 
 impl ActivityRecord {
-  // discriminator (8) included
-  pub const SPACE: usize = 1 + (5 * 8) + 32;
+  pub const SPACE: usize =
+    space::DISCRIMINATOR + (4 * space::U64) + space::PUBKEY + space::ENUM;
 
   /// AKA `b"activity"`.
   #[constant]
   pub const SEED_PREFIX: &'static [u8] = b"activity";
+
+  /// Derives the address (and bump) of the ActivityRecord with the given
+  /// chain count. Clients can page through activities on this chain by
+  /// deriving from 1 up to `chain_stats.activities_count`.
+  pub fn pda(chain_count: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+      &[Self::SEED_PREFIX, &chain_count.to_le_bytes()[..]],
+      &crate::ID,
+    )
+  }
 }
@@ -1,3 +1,4 @@
+use crate::{error::ChainbillsError, state::space};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -23,8 +24,7 @@ pub struct ChainStats {
 }
 
 impl ChainStats {
-  // discriminator included
-  pub const SPACE: usize = 7 * 8;
+  pub const SPACE: usize = space::DISCRIMINATOR + (6 * space::U64);
 
   /// AKA `b"chain"`.
   #[constant]
@@ -34,31 +34,100 @@ impl ChainStats {
     self.users_count = 0;
     self.payables_count = 0;
     self.user_payments_count = 0;
+    self.payable_payments_count = 0;
     self.withdrawals_count = 0;
     self.activities_count = 0;
   }
 
-  pub fn next_user(&self) -> u64 {
-    self.users_count.checked_add(1).unwrap()
+  pub fn next_user(&self) -> Result<u64> {
+    self
+      .users_count
+      .checked_add(1)
+      .ok_or(error!(ChainbillsError::Overflow))
   }
 
-  pub fn next_payable(&self) -> u64 {
-    self.payables_count.checked_add(1).unwrap()
+  pub fn next_payable(&self) -> Result<u64> {
+    self
+      .payables_count
+      .checked_add(1)
+      .ok_or(error!(ChainbillsError::Overflow))
   }
 
-  pub fn next_user_payment(&self) -> u64 {
-    self.user_payments_count.checked_add(1).unwrap()
+  pub fn next_user_payment(&self) -> Result<u64> {
+    self
+      .user_payments_count
+      .checked_add(1)
+      .ok_or(error!(ChainbillsError::Overflow))
   }
 
-  pub fn next_payable_payment(&self) -> u64 {
-    self.payable_payments_count.checked_add(1).unwrap()
+  pub fn next_payable_payment(&self) -> Result<u64> {
+    self
+      .payable_payments_count
+      .checked_add(1)
+      .ok_or(error!(ChainbillsError::Overflow))
   }
 
-  pub fn next_withdrawal(&self) -> u64 {
-    self.withdrawals_count.checked_add(1).unwrap()
+  pub fn next_withdrawal(&self) -> Result<u64> {
+    self
+      .withdrawals_count
+      .checked_add(1)
+      .ok_or(error!(ChainbillsError::Overflow))
   }
 
-  pub fn next_activity(&self) -> u64 {
-    self.activities_count.checked_add(1).unwrap()
+  pub fn next_activity(&self) -> Result<u64> {
+    self.next_activities(1)
+  }
+
+  /// The activities count after recording count more activities. For
+  /// instructions that record more than one activity at once.
+  pub fn next_activities(&self, count: u64) -> Result<u64> {
+    self
+      .activities_count
+      .checked_add(count)
+      .ok_or(error!(ChainbillsError::Overflow))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn chain_stats(count: u64) -> ChainStats {
+    ChainStats {
+      users_count: count,
+      payables_count: count,
+      user_payments_count: count,
+      payable_payments_count: count,
+      withdrawals_count: count,
+      activities_count: count,
+    }
+  }
+
+  #[test]
+  fn returns_the_incremented_counts() {
+    let stats = chain_stats(u64::MAX - 2);
+    assert_eq!(stats.next_user().unwrap(), u64::MAX - 1);
+    assert_eq!(stats.next_payable().unwrap(), u64::MAX - 1);
+    assert_eq!(stats.next_user_payment().unwrap(), u64::MAX - 1);
+    assert_eq!(stats.next_payable_payment().unwrap(), u64::MAX - 1);
+    assert_eq!(stats.next_withdrawal().unwrap(), u64::MAX - 1);
+    assert_eq!(stats.next_activity().unwrap(), u64::MAX - 1);
+    assert_eq!(stats.next_activities(2).unwrap(), u64::MAX);
+  }
+
+  #[test]
+  fn fails_past_the_overflow_boundary() {
+    let stats = chain_stats(u64::MAX);
+    let overflow: Error = ChainbillsError::Overflow.into();
+    assert_eq!(stats.next_user().unwrap_err(), overflow);
+    assert_eq!(stats.next_payable().unwrap_err(), overflow);
+    assert_eq!(stats.next_user_payment().unwrap_err(), overflow);
+    assert_eq!(stats.next_payable_payment().unwrap_err(), overflow);
+    assert_eq!(stats.next_withdrawal().unwrap_err(), overflow);
+    assert_eq!(stats.next_activity().unwrap_err(), overflow);
+    assert_eq!(
+      chain_stats(u64::MAX - 1).next_activities(2).unwrap_err(),
+      overflow
+    );
   }
 }
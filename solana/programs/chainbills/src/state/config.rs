@@ -1,4 +1,5 @@
-use anchor_lang::prelude::*;
+use crate::{error::ChainbillsError, state::space};
+use anchor_lang::{prelude::*, Discriminator};
 
 #[account(zero_copy)]
 /// Config account data. Mainly Wormhole-related addresses and infos.
@@ -12,7 +13,7 @@ pub struct Config {
   
   /// Deployer of this program.
   pub owner: Pubkey, // 32 bytes
-  
+
   /// Chainbills' [FeeCollector](FeeCollector) address.
   pub chainbills_fee_collector: Pubkey, // 32 bytes
   
@@ -31,12 +32,131 @@ pub struct Config {
   /// address for Wormhole messages. It tracks the number of messages posted
   /// by this program.
   pub wormhole_sequence: Pubkey, // 32 bytes
+
+  /// Whether payments, withdrawals, and payable creation are paused.
+  /// Stored as a u8 (0 or 1) as bool isn't allowed in zero-copy accounts.
+  /// Use [is_paused](Config::is_paused) to read it.
+  pub paused: u8, // 1 byte
+
+  /// Whether cross-chain (Wormhole) operations are paused. Independent of
+  /// [paused](Config::paused), so local operations can continue while
+  /// bridging is frozen. Stored as a u8 (0 or 1) like `paused`.
+  pub cross_chain_paused: u8, // 1 byte
+
+  /// The minimum Wormhole consistency level (finality) that VAAs received
+  /// from other chains must have been emitted with. Adjustable by the owner.
+  /// Use [check_vaa_finality](crate::handlers::check_vaa_finality) on
+  /// received VAAs.
+  pub min_vaa_finality: u8, // 1 byte
+
+  /// Keeps the account free of implicit padding, as zero-copy requires.
+  pub _padding: u8, // 1 byte
+
+  /// The maximum amount that can be withdrawn from a payable in a single
+  /// withdrawal. Zero means unlimited. Adjustable by the owner.
+  pub max_withdrawal_amount: u64, // 8 bytes
+
+  /// Whether payments alone are paused. Independent of
+  /// [paused](Config::paused), so withdrawals can continue while payments
+  /// are halted. Stored as a u8 (0 or 1) like `paused`.
+  pub payments_paused: u8, // 1 byte
+
+  /// Whether withdrawals alone are paused. Independent of
+  /// [paused](Config::paused), so payments can continue while withdrawals
+  /// are halted. Stored as a u8 (0 or 1) like `paused`.
+  pub withdrawals_paused: u8, // 1 byte
+
+  /// The account proposed to become the next owner. Has to accept the
+  /// ownership to become the owner. Default Pubkey when there is none.
+  /// Kept after the older fields so their offsets don't change.
+  pub pending_owner: Pubkey, // 32 bytes
+
+  /// Keeps the account's size a multiple of 8, as zero-copy requires.
+  pub _end_padding: [u8; 6], // 6 bytes
 }
 
 impl Config {
-  // discriminator (8) included
-  pub const SPACE: usize = 2 + 2 + 8 + (6 * 32);
+  pub const SPACE: usize = space::DISCRIMINATOR
+    + (2 * space::U16)
+    + (6 * space::PUBKEY)
+    + (4 * space::U8)
+    + space::U64
+    + (2 * space::U8)
+    + space::PUBKEY
+    + (6 * space::U8);
+
+  /// The highest withdrawal fee percentage that can be set, with 2 decimal
+  /// places like [withdrawal_fee_percentage](Config::withdrawal_fee_percentage).
+  /// 1000 means 10.00%. Keeps fee changes from taking most of a withdrawal.
+  #[constant]
+  pub const MAX_WITHDRAWAL_FEE_PERCENTAGE: u16 = 1000;
 
   /// AKA `b"config"`.
   pub const SEED_PREFIX: &'static [u8] = b"config";
+
+  /// Reads the owner from the raw data of the config account. Works for
+  /// config accounts created before fields were added to Config, which can't
+  /// be loaded until [migrate_config](crate::chainbills::migrate_config)
+  /// grows them to [SPACE](Config::SPACE). The owner's offset has never
+  /// changed.
+  pub fn read_owner(info: &AccountInfo) -> Result<Pubkey> {
+    const OWNER_OFFSET: usize = space::DISCRIMINATOR + (2 * space::U16);
+    let data = info.try_borrow_data()?;
+    require!(
+      *info.owner == crate::ID
+        && data.len() >= OWNER_OFFSET + space::PUBKEY
+        && data[..space::DISCRIMINATOR] == Config::DISCRIMINATOR,
+      ChainbillsError::InvalidConfigAccount
+    );
+    let mut owner = [0u8; 32];
+    owner.copy_from_slice(&data[OWNER_OFFSET..OWNER_OFFSET + space::PUBKEY]);
+    Ok(Pubkey::new_from_array(owner))
+  }
+
+  /// Whether payments, withdrawals, and payable creation are paused.
+  pub fn is_paused(&self) -> bool {
+    self.paused != 0
+  }
+
+  /// Whether cross-chain (Wormhole) operations are paused.
+  pub fn is_cross_chain_paused(&self) -> bool {
+    self.cross_chain_paused != 0
+  }
+
+  /// Whether payments alone are paused.
+  pub fn is_payments_paused(&self) -> bool {
+    self.payments_paused != 0
+  }
+
+  /// Whether withdrawals alone are paused.
+  pub fn is_withdrawals_paused(&self) -> bool {
+    self.withdrawals_paused != 0
+  }
+}
+
+#[cfg(test)]
+impl Config {
+  /// An unpaused config with 2% withdrawal fees and no withdrawal cap, for
+  /// tests to override the fields they need.
+  pub fn test_default() -> Self {
+    Config {
+      chain_id: 1,
+      withdrawal_fee_percentage: 200,
+      owner: Pubkey::new_unique(),
+      chainbills_fee_collector: Pubkey::new_unique(),
+      wormhole_bridge: Pubkey::new_unique(),
+      wormhole_emitter: Pubkey::new_unique(),
+      wormhole_fee_collector: Pubkey::new_unique(),
+      wormhole_sequence: Pubkey::new_unique(),
+      paused: 0,
+      cross_chain_paused: 0,
+      min_vaa_finality: 0,
+      _padding: 0,
+      max_withdrawal_amount: 0,
+      payments_paused: 0,
+      withdrawals_paused: 0,
+      pending_owner: Pubkey::default(),
+      _end_padding: [0; 6],
+    }
+  }
 }
@@ -1,3 +1,4 @@
+use crate::state::space;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -9,9 +10,17 @@ pub struct ForeignContract {
 }
 
 impl ForeignContract {
-  // discriminator first
-  pub const SPACE: usize = 8 + 32;
+  pub const SPACE: usize = space::DISCRIMINATOR + space::BYTES32;
 
   /// AKA `b"foreign_contract"`.
   pub const SEED_PREFIX: &'static [u8] = b"foreign_contract";
+
+  /// Derives the address (and bump) of the ForeignContract registered for
+  /// the given Wormhole Chain ID.
+  pub fn pda(chain: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+      &[Self::SEED_PREFIX, &chain.to_le_bytes()[..]],
+      &crate::ID,
+    )
+  }
 }
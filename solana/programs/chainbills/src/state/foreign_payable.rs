@@ -0,0 +1,37 @@
+use crate::state::space;
+use anchor_lang::prelude::*;
+
+#[account]
+/// A local mirror of a payable created on another chain. Lets payments on
+/// this chain target it.
+pub struct ForeignPayable {
+  /// The Payable's ID on its chain.
+  pub payable_id: [u8; 32], // 32 bytes
+
+  /// The Wormhole Chain ID of the chain on which the payable was created.
+  pub chain_id: u16, // 2 bytes
+
+  /// The Wormhole-normalized wallet address of the payable's host.
+  pub host: [u8; 32], // 32 bytes
+
+  /// When this mirror was initialized on this chain.
+  pub created_at: u64, // 8 bytes
+}
+
+impl ForeignPayable {
+  pub const SPACE: usize = space::DISCRIMINATOR
+    + space::BYTES32
+    + space::U16
+    + space::BYTES32
+    + space::U64;
+
+  /// AKA `b"foreign_payable"`.
+  #[constant]
+  pub const SEED_PREFIX: &'static [u8] = b"foreign_payable";
+
+  /// Derives the address (and bump) of the ForeignPayable mirroring the
+  /// payable with the given ID.
+  pub fn pda(payable_id: [u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[Self::SEED_PREFIX, &payable_id], &crate::ID)
+  }
+}
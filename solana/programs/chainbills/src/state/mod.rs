@@ -1,13 +1,21 @@
 pub mod activity_record;
 pub mod chain_stats;
+// The Pod derive behind zero_copy emits a padding-check struct that newer
+// compilers flag as dead code.
+#[allow(dead_code)]
 pub mod config;
 pub mod foreign_contract;
+pub mod foreign_payable;
 pub mod payable;
 pub mod payable_activity_info;
 pub mod payable_per_chain_payment_info;
 pub mod payable_per_chain_payments_counter;
 pub mod payable_payment;
 pub mod payable_withdrawal_info;
+pub mod payment_refund;
+pub mod relayer;
+pub mod space;
+pub mod supported_tokens;
 pub mod token_and_amount;
 pub mod token_details;
 pub mod user;
@@ -21,12 +29,16 @@ pub use activity_record::*;
 pub use chain_stats::*;
 pub use config::*;
 pub use foreign_contract::*;
+pub use foreign_payable::*;
 pub use payable::*;
 pub use payable_activity_info::*;
 pub use payable_per_chain_payment_info::*;
 pub use payable_per_chain_payments_counter::*;
 pub use payable_payment::*;
 pub use payable_withdrawal_info::*;
+pub use payment_refund::*;
+pub use relayer::*;
+pub use supported_tokens::*;
 pub use token_and_amount::*;
 pub use token_details::*;
 pub use user::*;
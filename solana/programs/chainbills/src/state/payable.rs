@@ -1,4 +1,7 @@
-use crate::state::TokenAndAmount;
+use crate::{
+  error::ChainbillsError,
+  state::{space, TokenAndAmount},
+};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -31,12 +34,43 @@ pub struct Payable {
   pub is_closed: bool, // 1 byte
 
   /// The allowed tokens (and their amounts) on this payable.
-  /* TokenAndAmount::SPACE * len() */
+  /* 4 + TokenAndAmount::SPACE * len() */
   pub allowed_tokens_and_amounts: Vec<TokenAndAmount>,
 
   /// Records of how much is in this payable.
-  /* TokenAndAmount::SPACE * len() */
+  /* 4 + TokenAndAmount::SPACE * len() */
   pub balances: Vec<TokenAndAmount>,
+
+  /// Where withdrawal fees from this payable go. Falls back to the
+  /// chainbills_fee_collector in Config when not set. Only the owner of this
+  /// program can set it, so hosts can't redirect the protocol's fees.
+  pub fee_recipient: Option<Pubkey>, // 1 + 32 bytes
+
+  /// The timestamp (in seconds) after which this payable stops accepting
+  /// payments. None means the payable never expires.
+  pub expires_at: Option<u64>, // 1 + 8 bytes
+
+  /// The maximum amount a single payment can have. Only applies when
+  /// allowed_tokens_and_amounts is empty (free payments).
+  pub max_payment_amount: Option<u64>, // 1 + 8 bytes
+
+  /// The token in which this payable's goal is set. The payable gets closed
+  /// once its balance of this token reaches the goal_amount.
+  pub goal_token: Option<Pubkey>, // 1 + 32 bytes
+
+  /// The amount of goal_token at which this payable gets closed.
+  pub goal_amount: Option<u64>, // 1 + 8 bytes
+
+  /// Whether this payable rejects payments bridged from other chains. Local
+  /// payments are always accepted. The inverse of CosmWasm's
+  /// accepts_cross_chain, so that payables from before it was added read
+  /// false and keep accepting them. Use
+  /// [accepts_cross_chain](Payable::accepts_cross_chain) to read it.
+  pub rejects_cross_chain: bool, // 1 byte
+
+  /// The maximum number of payments this payable accepts. It gets closed
+  /// once its payments_count reaches this. None means there is no cap.
+  pub max_payments: Option<u64>, // 1 + 8 bytes
 }
 
 impl Payable {
@@ -44,28 +78,133 @@ impl Payable {
   #[constant]
   pub const SEED_PREFIX: &'static [u8] = b"payable";
 
-  pub fn next_payment(&self) -> u64 {
-    self.payments_count.checked_add(1).unwrap()
+  /// The maximum number of allowed tokens and amounts a payable can have.
+  /// Keeps the payable's account space and the creating transaction's size
+  /// (each token also needs a TokenDetails remaining account) bounded.
+  #[constant]
+  pub const MAX_PAYABLES_TOKENS: usize = 10;
+
+  /// The maximum number of distinct tokens a payable can hold balances in.
+  /// Keeps payables from being bloated with payments in many junk tokens.
+  #[constant]
+  pub const MAX_PAYABLE_BALANCES: usize = 20;
+
+  pub fn next_payment(&self) -> Result<u64> {
+    self
+      .payments_count
+      .checked_add(1)
+      .ok_or(error!(ChainbillsError::Overflow))
+  }
+
+  pub fn next_withdrawal(&self) -> Result<u64> {
+    self
+      .withdrawals_count
+      .checked_add(1)
+      .ok_or(error!(ChainbillsError::Overflow))
+  }
+
+  pub fn next_activity(&self) -> Result<u64> {
+    self
+      .activities_count
+      .checked_add(1)
+      .ok_or(error!(ChainbillsError::Overflow))
+  }
+
+  /// The space needed to store a payable with the given numbers of allowed
+  /// tokens and amounts and of balances. Space is reserved for the optional
+  /// fields whether they are set or not.
+  pub const fn space_for(ataa_len: usize, balances_len: usize) -> usize {
+    space::DISCRIMINATOR
+      + (6 * space::U64)
+      + space::PUBKEY
+      + space::BOOL
+      + space::vec(ataa_len, TokenAndAmount::SPACE)
+      + space::vec(balances_len, TokenAndAmount::SPACE)
+      + space::option(space::PUBKEY) // fee_recipient
+      + space::option(space::U64) // expires_at
+      + space::option(space::U64) // max_payment_amount
+      + space::option(space::PUBKEY) // goal_token
+      + space::option(space::U64) // goal_amount
+      + space::BOOL // rejects_cross_chain
+      + space::option(space::U64) // max_payments
+  }
+
+  /// The most space that a payable can take, with the maximum numbers of
+  /// allowed tokens and amounts and of balances.
+  pub const MAX_SPACE: usize =
+    Self::space_for(Self::MAX_PAYABLES_TOKENS, Self::MAX_PAYABLE_BALANCES);
+
+  /// Reads a payable from account data that may have been written before
+  /// fields were added to the end of Payable. The missing fields take their
+  /// zero defaults (None for the optional ones), as Borsh reads them from
+  /// zeroed bytes.
+  pub fn read_compat(data: &[u8]) -> Result<Payable> {
+    let mut padded = data.to_vec();
+    padded.resize(data.len() + Self::space_for(0, 0), 0);
+    Payable::try_deserialize(&mut padded.as_slice())
+  }
+
+  /// The account that should receive withdrawal fees from this payable.
+  pub fn fee_collector(&self, default_collector: Pubkey) -> Pubkey {
+    self.fee_recipient.unwrap_or(default_collector)
+  }
+
+  /// Whether this payable accepts payments bridged from other chains.
+  pub fn accepts_cross_chain(&self) -> bool {
+    !self.rejects_cross_chain
   }
 
-  pub fn next_withdrawal(&self) -> u64 {
-    self.withdrawals_count.checked_add(1).unwrap()
+  /// This payable's balance of the given token. Zero if it has none.
+  pub fn balance_of(&self, token: Pubkey) -> u64 {
+    self
+      .balances
+      .iter()
+      .find(|bal| bal.token == token)
+      .map_or(0, |bal| bal.amount)
   }
 
-  pub fn next_activity(&self) -> u64 {
-    self.activities_count.checked_add(1).unwrap()
+  /// Whether this payable's balance of its goal_token has reached its
+  /// goal_amount. Always false if no goal was set.
+  pub fn has_reached_goal(&self) -> bool {
+    match (self.goal_token, self.goal_amount) {
+      (Some(token), Some(goal_amount)) => self
+        .balances
+        .iter()
+        .any(|bal| bal.token == token && bal.amount >= goal_amount),
+      _ => false,
+    }
+  }
+
+  /// How much more of its goal_token this payable needs to reach its goal.
+  /// None if no goal was set.
+  pub fn remaining_to_goal(&self) -> Option<u64> {
+    match (self.goal_token, self.goal_amount) {
+      (Some(token), Some(goal_amount)) => {
+        Some(goal_amount.saturating_sub(self.balance_of(token)))
+      }
+      _ => None,
+    }
+  }
+
+  /// How many more payments this payable accepts before reaching its
+  /// max_payments. None if there is no cap.
+  pub fn remaining_slots(&self) -> Option<u64> {
+    self
+      .max_payments
+      .map(|max_payments| max_payments.saturating_sub(self.payments_count))
+  }
+
+  /// The space needed to store this payable as it currently is.
+  pub fn space(&self) -> usize {
+    Self::space_for(self.allowed_tokens_and_amounts.len(), self.balances.len())
   }
 
   pub fn space_new(ataa_len: usize) -> usize {
-    // discriminator (8) included
-    1 + (7 * 8) + 32 + (ataa_len * TokenAndAmount::SPACE)
+    Self::space_for(ataa_len, 0)
   }
 
   pub fn space_update_ataa(&self, ataa_len: usize) -> usize {
-    1 + (6 * 8) // discriminator (8) included
-      + 32
-      + (ataa_len * TokenAndAmount::SPACE)
-      + (self.balances.len() * TokenAndAmount::SPACE)
+    Self::space_for(ataa_len, self.balances.len())
   }
 
   pub fn space_update_balance(&self, token: Pubkey) -> usize {
@@ -74,9 +213,64 @@ impl Payable {
     let new_bals_len =
       self.balances.len() + if will_add_new_balance { 1 } else { 0 };
 
-    1 + (6 * 8) // discriminator (8) included
-      + 32
-      + (self.allowed_tokens_and_amounts.len() * TokenAndAmount::SPACE)
-      + (new_bals_len * TokenAndAmount::SPACE)
+    Self::space_for(self.allowed_tokens_and_amounts.len(), new_bals_len)
+  }
+}
+
+#[cfg(test)]
+impl Payable {
+  /// An open payable with no tokens, balances or payments, for tests to
+  /// override the fields they need.
+  pub fn test_default() -> Self {
+    Payable {
+      chain_count: 1,
+      host: Pubkey::new_unique(),
+      host_count: 1,
+      created_at: 0,
+      payments_count: 0,
+      withdrawals_count: 0,
+      activities_count: 1,
+      is_closed: false,
+      allowed_tokens_and_amounts: vec![],
+      balances: vec![],
+      fee_recipient: None,
+      expires_at: None,
+      max_payment_amount: None,
+      goal_token: None,
+      goal_amount: None,
+      rejects_cross_chain: false,
+      max_payments: None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn payable(count: u64) -> Payable {
+    Payable {
+      payments_count: count,
+      withdrawals_count: count,
+      activities_count: count,
+      ..Payable::test_default()
+    }
+  }
+
+  #[test]
+  fn returns_the_incremented_counts() {
+    let payable = payable(u64::MAX - 1);
+    assert_eq!(payable.next_payment().unwrap(), u64::MAX);
+    assert_eq!(payable.next_withdrawal().unwrap(), u64::MAX);
+    assert_eq!(payable.next_activity().unwrap(), u64::MAX);
+  }
+
+  #[test]
+  fn fails_past_the_overflow_boundary() {
+    let payable = payable(u64::MAX);
+    let overflow: Error = ChainbillsError::Overflow.into();
+    assert_eq!(payable.next_payment().unwrap_err(), overflow);
+    assert_eq!(payable.next_withdrawal().unwrap_err(), overflow);
+    assert_eq!(payable.next_activity().unwrap_err(), overflow);
   }
 }
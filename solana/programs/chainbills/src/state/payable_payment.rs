@@ -1,4 +1,4 @@
-use crate::state::TokenAndAmount;
+use crate::state::{space, TokenAndAmount, UserPayment};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -32,11 +32,20 @@ pub struct PayablePayment {
 
   /// The amount and token that the payer paid
   pub details: TokenAndAmount, // TokenAndAmount::SPACE
+
+  /// A free-text reference (e.g. an order number or invoice ID) that the
+  /// payer attached to this payment.
+  pub memo: Option<String>, // 1 + 4 + UserPayment::MAX_MEMO_LENGTH bytes
 }
 
 impl PayablePayment {
-  // discriminator (8) included
-  pub const SPACE: usize = 2 + (5 * 8) + (2 * 32) + TokenAndAmount::SPACE;
+  pub const SPACE: usize = space::DISCRIMINATOR
+    + space::PUBKEY
+    + space::BYTES32
+    + (4 * space::U64)
+    + space::U16
+    + TokenAndAmount::SPACE
+    + space::option(space::string(UserPayment::MAX_MEMO_LENGTH));
 
   /// AKA `b"payment"`.
   #[constant]
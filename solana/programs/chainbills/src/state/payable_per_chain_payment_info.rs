@@ -1,3 +1,4 @@
+use crate::state::space;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -9,6 +10,5 @@ pub struct PayablePerChainPaymentInfo {
 }
 
 impl PayablePerChainPaymentInfo {
-  // discriminator (8) included
-  pub const SPACE: usize = 8 + 8;
+  pub const SPACE: usize = space::DISCRIMINATOR + space::U64;
 }
@@ -1,3 +1,4 @@
+use crate::{error::ChainbillsError, state::space};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -9,10 +10,36 @@ pub struct PayablePerChainPaymentsCounter {
 }
 
 impl PayablePerChainPaymentsCounter {
-  // discriminator (8) included
-  pub const SPACE: usize = 8 + 8;
+  pub const SPACE: usize = space::DISCRIMINATOR + space::U64;
 
-  pub fn next_payment(&self) -> u64 {
-    self.payments_count.checked_add(1).unwrap()
+  pub fn next_payment(&self) -> Result<u64> {
+    self
+      .payments_count
+      .checked_add(1)
+      .ok_or(error!(ChainbillsError::Overflow))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn returns_the_incremented_count() {
+    let counter = PayablePerChainPaymentsCounter {
+      payments_count: u64::MAX - 1,
+    };
+    assert_eq!(counter.next_payment().unwrap(), u64::MAX);
+  }
+
+  #[test]
+  fn fails_past_the_overflow_boundary() {
+    let counter = PayablePerChainPaymentsCounter {
+      payments_count: u64::MAX,
+    };
+    assert_eq!(
+      counter.next_payment().unwrap_err(),
+      ChainbillsError::Overflow.into()
+    );
   }
 }
@@ -1,3 +1,4 @@
+use crate::state::space;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -12,8 +13,7 @@ pub struct PayableWithdrawalInfo {
 }
 
 impl PayableWithdrawalInfo {
-  // discriminator (8) included
-  pub const SPACE: usize = 8 + 8;
+  pub const SPACE: usize = space::DISCRIMINATOR + space::U64;
 
   /// AKA `b"payment"`.
   #[constant]
@@ -0,0 +1,26 @@
+use crate::state::space;
+use anchor_lang::prelude::*;
+
+#[account]
+/// Marks a user's payment as refunded by the host of the payable it was made
+/// to. Its PDA is derived from the UserPayment, so that each payment can only
+/// be refunded once.
+pub struct PaymentRefund {
+  /// Whether the payment has been refunded.
+  pub is_refunded: bool, // 1 byte
+
+  /// The amount that was refunded to the payer.
+  pub amount: u64, // 8 bytes
+
+  /// When the refund was made.
+  pub timestamp: u64, // 8 bytes
+}
+
+impl PaymentRefund {
+  pub const SPACE: usize =
+    space::DISCRIMINATOR + space::BOOL + (2 * space::U64);
+
+  /// AKA `b"payment_refund"`.
+  #[constant]
+  pub const SEED_PREFIX: &'static [u8] = b"payment_refund";
+}
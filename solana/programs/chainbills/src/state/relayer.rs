@@ -0,0 +1,28 @@
+use crate::state::space;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+/// Whether a wallet is a trusted relayer. Relayers can act on behalf of
+/// other wallets, like in meta-transactions and cross-chain relaying.
+pub struct Relayer {
+  /// Whether the relayer is currently authorized.
+  pub is_authorized: bool, // 1 byte
+}
+
+impl Relayer {
+  pub const SPACE: usize = space::DISCRIMINATOR + space::BOOL;
+
+  /// AKA `b"relayer"`.
+  #[constant]
+  pub const SEED_PREFIX: &'static [u8] = b"relayer";
+
+  /// Derives the address (and bump) of the Relayer account of the given
+  /// wallet.
+  pub fn pda(wallet: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+      &[Self::SEED_PREFIX, wallet.as_ref()],
+      &crate::ID,
+    )
+  }
+}
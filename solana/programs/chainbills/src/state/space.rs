@@ -0,0 +1,262 @@
+//! Serialized sizes (in bytes) of the types that make up accounts. The SPACE
+//! of every account is summed from these, field by field, so that it can't
+//! drift from what the account actually serializes to.
+
+/// The discriminator that Anchor prefixes every account with.
+pub const DISCRIMINATOR: usize = 8;
+
+pub const BOOL: usize = 1;
+pub const U8: usize = 1;
+pub const U16: usize = 2;
+pub const U32: usize = 4;
+pub const U64: usize = 8;
+pub const PUBKEY: usize = 32;
+pub const BYTES32: usize = 32;
+
+/// A fieldless enum is serialized as its variant's index.
+pub const ENUM: usize = 1;
+
+/// An Option takes a byte for whether it is set, plus the space of its
+/// value. The value's space is reserved whether set or not.
+pub const fn option(value: usize) -> usize {
+  1 + value
+}
+
+/// A Vec takes 4 bytes for its length, plus the space of its items.
+pub const fn vec(len: usize, item: usize) -> usize {
+  4 + len * item
+}
+
+/// A String takes 4 bytes for its length, plus its bytes.
+pub const fn string(max_len: usize) -> usize {
+  vec(max_len, U8)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::state::*;
+  use anchor_lang::prelude::*;
+
+  fn serialized_len<T: AccountSerialize>(account: &T) -> usize {
+    let mut data = vec![];
+    account.try_serialize(&mut data).unwrap();
+    data.len()
+  }
+
+  fn taa() -> TokenAndAmount {
+    TokenAndAmount {
+      token: Pubkey::new_unique(),
+      amount: u64::MAX,
+    }
+  }
+
+  #[test]
+  fn declared_spaces_match_serialized_lengths() {
+    let key = Pubkey::new_unique();
+    let cases = [
+      (
+        serialized_len(&ActivityRecord {
+          chain_count: u64::MAX,
+          user_count: u64::MAX,
+          payable_count: u64::MAX,
+          timestamp: u64::MAX,
+          entity: key,
+          activity_type: ActivityType::RefundedPayment,
+        }),
+        ActivityRecord::SPACE,
+      ),
+      (
+        serialized_len(&ChainStats {
+          users_count: u64::MAX,
+          payables_count: u64::MAX,
+          user_payments_count: u64::MAX,
+          payable_payments_count: u64::MAX,
+          withdrawals_count: u64::MAX,
+          activities_count: u64::MAX,
+        }),
+        ChainStats::SPACE,
+      ),
+      (
+        serialized_len(&ForeignContract { address: [1; 32] }),
+        ForeignContract::SPACE,
+      ),
+      (
+        serialized_len(&ForeignPayable {
+          payable_id: [1; 32],
+          chain_id: u16::MAX,
+          host: [1; 32],
+          created_at: u64::MAX,
+        }),
+        ForeignPayable::SPACE,
+      ),
+      (
+        serialized_len(&PayableActivityInfo {
+          chain_count: u64::MAX,
+        }),
+        PayableActivityInfo::SPACE,
+      ),
+      (
+        serialized_len(&PayablePayment {
+          payable_id: key,
+          payer: [1; 32],
+          chain_count: u64::MAX,
+          payer_chain_id: u16::MAX,
+          local_chain_count: u64::MAX,
+          payable_count: u64::MAX,
+          timestamp: u64::MAX,
+          details: taa(),
+          memo: Some("a".repeat(UserPayment::MAX_MEMO_LENGTH)),
+        }),
+        PayablePayment::SPACE,
+      ),
+      (
+        serialized_len(&PayablePerChainPaymentInfo {
+          payable_count: u64::MAX,
+        }),
+        PayablePerChainPaymentInfo::SPACE,
+      ),
+      (
+        serialized_len(&PayablePerChainPaymentsCounter {
+          payments_count: u64::MAX,
+        }),
+        PayablePerChainPaymentsCounter::SPACE,
+      ),
+      (
+        serialized_len(&PayableWithdrawalInfo {
+          host_count: u64::MAX,
+        }),
+        PayableWithdrawalInfo::SPACE,
+      ),
+      (
+        serialized_len(&PaymentRefund {
+          is_refunded: true,
+          amount: u64::MAX,
+          timestamp: u64::MAX,
+        }),
+        PaymentRefund::SPACE,
+      ),
+      (
+        serialized_len(&Relayer {
+          is_authorized: true,
+        }),
+        Relayer::SPACE,
+      ),
+      (
+        serialized_len(&SupportedTokens {
+          tokens: vec![key; 3],
+        }),
+        SupportedTokens::space_for(3),
+      ),
+      (
+        serialized_len(&TokenDetails {
+          mint: key,
+          max_withdrawal_fees: u64::MAX,
+          total_user_paid: u64::MAX,
+          total_payable_received: u64::MAX,
+          total_withdrawn: u64::MAX,
+          total_withdrawal_fees_collected: u64::MAX,
+          pending_max_withdrawal_fees: u64::MAX,
+          pending_fees_effective_at: u64::MAX,
+          ..TokenDetails::test_default()
+        }),
+        TokenDetails::SPACE,
+      ),
+      (
+        serialized_len(&User {
+          chain_count: u64::MAX,
+          payables_count: u64::MAX,
+          payments_count: u64::MAX,
+          withdrawals_count: u64::MAX,
+          activities_count: u64::MAX,
+        }),
+        User::SPACE,
+      ),
+      (
+        serialized_len(&UserActivityInfo {
+          chain_count: u64::MAX,
+        }),
+        UserActivityInfo::SPACE,
+      ),
+      (
+        serialized_len(&UserAddress { address: key }),
+        UserAddress::SPACE,
+      ),
+      (
+        serialized_len(&UserPayment {
+          payable_id: [1; 32],
+          payer: key,
+          payable_chain_id: u16::MAX,
+          chain_count: u64::MAX,
+          payer_count: u64::MAX,
+          timestamp: u64::MAX,
+          details: taa(),
+          memo: Some("a".repeat(UserPayment::MAX_MEMO_LENGTH)),
+        }),
+        UserPayment::SPACE,
+      ),
+      (
+        serialized_len(&Withdrawal {
+          payable_id: key,
+          host: key,
+          chain_count: u64::MAX,
+          host_count: u64::MAX,
+          payable_count: u64::MAX,
+          timestamp: u64::MAX,
+          details: taa(),
+        }),
+        Withdrawal::SPACE,
+      ),
+      (
+        serialized_len(&WormholeReceived {
+          batch_id: u32::MAX,
+          vaa_hash: [1; 32],
+        }),
+        WormholeReceived::SPACE,
+      ),
+    ];
+    for (len, space) in cases {
+      assert_eq!(len, space);
+    }
+
+    // Zero-copy accounts are stored as their raw bytes.
+    assert_eq!(
+      Config::SPACE,
+      super::DISCRIMINATOR + std::mem::size_of::<Config>()
+    );
+  }
+
+  #[test]
+  fn declared_payable_space_matches_serialized_length() {
+    let payable = |ataa_len: usize, balances_len: usize| Payable {
+      chain_count: u64::MAX,
+      host_count: u64::MAX,
+      created_at: u64::MAX,
+      payments_count: u64::MAX,
+      withdrawals_count: u64::MAX,
+      activities_count: u64::MAX,
+      is_closed: true,
+      allowed_tokens_and_amounts: (0..ataa_len).map(|_| taa()).collect(),
+      balances: (0..balances_len).map(|_| taa()).collect(),
+      fee_recipient: Some(Pubkey::new_unique()),
+      expires_at: Some(u64::MAX),
+      max_payment_amount: Some(u64::MAX),
+      goal_token: Some(Pubkey::new_unique()),
+      goal_amount: Some(u64::MAX),
+      max_payments: Some(u64::MAX),
+      ..Payable::test_default()
+    };
+
+    let max =
+      payable(Payable::MAX_PAYABLES_TOKENS, Payable::MAX_PAYABLE_BALANCES);
+    assert_eq!(serialized_len(&max), Payable::MAX_SPACE);
+    assert_eq!(max.space(), Payable::MAX_SPACE);
+
+    for (ataa_len, balances_len) in [(0, 0), (1, 0), (0, 1), (3, 5)] {
+      let payable = payable(ataa_len, balances_len);
+      assert_eq!(serialized_len(&payable), payable.space());
+      if balances_len == 0 {
+        assert_eq!(serialized_len(&payable), Payable::space_new(ataa_len));
+      }
+    }
+  }
+}
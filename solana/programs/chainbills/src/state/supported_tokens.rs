@@ -0,0 +1,90 @@
+use crate::state::space;
+use anchor_lang::prelude::*;
+
+#[account]
+/// Lists the tokens in which payments are currently accepted, so that clients
+/// can enumerate them without knowing their mints beforehand. The native
+/// token (Solana) is listed as the program's ID.
+pub struct SupportedTokens {
+  /// The mints of the supported tokens, in the order they were supported.
+  pub tokens: Vec<Pubkey>, // 4 bytes + 32 bytes per token
+}
+
+impl SupportedTokens {
+  /// AKA `b"supported_tokens"`.
+  #[constant]
+  pub const SEED_PREFIX: &'static [u8] = b"supported_tokens";
+
+  pub const fn space_for(tokens_len: usize) -> usize {
+    space::DISCRIMINATOR + space::vec(tokens_len, space::PUBKEY)
+  }
+
+  pub fn space(&self) -> usize {
+    Self::space_for(self.tokens.len())
+  }
+
+  /// The space the account needs after the token's support status is set.
+  pub fn space_update(&self, token: Pubkey, is_supported: bool) -> usize {
+    let is_listed = self.tokens.contains(&token);
+    match (is_listed, is_supported) {
+      (false, true) => Self::space_for(self.tokens.len() + 1),
+      (true, false) => Self::space_for(self.tokens.len() - 1),
+      _ => self.space(),
+    }
+  }
+
+  /// Adds the token to or removes it from the list. Tokens are listed once.
+  pub fn set_supported(&mut self, token: Pubkey, is_supported: bool) {
+    if is_supported {
+      if !self.tokens.contains(&token) {
+        self.tokens.push(token);
+      }
+    } else {
+      self.tokens.retain(|t| *t != token);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn lists_supported_tokens_once() {
+    let mut supported_tokens = SupportedTokens { tokens: vec![] };
+    let first = Pubkey::new_unique();
+    let second = Pubkey::new_unique();
+
+    assert_eq!(
+      supported_tokens.space_update(first, true),
+      SupportedTokens::space_for(1)
+    );
+    supported_tokens.set_supported(first, true);
+    supported_tokens.set_supported(second, true);
+    assert_eq!(supported_tokens.tokens, vec![first, second]);
+
+    // Supporting a listed token again doesn't list it twice.
+    assert_eq!(
+      supported_tokens.space_update(first, true),
+      supported_tokens.space()
+    );
+    supported_tokens.set_supported(first, true);
+    assert_eq!(supported_tokens.tokens, vec![first, second]);
+
+    // Unsupported tokens are removed and the account shrinks.
+    assert_eq!(
+      supported_tokens.space_update(first, false),
+      SupportedTokens::space_for(1)
+    );
+    supported_tokens.set_supported(first, false);
+    assert_eq!(supported_tokens.tokens, vec![second]);
+
+    // Unsupporting an unlisted token changes nothing.
+    assert_eq!(
+      supported_tokens.space_update(first, false),
+      supported_tokens.space()
+    );
+    supported_tokens.set_supported(first, false);
+    assert_eq!(supported_tokens.tokens, vec![second]);
+  }
+}
@@ -1,3 +1,4 @@
+use crate::state::space;
 use anchor_lang::prelude::*;
 
 /// A combination of a token address and its associated amount.
@@ -15,5 +16,5 @@ pub struct TokenAndAmount {
 }
 
 impl TokenAndAmount {
-  pub const SPACE: usize = 32 + 8;
+  pub const SPACE: usize = space::PUBKEY + space::U64;
 }
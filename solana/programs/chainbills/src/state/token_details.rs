@@ -1,3 +1,5 @@
+use crate::error::ChainbillsError;
+use crate::state::space;
 use anchor_lang::prelude::*;
 
 /// Keeps track of details about supported tokens.
@@ -24,33 +26,126 @@ pub struct TokenDetails {
 
   /// The total amount of fees collected from withdrawals in this token.
   pub total_withdrawal_fees_collected: u64, // 8 bytes
+
+  /// Increased maximum withdrawal fees that replace max_withdrawal_fees once
+  /// pending_fees_effective_at is reached.
+  pub pending_max_withdrawal_fees: u64, // 8 bytes
+
+  /// The timestamp from which pending_max_withdrawal_fees applies. Zero when
+  /// there are no pending fees.
+  pub pending_fees_effective_at: u64, // 8 bytes
 }
 
 impl TokenDetails {
-  // discriminator (8) included
-  pub const SPACE: usize = 1 + 6 * 8 + 32;
+  pub const SPACE: usize =
+    space::DISCRIMINATOR + space::PUBKEY + space::BOOL + (7 * space::U64);
+
+  /// How long (in seconds) increases of max withdrawal fees wait before they
+  /// apply, so that hosts can withdraw at the old fees first. Two days.
+  #[constant]
+  pub const FEES_TIMELOCK: u64 = 2 * 24 * 60 * 60;
 
   /// AKA `b"token_details`.
   #[constant]
   pub const SEED_PREFIX: &'static [u8] = b"token_details";
 
-  pub fn add_user_paid(&mut self, amount: u64) {
-    self.total_user_paid = self.total_user_paid.checked_add(amount).unwrap()
+  /// The maximum withdrawal fees that apply at the given timestamp, taking
+  /// into account pending fees whose timelock has elapsed.
+  pub fn effective_max_withdrawal_fees(&self, now: u64) -> u64 {
+    if self.pending_fees_effective_at != 0
+      && now >= self.pending_fees_effective_at
+    {
+      self.pending_max_withdrawal_fees
+    } else {
+      self.max_withdrawal_fees
+    }
+  }
+
+  /// Makes pending fees whose timelock has elapsed the max_withdrawal_fees.
+  pub fn promote_pending_fees(&mut self, now: u64) {
+    self.max_withdrawal_fees = self.effective_max_withdrawal_fees(now);
+    if self.pending_fees_effective_at != 0
+      && now >= self.pending_fees_effective_at
+    {
+      self.pending_max_withdrawal_fees = 0;
+      self.pending_fees_effective_at = 0;
+    }
+  }
+
+  /// Sets the maximum withdrawal fees and returns the timestamp from which
+  /// they apply. Increases wait for FEES_TIMELOCK, replacing any pending
+  /// fees. Decreases and the fees of newly added tokens apply immediately.
+  pub fn schedule_max_withdrawal_fees(
+    &mut self,
+    max_withdrawal_fees: u64,
+    now: u64,
+  ) -> Result<u64> {
+    self.promote_pending_fees(now);
+    self.pending_max_withdrawal_fees = 0;
+    self.pending_fees_effective_at = 0;
+    if self.mint == Pubkey::default()
+      || max_withdrawal_fees <= self.max_withdrawal_fees
+    {
+      self.max_withdrawal_fees = max_withdrawal_fees;
+      return Ok(now);
+    }
+
+    let effective_at = now
+      .checked_add(Self::FEES_TIMELOCK)
+      .ok_or(ChainbillsError::Overflow)?;
+    self.pending_max_withdrawal_fees = max_withdrawal_fees;
+    self.pending_fees_effective_at = effective_at;
+    Ok(effective_at)
   }
 
-  pub fn add_payable_received(&mut self, amount: u64) {
-    self.total_payable_received =
-      self.total_payable_received.checked_add(amount).unwrap()
+  pub fn add_user_paid(&mut self, amount: u64) -> Result<()> {
+    self.total_user_paid = self
+      .total_user_paid
+      .checked_add(amount)
+      .ok_or(ChainbillsError::Overflow)?;
+    Ok(())
   }
 
-  pub fn add_withdrawn(&mut self, amount: u64) {
-    self.total_withdrawn = self.total_withdrawn.checked_add(amount).unwrap()
+  pub fn add_payable_received(&mut self, amount: u64) -> Result<()> {
+    self.total_payable_received = self
+      .total_payable_received
+      .checked_add(amount)
+      .ok_or(ChainbillsError::Overflow)?;
+    Ok(())
+  }
+
+  pub fn add_withdrawn(&mut self, amount: u64) -> Result<()> {
+    self.total_withdrawn = self
+      .total_withdrawn
+      .checked_add(amount)
+      .ok_or(ChainbillsError::Overflow)?;
+    Ok(())
   }
 
-  pub fn add_withdrawal_fees_collected(&mut self, amount: u64) {
+  pub fn add_withdrawal_fees_collected(&mut self, amount: u64) -> Result<()> {
     self.total_withdrawal_fees_collected = self
       .total_withdrawal_fees_collected
       .checked_add(amount)
-      .unwrap()
+      .ok_or(ChainbillsError::Overflow)?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+impl TokenDetails {
+  /// A supported token with no fees or totals, for tests to override the
+  /// fields they need.
+  pub fn test_default() -> Self {
+    TokenDetails {
+      mint: Pubkey::new_unique(),
+      is_supported: true,
+      max_withdrawal_fees: 0,
+      total_user_paid: 0,
+      total_payable_received: 0,
+      total_withdrawn: 0,
+      total_withdrawal_fees_collected: 0,
+      pending_max_withdrawal_fees: 0,
+      pending_fees_effective_at: 0,
+    }
   }
 }
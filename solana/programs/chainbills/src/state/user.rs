@@ -1,3 +1,4 @@
+use crate::{error::ChainbillsError, state::space};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -21,22 +22,96 @@ pub struct User {
 }
 
 impl User {
-  // discriminator (8) included
-  pub const SPACE: usize = 6 * 8;
+  pub const SPACE: usize = space::DISCRIMINATOR + (5 * space::U64);
 
-  pub fn next_payable(&self) -> u64 {
-    self.payables_count.checked_add(1).unwrap()
+  pub fn next_payable(&self) -> Result<u64> {
+    self
+      .payables_count
+      .checked_add(1)
+      .ok_or(error!(ChainbillsError::Overflow))
   }
 
-  pub fn next_payment(&self) -> u64 {
-    self.payments_count.checked_add(1).unwrap()
+  pub fn next_payment(&self) -> Result<u64> {
+    self
+      .payments_count
+      .checked_add(1)
+      .ok_or(error!(ChainbillsError::Overflow))
   }
 
-  pub fn next_withdrawal(&self) -> u64 {
-    self.withdrawals_count.checked_add(1).unwrap()
+  pub fn next_withdrawal(&self) -> Result<u64> {
+    self
+      .withdrawals_count
+      .checked_add(1)
+      .ok_or(error!(ChainbillsError::Overflow))
   }
 
-  pub fn next_activity(&self) -> u64 {
-    self.activities_count.checked_add(1).unwrap()
+  pub fn next_activity(&self) -> Result<u64> {
+    self
+      .activities_count
+      .checked_add(1)
+      .ok_or(error!(ChainbillsError::Overflow))
+  }
+
+  /// Reads a User from its account's owner and data. Fails with
+  /// UserNotInitialized if the wallet never initialized a User.
+  pub fn read(owner: &Pubkey, data: &[u8]) -> Result<User> {
+    require!(*owner == crate::ID, ChainbillsError::UserNotInitialized);
+    User::try_deserialize(&mut &data[..])
+      .map_err(|_| error!(ChainbillsError::UserNotInitialized))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn user(count: u64) -> User {
+    User {
+      chain_count: 1,
+      payables_count: count,
+      payments_count: count,
+      withdrawals_count: count,
+      activities_count: count,
+    }
+  }
+
+  #[test]
+  fn returns_the_incremented_counts() {
+    let user = user(u64::MAX - 1);
+    assert_eq!(user.next_payable().unwrap(), u64::MAX);
+    assert_eq!(user.next_payment().unwrap(), u64::MAX);
+    assert_eq!(user.next_withdrawal().unwrap(), u64::MAX);
+    assert_eq!(user.next_activity().unwrap(), u64::MAX);
+  }
+
+  #[test]
+  fn fails_past_the_overflow_boundary() {
+    let user = user(u64::MAX);
+    let overflow: Error = ChainbillsError::Overflow.into();
+    assert_eq!(user.next_payable().unwrap_err(), overflow);
+    assert_eq!(user.next_payment().unwrap_err(), overflow);
+    assert_eq!(user.next_withdrawal().unwrap_err(), overflow);
+    assert_eq!(user.next_activity().unwrap_err(), overflow);
+  }
+
+  #[test]
+  fn reads_only_initialized_users() {
+    let mut data = vec![];
+    user(3).try_serialize(&mut data).unwrap();
+    assert_eq!(User::read(&crate::ID, &data).unwrap().withdrawals_count, 3);
+
+    // A wallet that never initialized has an empty system-owned account.
+    assert_eq!(
+      User::read(&System::id(), &[]).err().unwrap(),
+      ChainbillsError::UserNotInitialized.into()
+    );
+    assert_eq!(
+      User::read(&crate::ID, &[]).err().unwrap(),
+      ChainbillsError::UserNotInitialized.into()
+    );
+    assert_eq!(
+      User::read(&Pubkey::new_unique(), &data).err().unwrap(),
+      ChainbillsError::UserNotInitialized.into()
+    );
   }
 }
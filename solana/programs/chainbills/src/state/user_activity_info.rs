@@ -1,3 +1,4 @@
+use crate::state::{space, ActivityRecord};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -9,6 +10,18 @@ pub struct UserActivityInfo {
 }
 
 impl UserActivityInfo {
-  // discriminator (8) included
-  pub const SPACE: usize = 8 + 8;
+  pub const SPACE: usize = space::DISCRIMINATOR + space::U64;
+
+  /// Derives the address (and bump) of the UserActivityInfo of the given
+  /// wallet's nth activity.
+  pub fn pda(wallet: Pubkey, user_count: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+      &[
+        wallet.as_ref(),
+        ActivityRecord::SEED_PREFIX,
+        &user_count.to_le_bytes()[..],
+      ],
+      &crate::ID,
+    )
+  }
 }
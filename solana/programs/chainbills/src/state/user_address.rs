@@ -1,3 +1,4 @@
+use crate::state::space;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -9,8 +10,7 @@ pub struct UserAddress {
 }
 
 impl UserAddress {
-  // discriminator (8) included
-  pub const SPACE: usize = 8 + 8;
+  pub const SPACE: usize = space::DISCRIMINATOR + space::PUBKEY;
 
   /// AKA `b"user_address`.
   #[constant]
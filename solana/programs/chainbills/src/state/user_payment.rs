@@ -1,4 +1,4 @@
-use crate::state::TokenAndAmount;
+use crate::state::{space, TokenAndAmount};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -30,11 +30,24 @@ pub struct UserPayment {
 
   /// The amount and token that the payer paid
   pub details: TokenAndAmount, // TokenAndAmount::SPACE
+
+  /// A free-text reference (e.g. an order number or invoice ID) that the
+  /// payer attached to this payment.
+  pub memo: Option<String>, // 1 + 4 + MAX_MEMO_LENGTH bytes
 }
 
 impl UserPayment {
-  // discriminator (8) included
-  pub const SPACE: usize = 2 + (4 * 8) + (2 * 32) + TokenAndAmount::SPACE;
+  pub const SPACE: usize = space::DISCRIMINATOR
+    + space::BYTES32
+    + space::PUBKEY
+    + space::U16
+    + (3 * space::U64)
+    + TokenAndAmount::SPACE
+    + space::option(space::string(Self::MAX_MEMO_LENGTH));
+
+  /// The maximum length (in bytes) of the memo attached to a payment.
+  #[constant]
+  pub const MAX_MEMO_LENGTH: usize = 64;
 
   /// AKA `b"payment"`.
   #[constant]
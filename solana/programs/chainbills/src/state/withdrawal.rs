@@ -1,4 +1,4 @@
-use crate::state::TokenAndAmount;
+use crate::state::{space, TokenAndAmount};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -30,10 +30,29 @@ pub struct Withdrawal {
 }
 
 impl Withdrawal {
-  // discriminator (8) included
-  pub const SPACE: usize = (5 * 8) + (2 * 32) + TokenAndAmount::SPACE;
+  pub const SPACE: usize = space::DISCRIMINATOR
+    + (2 * space::PUBKEY)
+    + (4 * space::U64)
+    + TokenAndAmount::SPACE;
 
   /// AKA `b"withdrawal"`.
   #[constant]
   pub const SEED_PREFIX: &'static [u8] = b"withdrawal";
+
+  /// The smallest amount (with its decimals) of any token that can be
+  /// withdrawn. Smaller amounts are dust on which percentage fees truncate.
+  pub const MIN_AMOUNT: u64 = 10;
+
+  /// The decimals of a whole token down to which withdrawals are allowed, so
+  /// that the minimum withdrawal is a millionth of a token.
+  pub const MIN_AMOUNT_PRECISION: u8 = 6;
+
+  /// The smallest amount of a token with the given decimals that can be
+  /// withdrawn: a millionth of a whole token, but never less than MIN_AMOUNT.
+  pub fn min_amount(decimals: u8) -> u64 {
+    10u64
+      .checked_pow(decimals.saturating_sub(Self::MIN_AMOUNT_PRECISION).into())
+      .unwrap_or(u64::MAX)
+      .max(Self::MIN_AMOUNT)
+  }
 }
@@ -1,3 +1,4 @@
+use crate::state::space;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -5,15 +6,20 @@ use anchor_lang::prelude::*;
 /// Holds data for every received message. Prevents replay attacks.
 pub struct WormholeReceived {
   /// AKA nonce. Should always be zero.
-  pub batch_id: u32, // 32 bytes
+  pub batch_id: u32, // 4 bytes
   /// Keccak256 hash of verified Wormhole message.
   pub vaa_hash: [u8; 32], // 32 bytes
 }
 
 impl WormholeReceived {
-  // discriminator first
-  pub const SPACE: usize = 8 + 32 + 32;
+  pub const SPACE: usize = space::DISCRIMINATOR + space::U32 + space::BYTES32;
 
   /// AKA `b"wormhole_received"`.
   pub const SEED_PREFIX: &'static [u8] = b"wormhole_received";
+
+  /// Derives the address (and bump) of the WormholeReceived account of the
+  /// VAA with the given hash. The account exists once the VAA is consumed.
+  pub fn pda(vaa_hash: [u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[Self::SEED_PREFIX, &vaa_hash], &crate::ID)
+  }
 }
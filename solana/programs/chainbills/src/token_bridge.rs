@@ -0,0 +1,107 @@
+//! Minimal bindings of Wormhole's Token Bridge program, for redeeming
+//! cross-chain payments. wormhole-anchor-sdk's token-bridge feature doesn't
+//! build with this program's borsh, so only what is used is kept here.
+use crate::error::ChainbillsError;
+use anchor_lang::{prelude::*, solana_program};
+
+#[cfg(feature = "mainnet")]
+declare_id!("wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb");
+
+#[cfg(all(feature = "testnet", not(feature = "mainnet")))]
+declare_id!("DZnkkTmCiFWfYTfT41X3Rd1kDgozqzxWaHqsw6W4x2oe");
+
+#[cfg(all(
+  feature = "devnet",
+  not(feature = "mainnet"),
+  not(feature = "testnet")
+))]
+declare_id!("B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE");
+
+/// The Token Bridge's config account. AKA `b"config"`.
+pub const SEED_PREFIX_CONFIG: &[u8] = b"config";
+
+/// Signs the release of tokens from custody. AKA `b"custody_signer"`.
+pub const SEED_PREFIX_CUSTODY_SIGNER: &[u8] = b"custody_signer";
+
+/// The index of the CompleteNativeWithPayload instruction in the Token
+/// Bridge's instructions.
+const COMPLETE_NATIVE_WITH_PAYLOAD: u8 = 9;
+
+#[derive(Debug, Clone)]
+pub struct TokenBridge;
+
+impl Id for TokenBridge {
+  fn id() -> Pubkey {
+    ID
+  }
+}
+
+/// Converts a Wormhole-normalized amount (with at most 8 decimals) to the
+/// amount with the mint's decimals. Fails with Overflow if that amount
+/// doesn't fit in a u64.
+pub fn denormalize_amount(amount: u64, mint_decimals: u8) -> Result<u64> {
+  if mint_decimals > 8 {
+    10u64
+      .checked_pow((mint_decimals - 8) as u32)
+      .and_then(|factor| amount.checked_mul(factor))
+      .ok_or(error!(ChainbillsError::Overflow))
+  } else {
+    Ok(amount)
+  }
+}
+
+#[derive(Accounts)]
+pub struct CompleteTransferNativeWithPayload<'info> {
+  pub payer: AccountInfo<'info>,
+  pub config: AccountInfo<'info>,
+  pub vaa: AccountInfo<'info>,
+  pub claim: AccountInfo<'info>,
+  pub foreign_endpoint: AccountInfo<'info>,
+  pub to: AccountInfo<'info>,
+  pub redeemer: AccountInfo<'info>,
+  pub custody: AccountInfo<'info>,
+  pub mint: AccountInfo<'info>,
+  pub custody_signer: AccountInfo<'info>,
+  pub rent: AccountInfo<'info>,
+  pub system_program: AccountInfo<'info>,
+  pub token_program: AccountInfo<'info>,
+  pub wormhole_program: AccountInfo<'info>,
+}
+
+/// Redeems a transfer (with payload) of a Solana-native token, releasing the
+/// tokens from custody into `to`. The redeemer must sign and be the
+/// transfer's recipient.
+pub fn complete_transfer_native_with_payload<'info>(
+  ctx: CpiContext<'_, '_, '_, 'info, CompleteTransferNativeWithPayload<'info>>,
+) -> Result<()> {
+  let accounts = &ctx.accounts;
+  let ix = solana_program::instruction::Instruction {
+    program_id: ctx.program.key(),
+    accounts: vec![
+      AccountMeta::new(accounts.payer.key(), true),
+      AccountMeta::new_readonly(accounts.config.key(), false),
+      AccountMeta::new_readonly(accounts.vaa.key(), false),
+      AccountMeta::new(accounts.claim.key(), false),
+      AccountMeta::new_readonly(accounts.foreign_endpoint.key(), false),
+      AccountMeta::new(accounts.to.key(), false),
+      AccountMeta::new_readonly(accounts.redeemer.key(), true),
+      // The Token Bridge's fee recipient. Relayer fees aren't used.
+      AccountMeta::new(accounts.to.key(), false),
+      AccountMeta::new(accounts.custody.key(), false),
+      AccountMeta::new_readonly(accounts.mint.key(), false),
+      AccountMeta::new_readonly(accounts.custody_signer.key(), false),
+      AccountMeta::new_readonly(accounts.rent.key(), false),
+      AccountMeta::new_readonly(accounts.system_program.key(), false),
+      AccountMeta::new_readonly(accounts.wormhole_program.key(), false),
+      AccountMeta::new_readonly(accounts.token_program.key(), false),
+    ],
+    data: vec![COMPLETE_NATIVE_WITH_PAYLOAD],
+  };
+
+  solana_program::program::invoke_signed(
+    &ix,
+    &ToAccountInfos::to_account_infos(&ctx),
+    ctx.signer_seeds,
+  )
+  .map_err(Into::into)
+}